@@ -4,4 +4,14 @@ fn main() {
   if version_check::is_min_version("1.44.0").unwrap_or(true) {
     println!("cargo:rustc-cfg=stable_i128");
   }
+
+  println!("cargo:rustc-check-cfg=cfg(const_generics)");
+  if version_check::is_min_version("1.51.0").unwrap_or(false) {
+    println!("cargo:rustc-cfg=const_generics");
+  }
+
+  println!("cargo:rustc-check-cfg=cfg(backtrace)");
+  if version_check::is_min_version("1.65.0").unwrap_or(false) {
+    println!("cargo:rustc-cfg=backtrace");
+  }
 }
@@ -0,0 +1,186 @@
+//! Parsers decoding base64 data
+//!
+//! Base64 encoded data is found in MIME messages, JSON web tokens or PEM
+//! files. The [base64] parser recognizes a run of base64 characters and
+//! decodes it while parsing.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::ops::RangeFrom;
+use crate::lib::std::vec::Vec;
+use crate::traits::{AsChar, InputIter, InputLength, Slice};
+
+/// Base64 variant accepted by the [base64] parser
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Config {
+  /// Uses the URL and filename safe alphabet, with `-` and `_` instead of `+` and `/`
+  pub url_safe: bool,
+  /// Requires `=` padding up to a multiple of 4 characters
+  pub padding: bool,
+}
+
+/// Standard alphabet, with padding
+pub const STANDARD: Config = Config {
+  url_safe: false,
+  padding: true,
+};
+
+/// Standard alphabet, without padding
+pub const STANDARD_NO_PAD: Config = Config {
+  url_safe: false,
+  padding: false,
+};
+
+/// URL safe alphabet, with padding
+pub const URL_SAFE: Config = Config {
+  url_safe: true,
+  padding: true,
+};
+
+/// URL safe alphabet, without padding
+pub const URL_SAFE_NO_PAD: Config = Config {
+  url_safe: true,
+  padding: false,
+};
+
+impl Config {
+  fn decode(&self, c: char) -> Option<u32> {
+    match c {
+      'A'..='Z' => Some(c as u32 - 'A' as u32),
+      'a'..='z' => Some(c as u32 - 'a' as u32 + 26),
+      '0'..='9' => Some(c as u32 - '0' as u32 + 52),
+      '+' if !self.url_safe => Some(62),
+      '/' if !self.url_safe => Some(63),
+      '-' if self.url_safe => Some(62),
+      '_' if self.url_safe => Some(63),
+      _ => None,
+    }
+  }
+}
+
+/// Recognizes a run of base64 characters and decodes it.
+///
+/// The run ends at the first character outside of the alphabet selected by
+/// `config`. If `config` requires padding, the run must then be followed by
+/// the right number of `=` characters.
+///
+/// The error points to the exact position of the invalid character: a missing
+/// padding character, or the last character of the run if it does not form
+/// valid base64 (wrong length, or unused bits that are not zero).
+///
+/// *Complete version*: the input is expected to contain the whole run.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::base64::{base64, STANDARD, URL_SAFE_NO_PAD};
+///
+/// fn pem(i: &str) -> IResult<&str, Vec<u8>> {
+///   base64(STANDARD)(i)
+/// }
+///
+/// fn jwt(i: &str) -> IResult<&str, Vec<u8>> {
+///   base64(URL_SAFE_NO_PAD)(i)
+/// }
+///
+/// assert_eq!(pem("bm9t\n"), Ok(("\n", b"nom".to_vec())));
+/// assert_eq!(pem("bm8=\n"), Ok(("\n", b"no".to_vec())));
+/// assert_eq!(pem("bm8\n"), Err(Err::Error(Error::new("\n", ErrorKind::Base64))));
+/// assert_eq!(jwt("_-8.x"), Ok((".x", vec![0xff, 0xef])));
+/// ```
+pub fn base64<I, E>(config: Config) -> impl Fn(I) -> IResult<I, Vec<u8>, E>
+where
+  I: Clone + InputIter + InputLength + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let mut res = Vec::new();
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    let mut count = 0;
+    let mut last = 0;
+    let mut end = input.input_len();
+    let mut next = None;
+
+    let mut it = input.iter_indices();
+    for (index, c) in it.by_ref() {
+      let c = c.as_char();
+      match config.decode(c) {
+        Some(v) => {
+          acc = (acc << 6) | v;
+          bits += 6;
+          if bits >= 8 {
+            bits -= 8;
+            res.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+          }
+          count += 1;
+          last = index;
+        }
+        None => {
+          end = index;
+          next = Some(c);
+          break;
+        }
+      }
+    }
+
+    // a single character cannot encode a full byte, and the
+    // bits left over at the end of the run must be zero
+    if count % 4 == 1 || acc != 0 {
+      return Err(Err::Error(E::from_error_kind(
+        input.slice(last..),
+        ErrorKind::Base64,
+      )));
+    }
+
+    let mut index = end;
+    if config.padding {
+      for _ in 0..(4 - count % 4) % 4 {
+        if next != Some('=') {
+          return Err(Err::Error(E::from_error_kind(
+            input.slice(index..),
+            ErrorKind::Base64,
+          )));
+        }
+        index += 1;
+        next = it.next().map(|(_, c)| c.as_char());
+      }
+    }
+
+    Ok((input.slice(index..), res))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  #[test]
+  fn base64_errors() {
+    let p = base64::<_, Error<&[u8]>>(STANDARD);
+    assert_eq!(p(&b""[..]), Ok((&b""[..], vec![])));
+    assert_eq!(
+      p(&b"TWFu+/8="[..]),
+      Ok((&b""[..], vec![b'M', b'a', b'n', 0xfb, 0xff]))
+    );
+    assert_eq!(
+      p(&b"bm9=;"[..]),
+      Err(Err::Error(Error::new(&b"9=;"[..], ErrorKind::Base64)))
+    );
+    assert_eq!(
+      p(&b"bm9tb"[..]),
+      Err(Err::Error(Error::new(&b"b"[..], ErrorKind::Base64)))
+    );
+    assert_eq!(
+      p(&b"bQ=;"[..]),
+      Err(Err::Error(Error::new(&b";"[..], ErrorKind::Base64)))
+    );
+    assert_eq!(
+      base64::<_, Error<&[u8]>>(URL_SAFE)(&b"-_=="[..]),
+      Err(Err::Error(Error::new(&b"_=="[..], ErrorKind::Base64)))
+    );
+  }
+}
@@ -8,7 +8,7 @@ pub mod complete;
 pub mod streaming;
 
 use crate::error::{ErrorKind, ParseError};
-use crate::internal::{Err, IResult, Needed};
+use crate::internal::{Err, IResult};
 use crate::lib::std::ops::RangeFrom;
 use crate::traits::{ErrorConvert, Slice};
 
@@ -114,10 +114,11 @@ where
     let i = (input, offset);
     match parser(inner) {
       Ok((rest, res)) => Ok(((rest, 0), res)),
-      Err(Err::Incomplete(Needed::Unknown)) => Err(Err::Incomplete(Needed::Unknown)),
-      Err(Err::Incomplete(Needed::Size(sz))) => Err(match sz.get().checked_mul(8) {
-        Some(v) => Err::Incomplete(Needed::new(v)),
-        None => Err::Failure(E2::from_error_kind(i, ErrorKind::TooLarge)),
+      Err(Err::Incomplete(needed)) => Err(match needed.size() {
+        Some(sz) if sz.get().checked_mul(8).is_none() => {
+          Err::Failure(E2::from_error_kind(i, ErrorKind::TooLarge))
+        }
+        _ => Err::Incomplete(needed.map(|sz| sz.get() * 8)),
       }),
       Err(Err::Error(e)) => Err(Err::Error(e.convert())),
       Err(Err::Failure(e)) => Err(Err::Failure(e.convert())),
@@ -0,0 +1,130 @@
+//! Byte order mark detection
+//!
+//! Files exported by some tools, mostly on Windows, start with a byte order
+//! mark indicating their encoding. [bom] recognizes it, and [skip_bom] lets
+//! a text parser ignore it.
+//!
+//! On a [partial](crate::partial::Partial) input, the start of a byte order
+//! mark returns `Err::Incomplete`.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult, Needed, Parser};
+use crate::traits::{AsBytes, InputLength, InputMode, InputTake};
+
+/// Byte order marks recognized by [bom]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Bom {
+  /// UTF-8, `EF BB BF`
+  Utf8,
+  /// UTF-16 little endian, `FF FE`
+  Utf16Le,
+  /// UTF-16 big endian, `FE FF`
+  Utf16Be,
+}
+
+impl Bom {
+  /// Encoded byte order mark
+  pub fn bytes(self) -> &'static [u8] {
+    match self {
+      Bom::Utf8 => b"\xEF\xBB\xBF",
+      Bom::Utf16Le => b"\xFF\xFE",
+      Bom::Utf16Be => b"\xFE\xFF",
+    }
+  }
+}
+
+/// Recognizes a byte order mark at the start of the input and returns which one it is.
+///
+/// Returns an `ErrorKind::Tag` error if there is none.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::bom::{bom, Bom};
+/// use nom::partial::Partial;
+///
+/// fn parser(i: &[u8]) -> IResult<&[u8], Bom> {
+///   bom(i)
+/// }
+///
+/// assert_eq!(parser(b"\xEF\xBB\xBFabc"), Ok((&b"abc"[..], Bom::Utf8)));
+/// assert_eq!(parser(b"\xFF\xFEa\x00"), Ok((&b"a\x00"[..], Bom::Utf16Le)));
+/// assert_eq!(parser(b"abc"), Err(Err::Error(Error::new(&b"abc"[..], ErrorKind::Tag))));
+/// assert_eq!(
+///   bom::<_, Error<_>>(Partial::new(&b"\xEF\xBB"[..])),
+///   Err(Err::Incomplete(Needed::new(1)))
+/// );
+/// ```
+pub fn bom<I, E: ParseError<I>>(input: I) -> IResult<I, Bom, E>
+where
+  I: AsBytes + InputTake + InputLength + InputMode,
+{
+  let bytes = input.as_bytes();
+  for b in [Bom::Utf8, Bom::Utf16Le, Bom::Utf16Be].iter() {
+    let mark = b.bytes();
+    if bytes.starts_with(mark) {
+      let (i, _) = input.take_split(mark.len());
+      return Ok((i, *b));
+    }
+    if input.is_partial() && !bytes.is_empty() && mark.starts_with(bytes) {
+      return Err(Err::Incomplete(Needed::new(mark.len() - bytes.len())));
+    }
+  }
+
+  if input.is_partial() && bytes.is_empty() {
+    return Err(Err::Incomplete(Needed::new(2)));
+  }
+  Err(Err::Error(E::from_error_kind(input, ErrorKind::Tag)))
+}
+
+/// Skips a byte order mark at the start of the input, if there is one, then applies `parser`.
+///
+/// ```rust
+/// # use nom::IResult;
+/// use nom::bom::skip_bom;
+/// use nom::character::complete::alpha1;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   skip_bom(alpha1)(i)
+/// }
+///
+/// assert_eq!(parser("\u{feff}abc"), Ok(("", "abc")));
+/// assert_eq!(parser("abc"), Ok(("", "abc")));
+/// ```
+pub fn skip_bom<I, O, E: ParseError<I>, F>(mut parser: F) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  I: Clone + AsBytes + InputTake + InputLength + InputMode,
+  F: Parser<I, O, E>,
+{
+  move |input: I| match bom::<I, E>(input.clone()) {
+    Ok((i, _)) => parser.parse(i),
+    Err(Err::Error(_)) => parser.parse(input),
+    Err(e) => Err(e),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::partial::Partial;
+
+  #[test]
+  fn partial_bom() {
+    let parser = bom::<_, (_, ErrorKind)>;
+    assert_eq!(
+      parser(Partial::new(&b""[..])),
+      Err(Err::Incomplete(Needed::new(2)))
+    );
+    assert_eq!(
+      parser(Partial::new(&b"\xFE"[..])),
+      Err(Err::Incomplete(Needed::new(1)))
+    );
+    assert_eq!(
+      parser(Partial::new(&b"\xFE\xFF\x00"[..])),
+      Ok((Partial::new(&b"\x00"[..]), Bom::Utf16Be))
+    );
+    assert_eq!(
+      parser(Partial::new(&b"\xFEa"[..])),
+      Err(Err::Error((Partial::new(&b"\xFEa"[..]), ErrorKind::Tag)))
+    );
+  }
+}
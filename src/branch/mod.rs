@@ -50,6 +50,76 @@ pub fn alt<I: Clone, O, E: ParseError<I>, List: Alt<I, O, E>>(
   move |i: I| l.choice(i)
 }
 
+/// Helper trait for the [alt_lazy()] combinator.
+///
+/// This trait is implemented for tuples of up to 21 closures returning parsers
+pub trait LazyBranches {
+  /// Tuple of the branches, each building its parser on first use
+  type Branches;
+
+  /// Wraps each closure in a [LazyBranch]
+  fn into_branches(self) -> Self::Branches;
+}
+
+/// Parser built by a closure the first time it is applied, used by [alt_lazy()]
+pub struct LazyBranch<F, P> {
+  make: Option<F>,
+  parser: Option<P>,
+}
+
+impl<I, O, E, F, P> Parser<I, O, E> for LazyBranch<F, P>
+where
+  F: FnOnce() -> P,
+  P: Parser<I, O, E>,
+{
+  fn parse(&mut self, input: I) -> IResult<I, O, E> {
+    if let Some(make) = self.make.take() {
+      self.parser = Some(make());
+    }
+    match self.parser.as_mut() {
+      Some(parser) => parser.parse(input),
+      None => unreachable!("the parser is built on first use"),
+    }
+  }
+}
+
+/// Tests a list of branches one by one until one succeeds, building the
+/// parser of each branch only when it is first attempted.
+///
+/// It takes as argument a tuple of closures returning parsers, and behaves
+/// like [alt()] otherwise. A parser is built at most once, then reused by
+/// the following calls. This avoids constructing costly branches, like
+/// large tables, when a previous branch usually matches.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::branch::alt_lazy;
+/// use nom::bytes::complete::tag;
+/// use nom::character::complete::digit1;
+///
+/// let mut parser = alt_lazy((
+///   || digit1,
+///   || {
+///     // only built when the input does not start with a digit
+///     let keyword = String::from("none");
+///     tag(keyword)
+///   },
+/// ));
+///
+/// assert_eq!(parser("123"), Ok(("", "123")));
+/// assert_eq!(parser("none"), Ok(("", "none")));
+/// assert_eq!(parser("abc"), Err(Err::Error(Error::new("abc", ErrorKind::Tag))));
+/// ```
+pub fn alt_lazy<I: Clone, O, E: ParseError<I>, List: LazyBranches>(
+  l: List,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  List::Branches: Alt<I, O, E>,
+{
+  let mut branches = l.into_branches();
+  move |i: I| branches.choice(i)
+}
+
 /// Helper trait for the [permutation()] combinator.
 ///
 /// This trait is implemented for tuples of up to 21 elements
@@ -85,6 +155,26 @@ pub trait Permutation<I, O, E> {
 /// # }
 /// ```
 ///
+/// On failure, the error is built with [append_element](ParseError::append_element),
+/// from the error of the first parser that was not applied, with the position
+/// where no remaining parser could be applied and the index of that parser.
+/// ```rust
+/// # use nom::{Err, error::{ErrorKind, VerboseError, VerboseErrorKind}, IResult};
+/// use nom::branch::permutation;
+/// use nom::bytes::complete::tag;
+///
+/// fn attributes(input: &str) -> IResult<&str, (&str, &str, &str), VerboseError<&str>> {
+///   permutation((tag("id;"), tag("name;"), tag("type;")))(input)
+/// }
+///
+/// assert_eq!(attributes("type;id;end"), Err(Err::Error(VerboseError {
+///   errors: vec![
+///     ("end", VerboseErrorKind::Tag("name;".to_string())),
+///     ("end", VerboseErrorKind::Element(ErrorKind::Permutation, 1)),
+///   ]
+/// })));
+/// ```
+///
 /// The parsers are applied greedily: if there are multiple unapplied parsers
 /// that could parse the next slice of input, the first one is used.
 /// ```rust
@@ -159,6 +249,38 @@ macro_rules! alt_trait_inner(
 
 alt_trait!(A B C D E F G H I J K L M N O P Q R S T U);
 
+macro_rules! lazy_branches_trait(
+  ($first:ident $first_p:ident, $second:ident $second_p:ident, $($id:ident $p:ident),+) => (
+    lazy_branches_trait!(__impl $first $first_p, $second $second_p; $($id $p),+);
+  );
+  (__impl $($current:ident $current_p:ident),+; $head:ident $head_p:ident, $($id:ident $p:ident),+) => (
+    lazy_branches_trait_impl!($($current $current_p),+);
+
+    lazy_branches_trait!(__impl $($current $current_p),+, $head $head_p; $($id $p),+);
+  );
+  (__impl $($current:ident $current_p:ident),+; $head:ident $head_p:ident) => (
+    lazy_branches_trait_impl!($($current $current_p),+);
+    lazy_branches_trait_impl!($($current $current_p),+, $head $head_p);
+  );
+);
+
+macro_rules! lazy_branches_trait_impl(
+  ($($id:ident $p:ident),+) => (
+    impl<$($id: FnOnce() -> $p, $p),+> LazyBranches for ( $($id),+ ) {
+      type Branches = ( $(LazyBranch<$id, $p>),+ );
+
+      #[allow(non_snake_case)]
+      fn into_branches(self) -> Self::Branches {
+        let ( $($id),+ ) = self;
+        ( $(LazyBranch { make: Some($id), parser: None }),+ )
+      }
+    }
+  );
+);
+
+lazy_branches_trait!(A PA, B PB, C PC, D PD, E PE, F PF, G PG, H PH, I PI, J PJ, K PK, L PL,
+  M PM, N PN, O PO, P PP, Q PQ, R PR, S PS, T PT, U PU);
+
 macro_rules! permutation_trait(
   (
     $name1:ident $ty1:ident $item1:ident
@@ -191,13 +313,20 @@ macro_rules! permutation_trait_impl(
 
         loop {
           let mut err: Option<Error> = None;
-          permutation_trait_inner!(0, self, input, res, err, $($name)+);
+          let mut missing: Option<usize> = None;
+          permutation_trait_inner!(0, self, input, res, err, missing, $($name)+);
 
           // If we reach here, every iterator has either been applied before,
           // or errored on the remaining input
-          if let Some(err) = err {
-            // There are remaining parsers, and all errored on the remaining input
-            return Err(Err::Error(Error::append(input, ErrorKind::Permutation, err)));
+          if let (Some(err), Some(index)) = (err, missing) {
+            // There are remaining parsers, and all errored on the remaining input:
+            // report the first one that was not applied
+            return Err(Err::Error(Error::append_element(
+              input,
+              ErrorKind::Permutation,
+              index,
+              err,
+            )));
           }
 
           // All parsers were applied
@@ -212,7 +341,7 @@ macro_rules! permutation_trait_impl(
 );
 
 macro_rules! permutation_trait_inner(
-  ($it:tt, $self:expr, $input:ident, $res:expr, $err:expr, $head:ident $($id:ident)*) => (
+  ($it:tt, $self:expr, $input:ident, $res:expr, $err:expr, $missing:expr, $head:ident $($id:ident)*) => (
     if $res.$it.is_none() {
       match $self.$it.parse($input.clone()) {
         Ok((i, o)) => {
@@ -221,17 +350,18 @@ macro_rules! permutation_trait_inner(
           continue;
         }
         Err(Err::Error(e)) => {
-          $err = Some(match $err {
-            Some(err) => err.or(e),
-            None => e,
-          });
+          // keep the error of the reported element
+          if $missing.is_none() {
+            $err = Some(e);
+            $missing = Some($it);
+          }
         }
         Err(e) => return Err(e),
       };
     }
-    succ!($it, permutation_trait_inner!($self, $input, $res, $err, $($id)*));
+    succ!($it, permutation_trait_inner!($self, $input, $res, $err, $missing, $($id)*));
   );
-  ($it:tt, $self:expr, $input:ident, $res:expr, $err:expr,) => ();
+  ($it:tt, $self:expr, $input:ident, $res:expr, $err:expr, $missing:expr,) => ();
 );
 
 permutation_trait!(
@@ -257,3 +387,52 @@ permutation_trait!(
   FnT T t
   FnU U u
 );
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::bytes::complete::tag;
+  use core::cell::Cell;
+
+  #[test]
+  fn alt_lazy_builds_branches_once() {
+    let built = Cell::new(0);
+    let mut parser = alt_lazy::<_, _, (&str, ErrorKind), _>((
+      || tag("a"),
+      || {
+        built.set(built.get() + 1);
+        tag("b")
+      },
+      || -> fn(&str) -> IResult<&str, &str, (&str, ErrorKind)> { unreachable!() },
+    ));
+
+    assert_eq!(parser("a"), Ok(("", "a")));
+    assert_eq!(built.get(), 0);
+    assert_eq!(parser("b"), Ok(("", "b")));
+    assert_eq!(parser("b"), Ok(("", "b")));
+    assert_eq!(built.get(), 1);
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn permutation_missing_element() {
+    use crate::error::{ErrorFrames, VerboseError};
+
+    fn parser(i: &str) -> IResult<&str, (&str, &str, &str), VerboseError<&str>> {
+      permutation((tag("a"), tag("b"), tag("c")))(i)
+    }
+
+    let input = "bx";
+    let e = match parser(input) {
+      Err(Err::Error(e)) => e,
+      _ => unreachable!(),
+    };
+    let outer = e.frames().last().unwrap();
+    assert_eq!(outer.kind, Some(ErrorKind::Permutation));
+    assert_eq!(outer.element, Some(0));
+    assert_eq!(outer.offset(&input), 1);
+    let inner = e.frames().next().unwrap();
+    assert_eq!(inner.expected_tag, Some("a"));
+    assert_eq!(inner.offset(&input), 1);
+  }
+}
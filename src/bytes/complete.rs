@@ -15,6 +15,11 @@ use crate::traits::{
 /// The input data will be compared to the tag combinator's argument and will return the part of
 /// the input that matches the argument
 ///
+/// The pattern can be a string, a byte slice, their owned `String` and `Vec<u8>`
+/// versions, or a single `char` or `u8`. An owned pattern is cloned every time
+/// the parser is applied: for a pattern built at runtime, keep it alive and pass
+/// a borrow like `tag(pattern.as_str())` instead, which does not allocate.
+///
 /// It will return `Err(Err::Error((_, ErrorKind::Tag)))` if the input doesn't match the pattern
 /// # Example
 /// ```rust
@@ -42,10 +47,7 @@ where
     let t = tag.clone();
     let res: IResult<_, _, Error> = match i.compare(t) {
       CompareResult::Ok => Ok(i.take_split(tag_len)),
-      _ => {
-        let e: ErrorKind = ErrorKind::Tag;
-        Err(Err::Error(Error::from_error_kind(i, e)))
-      }
+      _ => Err(Err::Error(Error::from_tag(i, &tag))),
     };
     res
   }
@@ -86,10 +88,7 @@ where
 
     let res: IResult<_, _, Error> = match (i).compare_no_case(t) {
       CompareResult::Ok => Ok(i.take_split(tag_len)),
-      _ => {
-        let e: ErrorKind = ErrorKind::Tag;
-        Err(Err::Error(Error::from_error_kind(i, e)))
-      }
+      _ => Err(Err::Error(Error::from_tag(i, &tag))),
     };
     res
   }
@@ -195,6 +194,38 @@ where
   move |i: Input| i.split_at_position_complete(|c| !cond(c))
 }
 
+/// Returns the longest input slice (if any) that matches the predicate, and the
+/// first item that does not match.
+///
+/// The second output is the item that stopped the scan, `None` at the end of the input.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::bytes::complete::take_while_and_peek;
+/// use nom::character::is_alphabetic;
+///
+/// fn alpha(s: &[u8]) -> IResult<&[u8], (&[u8], Option<u8>)> {
+///   take_while_and_peek(is_alphabetic)(s)
+/// }
+///
+/// assert_eq!(alpha(b"latin123"), Ok((&b"123"[..], (&b"latin"[..], Some(b'1')))));
+/// assert_eq!(alpha(b"12345"), Ok((&b"12345"[..], (&b""[..], Some(b'1')))));
+/// assert_eq!(alpha(b"latin"), Ok((&b""[..], (&b"latin"[..], None))));
+/// ```
+pub fn take_while_and_peek<F, Input, Error: ParseError<Input>>(
+  cond: F,
+) -> impl Fn(Input) -> IResult<Input, (Input, Option<<Input as InputIter>::Item>), Error>
+where
+  Input: InputTakeAtPosition + InputIter,
+  F: Fn(<Input as InputTakeAtPosition>::Item) -> bool,
+{
+  move |i: Input| {
+    let (rest, matched) = i.split_at_position_complete(|c| !cond(c))?;
+    let next = rest.iter_elements().next();
+    Ok((rest, (matched, next)))
+  }
+}
+
 /// Returns the longest (at least 1) input slice that matches the predicate.
 ///
 /// The parser will return the longest slice that matches the given predicate *(a function that
@@ -265,54 +296,25 @@ where
   move |i: Input| {
     let input = i;
 
-    match input.position(|c| !cond(c)) {
-      Some(idx) => {
-        if idx >= m {
-          if idx <= n {
-            let res: IResult<_, _, Error> = if let Ok(index) = input.slice_index(idx) {
-              Ok(input.take_split(index))
-            } else {
-              Err(Err::Error(Error::from_error_kind(
-                input,
-                ErrorKind::TakeWhileMN,
-              )))
-            };
-            res
-          } else {
-            let res: IResult<_, _, Error> = if let Ok(index) = input.slice_index(n) {
-              Ok(input.take_split(index))
-            } else {
-              Err(Err::Error(Error::from_error_kind(
-                input,
-                ErrorKind::TakeWhileMN,
-              )))
-            };
-            res
-          }
-        } else {
-          let e = ErrorKind::TakeWhileMN;
-          Err(Err::Error(Error::from_error_kind(input, e)))
-        }
-      }
-      None => {
-        let len = input.input_len();
-        if len >= n {
-          match input.slice_index(n) {
-            Ok(index) => Ok(input.take_split(index)),
-            Err(_needed) => Err(Err::Error(Error::from_error_kind(
-              input,
-              ErrorKind::TakeWhileMN,
-            ))),
-          }
-        } else if len >= m && len <= n {
-          let res: IResult<_, _, Error> = Ok((input.slice(len..), input));
-          res
-        } else {
-          let e = ErrorKind::TakeWhileMN;
-          Err(Err::Error(Error::from_error_kind(input, e)))
-        }
+    // counts the matching elements, stopping after n of them
+    let mut count = 0;
+    let mut end = None;
+    for (index, c) in input.iter_indices() {
+      if count == n || !cond(c) {
+        end = Some(index);
+        break;
       }
+      count += 1;
+    }
+
+    if count < m {
+      return Err(Err::Error(Error::from_error_kind(
+        input,
+        ErrorKind::TakeWhileMN,
+      )));
     }
+    let index = end.unwrap_or_else(|| input.input_len());
+    Ok(input.take_split(index))
   }
 }
 
@@ -380,6 +382,60 @@ where
   }
 }
 
+/// Returns the longest (m <= len <= n) input slice till a predicate is met.
+///
+/// The parser stops at the first element matching the predicate, or after `n` elements.
+///
+/// It will return an `Err::Error((_, ErrorKind::TakeTillMN))` if less than `m` elements
+/// were taken.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::bytes::complete::take_till_m_n;
+///
+/// fn user(s: &str) -> IResult<&str, &str> {
+///   take_till_m_n(2, 8, |c| c == '@')(s)
+/// }
+///
+/// assert_eq!(user("geal@host"), Ok(("@host", "geal")));
+/// assert_eq!(user("geoffroycouprie@host"), Ok(("couprie@host", "geoffroy")));
+/// assert_eq!(user("geal"), Ok(("", "geal")));
+/// assert_eq!(user("g@host"), Err(Err::Error(Error::new("g@host", ErrorKind::TakeTillMN))));
+/// ```
+pub fn take_till_m_n<F, Input, Error: ParseError<Input>>(
+  m: usize,
+  n: usize,
+  cond: F,
+) -> impl Fn(Input) -> IResult<Input, Input, Error>
+where
+  Input: InputTake + InputIter + InputLength,
+  F: Fn(<Input as InputIter>::Item) -> bool,
+{
+  move |i: Input| {
+    let input = i;
+
+    // counts the elements before the predicate, stopping after n of them
+    let mut count = 0;
+    let mut end = None;
+    for (index, c) in input.iter_indices() {
+      if count == n || cond(c) {
+        end = Some(index);
+        break;
+      }
+      count += 1;
+    }
+
+    if count < m {
+      return Err(Err::Error(Error::from_error_kind(
+        input,
+        ErrorKind::TakeTillMN,
+      )));
+    }
+    let index = end.unwrap_or_else(|| input.input_len());
+    Ok(input.take_split(index))
+  }
+}
+
 /// Returns an input slice containing the first N input elements (Input[..N]).
 ///
 /// It will return `Err(Err::Error((_, ErrorKind::Eof)))` if the input is shorter than the argument.
@@ -412,6 +468,36 @@ where
   }
 }
 
+/// Returns an input slice containing the first N input elements (Input[..N])
+/// without consuming them.
+///
+/// It will return `Err(Err::Error((_, ErrorKind::Eof)))` if the input is shorter than the argument.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::bytes::complete::peek_n;
+///
+/// fn magic(s: &[u8]) -> IResult<&[u8], &[u8]> {
+///   peek_n(4usize)(s)
+/// }
+///
+/// assert_eq!(magic(b"\x7fELF\x02"), Ok((&b"\x7fELF\x02"[..], &b"\x7fELF"[..])));
+/// assert_eq!(magic(b"PK"), Err(Err::Error(Error::new(&b"PK"[..], ErrorKind::Eof))));
+/// ```
+pub fn peek_n<C, Input, Error: ParseError<Input>>(
+  count: C,
+) -> impl Fn(Input) -> IResult<Input, Input, Error>
+where
+  Input: InputIter + InputTake + Clone,
+  C: ToUsize,
+{
+  let c = count.to_usize();
+  move |i: Input| match i.slice_index(c) {
+    Err(_needed) => Err(Err::Error(Error::from_error_kind(i, ErrorKind::Eof))),
+    Ok(index) => Ok((i.clone(), i.take(index))),
+  }
+}
+
 /// Returns the input slice up to the first occurrence of the pattern.
 ///
 /// It doesn't consume the pattern. It will return `Err(Err::Error((_, ErrorKind::TakeUntil)))`
@@ -448,6 +534,59 @@ where
   }
 }
 
+/// Returns the input slice up to the first occurrence of the pattern, in
+/// chunks of at most `max` elements.
+///
+/// It returns the data before the pattern and `true` if the pattern was found,
+/// or `false` if the input was cut at `max` elements or at its end. It doesn't
+/// consume the pattern, and `max` must not be zero. It will return
+/// `Err(Err::Error((_, ErrorKind::TakeUntil)))` if the input is empty.
+///
+/// Each call only searches the first `max` elements and the length of the
+/// pattern after them, so the whole input is read in linear time.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::bytes::complete::take_until_partial;
+///
+/// fn until_eof(s: &str) -> IResult<&str, (&str, bool)> {
+///   take_until_partial("eof", 8)(s)
+/// }
+///
+/// assert_eq!(until_eof("hello, worldeof"), Ok(("orldeof", ("hello, w", false))));
+/// assert_eq!(until_eof("worldeof"), Ok(("eof", ("world", true))));
+/// assert_eq!(until_eof("worlde"), Ok(("", ("worlde", false))));
+/// assert_eq!(until_eof(""), Err(Err::Error(Error::new("", ErrorKind::TakeUntil))));
+/// ```
+pub fn take_until_partial<T, Input, Error: ParseError<Input>>(
+  tag: T,
+  max: usize,
+) -> impl Fn(Input) -> IResult<Input, (Input, bool), Error>
+where
+  Input: InputTake + InputLength + InputIter + FindSubstring<T>,
+  T: InputLength + Clone,
+{
+  move |i: Input| {
+    let len = i.input_len();
+    let limit = i.slice_index(max).unwrap_or(len);
+    // a pattern starting at `limit` ends within `max + tag_len` elements
+    let window_end = i
+      .slice_index(max.saturating_add(tag.input_len()))
+      .unwrap_or(len);
+
+    let (end, found) = match i.take(window_end).find_substring(tag.clone()) {
+      Some(index) if index <= limit => (index, true),
+      _ => (limit, false),
+    };
+
+    if end == 0 && !found {
+      return Err(Err::Error(Error::from_error_kind(i, ErrorKind::TakeUntil)));
+    }
+    let (rest, chunk) = i.take_split(end);
+    Ok((rest, (chunk, found)))
+  }
+}
+
 /// Matches a byte string with escaped characters.
 ///
 /// * The first argument matches the normal characters (it must not accept the control character)
@@ -709,6 +848,387 @@ where
   escaped_transform(normal, control_char, transform)(i)
 }
 
+/// Matches a byte string with escaped characters, accumulating the result
+/// into a collection created by `init`.
+///
+/// This works like [escaped_transform], but the output collection is chosen by
+/// the caller instead of by the input type: it can be a `String` or a
+/// `Vec<u8>`, an `ArrayString` or an `ArrayVec` (with the `arrayvec` feature),
+/// or a `SmallVec` (with the `smallvec` feature), so it does not need the
+/// `alloc` feature. Both parsers must produce items or slices of the
+/// collection's element type, like `char` and `&str` for a string.
+///
+/// If the collection runs out of capacity, this returns a `Failure` with
+/// `ErrorKind::TooLarge`.
+///
+/// ```
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::bytes::complete::{escaped_transform_with, tag};
+/// use nom::character::complete::alpha1;
+/// use nom::branch::alt;
+/// use nom::combinator::value;
+///
+/// fn parser(input: &str) -> IResult<&str, String> {
+///   escaped_transform_with(
+///     alpha1,
+///     '\\',
+///     alt((
+///       value("\\", tag("\\")),
+///       value("\"", tag("\"")),
+///       value("\n", tag("n")),
+///     )),
+///     String::new,
+///   )(input)
+/// }
+///
+/// assert_eq!(parser("ab\\\"cd;"), Ok((";", String::from("ab\"cd"))));
+/// assert_eq!(parser("ab\\ncd"), Ok(("", String::from("ab\ncd"))));
+/// ```
+pub fn escaped_transform_with<Input, Error, F, G, H, O1, O2, Output>(
+  mut normal: F,
+  control_char: char,
+  mut transform: G,
+  mut init: H,
+) -> impl FnMut(Input) -> IResult<Input, Output, Error>
+where
+  Input: Clone
+    + crate::traits::Offset
+    + InputLength
+    + InputTake
+    + InputTakeAtPosition
+    + Slice<RangeFrom<usize>>
+    + InputIter,
+  Output: crate::traits::Sink<O1> + crate::traits::Sink<O2>,
+  <Input as InputIter>::Item: crate::traits::AsChar,
+  F: Parser<Input, O1, Error>,
+  G: Parser<Input, O2, Error>,
+  H: FnMut() -> Output,
+  Error: ParseError<Input>,
+{
+  move |input: Input| {
+    let mut res = init();
+    let (i, ()) = transform_escaped(input, &mut normal, control_char, &mut transform, &mut res)?;
+    Ok((i, res))
+  }
+}
+
+/// Matches a byte string with escaped characters, writing the result into `sink`.
+///
+/// This works like [escaped_transform_with], but instead of returning a new collection
+/// for each string, the output is appended to `sink`, which can be a mutable reference
+/// to a `String` or a `Vec<u8>` (or to their `arrayvec` and `smallvec` counterparts),
+/// or a writer wrapped in [FmtSink](crate::FmtSink) or [IoSink](crate::IoSink).
+/// This avoids building large intermediate strings.
+///
+/// If the sink cannot store the output, this returns a `Failure` with `ErrorKind::TooLarge`,
+/// or `ErrorKind::Write` if the underlying writer returned an error.
+///
+/// ```
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::bytes::complete::{escaped_transform_into, tag};
+/// use nom::character::complete::alpha1;
+/// use nom::branch::alt;
+/// use nom::combinator::value;
+///
+/// fn parser<'a>(input: &'a str, out: &mut String) -> IResult<&'a str, ()> {
+///   escaped_transform_into(
+///     alpha1,
+///     '\\',
+///     alt((value("\\", tag("\\")), value("\n", tag("n")))),
+///     out,
+///   )(input)
+/// }
+///
+/// let mut out = String::new();
+/// assert_eq!(parser("ab\\ncd;", &mut out), Ok((";", ())));
+/// assert_eq!(out, "ab\ncd");
+/// ```
+pub fn escaped_transform_into<Input, Error, F, G, O1, O2, S>(
+  mut normal: F,
+  control_char: char,
+  mut transform: G,
+  mut sink: S,
+) -> impl FnMut(Input) -> IResult<Input, (), Error>
+where
+  Input: Clone
+    + crate::traits::Offset
+    + InputLength
+    + InputTake
+    + InputTakeAtPosition
+    + Slice<RangeFrom<usize>>
+    + InputIter,
+  S: crate::traits::Sink<O1> + crate::traits::Sink<O2>,
+  <Input as InputIter>::Item: crate::traits::AsChar,
+  F: Parser<Input, O1, Error>,
+  G: Parser<Input, O2, Error>,
+  Error: ParseError<Input>,
+{
+  move |input: Input| transform_escaped(input, &mut normal, control_char, &mut transform, &mut sink)
+}
+
+fn transform_escaped<Input, Error, F, G, O1, O2, S>(
+  input: Input,
+  normal: &mut F,
+  control_char: char,
+  transform: &mut G,
+  res: &mut S,
+) -> IResult<Input, (), Error>
+where
+  Input: Clone
+    + crate::traits::Offset
+    + InputLength
+    + InputTake
+    + InputTakeAtPosition
+    + Slice<RangeFrom<usize>>
+    + InputIter,
+  S: crate::traits::Sink<O1> + crate::traits::Sink<O2>,
+  <Input as InputIter>::Item: crate::traits::AsChar,
+  F: Parser<Input, O1, Error>,
+  G: Parser<Input, O2, Error>,
+  Error: ParseError<Input>,
+{
+  use crate::traits::AsChar;
+
+  let mut index = 0;
+
+  let i = input.clone();
+
+  while index < i.input_len() {
+    let remainder = i.slice(index..);
+    match normal.parse(remainder.clone()) {
+      Ok((i2, o)) => {
+        if let Err(kind) = res.extend_from(o) {
+          return Err(Err::Failure(Error::from_error_kind(remainder, kind)));
+        }
+        if i2.input_len() == 0 {
+          return Ok((i.slice(i.input_len()..), ()));
+        } else {
+          index = input.offset(&i2);
+        }
+      }
+      Err(Err::Error(_)) => {
+        // unwrap() should be safe here since index < $i.input_len()
+        if remainder.iter_elements().next().unwrap().as_char() == control_char {
+          let next = index + control_char.len_utf8();
+          let input_len = input.input_len();
+
+          if next >= input_len {
+            return Err(Err::Error(Error::from_error_kind(
+              remainder,
+              ErrorKind::EscapedTransform,
+            )));
+          } else {
+            match transform.parse(i.slice(next..)) {
+              Ok((i2, o)) => {
+                if let Err(kind) = res.extend_from(o) {
+                  return Err(Err::Failure(Error::from_error_kind(remainder, kind)));
+                }
+                if i2.input_len() == 0 {
+                  return Ok((i.slice(i.input_len()..), ()));
+                } else {
+                  index = input.offset(&i2);
+                }
+              }
+              Err(e) => return Err(e),
+            }
+          }
+        } else {
+          if index == 0 {
+            return Err(Err::Error(Error::from_error_kind(
+              remainder,
+              ErrorKind::EscapedTransform,
+            )));
+          }
+          return Ok((remainder, ()));
+        }
+      }
+      Err(e) => return Err(e),
+    }
+  }
+  Ok((input.slice(index..), ()))
+}
+
+/// Matches a string with escape sequences recognized by a parser, and
+/// transforms them.
+///
+/// * The first argument matches the normal characters (it must not match the escape sequences)
+/// * The second argument matches a whole escape sequence, including its prefix, and transforms it
+///
+/// If the first parser succeeds without consuming anything, the escape sequences are tried
+/// at that position, so parsers like `alpha0` can be used for the normal characters.
+///
+/// Unlike [escaped_transform], the escape sequences do not have to start with a single
+/// control character, so this can handle sequences like `\u{1F600}`, `%%`, `\x41`
+/// or doubled quotes (`""`).
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::bytes::complete::{escaped_transform_seq, is_not, tag, take_while_m_n};
+/// use nom::branch::alt;
+/// use nom::combinator::{map_opt, value};
+/// use nom::sequence::preceded;
+///
+/// fn parser(input: &str) -> IResult<&str, String> {
+///   escaped_transform_seq(
+///     is_not("\\\""),
+///     alt((
+///       value('"', tag("\"\"")),
+///       map_opt(
+///         preceded(tag("\\x"), take_while_m_n(2, 2, |c: char| c.is_ascii_hexdigit())),
+///         |s: &str| u8::from_str_radix(s, 16).ok().map(char::from),
+///       ),
+///     )),
+///   )(input)
+/// }
+///
+/// assert_eq!(parser("a\"\"b\\x41c\"rest"), Ok(("\"rest", String::from("a\"bAc"))));
+/// assert_eq!(parser("abc"), Ok(("", String::from("abc"))));
+/// assert_eq!(parser("\"rest"), Err(Err::Error(Error::new("\"rest", ErrorKind::EscapedTransform))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn escaped_transform_seq<Input, Error, F, G, O1, O2, ExtendItem, Output>(
+  mut normal: F,
+  mut escape: G,
+) -> impl FnMut(Input) -> IResult<Input, Output, Error>
+where
+  Input: Clone + crate::traits::Offset + InputLength + Slice<RangeFrom<usize>>,
+  Input: crate::traits::ExtendInto<Item = ExtendItem, Extender = Output>,
+  O1: crate::traits::ExtendInto<Item = ExtendItem, Extender = Output>,
+  O2: crate::traits::ExtendInto<Item = ExtendItem, Extender = Output>,
+  F: Parser<Input, O1, Error>,
+  G: Parser<Input, O2, Error>,
+  Error: ParseError<Input>,
+{
+  move |input: Input| {
+    let mut index = 0;
+    let mut res = input.new_builder();
+
+    while index < input.input_len() {
+      let remainder = input.slice(index..);
+      match normal.parse(remainder.clone()) {
+        // a normal parser matching nothing, like `alpha0`, gives way to the escape sequences
+        Ok((i2, o)) if i2.input_len() != remainder.input_len() => {
+          o.extend_into(&mut res);
+          index = input.offset(&i2);
+          continue;
+        }
+        Ok(_) | Err(Err::Error(_)) => {}
+        Err(e) => return Err(e),
+      }
+
+      match escape.parse(remainder.clone()) {
+        Ok((i2, o)) => {
+          // infinite loop check: the parser must always consume
+          if i2.input_len() == remainder.input_len() {
+            return Err(Err::Error(Error::from_error_kind(
+              remainder,
+              ErrorKind::EscapedTransform,
+            )));
+          }
+          o.extend_into(&mut res);
+          index = input.offset(&i2);
+        }
+        Err(Err::Error(_)) => {
+          if index == 0 {
+            return Err(Err::Error(Error::from_error_kind(
+              remainder,
+              ErrorKind::EscapedTransform,
+            )));
+          }
+          return Ok((remainder, res));
+        }
+        Err(e) => return Err(e),
+      }
+    }
+    Ok((input.slice(index..), res))
+  }
+}
+
+/// Recognizes a run of percent-encoded data and decodes it.
+///
+/// The run is made of the bytes accepted by `allowed`, which are kept as is,
+/// and of `%` followed by two hexadecimal digits, which are decoded to the
+/// corresponding byte. `%` always starts an encoded byte, even if it is
+/// accepted by `allowed`. If no byte had to be decoded, the result borrows
+/// from the input.
+///
+/// *Complete version*: an incomplete `%` sequence at the end of the input is an error.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::bytes::complete::percent_decoded;
+/// use std::borrow::Cow;
+///
+/// fn parser(s: &[u8]) -> IResult<&[u8], Cow<[u8]>> {
+///   percent_decoded(&b"abcdefghijklmnopqrstuvwxyz-._~"[..])(s)
+/// }
+///
+/// assert_eq!(parser(b"abc%20d&"), Ok((&b"&"[..], Cow::Owned(b"abc d".to_vec()))));
+/// assert_eq!(parser(b"abc&"), Ok((&b"&"[..], Cow::Borrowed(&b"abc"[..]))));
+/// assert_eq!(parser(b"abc%2"), Err(Err::Error(Error::new(&b"%2"[..], ErrorKind::PercentEncoding))));
+/// assert_eq!(parser(b"abc%zz"), Err(Err::Error(Error::new(&b"%zz"[..], ErrorKind::PercentEncoding))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn percent_decoded<'a, T, Error: ParseError<&'a [u8]>>(
+  allowed: T,
+) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], crate::lib::std::borrow::Cow<'a, [u8]>, Error>
+where
+  T: FindToken<u8>,
+{
+  use crate::lib::std::borrow::Cow;
+  use crate::lib::std::vec::Vec;
+
+  move |input: &'a [u8]| {
+    let mut decoded: Option<Vec<u8>> = None;
+    let mut index = 0;
+
+    while index < input.len() {
+      let b = input[index];
+      if b == b'%' {
+        let digits = &input[index + 1..];
+        if digits.len() < 2 {
+          return Err(Err::Error(Error::from_error_kind(
+            &input[index..],
+            ErrorKind::PercentEncoding,
+          )));
+        }
+        match (
+          (digits[0] as char).to_digit(16),
+          (digits[1] as char).to_digit(16),
+        ) {
+          (Some(hi), Some(lo)) => {
+            decoded
+              .get_or_insert_with(|| input[..index].to_vec())
+              .push((hi * 16 + lo) as u8);
+            index += 3;
+          }
+          _ => {
+            return Err(Err::Error(Error::from_error_kind(
+              &input[index..],
+              ErrorKind::PercentEncoding,
+            )))
+          }
+        }
+      } else if allowed.find_token(b) {
+        if let Some(v) = decoded.as_mut() {
+          v.push(b);
+        }
+        index += 1;
+      } else {
+        break;
+      }
+    }
+
+    let res = match decoded {
+      Some(v) => Cow::Owned(v),
+      None => Cow::Borrowed(&input[..index]),
+    };
+    Ok((&input[index..], res))
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -726,4 +1246,158 @@ mod tests {
       super::take_while_m_n(1, 1, |c: char| c.is_alphabetic())("øn");
     assert_eq!(result, Ok(("n", "ø")));
   }
+
+  #[test]
+  fn complete_take_while_m_n_utf8_counts_chars() {
+    let parser =
+      super::take_while_m_n::<_, _, (&str, ErrorKind)>(3, 3, |c: char| c.is_alphabetic());
+    assert_eq!(
+      parser("øn1"),
+      Err(Err::Error(("øn1", ErrorKind::TakeWhileMN)))
+    );
+    assert_eq!(parser("ønøn"), Ok(("n", "ønø")));
+  }
+
+  #[test]
+  fn complete_tag_single_element() {
+    let brace = tag::<_, _, (&str, ErrorKind)>('{');
+    assert_eq!(brace("{a}"), Ok(("a}", "{")));
+    assert_eq!(brace("a}"), Err(Err::Error(("a}", ErrorKind::Tag))));
+    assert_eq!(brace(""), Err(Err::Error(("", ErrorKind::Tag))));
+    assert_eq!(tag::<_, _, (&str, ErrorKind)>('é')("éa"), Ok(("a", "é")));
+
+    let newline = tag::<_, _, (&[u8], ErrorKind)>(b'\n');
+    assert_eq!(newline(&b"\nab"[..]), Ok((&b"ab"[..], &b"\n"[..])));
+    assert_eq!(
+      newline(&b"ab"[..]),
+      Err(Err::Error((&b"ab"[..], ErrorKind::Tag)))
+    );
+    assert_eq!(
+      tag::<_, _, (&[u8], ErrorKind)>('é')("éa".as_bytes()),
+      Ok((&b"a"[..], "é".as_bytes()))
+    );
+    assert_eq!(
+      tag_no_case::<_, _, (&[u8], ErrorKind)>(b'a')(&b"Ab"[..]),
+      Ok((&b"b"[..], &b"A"[..]))
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn complete_tag_owned() {
+    use crate::lib::std::string::String;
+    use crate::lib::std::vec::Vec;
+
+    let delimiter = String::from("::");
+    let sep = tag::<_, _, (&str, ErrorKind)>(delimiter.clone());
+    assert_eq!(sep("::a"), Ok(("a", "::")));
+    assert_eq!(sep(":a"), Err(Err::Error((":a", ErrorKind::Tag))));
+
+    let keyword = tag_no_case::<_, _, (&[u8], ErrorKind)>(Vec::from(&b"select"[..]));
+    assert_eq!(keyword(&b"SELECT *"[..]), Ok((&b" *"[..], &b"SELECT"[..])));
+    assert_eq!(
+      tag::<_, _, (&[u8], ErrorKind)>(delimiter.clone())(&b"::"[..]),
+      Ok((&b""[..], &b"::"[..]))
+    );
+
+    let borrowed = tag::<_, _, (&str, ErrorKind)>(delimiter.as_str());
+    assert_eq!(borrowed("::a"), Ok(("a", "::")));
+  }
+
+  #[test]
+  fn complete_tag_long_arrays() {
+    const MAGIC: [u8; 40] = *b"0123456789abcdefghijklmnopqrstuvwxyzABCD";
+    let input = &b"0123456789abcdefghijklmnopqrstuvwxyzABCDE"[..];
+    assert_eq!(
+      tag::<_, _, (&[u8], ErrorKind)>(MAGIC)(input),
+      Ok((&b"E"[..], &MAGIC[..]))
+    );
+    assert_eq!(
+      tag::<_, _, (&[u8], ErrorKind)>(&MAGIC)(input),
+      Ok((&b"E"[..], &MAGIC[..]))
+    );
+    assert_eq!(
+      is_a::<_, _, (&[u8], ErrorKind)>(MAGIC)(&b"cab;"[..]),
+      Ok((&b";"[..], &b"cab"[..]))
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "arrayvec")]
+  fn complete_escaped_transform_with_arraystring() {
+    use crate::branch::alt;
+    use crate::character::complete::alpha1;
+    use crate::combinator::value;
+    use crate::error::{Error, ErrorKind};
+    use crate::Err;
+    use arrayvec::ArrayString;
+
+    fn esc(i: &str) -> IResult<&str, ArrayString<[u8; 8]>> {
+      escaped_transform_with(
+        alpha1,
+        '\\',
+        alt((value("\\", tag("\\")), value("\n", tag("n")))),
+        ArrayString::new,
+      )(i)
+    }
+
+    assert_eq!(
+      esc("ab\\ncd;"),
+      Ok((";", ArrayString::from("ab\ncd").unwrap()))
+    );
+    assert_eq!(
+      esc("abcdefgh\\n"),
+      Err(Err::Failure(Error::new("\\n", ErrorKind::TooLarge)))
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn complete_escaped_transform_into_write_error() {
+    use crate::character::complete::alpha1;
+    use crate::combinator::value;
+    use crate::error::{Error, ErrorKind};
+    use crate::traits::IoSink;
+    use crate::Err;
+
+    let mut buf = [0u8; 4];
+    let mut sink = IoSink::new(&mut buf[..]);
+    let res: IResult<&str, ()> =
+      escaped_transform_into(alpha1, '\\', value("\n", tag("n")), &mut sink)("ab\\ncdef");
+    assert_eq!(res, Err(Err::Failure(Error::new("cdef", ErrorKind::Write))));
+    assert_eq!(
+      sink.take_error().map(|e| e.kind()),
+      Some(std::io::ErrorKind::WriteZero)
+    );
+  }
+
+  #[test]
+  fn complete_take_while_and_peek_utf8() {
+    use crate::partial::Partial;
+
+    let result: IResult<&str, (&str, Option<char>)> =
+      super::take_while_and_peek(|c: char| c.is_alphabetic())("øn=1");
+    assert_eq!(result, Ok(("=1", ("øn", Some('=')))));
+
+    let result: IResult<Partial<&str>, (Partial<&str>, Option<char>)> =
+      crate::bytes::take_while_and_peek(|c: char| c.is_alphabetic())(Partial::new("øn"));
+    assert_eq!(result, Err(Err::Incomplete(crate::Needed::new(1))));
+  }
+
+  #[test]
+  fn complete_take_till_m_n_utf8() {
+    use crate::partial::Partial;
+
+    let parser = super::take_till_m_n::<_, _, (&str, ErrorKind)>(2, 3, |c: char| c == ':');
+    assert_eq!(parser("øn:1"), Ok((":1", "øn")));
+    assert_eq!(parser("ønøn:1"), Ok(("n:1", "ønø")));
+    assert_eq!(
+      parser("ø:1"),
+      Err(Err::Error(("ø:1", ErrorKind::TakeTillMN)))
+    );
+
+    let result: IResult<Partial<&str>, Partial<&str>> =
+      crate::bytes::take_till_m_n(2, 3, |c: char| c == ':')(Partial::new("øn"));
+    assert_eq!(result, Err(Err::Incomplete(crate::Needed::new(1))));
+  }
 }
@@ -4,3 +4,433 @@
 mod macros;
 pub mod complete;
 pub mod streaming;
+
+use crate::error::ParseError;
+use crate::internal::IResult;
+use crate::lib::std::ops::RangeFrom;
+use crate::traits::{
+  Compare, FindSubstring, FindToken, InputIter, InputLength, InputMode, InputTake,
+  InputTakeAtPosition, Slice, ToUsize,
+};
+
+dispatch_mode! {
+  /// Recognizes a pattern
+  ///
+  /// Uses [streaming::tag] if the input is [partial](crate::partial::Partial),
+  /// and [complete::tag] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::tag;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = tag::<_, _, Error<_>>("Hello");
+  /// assert_eq!(parser("Hello, World!"), Ok((", World!", "Hello")));
+  /// assert_eq!(parser("Hel"), Err(Err::Error(Error::new("Hel", ErrorKind::Tag))));
+  ///
+  /// // the same pattern on a partial input
+  /// let parser = tag::<_, _, Error<_>>("Hello");
+  /// assert_eq!(parser(Partial::new("Hel")), Err(Err::Incomplete(Needed::new(2))));
+  /// ```
+  pub fn tag<T, Input, Error: ParseError<Input>>(
+    tag: T = tag.clone(),
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTake + InputLength + Compare<T> + InputMode,
+    T: InputLength + Clone,
+}
+
+dispatch_mode! {
+  /// Recognizes a case insensitive pattern.
+  ///
+  /// Uses [streaming::tag_no_case] if the input is [partial](crate::partial::Partial),
+  /// and [complete::tag_no_case] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::tag_no_case;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = tag_no_case::<_, _, Error<_>>("hello");
+  /// assert_eq!(parser("HeLLo, World!"), Ok((", World!", "HeLLo")));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = tag_no_case::<_, _, Error<_>>("hello");
+  /// assert_eq!(parser(Partial::new("He")), Err(Err::Incomplete(Needed::new(3))));
+  /// ```
+  pub fn tag_no_case<T, Input, Error: ParseError<Input>>(
+    tag: T = tag.clone(),
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTake + InputLength + Compare<T> + InputMode,
+    T: InputLength + Clone,
+}
+
+dispatch_mode! {
+  /// Parse till certain characters are met.
+  ///
+  /// Uses [streaming::is_not] if the input is [partial](crate::partial::Partial),
+  /// and [complete::is_not] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::is_not;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = is_not::<_, _, Error<_>>(" \t\r\n");
+  /// assert_eq!(parser("Hello, World!"), Ok((" World!", "Hello,")));
+  /// assert_eq!(parser("Hello"), Ok(("", "Hello")));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = is_not::<_, _, Error<_>>(" \t\r\n");
+  /// assert_eq!(parser(Partial::new("Hello")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn is_not<T, Input, Error: ParseError<Input>>(
+    arr: T = arr.clone(),
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTakeAtPosition + InputMode,
+    T: FindToken<<Input as InputTakeAtPosition>::Item> + Clone,
+}
+
+dispatch_mode! {
+  /// Returns the longest slice of the matches the pattern.
+  ///
+  /// Uses [streaming::is_a] if the input is [partial](crate::partial::Partial),
+  /// and [complete::is_a] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::is_a;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = is_a::<_, _, Error<_>>("1234567890ABCDEF");
+  /// assert_eq!(parser("123 and"), Ok((" and", "123")));
+  /// assert_eq!(parser("DEADBEEF"), Ok(("", "DEADBEEF")));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = is_a::<_, _, Error<_>>("1234567890ABCDEF");
+  /// assert_eq!(parser(Partial::new("DEADBEEF")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn is_a<T, Input, Error: ParseError<Input>>(
+    arr: T = arr.clone(),
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTakeAtPosition + InputMode,
+    T: FindToken<<Input as InputTakeAtPosition>::Item> + Clone,
+}
+
+dispatch_mode! {
+  /// Returns the longest input slice (if any) that matches the predicate.
+  ///
+  /// Uses [streaming::take_while] if the input is [partial](crate::partial::Partial),
+  /// and [complete::take_while] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::take_while;
+  /// use nom::character::is_alphabetic;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = take_while::<_, _, Error<_>>(is_alphabetic);
+  /// assert_eq!(parser(&b"latin123"[..]), Ok((&b"123"[..], &b"latin"[..])));
+  /// assert_eq!(parser(&b"latin"[..]), Ok((&b""[..], &b"latin"[..])));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = take_while::<_, _, Error<_>>(is_alphabetic);
+  /// assert_eq!(parser(Partial::new(&b"latin"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn take_while<F, Input, Error: ParseError<Input>>(
+    cond: F = &cond,
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTakeAtPosition + InputMode,
+    F: Fn(<Input as InputTakeAtPosition>::Item) -> bool,
+}
+
+dispatch_mode! {
+  /// Returns the longest input slice (if any) that matches the predicate, and the
+  /// first item that does not match.
+  ///
+  /// Uses [streaming::take_while_and_peek] if the input is [partial](crate::partial::Partial),
+  /// and [complete::take_while_and_peek] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::take_while_and_peek;
+  /// use nom::character::is_alphabetic;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = take_while_and_peek::<_, _, Error<_>>(is_alphabetic);
+  /// assert_eq!(parser(&b"latin123"[..]), Ok((&b"123"[..], (&b"latin"[..], Some(b'1')))));
+  /// assert_eq!(parser(&b"latin"[..]), Ok((&b""[..], (&b"latin"[..], None))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = take_while_and_peek::<_, _, Error<_>>(is_alphabetic);
+  /// assert_eq!(parser(Partial::new(&b"latin"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn take_while_and_peek<F, Input, Error: ParseError<Input>>(
+    cond: F = &cond,
+  ) -> impl Fn(Input) -> IResult<Input, (Input, Option<<Input as InputIter>::Item>), Error>
+  where
+    Input: InputTakeAtPosition + InputIter + InputMode,
+    F: Fn(<Input as InputTakeAtPosition>::Item) -> bool,
+}
+
+dispatch_mode! {
+  /// Returns the longest (at least 1) input slice that matches the predicate.
+  ///
+  /// Uses [streaming::take_while1] if the input is [partial](crate::partial::Partial),
+  /// and [complete::take_while1] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::take_while1;
+  /// use nom::character::is_alphabetic;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = take_while1::<_, _, Error<_>>(is_alphabetic);
+  /// assert_eq!(parser(&b"latin123"[..]), Ok((&b"123"[..], &b"latin"[..])));
+  /// assert_eq!(parser(&b"12345"[..]), Err(Err::Error(Error::new(&b"12345"[..], ErrorKind::TakeWhile1))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = take_while1::<_, _, Error<_>>(is_alphabetic);
+  /// assert_eq!(parser(Partial::new(&b"latin"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn take_while1<F, Input, Error: ParseError<Input>>(
+    cond: F = &cond,
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTakeAtPosition + InputMode,
+    F: Fn(<Input as InputTakeAtPosition>::Item) -> bool,
+}
+
+dispatch_mode! {
+  /// Returns the longest (m <= len <= n) input slice  that matches the predicate.
+  ///
+  /// Uses [streaming::take_while_m_n] if the input is [partial](crate::partial::Partial),
+  /// and [complete::take_while_m_n] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::take_while_m_n;
+  /// use nom::character::is_alphabetic;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = take_while_m_n::<_, _, Error<_>>(3, 6, is_alphabetic);
+  /// assert_eq!(parser(&b"latin123"[..]), Ok((&b"123"[..], &b"latin"[..])));
+  /// assert_eq!(parser(&b"lat"[..]), Ok((&b""[..], &b"lat"[..])));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = take_while_m_n::<_, _, Error<_>>(3, 6, is_alphabetic);
+  /// assert_eq!(parser(Partial::new(&b"lat"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn take_while_m_n<F, Input, Error: ParseError<Input>>(
+    m: usize,
+    n: usize,
+    cond: F = &cond,
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTake + InputIter + InputLength + Slice<RangeFrom<usize>> + InputMode,
+    F: Fn(<Input as InputIter>::Item) -> bool,
+}
+
+dispatch_mode! {
+  /// Returns the longest input slice (if any) till a predicate is met.
+  ///
+  /// Uses [streaming::take_till] if the input is [partial](crate::partial::Partial),
+  /// and [complete::take_till] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::take_till;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = take_till::<_, _, Error<_>>(|c| c == ':');
+  /// assert_eq!(parser("latin:123"), Ok((":123", "latin")));
+  /// assert_eq!(parser("latin"), Ok(("", "latin")));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = take_till::<_, _, Error<_>>(|c| c == ':');
+  /// assert_eq!(parser(Partial::new("latin")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn take_till<F, Input, Error: ParseError<Input>>(
+    cond: F = &cond,
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTakeAtPosition + InputMode,
+    F: Fn(<Input as InputTakeAtPosition>::Item) -> bool,
+}
+
+dispatch_mode! {
+  /// Returns the longest (at least 1) input slice till a predicate is met.
+  ///
+  /// Uses [streaming::take_till1] if the input is [partial](crate::partial::Partial),
+  /// and [complete::take_till1] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::take_till1;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = take_till1::<_, _, Error<_>>(|c| c == ':');
+  /// assert_eq!(parser("latin:123"), Ok((":123", "latin")));
+  /// assert_eq!(parser(":empty"), Err(Err::Error(Error::new(":empty", ErrorKind::TakeTill1))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = take_till1::<_, _, Error<_>>(|c| c == ':');
+  /// assert_eq!(parser(Partial::new("latin")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn take_till1<F, Input, Error: ParseError<Input>>(
+    cond: F = &cond,
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTakeAtPosition + InputMode,
+    F: Fn(<Input as InputTakeAtPosition>::Item) -> bool,
+}
+
+dispatch_mode! {
+  /// Returns the longest (m <= len <= n) input slice till a predicate is met.
+  ///
+  /// Uses [streaming::take_till_m_n] if the input is [partial](crate::partial::Partial),
+  /// and [complete::take_till_m_n] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::take_till_m_n;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = take_till_m_n::<_, _, Error<_>>(2, 8, |c| c == '@');
+  /// assert_eq!(parser("geal@host"), Ok(("@host", "geal")));
+  /// assert_eq!(parser("geal"), Ok(("", "geal")));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = take_till_m_n::<_, _, Error<_>>(2, 8, |c| c == '@');
+  /// assert_eq!(parser(Partial::new("geal")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn take_till_m_n<F, Input, Error: ParseError<Input>>(
+    m: usize,
+    n: usize,
+    cond: F = &cond,
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTake + InputIter + InputLength + InputMode,
+    F: Fn(<Input as InputIter>::Item) -> bool,
+}
+
+dispatch_mode! {
+  /// Returns an input slice containing the first N input elements (Input[..N]).
+  ///
+  /// Uses [streaming::take] if the input is [partial](crate::partial::Partial),
+  /// and [complete::take] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::take;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = take::<_, _, Error<_>>(4usize);
+  /// assert_eq!(parser(&b"1234567"[..]), Ok((&b"567"[..], &b"1234"[..])));
+  /// assert_eq!(parser(&b"12"[..]), Err(Err::Error(Error::new(&b"12"[..], ErrorKind::Eof))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = take::<_, _, Error<_>>(4usize);
+  /// assert_eq!(parser(Partial::new(&b"12"[..])), Err(Err::Incomplete(Needed::new(2))));
+  /// ```
+  pub fn take<C, Input, Error: ParseError<Input>>(
+    count: C = count.to_usize(),
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputIter + InputTake + InputLength + InputMode,
+    C: ToUsize,
+}
+
+dispatch_mode! {
+  /// Returns an input slice containing the first N input elements (Input[..N])
+  /// without consuming them.
+  ///
+  /// Uses [streaming::peek_n] if the input is [partial](crate::partial::Partial),
+  /// and [complete::peek_n] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::peek_n;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = peek_n::<_, _, Error<_>>(4usize);
+  /// assert_eq!(parser(&b"\x7fELF\x02"[..]), Ok((&b"\x7fELF\x02"[..], &b"\x7fELF"[..])));
+  /// assert_eq!(parser(&b"PK"[..]), Err(Err::Error(Error::new(&b"PK"[..], ErrorKind::Eof))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = peek_n::<_, _, Error<_>>(4usize);
+  /// assert_eq!(parser(Partial::new(&b"PK"[..])), Err(Err::Incomplete(Needed::new(2))));
+  /// ```
+  pub fn peek_n<C, Input, Error: ParseError<Input>>(
+    count: C = count.to_usize(),
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputIter + InputTake + InputMode + Clone,
+    C: ToUsize,
+}
+
+dispatch_mode! {
+  /// Returns the input slice up to the first occurrence of the pattern.
+  ///
+  /// Uses [streaming::take_until] if the input is [partial](crate::partial::Partial),
+  /// and [complete::take_until] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::take_until;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = take_until::<_, _, Error<_>>("eof");
+  /// assert_eq!(parser("hello, worldeof"), Ok(("eof", "hello, world")));
+  /// assert_eq!(parser("hello, world"), Err(Err::Error(Error::new("hello, world", ErrorKind::TakeUntil))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = take_until::<_, _, Error<_>>("eof");
+  /// assert_eq!(parser(Partial::new("hello, world")), Err(Err::Incomplete(Needed::Unknown)));
+  /// ```
+  pub fn take_until<T, Input, Error: ParseError<Input>>(
+    tag: T = tag.clone(),
+  ) -> impl Fn(Input) -> IResult<Input, Input, Error>
+  where
+    Input: InputTake + InputLength + FindSubstring<T> + InputMode,
+    T: InputLength + Clone,
+}
+
+dispatch_mode! {
+  /// Returns the input slice up to the first occurrence of the pattern, in
+  /// chunks of at most `max` elements.
+  ///
+  /// Uses [streaming::take_until_partial] if the input is [partial](crate::partial::Partial),
+  /// and [complete::take_until_partial] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::bytes::take_until_partial;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = take_until_partial::<_, _, Error<_>>("eof", 8);
+  /// assert_eq!(parser("worldeof"), Ok(("eof", ("world", true))));
+  /// assert_eq!(parser("world"), Ok(("", ("world", false))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = take_until_partial::<_, _, Error<_>>("eof", 8);
+  /// assert_eq!(parser(Partial::new("e")), Err(Err::Incomplete(Needed::Unknown)));
+  /// ```
+  pub fn take_until_partial<T, Input, Error: ParseError<Input>>(
+    tag: T = tag.clone(),
+    max: usize,
+  ) -> impl Fn(Input) -> IResult<Input, (Input, bool), Error>
+  where
+    Input: InputTake
+      + InputLength
+      + InputIter
+      + FindSubstring<T>
+      + Compare<T>
+      + Slice<RangeFrom<usize>>
+      + InputMode,
+    T: InputLength + Clone,
+}
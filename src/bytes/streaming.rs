@@ -14,6 +14,11 @@ use crate::traits::{
 ///
 /// The input data will be compared to the tag combinator's argument and will return the part of
 /// the input that matches the argument.
+///
+/// The pattern can be a string, a byte slice, their owned `String` and `Vec<u8>`
+/// versions, or a single `char` or `u8`. An owned pattern is cloned every time
+/// the parser is applied: for a pattern built at runtime, keep it alive and pass
+/// a borrow like `tag(pattern.as_str())` instead, which does not allocate.
 /// # Example
 /// ```rust
 /// # #[macro_use] extern crate nom;
@@ -43,10 +48,7 @@ where
     let res: IResult<_, _, Error> = match i.compare(t) {
       CompareResult::Ok => Ok(i.take_split(tag_len)),
       CompareResult::Incomplete => Err(Err::Incomplete(Needed::new(tag_len - i.input_len()))),
-      CompareResult::Error => {
-        let e: ErrorKind = ErrorKind::Tag;
-        Err(Err::Error(Error::from_error_kind(i, e)))
-      }
+      CompareResult::Error => Err(Err::Error(Error::from_tag(i, &tag))),
     };
     res
   }
@@ -86,10 +88,7 @@ where
     let res: IResult<_, _, Error> = match (i).compare_no_case(t) {
       CompareResult::Ok => Ok(i.take_split(tag_len)),
       CompareResult::Incomplete => Err(Err::Incomplete(Needed::new(tag_len - i.input_len()))),
-      CompareResult::Error => {
-        let e: ErrorKind = ErrorKind::Tag;
-        Err(Err::Error(Error::from_error_kind(i, e)))
-      }
+      CompareResult::Error => Err(Err::Error(Error::from_tag(i, &tag))),
     };
     res
   }
@@ -200,6 +199,42 @@ where
   move |i: Input| i.split_at_position(|c| !cond(c))
 }
 
+/// Returns the longest input slice (if any) that matches the predicate, and the
+/// first item that does not match.
+///
+/// The second output is the item that stopped the scan, which is always present
+/// in this version.
+///
+/// # Streaming Specific
+/// *Streaming version* will return a `Err::Incomplete(Needed::new(1))` if the pattern reaches the end of the input.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::bytes::streaming::take_while_and_peek;
+/// use nom::character::is_alphabetic;
+///
+/// fn alpha(s: &[u8]) -> IResult<&[u8], (&[u8], Option<u8>)> {
+///   take_while_and_peek(is_alphabetic)(s)
+/// }
+///
+/// assert_eq!(alpha(b"latin123"), Ok((&b"123"[..], (&b"latin"[..], Some(b'1')))));
+/// assert_eq!(alpha(b"12345"), Ok((&b"12345"[..], (&b""[..], Some(b'1')))));
+/// assert_eq!(alpha(b"latin"), Err(Err::Incomplete(Needed::new(1))));
+/// ```
+pub fn take_while_and_peek<F, Input, Error: ParseError<Input>>(
+  cond: F,
+) -> impl Fn(Input) -> IResult<Input, (Input, Option<<Input as InputIter>::Item>), Error>
+where
+  Input: InputTakeAtPosition + InputIter,
+  F: Fn(<Input as InputTakeAtPosition>::Item) -> bool,
+{
+  move |i: Input| {
+    let (rest, matched) = i.split_at_position(|c| !cond(c))?;
+    let next = rest.iter_elements().next();
+    Ok((rest, (matched, next)))
+  }
+}
+
 /// Returns the longest (at least 1) input slice that matches the predicate.
 ///
 /// The parser will return the longest slice that matches the given predicate *(a function that
@@ -276,49 +311,27 @@ where
   move |i: Input| {
     let input = i;
 
-    match input.position(|c| !cond(c)) {
-      Some(idx) => {
-        if idx >= m {
-          if idx <= n {
-            let res: IResult<_, _, Error> = if let Ok(index) = input.slice_index(idx) {
-              Ok(input.take_split(index))
-            } else {
-              Err(Err::Error(Error::from_error_kind(
-                input,
-                ErrorKind::TakeWhileMN,
-              )))
-            };
-            res
-          } else {
-            let res: IResult<_, _, Error> = if let Ok(index) = input.slice_index(n) {
-              Ok(input.take_split(index))
-            } else {
-              Err(Err::Error(Error::from_error_kind(
-                input,
-                ErrorKind::TakeWhileMN,
-              )))
-            };
-            res
-          }
-        } else {
-          let e = ErrorKind::TakeWhileMN;
-          Err(Err::Error(Error::from_error_kind(input, e)))
-        }
+    // counts the matching elements, stopping after n of them
+    let mut count = 0;
+    let mut end = None;
+    for (index, c) in input.iter_indices() {
+      if count == n || !cond(c) {
+        end = Some(index);
+        break;
       }
+      count += 1;
+    }
+
+    match end {
+      Some(index) if count >= m => Ok(input.take_split(index)),
+      Some(_) => Err(Err::Error(Error::from_error_kind(
+        input,
+        ErrorKind::TakeWhileMN,
+      ))),
+      None if count == n => Ok(input.take_split(input.input_len())),
       None => {
-        let len = input.input_len();
-        if len >= n {
-          match input.slice_index(n) {
-            Ok(index) => Ok(input.take_split(index)),
-            Err(_needed) => Err(Err::Error(Error::from_error_kind(
-              input,
-              ErrorKind::TakeWhileMN,
-            ))),
-          }
-        } else {
-          let needed = if m > len { m - len } else { 1 };
-          Err(Err::Incomplete(Needed::new(needed)))
-        }
+        let needed = if m > count { m - count } else { 1 };
+        Err(Err::Incomplete(Needed::new(needed)))
       }
     }
   }
@@ -394,6 +407,68 @@ where
   }
 }
 
+/// Returns the longest (m <= len <= n) input slice till a predicate is met.
+///
+/// The parser stops at the first element matching the predicate, or after `n` elements.
+///
+/// It will return an `Err::Error((_, ErrorKind::TakeTillMN))` if less than `m` elements
+/// were taken.
+/// # Streaming Specific
+/// *Streaming version* will return a `Err::Incomplete(Needed::new(1))` if the input ends
+/// before the predicate is met and before `n` elements were taken.
+///
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::bytes::streaming::take_till_m_n;
+///
+/// fn user(s: &str) -> IResult<&str, &str> {
+///   take_till_m_n(2, 8, |c| c == '@')(s)
+/// }
+///
+/// assert_eq!(user("geal@host"), Ok(("@host", "geal")));
+/// assert_eq!(user("geoffroycouprie@host"), Ok(("couprie@host", "geoffroy")));
+/// assert_eq!(user("geal"), Err(Err::Incomplete(Needed::new(1))));
+/// assert_eq!(user("g@host"), Err(Err::Error(Error::new("g@host", ErrorKind::TakeTillMN))));
+/// ```
+pub fn take_till_m_n<F, Input, Error: ParseError<Input>>(
+  m: usize,
+  n: usize,
+  cond: F,
+) -> impl Fn(Input) -> IResult<Input, Input, Error>
+where
+  Input: InputTake + InputIter + InputLength,
+  F: Fn(<Input as InputIter>::Item) -> bool,
+{
+  move |i: Input| {
+    let input = i;
+
+    // counts the elements before the predicate, stopping after n of them
+    let mut count = 0;
+    let mut end = None;
+    for (index, c) in input.iter_indices() {
+      if count == n || cond(c) {
+        end = Some(index);
+        break;
+      }
+      count += 1;
+    }
+
+    match end {
+      Some(index) if count >= m => Ok(input.take_split(index)),
+      Some(_) => Err(Err::Error(Error::from_error_kind(
+        input,
+        ErrorKind::TakeTillMN,
+      ))),
+      None if count == n => Ok(input.take_split(input.input_len())),
+      None => {
+        let needed = if m > count { m - count } else { 1 };
+        Err(Err::Incomplete(Needed::new(needed)))
+      }
+    }
+  }
+}
+
 /// Returns an input slice containing the first N input elements (Input[..N]).
 ///
 /// # Streaming Specific
@@ -432,6 +507,41 @@ where
   }
 }
 
+/// Returns an input slice containing the first N input elements (Input[..N])
+/// without consuming them.
+///
+/// # Streaming Specific
+/// *Streaming version* if the input has less than N elements, `peek_n` will
+/// return a `Err::Incomplete(Needed::new(M))` where M is the number of
+/// additional bytes the parser would need to succeed, or `Needed::Unknown`
+/// for types like `&str`.
+///
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::bytes::streaming::peek_n;
+///
+/// fn magic(s: &[u8]) -> IResult<&[u8], &[u8]> {
+///   peek_n(4usize)(s)
+/// }
+///
+/// assert_eq!(magic(b"\x7fELF\x02"), Ok((&b"\x7fELF\x02"[..], &b"\x7fELF"[..])));
+/// assert_eq!(magic(b"PK"), Err(Err::Incomplete(Needed::new(2))));
+/// ```
+pub fn peek_n<C, Input, Error: ParseError<Input>>(
+  count: C,
+) -> impl Fn(Input) -> IResult<Input, Input, Error>
+where
+  Input: InputIter + InputTake + Clone,
+  C: ToUsize,
+{
+  let c = count.to_usize();
+  move |i: Input| match i.slice_index(c) {
+    Err(i) => Err(Err::Incomplete(i)),
+    Ok(index) => Ok((i.clone(), i.take(index))),
+  }
+}
+
 /// Returns the input slice up to the first occurrence of the pattern.
 ///
 /// It doesn't consume the pattern.
@@ -472,15 +582,89 @@ where
   }
 }
 
+/// Returns the input slice up to the first occurrence of the pattern, in
+/// chunks of at most `max` elements, without waiting for the pattern to arrive.
+///
+/// It returns the data before the pattern and `true` if the pattern was found,
+/// or `false` if the input was cut at `max` elements or at the end of the
+/// available data. The end of the input is kept if it could be the start of
+/// the pattern. It doesn't consume the pattern, and `max` must not be zero.
+///
+/// Each call only searches the first `max` elements and the length of the
+/// pattern after them, and the next call starts at most at the length of the
+/// pattern before the end of that search, so feeding the input in chunks
+/// takes linear time.
+///
+/// # Streaming Specific
+/// *Streaming version* will return a `Err::Incomplete(Needed::Unknown)` if the
+/// input is empty or could be the start of the pattern.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::bytes::streaming::take_until_partial;
+///
+/// fn until_eof(s: &str) -> IResult<&str, (&str, bool)> {
+///   take_until_partial("eof", 8)(s)
+/// }
+///
+/// assert_eq!(until_eof("hello, worldeof"), Ok(("orldeof", ("hello, w", false))));
+/// assert_eq!(until_eof("worldeof"), Ok(("eof", ("world", true))));
+/// assert_eq!(until_eof("worlde"), Ok(("e", ("world", false))));
+/// assert_eq!(until_eof("e"), Err(Err::Incomplete(Needed::Unknown)));
+/// assert_eq!(until_eof("eof"), Ok(("eof", ("", true))));
+/// ```
+pub fn take_until_partial<T, Input, Error: ParseError<Input>>(
+  tag: T,
+  max: usize,
+) -> impl Fn(Input) -> IResult<Input, (Input, bool), Error>
+where
+  Input:
+    InputTake + InputLength + InputIter + FindSubstring<T> + Compare<T> + Slice<RangeFrom<usize>>,
+  T: InputLength + Clone,
+{
+  move |i: Input| {
+    let len = i.input_len();
+    let tag_len = tag.input_len();
+    let limit = i.slice_index(max).unwrap_or(len);
+    // a pattern starting at `limit` ends within `max + tag_len` elements
+    let window_end = i.slice_index(max.saturating_add(tag_len)).unwrap_or(len);
+
+    let (end, found) = match i.take(window_end).find_substring(tag.clone()) {
+      Some(index) if index <= limit => (index, true),
+      Some(_) => (limit, false),
+      None if window_end < len => (limit, false),
+      None => {
+        // the end of the input could be the start of the pattern
+        let end = i
+          .iter_indices()
+          .map(|(index, _)| index)
+          .filter(|index| index + tag_len > len)
+          .find(|index| i.slice(*index..).compare(tag.clone()) == CompareResult::Incomplete)
+          .unwrap_or(len);
+        (end.min(limit), false)
+      }
+    };
+
+    if end == 0 && !found {
+      return Err(Err::Incomplete(Needed::Unknown));
+    }
+    let (rest, chunk) = i.take_split(end);
+    Ok((rest, (chunk, found)))
+  }
+}
+
 /// Matches a byte string with escaped characters.
 ///
 /// * The first argument matches the normal characters (it must not accept the control character)
 /// * The second argument is the control character (like `\` in most languages)
 /// * The third argument matches the escaped characters
+///
+/// Like the complete version, it returns an `ErrorKind::Escaped` error if it
+/// does not recognize anything.
 /// # Example
 /// ```
 /// # #[macro_use] extern crate nom;
-/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
 /// # use nom::character::complete::digit1;
 /// use nom::bytes::streaming::escaped;
 /// use nom::character::streaming::one_of;
@@ -491,6 +675,7 @@ where
 ///
 /// assert_eq!(esc("123;"), Ok((";", "123")));
 /// assert_eq!(esc("12\\\"34;"), Ok((";", "12\\\"34")));
+/// assert_eq!(esc(";"), Err(Err::Error(Error::new(";", ErrorKind::Escaped))));
 /// ```
 ///
 pub fn escaped<Input, Error, F, G, O1, O2>(
@@ -545,6 +730,12 @@ where
             }
           } else {
             let index = input.offset(&i);
+            if index == 0 {
+              return Err(Err::Error(Error::from_error_kind(
+                input,
+                ErrorKind::Escaped,
+              )));
+            }
             return Ok(input.take_split(index));
           }
         }
@@ -713,3 +904,415 @@ where
 {
   escaped_transform(normal, control_char, transform)(i)
 }
+
+/// Matches a byte string with escaped characters, accumulating the result
+/// into a collection created by `init`.
+///
+/// This works like [escaped_transform], but the output collection is chosen by
+/// the caller instead of by the input type: it can be a `String` or a
+/// `Vec<u8>`, an `ArrayString` or an `ArrayVec` (with the `arrayvec` feature),
+/// or a `SmallVec` (with the `smallvec` feature), so it does not need the
+/// `alloc` feature. Both parsers must produce items or slices of the
+/// collection's element type, like `char` and `&str` for a string.
+///
+/// If the collection runs out of capacity, this returns a `Failure` with
+/// `ErrorKind::TooLarge`.
+///
+/// ```
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::bytes::streaming::{escaped_transform_with, tag};
+/// use nom::character::streaming::alpha1;
+/// use nom::branch::alt;
+/// use nom::combinator::value;
+///
+/// fn parser(input: &str) -> IResult<&str, String> {
+///   escaped_transform_with(
+///     alpha1,
+///     '\\',
+///     alt((
+///       value("\\", tag("\\")),
+///       value("\"", tag("\"")),
+///       value("\n", tag("n")),
+///     )),
+///     String::new,
+///   )(input)
+/// }
+///
+/// assert_eq!(parser("ab\\\"cd;"), Ok((";", String::from("ab\"cd"))));
+/// assert_eq!(parser("ab\\ncd"), Err(Err::Incomplete(Needed::new(1))));
+/// ```
+pub fn escaped_transform_with<Input, Error, F, G, H, O1, O2, Output>(
+  mut normal: F,
+  control_char: char,
+  mut transform: G,
+  mut init: H,
+) -> impl FnMut(Input) -> IResult<Input, Output, Error>
+where
+  Input: Clone
+    + crate::traits::Offset
+    + InputLength
+    + InputTake
+    + InputTakeAtPosition
+    + Slice<RangeFrom<usize>>
+    + InputIter,
+  Output: crate::traits::Sink<O1> + crate::traits::Sink<O2>,
+  <Input as InputIter>::Item: crate::traits::AsChar,
+  F: Parser<Input, O1, Error>,
+  G: Parser<Input, O2, Error>,
+  H: FnMut() -> Output,
+  Error: ParseError<Input>,
+{
+  move |input: Input| {
+    let mut res = init();
+    let (i, ()) = transform_escaped(input, &mut normal, control_char, &mut transform, &mut res)?;
+    Ok((i, res))
+  }
+}
+
+/// Matches a byte string with escaped characters, writing the result into `sink`.
+///
+/// This works like [escaped_transform_with], but instead of returning a new collection
+/// for each string, the output is appended to `sink`, which can be a mutable reference
+/// to a `String` or a `Vec<u8>` (or to their `arrayvec` and `smallvec` counterparts),
+/// or a writer wrapped in [FmtSink](crate::FmtSink) or [IoSink](crate::IoSink).
+/// This avoids building large intermediate strings.
+///
+/// If the sink cannot store the output, this returns a `Failure` with `ErrorKind::TooLarge`,
+/// or `ErrorKind::Write` if the underlying writer returned an error.
+///
+/// *Note*: when this returns `Incomplete`, the output of the data parsed so far has already
+/// been written to the sink, so parsing again with more data will write it again.
+///
+/// ```
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::bytes::streaming::{escaped_transform_into, tag};
+/// use nom::character::streaming::alpha1;
+/// use nom::branch::alt;
+/// use nom::combinator::value;
+///
+/// fn parser<'a>(input: &'a str, out: &mut String) -> IResult<&'a str, ()> {
+///   escaped_transform_into(
+///     alpha1,
+///     '\\',
+///     alt((value("\\", tag("\\")), value("\n", tag("n")))),
+///     out,
+///   )(input)
+/// }
+///
+/// let mut out = String::new();
+/// assert_eq!(parser("ab\\ncd;", &mut out), Ok((";", ())));
+/// assert_eq!(out, "ab\ncd");
+/// ```
+pub fn escaped_transform_into<Input, Error, F, G, O1, O2, S>(
+  mut normal: F,
+  control_char: char,
+  mut transform: G,
+  mut sink: S,
+) -> impl FnMut(Input) -> IResult<Input, (), Error>
+where
+  Input: Clone
+    + crate::traits::Offset
+    + InputLength
+    + InputTake
+    + InputTakeAtPosition
+    + Slice<RangeFrom<usize>>
+    + InputIter,
+  S: crate::traits::Sink<O1> + crate::traits::Sink<O2>,
+  <Input as InputIter>::Item: crate::traits::AsChar,
+  F: Parser<Input, O1, Error>,
+  G: Parser<Input, O2, Error>,
+  Error: ParseError<Input>,
+{
+  move |input: Input| transform_escaped(input, &mut normal, control_char, &mut transform, &mut sink)
+}
+
+fn transform_escaped<Input, Error, F, G, O1, O2, S>(
+  input: Input,
+  normal: &mut F,
+  control_char: char,
+  transform: &mut G,
+  res: &mut S,
+) -> IResult<Input, (), Error>
+where
+  Input: Clone
+    + crate::traits::Offset
+    + InputLength
+    + InputTake
+    + InputTakeAtPosition
+    + Slice<RangeFrom<usize>>
+    + InputIter,
+  S: crate::traits::Sink<O1> + crate::traits::Sink<O2>,
+  <Input as InputIter>::Item: crate::traits::AsChar,
+  F: Parser<Input, O1, Error>,
+  G: Parser<Input, O2, Error>,
+  Error: ParseError<Input>,
+{
+  use crate::traits::AsChar;
+
+  let mut index = 0;
+
+  let i = input.clone();
+
+  while index < i.input_len() {
+    let remainder = i.slice(index..);
+    match normal.parse(remainder.clone()) {
+      Ok((i2, o)) => {
+        if let Err(kind) = res.extend_from(o) {
+          return Err(Err::Failure(Error::from_error_kind(remainder, kind)));
+        }
+        if i2.input_len() == 0 {
+          return Err(Err::Incomplete(Needed::Unknown));
+        } else {
+          index = input.offset(&i2);
+        }
+      }
+      Err(Err::Error(_)) => {
+        // unwrap() should be safe here since index < $i.input_len()
+        if remainder.iter_elements().next().unwrap().as_char() == control_char {
+          let next = index + control_char.len_utf8();
+          let input_len = input.input_len();
+
+          if next >= input_len {
+            return Err(Err::Incomplete(Needed::Unknown));
+          } else {
+            match transform.parse(i.slice(next..)) {
+              Ok((i2, o)) => {
+                if let Err(kind) = res.extend_from(o) {
+                  return Err(Err::Failure(Error::from_error_kind(remainder, kind)));
+                }
+                if i2.input_len() == 0 {
+                  return Err(Err::Incomplete(Needed::Unknown));
+                } else {
+                  index = input.offset(&i2);
+                }
+              }
+              Err(e) => return Err(e),
+            }
+          }
+        } else {
+          return Ok((remainder, ()));
+        }
+      }
+      Err(e) => return Err(e),
+    }
+  }
+  Err(Err::Incomplete(Needed::Unknown))
+}
+
+/// Matches a string with escape sequences recognized by a parser, and
+/// transforms them.
+///
+/// * The first argument matches the normal characters (it must not match the escape sequences)
+/// * The second argument matches a whole escape sequence, including its prefix, and transforms it
+///
+/// If the first parser succeeds without consuming anything, the escape sequences are tried
+/// at that position, so parsers like `alpha0` can be used for the normal characters.
+///
+/// Unlike [escaped_transform], the escape sequences do not have to start with a single
+/// control character, so this can handle sequences like `\u{1F600}`, `%%`, `\x41`
+/// or doubled quotes (`""`).
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::bytes::streaming::{escaped_transform_seq, is_not, tag, take_while_m_n};
+/// use nom::branch::alt;
+/// use nom::combinator::{map_opt, value};
+/// use nom::sequence::preceded;
+///
+/// fn parser(input: &str) -> IResult<&str, String> {
+///   escaped_transform_seq(
+///     is_not("\\\""),
+///     alt((
+///       value('"', tag("\"\"")),
+///       map_opt(
+///         preceded(tag("\\x"), take_while_m_n(2, 2, |c: char| c.is_ascii_hexdigit())),
+///         |s: &str| u8::from_str_radix(s, 16).ok().map(char::from),
+///       ),
+///     )),
+///   )(input)
+/// }
+///
+/// assert_eq!(parser("a\"\"b\\x41c\"rest"), Ok(("\"rest", String::from("a\"bAc"))));
+/// assert_eq!(parser("abc"), Err(Err::Incomplete(Needed::new(1))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn escaped_transform_seq<Input, Error, F, G, O1, O2, ExtendItem, Output>(
+  mut normal: F,
+  mut escape: G,
+) -> impl FnMut(Input) -> IResult<Input, Output, Error>
+where
+  Input: Clone + crate::traits::Offset + InputLength + Slice<RangeFrom<usize>>,
+  Input: crate::traits::ExtendInto<Item = ExtendItem, Extender = Output>,
+  O1: crate::traits::ExtendInto<Item = ExtendItem, Extender = Output>,
+  O2: crate::traits::ExtendInto<Item = ExtendItem, Extender = Output>,
+  F: Parser<Input, O1, Error>,
+  G: Parser<Input, O2, Error>,
+  Error: ParseError<Input>,
+{
+  move |input: Input| {
+    let mut index = 0;
+    let mut res = input.new_builder();
+
+    while index < input.input_len() {
+      let remainder = input.slice(index..);
+      match normal.parse(remainder.clone()) {
+        // a normal parser matching nothing, like `alpha0`, gives way to the escape sequences
+        Ok((i2, o)) if i2.input_len() != remainder.input_len() => {
+          o.extend_into(&mut res);
+          if i2.input_len() == 0 {
+            return Err(Err::Incomplete(Needed::Unknown));
+          }
+          index = input.offset(&i2);
+          continue;
+        }
+        Ok(_) | Err(Err::Error(_)) => {}
+        Err(e) => return Err(e),
+      }
+
+      match escape.parse(remainder.clone()) {
+        Ok((i2, o)) => {
+          // infinite loop check: the parser must always consume
+          if i2.input_len() == remainder.input_len() {
+            return Err(Err::Error(Error::from_error_kind(
+              remainder,
+              ErrorKind::EscapedTransform,
+            )));
+          }
+          o.extend_into(&mut res);
+          if i2.input_len() == 0 {
+            return Err(Err::Incomplete(Needed::Unknown));
+          }
+          index = input.offset(&i2);
+        }
+        Err(Err::Error(_)) => return Ok((remainder, res)),
+        Err(e) => return Err(e),
+      }
+    }
+    Err(Err::Incomplete(Needed::Unknown))
+  }
+}
+
+/// Recognizes a run of percent-encoded data and decodes it.
+///
+/// The run is made of the bytes accepted by `allowed`, which are kept as is,
+/// and of `%` followed by two hexadecimal digits, which are decoded to the
+/// corresponding byte. `%` always starts an encoded byte, even if it is
+/// accepted by `allowed`. If no byte had to be decoded, the result borrows
+/// from the input.
+///
+/// *Streaming version*: will return `Err(nom::Err::Incomplete(_))` if the input ends
+/// inside the run, or inside a `%` sequence.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::bytes::streaming::percent_decoded;
+/// use std::borrow::Cow;
+///
+/// fn parser(s: &[u8]) -> IResult<&[u8], Cow<[u8]>> {
+///   percent_decoded(&b"abcdefghijklmnopqrstuvwxyz-._~"[..])(s)
+/// }
+///
+/// assert_eq!(parser(b"abc%20d&"), Ok((&b"&"[..], Cow::Owned(b"abc d".to_vec()))));
+/// assert_eq!(parser(b"abc&"), Ok((&b"&"[..], Cow::Borrowed(&b"abc"[..]))));
+/// assert_eq!(parser(b"abc%2"), Err(Err::Incomplete(Needed::new(1))));
+/// assert_eq!(parser(b"abc"), Err(Err::Incomplete(Needed::new(1))));
+/// assert_eq!(parser(b"abc%zz"), Err(Err::Error(Error::new(&b"%zz"[..], ErrorKind::PercentEncoding))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn percent_decoded<'a, T, Error: ParseError<&'a [u8]>>(
+  allowed: T,
+) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], crate::lib::std::borrow::Cow<'a, [u8]>, Error>
+where
+  T: FindToken<u8>,
+{
+  use crate::lib::std::borrow::Cow;
+  use crate::lib::std::vec::Vec;
+
+  move |input: &'a [u8]| {
+    let mut decoded: Option<Vec<u8>> = None;
+    let mut index = 0;
+
+    while index < input.len() {
+      let b = input[index];
+      if b == b'%' {
+        let digits = &input[index + 1..];
+        if digits.len() < 2 {
+          if digits.iter().all(|d| d.is_ascii_hexdigit()) {
+            return Err(Err::Incomplete(Needed::new(2 - digits.len())));
+          }
+          return Err(Err::Error(Error::from_error_kind(
+            &input[index..],
+            ErrorKind::PercentEncoding,
+          )));
+        }
+        match (
+          (digits[0] as char).to_digit(16),
+          (digits[1] as char).to_digit(16),
+        ) {
+          (Some(hi), Some(lo)) => {
+            decoded
+              .get_or_insert_with(|| input[..index].to_vec())
+              .push((hi * 16 + lo) as u8);
+            index += 3;
+          }
+          _ => {
+            return Err(Err::Error(Error::from_error_kind(
+              &input[index..],
+              ErrorKind::PercentEncoding,
+            )))
+          }
+        }
+      } else if allowed.find_token(b) {
+        if let Some(v) = decoded.as_mut() {
+          v.push(b);
+        }
+        index += 1;
+      } else {
+        break;
+      }
+    }
+
+    if index == input.len() {
+      return Err(Err::Incomplete(Needed::new(1)));
+    }
+
+    let res = match decoded {
+      Some(v) => Cow::Owned(v),
+      None => Cow::Borrowed(&input[..index]),
+    };
+    Ok((&input[index..], res))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn take_until_partial_chunks() {
+    let parser = take_until_partial::<_, _, (&str, ErrorKind)>("\r\n", 3);
+
+    // chunks are counted in characters and cut at character boundaries
+    assert_eq!(parser("été!\r\n"), Ok(("!\r\n", ("été", false))));
+    assert_eq!(parser("!\r"), Ok(("\r", ("!", false))));
+    assert_eq!(parser("\r"), Err(Err::Incomplete(Needed::Unknown)));
+    assert_eq!(parser("\r\n"), Ok(("\r\n", ("", true))));
+    assert_eq!(parser("\rx"), Ok(("", ("\rx", false))));
+  }
+
+  #[test]
+  fn take_until_partial_split_tag() {
+    let parser = take_until_partial::<_, _, (&str, ErrorKind)>("eof", 8);
+
+    // the first chunk ends in the middle of the pattern, kept for the next one
+    let data = "abcdeof rest";
+    assert_eq!(parser(&data[..5]), Ok(("e", ("abcd", false))));
+    assert_eq!(parser(&data[4..]), Ok(("eof rest", ("", true))));
+
+    // the search stops after `max` elements and the length of the pattern
+    assert_eq!(parser("0123456789eof"), Ok(("89eof", ("01234567", false))));
+    assert_eq!(parser("01234567eof"), Ok(("eof", ("01234567", true))));
+  }
+}
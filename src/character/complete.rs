@@ -2,12 +2,14 @@
 //!
 //! Functions recognizing specific characters.
 
+use crate::combinator::iterator;
 use crate::error::ErrorKind;
 use crate::error::ParseError;
 use crate::internal::{Err, IResult};
 use crate::lib::std::ops::{Range, RangeFrom, RangeTo};
-use crate::traits::{AsChar, FindToken, InputIter, InputLength, InputTakeAtPosition, Slice};
+use crate::traits::{AsChar, FindToken, InputIter, InputLength, InputTake, InputTakeAtPosition};
 use crate::traits::{Compare, CompareResult};
+use crate::traits::{ParseTo, Slice};
 
 /// Recognizes one character.
 ///
@@ -239,6 +241,138 @@ where
   }
 }
 
+/// Recognizes a line and consumes its terminator, which can be `\n`, `\r\n` or a lone `\r`.
+///
+/// The returned line does not contain the terminator. The last line of the input
+/// does not need a terminator, but an empty input is an error.
+///
+/// *Complete version*: Will return an error if there's not enough input data.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// # use nom::character::complete::line;
+/// fn parser(input: &str) -> IResult<&str, &str> {
+///     line(input)
+/// }
+///
+/// assert_eq!(parser("ab\r\ncd"), Ok(("cd", "ab")));
+/// assert_eq!(parser("ab\rcd"), Ok(("cd", "ab")));
+/// assert_eq!(parser("\ncd"), Ok(("cd", "")));
+/// assert_eq!(parser("cd"), Ok(("", "cd")));
+/// assert_eq!(parser(""), Err(Err::Error(Error::new("", ErrorKind::Eof))));
+/// ```
+pub fn line<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+where
+  T: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  T: InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  let len = input.input_len();
+  if len == 0 {
+    return Err(Err::Error(E::from_error_kind(input, ErrorKind::Eof)));
+  }
+
+  match input.position(|item| {
+    let c = item.as_char();
+    c == '\r' || c == '\n'
+  }) {
+    None => Ok((input.slice(len..), input)),
+    Some(index) => {
+      let mut it = input.slice(index..).iter_elements();
+      let crlf = it.next().map(AsChar::as_char) == Some('\r')
+        && it.next().map(AsChar::as_char) == Some('\n');
+      let end = if crlf { index + 2 } else { index + 1 };
+      Ok((input.slice(end..), input.slice(..index)))
+    }
+  }
+}
+
+/// Recognizes a logical line made of a first line followed by continuation lines,
+/// which start with a space or a tab, like email headers.
+///
+/// The line terminators can be `\n`, `\r\n` or a lone `\r`. The returned line contains the
+/// folds, but not the final terminator, which is consumed. Use
+/// [unfold](crate::combinator::unfold) to remove the folds.
+///
+/// *Complete version*: the last line does not need a terminator, but an empty input is an error.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// # use nom::character::complete::folded_line;
+/// fn parser(input: &str) -> IResult<&str, &str> {
+///     folded_line(input)
+/// }
+///
+/// assert_eq!(parser("Subject: a\r\n long\r\n\tone\r\nTo: b"), Ok(("To: b", "Subject: a\r\n long\r\n\tone")));
+/// assert_eq!(parser("To: b"), Ok(("", "To: b")));
+/// assert_eq!(parser(""), Err(Err::Error(Error::new("", ErrorKind::Eof))));
+/// ```
+pub fn folded_line<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+where
+  T: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  T: InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  let len = input.input_len();
+  if len == 0 {
+    return Err(Err::Error(E::from_error_kind(input, ErrorKind::Eof)));
+  }
+
+  let mut start = 0;
+  loop {
+    let index = match input.slice(start..).position(|item| {
+      let c = item.as_char();
+      c == '\r' || c == '\n'
+    }) {
+      None => return Ok((input.slice(len..), input)),
+      Some(index) => start + index,
+    };
+
+    let mut it = input.slice(index..).iter_elements().map(AsChar::as_char);
+    let first = it.next();
+    let mut next = it.next();
+    let mut end = index + 1;
+    if first == Some('\r') && next == Some('\n') {
+      end += 1;
+      next = it.next();
+    }
+
+    match next {
+      Some(' ') | Some('\t') => start = end,
+      _ => return Ok((input.slice(end..), input.slice(..index))),
+    }
+  }
+}
+
+/// Creates an iterator over the lines of the input, as recognized by [line].
+///
+/// Once the iterator is exhausted, [crate::combinator::ParserIterator::finish] returns the remaining input.
+///
+/// *Complete version*: the last line does not need a terminator.
+/// # Example
+///
+/// ```
+/// # use nom::{error::ErrorKind, IResult};
+/// # use nom::character::complete::lines;
+/// let mut it = lines::<_, (_, ErrorKind)>("first\r\nsecond\rthird\n\nlast");
+///
+/// let parsed = it.collect::<Vec<_>>();
+/// let res: IResult<_, _, (_, ErrorKind)> = it.finish();
+///
+/// assert_eq!(parsed, vec!["first", "second", "third", "", "last"]);
+/// assert_eq!(res, Ok(("", ())));
+/// ```
+pub fn lines<T, E: ParseError<T>>(input: T) -> super::Lines<T, E>
+where
+  T: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  T: InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  iterator(input, line as fn(T) -> IResult<T, T, E>)
+}
+
 /// Matches a newline character '\n'.
 ///
 /// *Complete version*: Will return an error if there's not enough input data.
@@ -684,6 +818,222 @@ where
   )
 }
 
+/// Recognizes an ASCII identifier: a letter or underscore followed by letters,
+/// digits and underscores, like `[A-Za-z_][A-Za-z0-9_]*`
+///
+/// *Complete version*: Will return an error if there's not enough input data,
+/// or the whole input if no terminating token is found.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult, Needed};
+/// # use nom::character::complete::identifier;
+/// fn parser(input: &str) -> IResult<&str, &str> {
+///     identifier(input)
+/// }
+///
+/// assert_eq!(parser("_foo1 = 2"), Ok((" = 2", "_foo1")));
+/// assert_eq!(parser("bar"), Ok(("", "bar")));
+/// assert_eq!(parser("1bar"), Err(Err::Error(Error::new("1bar", ErrorKind::Identifier))));
+/// assert_eq!(parser(""), Err(Err::Error(Error::new("", ErrorKind::Identifier))));
+/// ```
+pub fn identifier<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+where
+  T: InputTakeAtPosition + InputIter + InputTake + InputLength + Slice<RangeFrom<usize>>,
+  <T as InputTakeAtPosition>::Item: AsChar,
+  <T as InputIter>::Item: AsChar + Copy,
+{
+  identifier_with(
+    |c| c.is_ascii_alphabetic() || c == '_',
+    |c| c.is_ascii_alphanumeric() || c == '_',
+  )(input)
+}
+
+/// Recognizes an identifier made of a character accepted by `start` followed
+/// by characters accepted by `cont`
+///
+/// *Complete version*: Will return an error if there's not enough input data,
+/// or the whole input if no terminating token is found.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult, Needed};
+/// # use nom::character::complete::identifier_with;
+/// fn parser(input: &str) -> IResult<&str, &str> {
+///     identifier_with(|c| c.is_alphabetic(), |c| c.is_alphanumeric() || c == '-')(input)
+/// }
+///
+/// assert_eq!(parser("kebab-case-1;"), Ok((";", "kebab-case-1")));
+/// assert_eq!(parser("-a"), Err(Err::Error(Error::new("-a", ErrorKind::Identifier))));
+/// ```
+pub fn identifier_with<T, E: ParseError<T>, S, C>(
+  start: S,
+  cont: C,
+) -> impl Fn(T) -> IResult<T, T, E>
+where
+  T: InputTakeAtPosition + InputIter + InputTake + InputLength + Slice<RangeFrom<usize>>,
+  <T as InputTakeAtPosition>::Item: AsChar,
+  <T as InputIter>::Item: AsChar + Copy,
+  S: Fn(char) -> bool,
+  C: Fn(char) -> bool,
+{
+  move |input: T| match input.iter_elements().next() {
+    Some(c) if start(c.as_char()) => {
+      let first_len = c.len();
+      let (_, rest) = input
+        .slice(first_len..)
+        .split_at_position_complete::<_, E>(|item| !cont(item.as_char()))?;
+      Ok(input.take_split(first_len + rest.input_len()))
+    }
+    _ => Err(Err::Error(E::from_error_kind(input, ErrorKind::Identifier))),
+  }
+}
+
+/// Recognizes between `m` and `n` ASCII digits and converts them to an integer.
+///
+/// At most `n` digits are taken. Fewer than `m` digits, or no digit at all, is
+/// an error with `ErrorKind::DigitCount`, and a value that does not fit in the
+/// output type is an error with `ErrorKind::TooLarge`.
+///
+/// *Complete version*: the digits can end with the input.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::character::complete::digit_m_n_value;
+/// use nom::sequence::tuple;
+///
+/// fn date(s: &str) -> IResult<&str, (u16, u8, u8)> {
+///   tuple((digit_m_n_value(4, 4), digit_m_n_value(2, 2), digit_m_n_value(2, 2)))(s)
+/// }
+///
+/// assert_eq!(date("20210630T"), Ok(("T", (2021, 6, 30))));
+/// assert_eq!(date("2021063"), Err(Err::Error(Error::new("3", ErrorKind::DigitCount))));
+/// assert_eq!(
+///   digit_m_n_value::<_, u8, Error<_>>(1, 3)("256"),
+///   Err(Err::Error(Error::new("256", ErrorKind::TooLarge)))
+/// );
+/// ```
+pub fn digit_m_n_value<T, O, E: ParseError<T>>(m: usize, n: usize) -> impl Fn(T) -> IResult<T, O, E>
+where
+  T: InputIter + InputLength + InputTake + ParseTo<O>,
+  <T as InputIter>::Item: AsChar,
+{
+  move |input: T| {
+    // counts the digits, stopping after n of them
+    let mut count = 0;
+    let mut end = None;
+    for (index, c) in input.iter_indices() {
+      if count == n || !c.is_dec_digit() {
+        end = Some(index);
+        break;
+      }
+      count += 1;
+    }
+
+    if count < m || count == 0 {
+      return Err(Err::Error(E::from_error_kind(input, ErrorKind::DigitCount)));
+    }
+    let (rest, digits) = input.take_split(end.unwrap_or_else(|| input.input_len()));
+    match digits.parse_to() {
+      Some(value) => Ok((rest, value)),
+      None => Err(Err::Error(E::from_error_kind(input, ErrorKind::TooLarge))),
+    }
+  }
+}
+
+/// Recognizes a run of percent-encoded text and decodes it.
+///
+/// The run is made of the characters accepted by `allowed`, which are kept
+/// as is, and of `%` followed by two hexadecimal digits, which are decoded to
+/// the corresponding byte. `%` always starts an encoded byte, even if it is
+/// accepted by `allowed`. The decoded bytes must form valid UTF-8. If nothing
+/// had to be decoded, the result borrows from the input.
+///
+/// *Complete version*: an incomplete `%` sequence at the end of the input is an error.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::character::complete::percent_decoded;
+/// use std::borrow::Cow;
+///
+/// fn parser(s: &str) -> IResult<&str, Cow<str>> {
+///   percent_decoded("abcdefghijklmnopqrstuvwxyz-._~")(s)
+/// }
+///
+/// assert_eq!(parser("caf%C3%A9&"), Ok(("&", Cow::Owned(String::from("café")))));
+/// assert_eq!(parser("abc&"), Ok(("&", Cow::Borrowed("abc"))));
+/// assert_eq!(parser("abc%2"), Err(Err::Error(Error::new("%2", ErrorKind::PercentEncoding))));
+/// assert_eq!(parser("abc%FF"), Err(Err::Error(Error::new("abc%FF", ErrorKind::PercentEncoding))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn percent_decoded<'a, T, Error: ParseError<&'a str>>(
+  allowed: T,
+) -> impl Fn(&'a str) -> IResult<&'a str, crate::lib::std::borrow::Cow<'a, str>, Error>
+where
+  T: FindToken<char>,
+{
+  use crate::lib::std::borrow::Cow;
+  use crate::lib::std::string::String;
+  use crate::lib::std::vec::Vec;
+
+  move |input: &'a str| {
+    let bytes = input.as_bytes();
+    let mut decoded: Option<Vec<u8>> = None;
+    let mut index = 0;
+
+    while let Some(c) = input[index..].chars().next() {
+      if c == '%' {
+        let digits = &bytes[index + 1..];
+        if digits.len() < 2 {
+          return Err(Err::Error(Error::from_error_kind(
+            &input[index..],
+            ErrorKind::PercentEncoding,
+          )));
+        }
+        match (
+          (digits[0] as char).to_digit(16),
+          (digits[1] as char).to_digit(16),
+        ) {
+          (Some(hi), Some(lo)) => {
+            decoded
+              .get_or_insert_with(|| bytes[..index].to_vec())
+              .push((hi * 16 + lo) as u8);
+            index += 3;
+          }
+          _ => {
+            return Err(Err::Error(Error::from_error_kind(
+              &input[index..],
+              ErrorKind::PercentEncoding,
+            )))
+          }
+        }
+      } else if allowed.find_token(c) {
+        if let Some(v) = decoded.as_mut() {
+          v.extend_from_slice(&bytes[index..index + c.len_utf8()]);
+        }
+        index += c.len_utf8();
+      } else {
+        break;
+      }
+    }
+
+    let res = match decoded {
+      Some(v) => match String::from_utf8(v) {
+        Ok(s) => Cow::Owned(s),
+        Err(_) => {
+          return Err(Err::Error(Error::from_error_kind(
+            input,
+            ErrorKind::PercentEncoding,
+          )))
+        }
+      },
+      None => Cow::Borrowed(&input[..index]),
+    };
+    Ok((&input[index..], res))
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -1020,4 +1370,53 @@ mod tests {
       Err(Err::Error(error_position!("\ra", ErrorKind::CrLf)))
     );
   }
+
+  #[test]
+  fn identifier_test() {
+    assert_parse!(identifier(&b"_a1_ b"[..]), Ok((&b" b"[..], &b"_a1_"[..])));
+    assert_parse!(identifier(&b"_"[..]), Ok((&b""[..], &b"_"[..])));
+    assert_parse!(
+      identifier("éa"),
+      Err(Err::Error(error_position!("éa", ErrorKind::Identifier)))
+    );
+    assert_parse!(identifier("aé"), Ok(("é", "a")));
+    assert_parse!(
+      identifier(""),
+      Err(Err::Error(error_position!("", ErrorKind::Identifier)))
+    );
+    let unicode = identifier_with(|c| c.is_alphabetic(), |c| c.is_alphanumeric());
+    assert_parse!(unicode("éa1 b"), Ok((" b", "éa1")));
+
+    let partial: IResult<_, _, (_, ErrorKind)> =
+      crate::character::identifier(crate::partial::Partial::new("abc"));
+    assert_eq!(partial, Err(Err::Incomplete(crate::Needed::new(1))));
+    let partial: IResult<_, _, (_, ErrorKind)> =
+      crate::character::identifier(crate::partial::Partial::new(""));
+    assert_eq!(partial, Err(Err::Incomplete(crate::Needed::new(1))));
+  }
+
+  #[test]
+  fn digit_m_n_value_test() {
+    let hour = digit_m_n_value::<_, u8, (_, ErrorKind)>(2, 2);
+    assert_eq!(hour(&b"0930"[..]), Ok((&b"30"[..], 9)));
+    assert_eq!(
+      hour(&b"9:30"[..]),
+      Err(Err::Error((&b"9:30"[..], ErrorKind::DigitCount)))
+    );
+    assert_parse!(
+      digit_m_n_value::<_, u32, _>(0, 3)("x"),
+      Err(Err::Error(error_position!("x", ErrorKind::DigitCount)))
+    );
+    assert_parse!(
+      digit_m_n_value::<_, u32, _>(1, 3)("١٢"),
+      Err(Err::Error(error_position!("١٢", ErrorKind::DigitCount)))
+    );
+
+    let partial: IResult<_, u8, (_, ErrorKind)> =
+      crate::character::digit_m_n_value(2, 2)(crate::partial::Partial::new("0"));
+    assert_eq!(partial, Err(Err::Incomplete(crate::Needed::new(1))));
+    let partial: IResult<_, u8, (_, ErrorKind)> =
+      crate::character::digit_m_n_value(2, 2)(crate::partial::Partial::new("09"));
+    assert_eq!(partial, Ok((crate::partial::Partial::new(""), 9)));
+  }
 }
@@ -8,6 +8,17 @@ mod macros;
 pub mod complete;
 pub mod streaming;
 
+use crate::error::ParseError;
+use crate::internal::IResult;
+use crate::lib::std::ops::{Range, RangeFrom, RangeTo};
+use crate::traits::{
+  AsChar, Compare, FindToken, InputIter, InputLength, InputMode, InputTake, InputTakeAtPosition,
+  ParseTo, Slice,
+};
+
+/// Iterator returned by the `lines` functions of the [complete] and [streaming] modules
+pub type Lines<T, E> = crate::combinator::ParserIterator<T, E, fn(T) -> crate::IResult<T, T, E>>;
+
 /// Tests if byte is ASCII alphabetic: A-Z, a-z
 ///
 /// # Example
@@ -114,3 +125,673 @@ pub fn is_space(chr: u8) -> bool {
 pub fn is_newline(chr: u8) -> bool {
   chr == b'\n'
 }
+
+dispatch_mode! {
+  /// Recognizes one character.
+  ///
+  /// Uses [streaming::char] if the input is [partial](crate::partial::Partial),
+  /// and [complete::char] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::char;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = char::<_, Error<_>>('a');
+  /// assert_eq!(parser("abc"), Ok(("bc", 'a')));
+  /// assert_eq!(parser("bc"), Err(Err::Error(Error::new("bc", ErrorKind::Char))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = char::<_, Error<_>>('a');
+  /// assert_eq!(parser(Partial::new("")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn char<I, Error: ParseError<I>>(c: char) -> impl Fn(I) -> IResult<I, char, Error>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter + InputLength + InputMode,
+    <I as InputIter>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes one character and checks that it satisfies a predicate
+  ///
+  /// Uses [streaming::satisfy] if the input is [partial](crate::partial::Partial),
+  /// and [complete::satisfy] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::satisfy;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = satisfy::<_, _, Error<_>>(|c| c == 'a' || c == 'b');
+  /// assert_eq!(parser("abc"), Ok(("bc", 'a')));
+  /// assert_eq!(parser("cd"), Err(Err::Error(Error::new("cd", ErrorKind::Satisfy))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = satisfy::<_, _, Error<_>>(|c| c == 'a' || c == 'b');
+  /// assert_eq!(parser(Partial::new("")), Err(Err::Incomplete(Needed::Unknown)));
+  /// ```
+  pub fn satisfy<F, I, Error: ParseError<I>>(
+    cond: F = &cond,
+  ) -> impl Fn(I) -> IResult<I, char, Error>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter + InputMode,
+    <I as InputIter>::Item: AsChar,
+    F: Fn(char) -> bool,
+}
+
+dispatch_mode! {
+  /// Recognizes one of the provided characters.
+  ///
+  /// Uses [streaming::one_of] if the input is [partial](crate::partial::Partial),
+  /// and [complete::one_of] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::one_of;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = one_of::<_, _, Error<_>>("abc");
+  /// assert_eq!(parser("b"), Ok(("", 'b')));
+  /// assert_eq!(parser("d"), Err(Err::Error(Error::new("d", ErrorKind::OneOf))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = one_of::<_, _, Error<_>>("abc");
+  /// assert_eq!(parser(Partial::new("")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn one_of<I, T, Error: ParseError<I>>(
+    list: T = list.clone(),
+  ) -> impl Fn(I) -> IResult<I, char, Error>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter + InputMode,
+    <I as InputIter>::Item: AsChar + Copy,
+    T: FindToken<<I as InputIter>::Item> + Clone,
+}
+
+dispatch_mode! {
+  /// Recognizes a character that is not in the provided characters.
+  ///
+  /// Uses [streaming::none_of] if the input is [partial](crate::partial::Partial),
+  /// and [complete::none_of] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::none_of;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = none_of::<_, _, Error<_>>("abc");
+  /// assert_eq!(parser("z"), Ok(("", 'z')));
+  /// assert_eq!(parser("a"), Err(Err::Error(Error::new("a", ErrorKind::NoneOf))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = none_of::<_, _, Error<_>>("abc");
+  /// assert_eq!(parser(Partial::new("")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn none_of<I, T, Error: ParseError<I>>(
+    list: T = list.clone(),
+  ) -> impl Fn(I) -> IResult<I, char, Error>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter + InputMode,
+    <I as InputIter>::Item: AsChar + Copy,
+    T: FindToken<<I as InputIter>::Item> + Clone,
+}
+
+dispatch_mode! {
+  /// Recognizes the string "\r\n".
+  ///
+  /// Uses [streaming::crlf] if the input is [partial](crate::partial::Partial),
+  /// and [complete::crlf] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::crlf;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(crlf::<_, Error<_>>("\r\nc"), Ok(("c", "\r\n")));
+  /// assert_eq!(crlf::<_, Error<_>>("ab\r\nc"), Err(Err::Error(Error::new("ab\r\nc", ErrorKind::CrLf))));
+  /// assert_eq!(crlf::<_, Error<_>>(Partial::new("\r")), Err(Err::Incomplete(Needed::new(2))));
+  /// ```
+  pub fn crlf<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: Slice<Range<usize>> + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+    T: InputIter + InputMode,
+    T: Compare<&'static str>,
+}
+
+dispatch_mode! {
+  /// Recognizes a string of any char except '\r\n' or '\n'.
+  ///
+  /// Uses [streaming::not_line_ending] if the input is [partial](crate::partial::Partial),
+  /// and [complete::not_line_ending] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::not_line_ending;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(not_line_ending::<_, Error<_>>("ab\r\nc"), Ok(("\r\nc", "ab")));
+  /// assert_eq!(not_line_ending::<_, Error<_>>("abc"), Ok(("", "abc")));
+  /// assert_eq!(not_line_ending::<_, Error<_>>(Partial::new("abc")), Err(Err::Incomplete(Needed::Unknown)));
+  /// ```
+  pub fn not_line_ending<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: Slice<Range<usize>> + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+    T: InputIter + InputLength + InputMode,
+    T: Compare<&'static str>,
+    <T as InputIter>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes an end of line (both '\n' and '\r\n').
+  ///
+  /// Uses [streaming::line_ending] if the input is [partial](crate::partial::Partial),
+  /// and [complete::line_ending] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::line_ending;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(line_ending::<_, Error<_>>("\r\nc"), Ok(("c", "\r\n")));
+  /// assert_eq!(line_ending::<_, Error<_>>("\nc"), Ok(("c", "\n")));
+  /// assert_eq!(line_ending::<_, Error<_>>("ab\nc"), Err(Err::Error(Error::new("ab\nc", ErrorKind::CrLf))));
+  /// assert_eq!(line_ending::<_, Error<_>>(Partial::new("\r")), Err(Err::Incomplete(Needed::new(2))));
+  /// ```
+  pub fn line_ending<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: Slice<Range<usize>> + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+    T: InputIter + InputLength + InputMode,
+    T: Compare<&'static str>,
+}
+
+dispatch_mode! {
+  /// Recognizes a line and consumes its terminator, which can be `\n`, `\r\n` or a lone `\r`.
+  ///
+  /// Uses [streaming::line] if the input is [partial](crate::partial::Partial),
+  /// and [complete::line] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::line;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(line::<_, Error<_>>("ab\r\ncd"), Ok(("cd", "ab")));
+  /// assert_eq!(line::<_, Error<_>>("cd"), Ok(("", "cd")));
+  /// assert_eq!(line::<_, Error<_>>(Partial::new("cd")), Err(Err::Incomplete(Needed::Unknown)));
+  /// ```
+  pub fn line<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+    T: InputIter + InputLength + InputMode,
+    <T as InputIter>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes a logical line made of a first line followed by continuation lines,
+  /// which start with a space or a tab, like email headers.
+  ///
+  /// Uses [streaming::folded_line] if the input is [partial](crate::partial::Partial),
+  /// and [complete::folded_line] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::folded_line;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(folded_line::<_, Error<_>>("To: a\r\n b\r\nFrom: c"), Ok(("From: c", "To: a\r\n b")));
+  /// assert_eq!(folded_line::<_, Error<_>>("To: a"), Ok(("", "To: a")));
+  /// assert_eq!(folded_line::<_, Error<_>>(Partial::new("To: a\r\n")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn folded_line<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+    T: InputIter + InputLength + InputMode,
+    <T as InputIter>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Matches a newline character '\n'.
+  ///
+  /// Uses [streaming::newline] if the input is [partial](crate::partial::Partial),
+  /// and [complete::newline] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::newline;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(newline::<_, Error<_>>("\nc"), Ok(("c", '\n')));
+  /// assert_eq!(newline::<_, Error<_>>("\r\nc"), Err(Err::Error(Error::new("\r\nc", ErrorKind::Char))));
+  /// assert_eq!(newline::<_, Error<_>>(Partial::new("")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn newline<I, Error: ParseError<I>>(input: I) -> IResult<I, char, Error>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter + InputLength + InputMode,
+    <I as InputIter>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Matches a tab character '\t'.
+  ///
+  /// Uses [streaming::tab] if the input is [partial](crate::partial::Partial),
+  /// and [complete::tab] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::tab;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(tab::<_, Error<_>>("\tc"), Ok(("c", '\t')));
+  /// assert_eq!(tab::<_, Error<_>>("\r\nc"), Err(Err::Error(Error::new("\r\nc", ErrorKind::Char))));
+  /// assert_eq!(tab::<_, Error<_>>(Partial::new("")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn tab<I, Error: ParseError<I>>(input: I) -> IResult<I, char, Error>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter + InputLength + InputMode,
+    <I as InputIter>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Matches one element as a character.
+  ///
+  /// Uses [streaming::anychar] if the input is [partial](crate::partial::Partial),
+  /// and [complete::anychar] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::anychar;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(anychar::<_, Error<_>>("abc"), Ok(("bc", 'a')));
+  /// assert_eq!(anychar::<_, Error<_>>(""), Err(Err::Error(Error::new("", ErrorKind::Eof))));
+  /// assert_eq!(anychar::<_, Error<_>>(Partial::new("")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn anychar<T, E: ParseError<T>>(input: T) -> IResult<T, char, E>
+  where
+    T: InputIter + InputLength + Slice<RangeFrom<usize>> + InputMode,
+    <T as InputIter>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes zero or more lowercase and uppercase ASCII alphabetic characters: a-z, A-Z
+  ///
+  /// Uses [streaming::alpha0] if the input is [partial](crate::partial::Partial),
+  /// and [complete::alpha0] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::alpha0;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(alpha0::<_, Error<_>>("ab1c"), Ok(("1c", "ab")));
+  /// assert_eq!(alpha0::<_, Error<_>>("ab"), Ok(("", "ab")));
+  /// assert_eq!(alpha0::<_, Error<_>>(Partial::new("ab")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn alpha0<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes one or more lowercase and uppercase ASCII alphabetic characters: a-z, A-Z
+  ///
+  /// Uses [streaming::alpha1] if the input is [partial](crate::partial::Partial),
+  /// and [complete::alpha1] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::alpha1;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(alpha1::<_, Error<_>>("ab1c"), Ok(("1c", "ab")));
+  /// assert_eq!(alpha1::<_, Error<_>>("ab"), Ok(("", "ab")));
+  /// assert_eq!(alpha1::<_, Error<_>>("1c"), Err(Err::Error(Error::new("1c", ErrorKind::Alpha))));
+  /// assert_eq!(alpha1::<_, Error<_>>(Partial::new("ab")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn alpha1<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes zero or more ASCII numerical characters: 0-9
+  ///
+  /// Uses [streaming::digit0] if the input is [partial](crate::partial::Partial),
+  /// and [complete::digit0] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::digit0;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(digit0::<_, Error<_>>("21c"), Ok(("c", "21")));
+  /// assert_eq!(digit0::<_, Error<_>>("21"), Ok(("", "21")));
+  /// assert_eq!(digit0::<_, Error<_>>(Partial::new("21")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn digit0<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes one or more ASCII numerical characters: 0-9
+  ///
+  /// Uses [streaming::digit1] if the input is [partial](crate::partial::Partial),
+  /// and [complete::digit1] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+  /// use nom::character::digit1;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(digit1::<_, Error<_>>("21c"), Ok(("c", "21")));
+  /// assert_eq!(digit1::<_, Error<_>>("21"), Ok(("", "21")));
+  /// assert_eq!(digit1::<_, Error<_>>(Partial::new("21")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn digit1<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes zero or more ASCII hexadecimal numerical characters: 0-9, A-F, a-f
+  ///
+  /// Uses [streaming::hex_digit0] if the input is [partial](crate::partial::Partial),
+  /// and [complete::hex_digit0] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::hex_digit0;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(hex_digit0::<_, Error<_>>("21cZ"), Ok(("Z", "21c")));
+  /// assert_eq!(hex_digit0::<_, Error<_>>("21c"), Ok(("", "21c")));
+  /// assert_eq!(hex_digit0::<_, Error<_>>(Partial::new("21c")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn hex_digit0<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes one or more ASCII hexadecimal numerical characters: 0-9, A-F, a-f
+  ///
+  /// Uses [streaming::hex_digit1] if the input is [partial](crate::partial::Partial),
+  /// and [complete::hex_digit1] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::hex_digit1;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(hex_digit1::<_, Error<_>>("21cZ"), Ok(("Z", "21c")));
+  /// assert_eq!(hex_digit1::<_, Error<_>>("21c"), Ok(("", "21c")));
+  /// assert_eq!(hex_digit1::<_, Error<_>>("H2"), Err(Err::Error(Error::new("H2", ErrorKind::HexDigit))));
+  /// assert_eq!(hex_digit1::<_, Error<_>>(Partial::new("21c")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn hex_digit1<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes zero or more octal characters: 0-7
+  ///
+  /// Uses [streaming::oct_digit0] if the input is [partial](crate::partial::Partial),
+  /// and [complete::oct_digit0] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::oct_digit0;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(oct_digit0::<_, Error<_>>("21cZ"), Ok(("cZ", "21")));
+  /// assert_eq!(oct_digit0::<_, Error<_>>("21"), Ok(("", "21")));
+  /// assert_eq!(oct_digit0::<_, Error<_>>(Partial::new("21")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn oct_digit0<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes one or more octal characters: 0-7
+  ///
+  /// Uses [streaming::oct_digit1] if the input is [partial](crate::partial::Partial),
+  /// and [complete::oct_digit1] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::oct_digit1;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(oct_digit1::<_, Error<_>>("21cZ"), Ok(("cZ", "21")));
+  /// assert_eq!(oct_digit1::<_, Error<_>>("21"), Ok(("", "21")));
+  /// assert_eq!(oct_digit1::<_, Error<_>>("H2"), Err(Err::Error(Error::new("H2", ErrorKind::OctDigit))));
+  /// assert_eq!(oct_digit1::<_, Error<_>>(Partial::new("21")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn oct_digit1<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes zero or more ASCII numerical and alphabetic characters: 0-9, a-z, A-Z
+  ///
+  /// Uses [streaming::alphanumeric0] if the input is [partial](crate::partial::Partial),
+  /// and [complete::alphanumeric0] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::alphanumeric0;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(alphanumeric0::<_, Error<_>>("21cZ%1"), Ok(("%1", "21cZ")));
+  /// assert_eq!(alphanumeric0::<_, Error<_>>("21cZ"), Ok(("", "21cZ")));
+  /// assert_eq!(alphanumeric0::<_, Error<_>>(Partial::new("21cZ")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn alphanumeric0<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes one or more ASCII numerical and alphabetic characters: 0-9, a-z, A-Z
+  ///
+  /// Uses [streaming::alphanumeric1] if the input is [partial](crate::partial::Partial),
+  /// and [complete::alphanumeric1] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::alphanumeric1;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(alphanumeric1::<_, Error<_>>("21cZ%1"), Ok(("%1", "21cZ")));
+  /// assert_eq!(alphanumeric1::<_, Error<_>>("21cZ"), Ok(("", "21cZ")));
+  /// assert_eq!(alphanumeric1::<_, Error<_>>("&H2"), Err(Err::Error(Error::new("&H2", ErrorKind::AlphaNumeric))));
+  /// assert_eq!(alphanumeric1::<_, Error<_>>(Partial::new("21cZ")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn alphanumeric1<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes zero or more spaces and tabs.
+  ///
+  /// Uses [streaming::space0] if the input is [partial](crate::partial::Partial),
+  /// and [complete::space0] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::space0;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(space0::<_, Error<_>>(" \t21c"), Ok(("21c", " \t")));
+  /// assert_eq!(space0::<_, Error<_>>(" \t"), Ok(("", " \t")));
+  /// assert_eq!(space0::<_, Error<_>>(Partial::new(" \t")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn space0<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar + Clone,
+}
+
+dispatch_mode! {
+  /// Recognizes one or more spaces and tabs.
+  ///
+  /// Uses [streaming::space1] if the input is [partial](crate::partial::Partial),
+  /// and [complete::space1] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::space1;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(space1::<_, Error<_>>(" \t21c"), Ok(("21c", " \t")));
+  /// assert_eq!(space1::<_, Error<_>>(" \t"), Ok(("", " \t")));
+  /// assert_eq!(space1::<_, Error<_>>("H2"), Err(Err::Error(Error::new("H2", ErrorKind::Space))));
+  /// assert_eq!(space1::<_, Error<_>>(Partial::new(" \t")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn space1<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar + Clone,
+}
+
+dispatch_mode! {
+  /// Recognizes zero or more spaces, tabs, carriage returns and line feeds.
+  ///
+  /// Uses [streaming::multispace0] if the input is [partial](crate::partial::Partial),
+  /// and [complete::multispace0] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::multispace0;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(multispace0::<_, Error<_>>(" \t\n\r21c"), Ok(("21c", " \t\n\r")));
+  /// assert_eq!(multispace0::<_, Error<_>>(" \t\n\r"), Ok(("", " \t\n\r")));
+  /// assert_eq!(multispace0::<_, Error<_>>(Partial::new(" \t\n\r")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn multispace0<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar + Clone,
+}
+
+dispatch_mode! {
+  /// Recognizes one or more spaces, tabs, carriage returns and line feeds.
+  ///
+  /// Uses [streaming::multispace1] if the input is [partial](crate::partial::Partial),
+  /// and [complete::multispace1] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::multispace1;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(multispace1::<_, Error<_>>(" \t\n\r21c"), Ok(("21c", " \t\n\r")));
+  /// assert_eq!(multispace1::<_, Error<_>>(" \t\n\r"), Ok(("", " \t\n\r")));
+  /// assert_eq!(multispace1::<_, Error<_>>("H2"), Err(Err::Error(Error::new("H2", ErrorKind::MultiSpace))));
+  /// assert_eq!(multispace1::<_, Error<_>>(Partial::new(" \t\n\r")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn multispace1<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputMode,
+    <T as InputTakeAtPosition>::Item: AsChar + Clone,
+}
+
+dispatch_mode! {
+  /// Recognizes an ASCII identifier: a letter or underscore followed by letters,
+  /// digits and underscores, like `[A-Za-z_][A-Za-z0-9_]*`
+  ///
+  /// Uses [streaming::identifier] if the input is [partial](crate::partial::Partial),
+  /// and [complete::identifier] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::identifier;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(identifier::<_, Error<_>>("_foo1 = 2"), Ok((" = 2", "_foo1")));
+  /// assert_eq!(identifier::<_, Error<_>>("1bar"), Err(Err::Error(Error::new("1bar", ErrorKind::Identifier))));
+  /// assert_eq!(identifier::<_, Error<_>>(Partial::new("bar")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn identifier<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputIter + InputTake + InputLength + InputMode,
+    T: Slice<RangeFrom<usize>>,
+    <T as InputTakeAtPosition>::Item: AsChar,
+    <T as InputIter>::Item: AsChar + Copy,
+}
+
+dispatch_mode! {
+  /// Recognizes an identifier made of a character accepted by `start` followed
+  /// by characters accepted by `cont`
+  ///
+  /// Uses [streaming::identifier_with] if the input is [partial](crate::partial::Partial),
+  /// and [complete::identifier_with] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::identifier_with;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = identifier_with::<_, Error<_>, _, _>(|c| c == '$', |c| c.is_ascii_alphanumeric());
+  /// assert_eq!(parser("$foo1 bar"), Ok((" bar", "$foo1")));
+  /// assert_eq!(parser("foo"), Err(Err::Error(Error::new("foo", ErrorKind::Identifier))));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = identifier_with::<_, Error<_>, _, _>(|c| c == '$', |c| c.is_ascii_alphanumeric());
+  /// assert_eq!(parser(Partial::new("$foo")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn identifier_with<T, E: ParseError<T>, S, C>(
+    start: S = &start,
+    cont: C = &cont,
+  ) -> impl Fn(T) -> IResult<T, T, E>
+  where
+    T: InputTakeAtPosition + InputIter + InputTake + InputLength + InputMode,
+    T: Slice<RangeFrom<usize>>,
+    <T as InputTakeAtPosition>::Item: AsChar,
+    <T as InputIter>::Item: AsChar + Copy,
+    S: Fn(char) -> bool,
+    C: Fn(char) -> bool,
+}
+
+dispatch_mode! {
+  /// Recognizes between `m` and `n` ASCII digits and converts them to an integer.
+  ///
+  /// Uses [streaming::digit_m_n_value] if the input is [partial](crate::partial::Partial),
+  /// and [complete::digit_m_n_value] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::character::digit_m_n_value;
+  /// use nom::partial::Partial;
+  ///
+  /// let parser = digit_m_n_value::<_, u16, Error<_>>(2, 3);
+  /// assert_eq!(parser("1234"), Ok(("4", 123)));
+  /// assert_eq!(parser("12"), Ok(("", 12)));
+  ///
+  /// // the same parser on a partial input
+  /// let parser = digit_m_n_value::<_, u16, Error<_>>(2, 3);
+  /// assert_eq!(parser(Partial::new("12")), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn digit_m_n_value<T, O, E: ParseError<T>>(
+    m: usize,
+    n: usize,
+  ) -> impl Fn(T) -> IResult<T, O, E>
+  where
+    T: InputIter + InputLength + InputTake + ParseTo<O> + InputMode,
+    <T as InputIter>::Item: AsChar,
+}
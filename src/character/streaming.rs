@@ -2,11 +2,13 @@
 //!
 //! Functions recognizing specific characters
 
+use crate::combinator::iterator;
 use crate::error::ParseError;
 use crate::internal::{Err, IResult, Needed};
 use crate::lib::std::ops::{Range, RangeFrom, RangeTo};
-use crate::traits::{AsChar, FindToken, InputIter, InputLength, InputTakeAtPosition, Slice};
+use crate::traits::{AsChar, FindToken, InputIter, InputLength, InputTake, InputTakeAtPosition};
 use crate::traits::{Compare, CompareResult};
+use crate::traits::{ParseTo, Slice};
 
 use crate::error::ErrorKind;
 
@@ -232,6 +234,124 @@ where
   }
 }
 
+/// Recognizes a line and consumes its terminator, which can be `\n`, `\r\n` or a lone `\r`.
+///
+/// The returned line does not contain the terminator.
+///
+/// *Streaming version*: Will return `Err(nom::Err::Incomplete(_))` if no terminator was found,
+/// or if the input ends with `\r`, since it could be the first half of a `\r\n` split across buffers.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::ErrorKind, IResult, Needed};
+/// # use nom::character::streaming::line;
+/// assert_eq!(line::<_, (_, ErrorKind)>("ab\r\ncd"), Ok(("cd", "ab")));
+/// assert_eq!(line::<_, (_, ErrorKind)>("ab\rcd"), Ok(("cd", "ab")));
+/// assert_eq!(line::<_, (_, ErrorKind)>("ab\r"), Err(Err::Incomplete(Needed::new(1))));
+/// assert_eq!(line::<_, (_, ErrorKind)>("ab"), Err(Err::Incomplete(Needed::Unknown)));
+/// ```
+pub fn line<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+where
+  T: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  T: InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  match input.position(|item| {
+    let c = item.as_char();
+    c == '\r' || c == '\n'
+  }) {
+    None => Err(Err::Incomplete(Needed::Unknown)),
+    Some(index) => {
+      let mut it = input.slice(index..).iter_elements();
+      let end = match (
+        it.next().map(AsChar::as_char),
+        it.next().map(AsChar::as_char),
+      ) {
+        (Some('\r'), None) => return Err(Err::Incomplete(Needed::new(1))),
+        (Some('\r'), Some('\n')) => index + 2,
+        _ => index + 1,
+      };
+      Ok((input.slice(end..), input.slice(..index)))
+    }
+  }
+}
+
+/// Recognizes a logical line made of a first line followed by continuation lines,
+/// which start with a space or a tab, like email headers.
+///
+/// The line terminators can be `\n`, `\r\n` or a lone `\r`. The returned line contains the
+/// folds, but not the final terminator, which is consumed. Use
+/// [unfold](crate::combinator::unfold) to remove the folds.
+///
+/// *Streaming version*: Will return `Err(nom::Err::Incomplete(_))` if no terminator was found,
+/// or if the input ends right after a terminator, since the next line could be a continuation.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::ErrorKind, IResult, Needed};
+/// # use nom::character::streaming::folded_line;
+/// assert_eq!(folded_line::<_, (_, ErrorKind)>("Subject: a\r\n b\r\nTo"), Ok(("To", "Subject: a\r\n b")));
+/// assert_eq!(folded_line::<_, (_, ErrorKind)>("Subject: a\r\n"), Err(Err::Incomplete(Needed::new(1))));
+/// assert_eq!(folded_line::<_, (_, ErrorKind)>("Subject: a"), Err(Err::Incomplete(Needed::Unknown)));
+/// ```
+pub fn folded_line<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+where
+  T: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  T: InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  let mut start = 0;
+  loop {
+    let index = match input.slice(start..).position(|item| {
+      let c = item.as_char();
+      c == '\r' || c == '\n'
+    }) {
+      None => return Err(Err::Incomplete(Needed::Unknown)),
+      Some(index) => start + index,
+    };
+
+    let mut it = input.slice(index..).iter_elements().map(AsChar::as_char);
+    let first = it.next();
+    let mut next = it.next();
+    let mut end = index + 1;
+    if first == Some('\r') && next == Some('\n') {
+      end += 1;
+      next = it.next();
+    }
+
+    match next {
+      None => return Err(Err::Incomplete(Needed::new(1))),
+      Some(' ') | Some('\t') => start = end,
+      _ => return Ok((input.slice(end..), input.slice(..index))),
+    }
+  }
+}
+
+/// Creates an iterator over the lines of the input, as recognized by [line].
+///
+/// *Streaming version*: the iteration stops before a line that is not terminated yet.
+/// [crate::combinator::ParserIterator::remaining] then returns the input starting at that line, which should
+/// be kept and completed with the next buffer.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// # use nom::character::streaming::lines;
+/// let mut it = lines::<_, (_, ErrorKind)>("first\r\nsecond\nthi");
+///
+/// assert_eq!(it.collect::<Vec<_>>(), vec!["first", "second"]);
+/// assert_eq!(it.remaining(), &"thi");
+/// assert_eq!(it.finish(), Err(Err::Incomplete(Needed::Unknown)));
+/// ```
+pub fn lines<T, E: ParseError<T>>(input: T) -> super::Lines<T, E>
+where
+  T: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  T: InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  iterator(input, line as fn(T) -> IResult<T, T, E>)
+}
+
 /// Matches a newline character '\\n'.
 ///
 /// *Streaming version*: Will return `Err(nom::Err::Incomplete(_))` if there's not enough input data.
@@ -609,6 +729,237 @@ where
   )
 }
 
+/// Recognizes an ASCII identifier: a letter or underscore followed by letters,
+/// digits and underscores, like `[A-Za-z_][A-Za-z0-9_]*`
+///
+/// *Streaming version*: Will return `Err(nom::Err::Incomplete(_))` if there's not enough input data,
+/// or if no terminating token is found.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult, Needed};
+/// # use nom::character::streaming::identifier;
+/// fn parser(input: &str) -> IResult<&str, &str> {
+///     identifier(input)
+/// }
+///
+/// assert_eq!(parser("_foo1 = 2"), Ok((" = 2", "_foo1")));
+/// assert_eq!(parser("1bar"), Err(Err::Error(Error::new("1bar", ErrorKind::Identifier))));
+/// assert_eq!(parser("bar"), Err(Err::Incomplete(Needed::new(1))));
+/// ```
+pub fn identifier<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
+where
+  T: InputTakeAtPosition + InputIter + InputTake + InputLength + Slice<RangeFrom<usize>>,
+  <T as InputTakeAtPosition>::Item: AsChar,
+  <T as InputIter>::Item: AsChar + Copy,
+{
+  identifier_with(
+    |c| c.is_ascii_alphabetic() || c == '_',
+    |c| c.is_ascii_alphanumeric() || c == '_',
+  )(input)
+}
+
+/// Recognizes an identifier made of a character accepted by `start` followed
+/// by characters accepted by `cont`
+///
+/// *Streaming version*: Will return `Err(nom::Err::Incomplete(_))` if there's not enough input data,
+/// or if no terminating token is found.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult, Needed};
+/// # use nom::character::streaming::identifier_with;
+/// fn parser(input: &str) -> IResult<&str, &str> {
+///     identifier_with(|c| c.is_alphabetic(), |c| c.is_alphanumeric() || c == '-')(input)
+/// }
+///
+/// assert_eq!(parser("kebab-case-1;"), Ok((";", "kebab-case-1")));
+/// assert_eq!(parser("-a"), Err(Err::Error(Error::new("-a", ErrorKind::Identifier))));
+/// assert_eq!(parser("kebab"), Err(Err::Incomplete(Needed::new(1))));
+/// ```
+pub fn identifier_with<T, E: ParseError<T>, S, C>(
+  start: S,
+  cont: C,
+) -> impl Fn(T) -> IResult<T, T, E>
+where
+  T: InputTakeAtPosition + InputIter + InputTake + InputLength + Slice<RangeFrom<usize>>,
+  <T as InputTakeAtPosition>::Item: AsChar,
+  <T as InputIter>::Item: AsChar + Copy,
+  S: Fn(char) -> bool,
+  C: Fn(char) -> bool,
+{
+  move |input: T| match input.iter_elements().next() {
+    Some(c) if start(c.as_char()) => {
+      let first_len = c.len();
+      let (_, rest) = input
+        .slice(first_len..)
+        .split_at_position::<_, E>(|item| !cont(item.as_char()))?;
+      Ok(input.take_split(first_len + rest.input_len()))
+    }
+    Some(_) => Err(Err::Error(E::from_error_kind(input, ErrorKind::Identifier))),
+    None => Err(Err::Incomplete(Needed::new(1))),
+  }
+}
+
+/// Recognizes between `m` and `n` ASCII digits and converts them to an integer.
+///
+/// At most `n` digits are taken. Fewer than `m` digits, or no digit at all, is
+/// an error with `ErrorKind::DigitCount`, and a value that does not fit in the
+/// output type is an error with `ErrorKind::TooLarge`.
+///
+/// *Streaming version*: will return `Err(nom::Err::Incomplete(_))` if the input
+/// ends before a non digit character or `n` digits.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::character::streaming::digit_m_n_value;
+///
+/// fn year(s: &str) -> IResult<&str, u16> {
+///   digit_m_n_value(4, 4)(s)
+/// }
+///
+/// assert_eq!(year("2021-06"), Ok(("-06", 2021)));
+/// assert_eq!(year("21-06"), Err(Err::Error(Error::new("21-06", ErrorKind::DigitCount))));
+/// assert_eq!(year("20"), Err(Err::Incomplete(Needed::new(2))));
+/// ```
+pub fn digit_m_n_value<T, O, E: ParseError<T>>(m: usize, n: usize) -> impl Fn(T) -> IResult<T, O, E>
+where
+  T: InputIter + InputLength + InputTake + ParseTo<O>,
+  <T as InputIter>::Item: AsChar,
+{
+  move |input: T| {
+    // counts the digits, stopping after n of them
+    let mut count = 0;
+    let mut end = None;
+    for (index, c) in input.iter_indices() {
+      if count == n || !c.is_dec_digit() {
+        end = Some(index);
+        break;
+      }
+      count += 1;
+    }
+
+    let index = match end {
+      Some(index) if count >= m && count > 0 => index,
+      Some(_) => {
+        return Err(Err::Error(E::from_error_kind(input, ErrorKind::DigitCount)));
+      }
+      None if count == n && n > 0 => input.input_len(),
+      None => {
+        let needed = if m > count { m - count } else { 1 };
+        return Err(Err::Incomplete(Needed::new(needed)));
+      }
+    };
+    let (rest, digits) = input.take_split(index);
+    match digits.parse_to() {
+      Some(value) => Ok((rest, value)),
+      None => Err(Err::Error(E::from_error_kind(input, ErrorKind::TooLarge))),
+    }
+  }
+}
+
+/// Recognizes a run of percent-encoded text and decodes it.
+///
+/// The run is made of the characters accepted by `allowed`, which are kept
+/// as is, and of `%` followed by two hexadecimal digits, which are decoded to
+/// the corresponding byte. `%` always starts an encoded byte, even if it is
+/// accepted by `allowed`. The decoded bytes must form valid UTF-8. If nothing
+/// had to be decoded, the result borrows from the input.
+///
+/// *Streaming version*: will return `Err(nom::Err::Incomplete(_))` if the input ends
+/// inside the run, or inside a `%` sequence.
+/// # Example
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::character::streaming::percent_decoded;
+/// use std::borrow::Cow;
+///
+/// fn parser(s: &str) -> IResult<&str, Cow<str>> {
+///   percent_decoded("abcdefghijklmnopqrstuvwxyz-._~")(s)
+/// }
+///
+/// assert_eq!(parser("caf%C3%A9&"), Ok(("&", Cow::Owned(String::from("café")))));
+/// assert_eq!(parser("abc&"), Ok(("&", Cow::Borrowed("abc"))));
+/// assert_eq!(parser("abc%2"), Err(Err::Incomplete(Needed::new(1))));
+/// assert_eq!(parser("abc"), Err(Err::Incomplete(Needed::new(1))));
+/// assert_eq!(parser("abc%FF&"), Err(Err::Error(Error::new("abc%FF&", ErrorKind::PercentEncoding))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn percent_decoded<'a, T, Error: ParseError<&'a str>>(
+  allowed: T,
+) -> impl Fn(&'a str) -> IResult<&'a str, crate::lib::std::borrow::Cow<'a, str>, Error>
+where
+  T: FindToken<char>,
+{
+  use crate::lib::std::borrow::Cow;
+  use crate::lib::std::string::String;
+  use crate::lib::std::vec::Vec;
+
+  move |input: &'a str| {
+    let bytes = input.as_bytes();
+    let mut decoded: Option<Vec<u8>> = None;
+    let mut index = 0;
+
+    while let Some(c) = input[index..].chars().next() {
+      if c == '%' {
+        let digits = &bytes[index + 1..];
+        if digits.len() < 2 {
+          if digits.iter().all(|d| d.is_ascii_hexdigit()) {
+            return Err(Err::Incomplete(Needed::new(2 - digits.len())));
+          }
+          return Err(Err::Error(Error::from_error_kind(
+            &input[index..],
+            ErrorKind::PercentEncoding,
+          )));
+        }
+        match (
+          (digits[0] as char).to_digit(16),
+          (digits[1] as char).to_digit(16),
+        ) {
+          (Some(hi), Some(lo)) => {
+            decoded
+              .get_or_insert_with(|| bytes[..index].to_vec())
+              .push((hi * 16 + lo) as u8);
+            index += 3;
+          }
+          _ => {
+            return Err(Err::Error(Error::from_error_kind(
+              &input[index..],
+              ErrorKind::PercentEncoding,
+            )))
+          }
+        }
+      } else if allowed.find_token(c) {
+        if let Some(v) = decoded.as_mut() {
+          v.extend_from_slice(&bytes[index..index + c.len_utf8()]);
+        }
+        index += c.len_utf8();
+      } else {
+        break;
+      }
+    }
+
+    if index == input.len() {
+      return Err(Err::Incomplete(Needed::new(1)));
+    }
+
+    let res = match decoded {
+      Some(v) => match String::from_utf8(v) {
+        Ok(s) => Cow::Owned(s),
+        Err(_) => {
+          return Err(Err::Error(Error::from_error_kind(
+            input,
+            ErrorKind::PercentEncoding,
+          )))
+        }
+      },
+      None => Cow::Borrowed(&input[..index]),
+    };
+    Ok((&input[index..], res))
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -992,4 +1343,28 @@ mod tests {
       Err(Err::Error(error_position!("\ra", ErrorKind::CrLf)))
     );
   }
+
+  #[test]
+  fn line_split_across_buffers() {
+    assert_parse!(line(&b"a\r"[..]), Err(Err::Incomplete(Needed::new(1))));
+    assert_parse!(line(&b"a\r\nb"[..]), Ok((&b"b"[..], &b"a"[..])));
+    assert_parse!(line(&b"\r\r"[..]), Ok((&b"\r"[..], &b""[..])));
+
+    let mut it = lines::<_, (_, ErrorKind)>(&b"a\nb\r"[..]);
+    assert_eq!(it.collect::<Vec<_>>(), vec![&b"a"[..]]);
+    assert_eq!(it.remaining(), &&b"b\r"[..]);
+  }
+
+  #[test]
+  fn folded_line_continuations() {
+    assert_parse!(
+      folded_line(&b"a\n b\r\tc\rd"[..]),
+      Ok((&b"d"[..], &b"a\n b\r\tc"[..]))
+    );
+    assert_parse!(
+      folded_line(&b"a\r"[..]),
+      Err(Err::Incomplete(Needed::new(1)))
+    );
+    assert_parse!(folded_line(&b"\n\n"[..]), Ok((&b"\n"[..], &b""[..])));
+  }
 }
@@ -0,0 +1,383 @@
+//! Adapter turning streaming parsers into tokio codecs
+//!
+//! [ParserDecoder] implements [Decoder](tokio_util::codec::Decoder) for a
+//! streaming parser on byte slices, so it can be used with
+//! [FramedRead](tokio_util::codec::FramedRead) or
+//! [Framed](tokio_util::codec::Framed). The parser is applied on the bytes
+//! received so far:
+//! * if it succeeds, the consumed bytes are removed from the buffer and the
+//!   output is returned
+//! * if it returns `Err::Incomplete`, the codec waits for more bytes
+//! * if it returns `Err::Error` or `Err::Failure`, the decoding fails with a
+//!   [DecodeError]
+//! * if it succeeds without consuming anything, the decoding fails with
+//!   `ErrorKind::Many0`, like `many0`, since it would return the same value
+//!   forever
+//!
+//! With the `async` feature, [parse_reader] and [parse_byte_stream] wrap an
+//! asynchronous source in such a decoder, and return the parsed values as a
+//! `Stream`.
+
+use crate::error::{ErrorFrames, ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::fmt;
+use ::bytes::{Buf, BytesMut};
+#[cfg(feature = "async")]
+use ::futures_core::Stream;
+use std::io;
+use std::marker::PhantomData;
+#[cfg(feature = "async")]
+use tokio::io::AsyncRead;
+use tokio_util::codec::Decoder;
+#[cfg(feature = "async")]
+use tokio_util::{codec::FramedRead, io::StreamReader};
+
+/// Error returned by [ParserDecoder]
+#[derive(Debug)]
+pub enum DecodeError {
+  /// Error from the underlying reader
+  Io(io::Error),
+  /// The parser returned an error
+  Parse {
+    /// Error code of the deepest frame of the error, `None` if the error
+    /// type does not record frames, like `()`
+    kind: Option<ErrorKind>,
+    /// Position of the deepest frame, from the start of the current frame
+    offset: Option<usize>,
+  },
+}
+
+impl From<io::Error> for DecodeError {
+  fn from(e: io::Error) -> Self {
+    DecodeError::Io(e)
+  }
+}
+
+impl fmt::Display for DecodeError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DecodeError::Io(e) => write!(f, "I/O error: {}", e),
+      DecodeError::Parse {
+        kind: Some(kind),
+        offset: Some(offset),
+      } => write!(
+        f,
+        "error {} at offset {} of the frame",
+        kind.description(),
+        offset
+      ),
+      DecodeError::Parse { .. } => write!(f, "parse error"),
+    }
+  }
+}
+
+impl std::error::Error for DecodeError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      DecodeError::Io(e) => Some(e),
+      DecodeError::Parse { .. } => None,
+    }
+  }
+}
+
+/// Streaming parser on byte slices, as accepted by [ParserDecoder]
+///
+/// It is implemented for functions returning any error type implementing
+/// [ParseError] and [ErrorFrames], borrowing the input or not. Closures
+/// cannot name an error type depending on the lifetime of their input, so
+/// parsers returning such errors must be written as functions.
+pub trait FrameParser<'a, O> {
+  /// Error type returned by the parser
+  type Error: ParseError<&'a [u8]> + ErrorFrames<&'a [u8]>;
+
+  /// Applies the parser on the bytes received so far
+  fn parse_frame(&mut self, input: &'a [u8]) -> IResult<&'a [u8], O, Self::Error>;
+}
+
+impl<'a, O, E, F> FrameParser<'a, O> for F
+where
+  F: FnMut(&'a [u8]) -> IResult<&'a [u8], O, E>,
+  E: ParseError<&'a [u8]> + ErrorFrames<&'a [u8]>,
+{
+  type Error = E;
+
+  fn parse_frame(&mut self, input: &'a [u8]) -> IResult<&'a [u8], O, E> {
+    self(input)
+  }
+}
+
+/// Decoder applying a streaming parser on the received bytes.
+///
+/// The parser must consume input when it succeeds, since the decoder is
+/// called again as long as it returns frames: a parser succeeding without
+/// consuming anything returns a [DecodeError::Parse] with `ErrorKind::Many0`.
+///
+/// ```rust
+/// use nom::codec::ParserDecoder;
+/// use nom::bytes::streaming::take;
+/// use nom::number::streaming::be_u8;
+/// use nom::combinator::map;
+/// use nom::multi::length_data;
+/// use nom::IResult;
+/// use bytes::BytesMut;
+/// use tokio_util::codec::Decoder;
+///
+/// fn frame(i: &[u8]) -> IResult<&[u8], Vec<u8>> {
+///   map(length_data(be_u8), |data: &[u8]| data.to_vec())(i)
+/// }
+///
+/// let mut decoder = ParserDecoder::new(frame);
+/// let mut buffer = BytesMut::from(&b"\x03ab"[..]);
+///
+/// assert_eq!(decoder.decode(&mut buffer).unwrap(), None);
+/// buffer.extend_from_slice(b"c\x01d");
+/// assert_eq!(decoder.decode(&mut buffer).unwrap(), Some(b"abc".to_vec()));
+/// assert_eq!(decoder.decode(&mut buffer).unwrap(), Some(b"d".to_vec()));
+/// assert!(buffer.is_empty());
+/// ```
+pub struct ParserDecoder<F, O> {
+  parser: F,
+  output: PhantomData<fn() -> O>,
+}
+
+impl<F, O> ParserDecoder<F, O>
+where
+  F: for<'a> FrameParser<'a, O>,
+{
+  /// Creates a decoder from a streaming parser
+  pub fn new(parser: F) -> Self {
+    ParserDecoder {
+      parser,
+      output: PhantomData,
+    }
+  }
+}
+
+impl<F, O> Decoder for ParserDecoder<F, O>
+where
+  F: for<'a> FrameParser<'a, O>,
+{
+  type Item = O;
+  type Error = DecodeError;
+
+  fn decode(&mut self, src: &mut BytesMut) -> Result<Option<O>, DecodeError> {
+    let parsed = match self.parser.parse_frame(&src[..]) {
+      Ok((remaining, _)) if remaining.len() == src.len() => {
+        return Err(DecodeError::Parse {
+          kind: Some(ErrorKind::Many0),
+          offset: Some(0),
+        })
+      }
+      Ok((remaining, output)) => Ok((src.len() - remaining.len(), output)),
+      Err(Err::Incomplete(needed)) => Err(needed),
+      Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+        let deepest = e.deepest();
+        return Err(DecodeError::Parse {
+          kind: deepest.and_then(|frame| frame.kind),
+          offset: deepest.map(|frame| src.len() - frame.input.len()),
+        });
+      }
+    };
+
+    match parsed {
+      Ok((consumed, output)) => {
+        src.advance(consumed);
+        Ok(Some(output))
+      }
+      Err(needed) => {
+        if let Some(size) = needed.size() {
+          src.reserve(size.get());
+        }
+        Ok(None)
+      }
+    }
+  }
+}
+
+/// Applies a streaming parser on the data read from an [AsyncRead],
+/// and returns the parsed values as a `Stream`.
+///
+/// The buffer grows as long as the parser returns `Err::Incomplete`. If the
+/// reader ends in the middle of a value, the stream returns a [DecodeError::Io].
+///
+/// ```rust
+/// use nom::codec::parse_reader;
+/// use nom::combinator::map;
+/// use nom::multi::length_data;
+/// use nom::number::streaming::be_u8;
+/// use nom::IResult;
+/// use futures::{executor::block_on, StreamExt};
+///
+/// fn frame(i: &[u8]) -> IResult<&[u8], Vec<u8>> {
+///   map(length_data(be_u8), |data: &[u8]| data.to_vec())(i)
+/// }
+///
+/// let frames = block_on(async {
+///   parse_reader(&b"\x02ab\x01c"[..], frame)
+///     .map(Result::unwrap)
+///     .collect::<Vec<_>>()
+///     .await
+/// });
+/// assert_eq!(frames, vec![b"ab".to_vec(), b"c".to_vec()]);
+/// ```
+#[cfg(feature = "async")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "async")))]
+pub fn parse_reader<R, F, O>(reader: R, parser: F) -> FramedRead<R, ParserDecoder<F, O>>
+where
+  R: AsyncRead,
+  F: for<'a> FrameParser<'a, O>,
+{
+  FramedRead::new(reader, ParserDecoder::new(parser))
+}
+
+/// Applies a streaming parser on the chunks of bytes returned by a `Stream`,
+/// and returns the parsed values as a `Stream`.
+///
+/// A value can be split across chunks. Errors from the source stream are
+/// returned as [DecodeError::Io].
+///
+/// ```rust
+/// use nom::codec::parse_byte_stream;
+/// use nom::character::streaming::{digit1, line_ending};
+/// use nom::combinator::map_res;
+/// use nom::sequence::terminated;
+/// use nom::IResult;
+/// use bytes::Bytes;
+/// use futures::{executor::block_on, stream, StreamExt};
+/// use std::io;
+///
+/// fn number(i: &[u8]) -> IResult<&[u8], u32> {
+///   map_res(terminated(digit1, line_ending), |d| std::str::from_utf8(d).unwrap().parse())(i)
+/// }
+///
+/// let chunks = stream::iter(vec![
+///   Ok::<_, io::Error>(Bytes::from_static(b"12\n3")),
+///   Ok(Bytes::from_static(b"45\r\n")),
+/// ]);
+/// let numbers = block_on(parse_byte_stream(chunks, number).map(Result::unwrap).collect::<Vec<_>>());
+/// assert_eq!(numbers, vec![12, 345]);
+/// ```
+#[cfg(feature = "async")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "async")))]
+pub fn parse_byte_stream<S, B, SE, F, O>(
+  stream: S,
+  parser: F,
+) -> FramedRead<StreamReader<S, B>, ParserDecoder<F, O>>
+where
+  S: Stream<Item = Result<B, SE>>,
+  B: Buf,
+  SE: Into<io::Error>,
+  F: for<'a> FrameParser<'a, O>,
+{
+  FramedRead::new(StreamReader::new(stream), ParserDecoder::new(parser))
+}
+
+impl<F, O> fmt::Debug for ParserDecoder<F, O> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("ParserDecoder").finish()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::bytes::streaming::tag;
+  use crate::error::VerboseError;
+
+  fn abcd(i: &[u8]) -> IResult<&[u8], usize> {
+    let (i, _) = tag("ab")(i)?;
+    let (i, o) = tag("cd")(i)?;
+    Ok((i, o.len()))
+  }
+
+  #[test]
+  fn decode_error_offset() {
+    let mut decoder = ParserDecoder::new(abcd);
+    let mut buffer = BytesMut::from(&b"abcdabx"[..]);
+
+    assert_eq!(decoder.decode(&mut buffer).unwrap(), Some(2));
+    match decoder.decode(&mut buffer) {
+      Err(DecodeError::Parse { kind, offset }) => {
+        assert_eq!(kind, Some(ErrorKind::Tag));
+        assert_eq!(offset, Some(2));
+      }
+      other => panic!("unexpected result: {:?}", other),
+    }
+
+    let e = DecodeError::Parse {
+      kind: Some(ErrorKind::Tag),
+      offset: Some(2),
+    };
+    assert_eq!(e.to_string(), "error Tag at offset 2 of the frame");
+  }
+
+  #[test]
+  fn decode_error_types() {
+    fn verbose(i: &[u8]) -> IResult<&[u8], usize, VerboseError<&[u8]>> {
+      let (i, o) = tag("ab")(i)?;
+      Ok((i, o.len()))
+    }
+    fn unit(i: &[u8]) -> IResult<&[u8], usize, ()> {
+      let (i, o) = tag("ab")(i)?;
+      Ok((i, o.len()))
+    }
+
+    let mut buffer = BytesMut::from(&b"abx"[..]);
+    let mut decoder = ParserDecoder::new(verbose);
+    assert_eq!(decoder.decode(&mut buffer).unwrap(), Some(2));
+    match decoder.decode(&mut buffer) {
+      Err(DecodeError::Parse { kind, offset }) => {
+        assert_eq!(kind, Some(ErrorKind::Tag));
+        assert_eq!(offset, Some(0));
+      }
+      other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut buffer = BytesMut::from(&b"x"[..]);
+    match ParserDecoder::new(unit).decode(&mut buffer) {
+      Err(DecodeError::Parse { kind, offset }) => {
+        assert_eq!(kind, None);
+        assert_eq!(offset, None);
+      }
+      other => panic!("unexpected result: {:?}", other),
+    }
+  }
+
+  #[test]
+  fn decode_no_progress() {
+    fn empty(i: &[u8]) -> IResult<&[u8], usize> {
+      Ok((i, 0))
+    }
+
+    let mut decoder = ParserDecoder::new(empty);
+    let mut buffer = BytesMut::from(&b"ab"[..]);
+    match decoder.decode(&mut buffer) {
+      Err(DecodeError::Parse { kind, offset }) => {
+        assert_eq!(kind, Some(ErrorKind::Many0));
+        assert_eq!(offset, Some(0));
+      }
+      other => panic!("unexpected result: {:?}", other),
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "async")]
+  fn parse_reader_truncated() {
+    use futures::{executor::block_on, StreamExt};
+
+    fn abcd(i: &[u8]) -> IResult<&[u8], usize> {
+      let (i, o) = tag("abcd")(i)?;
+      Ok((i, o.len()))
+    }
+
+    let mut stream = parse_reader(&b"abcdab"[..], abcd);
+
+    block_on(async {
+      assert_eq!(stream.next().await.map(Result::unwrap), Some(4));
+      match stream.next().await {
+        Some(Err(DecodeError::Io(_))) => {}
+        other => panic!("unexpected result: {:?}", other),
+      }
+    });
+  }
+}
@@ -0,0 +1,177 @@
+//! Conversion of parse errors to `codespan-reporting` diagnostics
+//!
+//! The error is reported at its innermost position, and each context frame
+//! and element index of a [VerboseError] becomes a secondary label, so the
+//! rendered diagnostic shows what the parser expected and what it was parsing
+//! at the time.
+//!
+//! ```rust
+//! use codespan_reporting::diagnostic::LabelStyle;
+//! use nom::{Err, IResult};
+//! use nom::character::complete::{char, digit1};
+//! use nom::codespan::ToDiagnostic;
+//! use nom::error::{context, VerboseError};
+//! use nom::sequence::{delimited, preceded};
+//!
+//! fn index(i: &str) -> IResult<&str, &str, VerboseError<&str>> {
+//!   preceded(char('a'), context("index", delimited(char('['), digit1, char(']'))))(i)
+//! }
+//!
+//! let input = "a[12";
+//! let e = match index(input) {
+//!   Err(Err::Error(e)) => e,
+//!   _ => unreachable!(),
+//! };
+//! let diagnostic = e.to_diagnostic((), input);
+//! assert_eq!(diagnostic.message, "expected ']'");
+//! assert_eq!(diagnostic.labels[0].style, LabelStyle::Primary);
+//! assert_eq!(diagnostic.labels[0].range, 4..4);
+//! assert_eq!(diagnostic.labels[1].message, "while parsing index");
+//! assert_eq!(diagnostic.labels[1].range, 1..2);
+//! ```
+
+use crate::error::{Error, ErrorKind, VerboseError, VerboseErrorKind};
+use crate::lib::std::ops::Range;
+use crate::lib::std::string::{String, ToString};
+use crate::lib::std::vec::Vec;
+use crate::traits::Offset;
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+
+/// Errors that can be reported as a diagnostic over the parsed source
+pub trait ToDiagnostic {
+  /// Creates an error diagnostic, `input` being the whole source the
+  /// parser was called on, registered as `file_id`
+  fn to_diagnostic<FileId: Copy>(&self, file_id: FileId, input: &str) -> Diagnostic<FileId>;
+}
+
+impl ToDiagnostic for Error<&str> {
+  fn to_diagnostic<FileId: Copy>(&self, file_id: FileId, input: &str) -> Diagnostic<FileId> {
+    let message = kind_message(self.code);
+    Diagnostic::error()
+      .with_message(message.clone())
+      .with_labels(vec![
+        Label::primary(file_id, range(input, self.input)).with_message(message)
+      ])
+  }
+}
+
+impl ToDiagnostic for VerboseError<&str> {
+  fn to_diagnostic<FileId: Copy>(&self, file_id: FileId, input: &str) -> Diagnostic<FileId> {
+    let mut labels = Vec::new();
+    let mut message = String::new();
+
+    for (i, (substring, kind)) in self.errors.iter().enumerate() {
+      match kind {
+        // the first error is where the parser failed, the following nom
+        // errors only repeat it from the combinators that propagated it
+        VerboseErrorKind::Nom(k) if i == 0 => message = kind_message(*k),
+        VerboseErrorKind::Char(c) if i == 0 => message = format!("expected '{}'", c),
+        VerboseErrorKind::Tag(t) if i == 0 => message = format!("expected `{}`", t),
+        VerboseErrorKind::Context(ctx) => labels.push(
+          Label::secondary(file_id, range(input, substring))
+            .with_message(format!("while parsing {}", ctx)),
+        ),
+        VerboseErrorKind::Nom(ErrorKind::Unclosed) => labels.push(
+          Label::secondary(file_id, range(input, substring)).with_message("unclosed delimiter"),
+        ),
+        VerboseErrorKind::Element(_, index) => labels.push(
+          Label::secondary(file_id, range(input, substring))
+            .with_message(format!("in element {}", index)),
+        ),
+        _ => {}
+      }
+    }
+
+    if let Some((substring, _)) = self.errors.first() {
+      labels.insert(
+        0,
+        Label::primary(file_id, range(input, substring)).with_message(message.clone()),
+      );
+    }
+
+    Diagnostic::error()
+      .with_message(message)
+      .with_labels(labels)
+  }
+}
+
+fn kind_message(kind: ErrorKind) -> String {
+  kind.description().to_string()
+}
+
+/// The range covers the first character at the error position, or is empty
+/// at the end of the input
+fn range(input: &str, at: &str) -> Range<usize> {
+  let start = input.offset(at);
+  let len = at.chars().next().map(char::len_utf8).unwrap_or(0);
+  start..start + len
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::branch::alt;
+  use crate::bytes::complete::tag;
+  use crate::character::complete::{alpha1, digit1, multispace0};
+  use crate::error::context;
+  use crate::sequence::{preceded, separated_pair};
+  use crate::{Err, IResult};
+  use codespan_reporting::diagnostic::LabelStyle;
+  use codespan_reporting::files::SimpleFile;
+  use codespan_reporting::term::{self, termcolor::NoColor};
+
+  fn assignment(i: &str) -> IResult<&str, (&str, &str), VerboseError<&str>> {
+    context(
+      "assignment",
+      separated_pair(
+        alpha1,
+        preceded(multispace0, tag("=")),
+        preceded(
+          multispace0,
+          context("value", alt((digit1, preceded(tag("-"), digit1)))),
+        ),
+      ),
+    )(i)
+  }
+
+  #[test]
+  fn labels() {
+    let input = "x = é";
+    let e = match assignment(input) {
+      Err(Err::Error(e)) => e,
+      res => panic!("unexpected result: {:?}", res),
+    };
+    let diagnostic = e.to_diagnostic((), input);
+    let labels: Vec<_> = diagnostic
+      .labels
+      .iter()
+      .map(|l| (l.style, l.range.clone(), l.message.as_str()))
+      .collect();
+    assert_eq!(
+      labels,
+      vec![
+        (LabelStyle::Primary, 4..6, "expected `-`"),
+        (LabelStyle::Secondary, 4..6, "while parsing value"),
+        (LabelStyle::Secondary, 0..1, "while parsing assignment"),
+      ]
+    );
+
+    let file = SimpleFile::new("input", input);
+    let mut out = NoColor::new(Vec::new());
+    term::emit(&mut out, &term::Config::default(), &file, &diagnostic).unwrap();
+    let rendered = String::from_utf8(out.into_inner()).unwrap();
+    assert!(rendered.contains("while parsing assignment"));
+  }
+
+  #[test]
+  fn simple_error() {
+    let input = "12";
+    let e = match alpha1::<_, Error<&str>>(input) {
+      Err(Err::Error(e)) => e,
+      res => panic!("unexpected result: {:?}", res),
+    };
+    let diagnostic = e.to_diagnostic((), input);
+    assert_eq!(diagnostic.message, "Alphabetic");
+    assert_eq!(diagnostic.labels[0].range, 0..1);
+  }
+}
@@ -5,7 +5,7 @@
 #[cfg(feature = "alloc")]
 use crate::lib::std::boxed::Box;
 
-use crate::error::{ErrorKind, FromExternalError, ParseError};
+use crate::error::{ContextError, ErrorKind, FromExternalError, ParseError, TrailingData};
 use crate::internal::*;
 use crate::lib::std::borrow::Borrow;
 use crate::lib::std::convert::Into;
@@ -15,6 +15,9 @@ use crate::lib::std::mem::transmute;
 use crate::lib::std::ops::{Range, RangeFrom, RangeTo};
 use crate::traits::{AsChar, InputIter, InputLength, InputTakeAtPosition, ParseTo};
 use crate::traits::{Compare, CompareResult, Offset, Slice};
+use core::cell::Cell;
+use core::num::NonZeroUsize;
+use core::sync::atomic::{AtomicBool, Ordering};
 
 #[macro_use]
 mod macros;
@@ -240,6 +243,419 @@ where
   map_parser(first, second)(input)
 }
 
+/// Applies a parser over at most the next `n` elements of the input, leaving
+/// what it did not consume to the following parsers.
+///
+/// If the parser returns `Incomplete` while it already sees `n` elements,
+/// the result is a `ErrorKind::TooLarge` error instead, since more data would
+/// not help.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::bytes::streaming::take_until;
+/// use nom::combinator::limit;
+///
+/// fn header(i: &[u8]) -> IResult<&[u8], &[u8]> {
+///   limit(8, take_until("\n"))(i)
+/// }
+///
+/// assert_eq!(header(b"abc\ndef\nghi"), Ok((&b"\ndef\nghi"[..], &b"abc"[..])));
+/// assert_eq!(header(b"abc"), Err(Err::Incomplete(Needed::Unknown)));
+/// assert_eq!(header(b"abcdefghijk"), Err(Err::Error(Error::new(&b"abcdefgh"[..], ErrorKind::TooLarge))));
+/// ```
+pub fn limit<I, O, E: ParseError<I>, F>(
+  n: usize,
+  mut parser: F,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  I: Clone + InputLength + Slice<RangeTo<usize>> + Slice<RangeFrom<usize>>,
+  F: Parser<I, O, E>,
+{
+  move |input: I| {
+    let len = input.input_len();
+    let window = if len > n {
+      input.slice(..n)
+    } else {
+      input.clone()
+    };
+    let window_len = window.input_len();
+
+    match parser.parse(window.clone()) {
+      Ok((rest, o)) => Ok((input.slice(window_len - rest.input_len()..), o)),
+      Err(Err::Incomplete(_)) if window_len == n => {
+        Err(Err::Error(E::from_error_kind(window, ErrorKind::TooLarge)))
+      }
+      Err(e) => Err(e),
+    }
+  }
+}
+
+/// Applies a parser over the result of another one, re-anchoring the errors
+/// of the second parser to the input of the first one.
+///
+/// `map` translates an offset in the output of the first parser to an offset
+/// in its input. See [reanchor](crate::error::reanchor): the frames of the
+/// error, including the ones added by [context](crate::error::context), are
+/// kept. The second parser can use any error type implementing
+/// [ErrorFrames](crate::error::ErrorFrames), which usually has to be written
+/// explicitly since it cannot be inferred from the first parser.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::bytes::complete::{tag, take_until};
+/// use nom::character::complete::digit1;
+/// use nom::combinator::{all_consuming, map_parser_at};
+/// use nom::sequence::delimited;
+///
+/// fn quoted_number(i: &str) -> IResult<&str, &str> {
+///   map_parser_at(
+///     delimited(tag("\""), take_until("\""), tag("\"")),
+///     all_consuming(digit1::<_, Error<_>>),
+///     |offset| offset + 1,
+///   )(i)
+/// }
+///
+/// assert_eq!(quoted_number("\"123\";"), Ok((";", "123")));
+/// assert_eq!(quoted_number("\"12a\";"), Err(Err::Error(Error::new("a\";", ErrorKind::Eof))));
+/// ```
+pub fn map_parser_at<I, O1, O2, E, E2, F, G, M>(
+  mut first: F,
+  mut second: G,
+  map: M,
+) -> impl FnMut(I) -> IResult<I, O2, E>
+where
+  I: Clone + Slice<RangeFrom<usize>> + for<'t> Compare<&'t str>,
+  O1: Clone + Offset,
+  E: ParseError<I> + ContextError<I>,
+  E2: crate::error::ErrorFrames<O1>,
+  F: Parser<I, O1, E>,
+  G: Parser<O1, O2, E2>,
+  M: Fn(usize) -> usize,
+{
+  move |input: I| {
+    let (rest, o1) = first.parse(input.clone())?;
+    match second.parse(o1.clone()) {
+      Ok((_, o2)) => Ok((rest, o2)),
+      Err(e) => Err(crate::error::reanchor(input, o1, e, &map)),
+    }
+  }
+}
+
+/// Reports the progress of a long parse, calling `callback(consumed, total)`
+/// each time `parser` succeeds after consuming at least `step` more elements
+/// since the previous report, and when it reaches the end of the input.
+///
+/// `total` is the length of the whole input. Wrap the element parser of a
+/// repetition combinator to get regular reports from its loop. The callback
+/// can also be used to keep a user interface responsive.
+///
+/// Applying it again from an earlier position starts the reports over, so the
+/// same parser can be reused for several inputs.
+///
+/// ```rust
+/// # use nom::IResult;
+/// use nom::character::complete::{alpha1, line_ending};
+/// use nom::combinator::with_progress;
+/// use nom::multi::many0;
+/// use nom::sequence::terminated;
+///
+/// let input = "abc\ndef\nghi\n";
+/// let mut reports = Vec::new();
+/// let res: IResult<_, _> = many0(with_progress(
+///   input.len(),
+///   5,
+///   |consumed, total| reports.push((consumed, total)),
+///   terminated(alpha1, line_ending),
+/// ))(input);
+///
+/// assert_eq!(res, Ok(("", vec!["abc", "def", "ghi"])));
+/// assert_eq!(reports, vec![(8, 12), (12, 12)]);
+/// ```
+pub fn with_progress<I, O, E: ParseError<I>, C, F>(
+  total: usize,
+  step: usize,
+  mut callback: C,
+  mut parser: F,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  I: InputLength,
+  C: FnMut(usize, usize),
+  F: Parser<I, O, E>,
+{
+  let mut reported = 0;
+  let mut position = 0;
+  move |input: I| {
+    // starting before the end of the previous call means the parser
+    // is reused for a new parse
+    if total.saturating_sub(input.input_len()) < position {
+      reported = 0;
+    }
+
+    let (input, o) = parser.parse(input)?;
+    let remaining = input.input_len();
+    let consumed = total.saturating_sub(remaining);
+    position = consumed;
+    if consumed >= reported + step || (remaining == 0 && consumed != reported) {
+      reported = consumed;
+      callback(consumed, total);
+    }
+    Ok((input, o))
+  }
+}
+
+/// Aborts the parse with an `ErrorKind::Cancelled` failure once `token` is set,
+/// checking it each time `parser` is applied.
+///
+/// The token is an `AtomicBool`, or a reference or `Arc` to one, so it can be
+/// set from another thread. Wrap the element parsers of repetition and branch
+/// combinators to check it regularly during a long parse.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use nom::character::complete::{alpha1, line_ending};
+/// use nom::combinator::cancellable;
+/// use nom::multi::many0;
+/// use nom::sequence::terminated;
+///
+/// let cancelled = AtomicBool::new(false);
+/// let mut parser = many0(cancellable(&cancelled, terminated(alpha1, line_ending)));
+///
+/// let res: IResult<_, _> = parser("abc\ndef\n");
+/// assert_eq!(res, Ok(("", vec!["abc", "def"])));
+///
+/// cancelled.store(true, Ordering::Relaxed);
+/// let res: IResult<_, _> = parser("abc\ndef\n");
+/// assert_eq!(res, Err(Err::Failure(Error::new("abc\ndef\n", ErrorKind::Cancelled))));
+/// ```
+pub fn cancellable<I, O, E: ParseError<I>, T, F>(
+  token: T,
+  mut parser: F,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  T: Borrow<AtomicBool>,
+  F: Parser<I, O, E>,
+{
+  move |input: I| {
+    if token.borrow().load(Ordering::Relaxed) {
+      Err(Err::Failure(E::from_error_kind(
+        input,
+        ErrorKind::Cancelled,
+      )))
+    } else {
+      parser.parse(input)
+    }
+  }
+}
+
+/// Converts the integer returned by `parser` to a flags type with `from_bits`,
+/// returning an `ErrorKind::Flags` error if it sets unknown bits.
+///
+/// This works with the `from_bits` function of types generated by the
+/// `bitflags` crate. The width and endianness of the integer are given by
+/// the parser, like [be_u16](crate::number::complete::be_u16). To ignore
+/// unknown bits, pass a function wrapping `from_bits_truncate` in `Some`,
+/// or use [flags_lossy] to get them.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::combinator::flags;
+/// use nom::number::complete::be_u8;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Mode(u8);
+///
+/// impl Mode {
+///   const ALL: u8 = 0b111;
+///
+///   fn from_bits(bits: u8) -> Option<Mode> {
+///     if bits & !Self::ALL == 0 { Some(Mode(bits)) } else { None }
+///   }
+/// }
+///
+/// fn mode(i: &[u8]) -> IResult<&[u8], Mode> {
+///   flags(be_u8, Mode::from_bits)(i)
+/// }
+///
+/// assert_eq!(mode(&[0b101, 0][..]), Ok((&[0][..], Mode(0b101))));
+/// assert_eq!(mode(&[0b1001][..]), Err(Err::Error(Error::new(&[0b1001][..], ErrorKind::Flags))));
+/// ```
+pub fn flags<I: Clone, N, F, E: ParseError<I>, P, C>(
+  mut parser: P,
+  from_bits: C,
+) -> impl FnMut(I) -> IResult<I, F, E>
+where
+  P: Parser<I, N, E>,
+  C: Fn(N) -> Option<F>,
+{
+  move |input: I| {
+    let (i, bits) = parser.parse(input.clone())?;
+    match from_bits(bits) {
+      Some(flags) => Ok((i, flags)),
+      None => Err(Err::Error(E::from_error_kind(input, ErrorKind::Flags))),
+    }
+  }
+}
+
+/// Converts the integer returned by `parser` to a flags type, also returning
+/// the bits that are not in `known`, zero if there are none.
+///
+/// `from_bits_truncate` gets the known bits only, and can be the function of
+/// the same name generated by the `bitflags` crate. The unknown bits can be
+/// reported as warnings, or kept to write them back.
+///
+/// ```rust
+/// # use nom::IResult;
+/// use nom::combinator::flags_lossy;
+/// use nom::number::complete::le_u16;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Features(u16);
+///
+/// fn features(i: &[u8]) -> IResult<&[u8], (Features, u16)> {
+///   flags_lossy(0x00ff, le_u16, Features)(i)
+/// }
+///
+/// assert_eq!(features(&[0x12, 0x00][..]), Ok((&[][..], (Features(0x12), 0))));
+/// assert_eq!(features(&[0x12, 0x80][..]), Ok((&[][..], (Features(0x12), 0x8000))));
+/// ```
+pub fn flags_lossy<I, N, F, E: ParseError<I>, P, C>(
+  known: N,
+  mut parser: P,
+  from_bits_truncate: C,
+) -> impl FnMut(I) -> IResult<I, (F, N), E>
+where
+  N: Copy + crate::lib::std::ops::BitAnd<Output = N> + crate::lib::std::ops::Not<Output = N>,
+  P: Parser<I, N, E>,
+  C: Fn(N) -> F,
+{
+  move |input: I| {
+    let (i, bits) = parser.parse(input)?;
+    Ok((i, (from_bits_truncate(bits & known), bits & !known)))
+  }
+}
+
+/// Shared allocation budget for [budgeted] and [budgeted_by]
+///
+/// Create one for each parse, and wrap the element parsers of collection
+/// building combinators like [many0](crate::multi::many0) or
+/// [count](crate::multi::count) to limit the size of what they collect.
+#[derive(Debug)]
+pub struct Budget {
+  remaining: Cell<usize>,
+}
+
+impl Budget {
+  /// Creates a budget allowing a total cost of `limit`
+  pub fn new(limit: usize) -> Self {
+    Budget {
+      remaining: Cell::new(limit),
+    }
+  }
+
+  /// Returns the cost that can still be spent
+  pub fn remaining(&self) -> usize {
+    self.remaining.get()
+  }
+
+  /// Spends `cost` from the budget, returning `false` if it is exceeded
+  pub fn spend(&self, cost: usize) -> bool {
+    match self.remaining.get().checked_sub(cost) {
+      Some(remaining) => {
+        self.remaining.set(remaining);
+        true
+      }
+      None => false,
+    }
+  }
+}
+
+/// Counts each output of `parser` against `budget`, failing with
+/// `ErrorKind::TooLarge` once it is exceeded.
+///
+/// The budget is a [Budget], or a reference or `Rc` to one. Sharing it between
+/// several combinators limits the total number of elements they collect.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::character::complete::{alpha1, char};
+/// use nom::combinator::{budgeted, Budget};
+/// use nom::multi::separated_list0;
+///
+/// let budget = Budget::new(3);
+/// let mut parser = separated_list0(char(','), budgeted(&budget, alpha1));
+///
+/// let res: IResult<_, _> = parser("a,b,c");
+/// assert_eq!(res, Ok(("", vec!["a", "b", "c"])));
+///
+/// let budget = Budget::new(3);
+/// let mut parser = separated_list0(char(','), budgeted(&budget, alpha1));
+///
+/// let res: IResult<_, _> = parser("a,b,c,d");
+/// assert_eq!(res, Err(Err::Failure(Error::new("d", ErrorKind::TooLarge))));
+/// ```
+pub fn budgeted<I, O, E: ParseError<I>, B, F>(
+  budget: B,
+  parser: F,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  I: Clone,
+  B: Borrow<Budget>,
+  F: Parser<I, O, E>,
+{
+  budgeted_by(budget, |_: &O| 1, parser)
+}
+
+/// Counts the `cost` of each output of `parser` against `budget`, failing with
+/// `ErrorKind::TooLarge` once it is exceeded.
+///
+/// The cost can be the length of the output, to limit the number of bytes
+/// copied from the input.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::combinator::{budgeted_by, Budget};
+/// use nom::multi::length_count;
+/// use nom::number::complete::u8;
+///
+/// fn strings<'a>(budget: &'a Budget) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], Vec<Vec<u8>>> {
+///   length_count(u8, budgeted_by(budget, Vec::len, length_count(u8, u8)))
+/// }
+///
+/// let budget = Budget::new(4);
+/// assert_eq!(
+///   strings(&budget)(&[2, 1, 1, 2, 2, 3][..]),
+///   Ok((&[][..], vec![vec![1], vec![2, 3]]))
+/// );
+/// assert_eq!(budget.remaining(), 1);
+///
+/// let budget = Budget::new(4);
+/// assert_eq!(
+///   strings(&budget)(&[2, 3, 1, 2, 3, 2, 4, 5][..]),
+///   Err(Err::Failure(Error::new(&[2, 4, 5][..], ErrorKind::TooLarge)))
+/// );
+/// ```
+pub fn budgeted_by<I, O, E: ParseError<I>, B, C, F>(
+  budget: B,
+  cost: C,
+  mut parser: F,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  I: Clone,
+  B: Borrow<Budget>,
+  C: Fn(&O) -> usize,
+  F: Parser<I, O, E>,
+{
+  move |input: I| {
+    let (i, o) = parser.parse(input.clone())?;
+    if budget.borrow().spend(cost(&o)) {
+      Ok((i, o))
+    } else {
+      Err(Err::Failure(E::from_error_kind(input, ErrorKind::TooLarge)))
+    }
+  }
+}
+
 /// Creates a new parser from the output of the first parser, then apply that parser over the rest of the input.
 ///
 /// ```rust
@@ -310,6 +726,53 @@ where
   opt(f)(input)
 }
 
+/// Optional parser with a default: will return `O::default()` if not successful.
+///
+/// ```rust
+/// # use nom::{Err,error::ErrorKind, IResult};
+/// use nom::combinator::opt_default;
+/// use nom::character::complete::alpha1;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   opt_default(alpha1)(i)
+/// }
+///
+/// assert_eq!(parser("abcd;"), Ok((";", "abcd")));
+/// assert_eq!(parser("123;"), Ok(("123;", "")));
+/// ```
+pub fn opt_default<I: Clone, O: Default, E: ParseError<I>, F>(
+  f: F,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  F: Parser<I, O, E>,
+{
+  map(opt(f), Option::unwrap_or_default)
+}
+
+/// Optional parser with a fallback: will return a clone of `val` if not successful.
+///
+/// ```rust
+/// # use nom::{Err,error::ErrorKind, IResult};
+/// use nom::combinator::{map_res, opt_or};
+/// use nom::character::complete::digit1;
+///
+/// fn port(i: &str) -> IResult<&str, u16> {
+///   opt_or(80, map_res(digit1, str::parse))(i)
+/// }
+///
+/// assert_eq!(port("8080;"), Ok((";", 8080)));
+/// assert_eq!(port(";"), Ok((";", 80)));
+/// ```
+pub fn opt_or<I: Clone, O: Clone, E: ParseError<I>, F>(
+  val: O,
+  f: F,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  F: Parser<I, O, E>,
+{
+  map(opt(f), move |o| o.unwrap_or_else(|| val.clone()))
+}
+
 /// Calls the parser if the condition is met.
 ///
 /// ```rust
@@ -356,6 +819,39 @@ where
   cond(b, f)(input)
 }
 
+/// Calls the first parser if the condition is met, the second one otherwise.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::combinator::if_else;
+/// use nom::number::complete::{be_u16, be_u32};
+///
+/// // the length field was extended to 32 bits in version 2
+/// fn length(version: u8, i: &[u8]) -> IResult<&[u8], u32> {
+///   if_else(version >= 2, be_u32, |i| be_u16(i).map(|(i, l)| (i, u32::from(l))))(i)
+/// }
+///
+/// assert_eq!(length(1, &[0, 5, 1][..]), Ok((&[1][..], 5)));
+/// assert_eq!(length(2, &[0, 0, 0, 5, 1][..]), Ok((&[1][..], 5)));
+/// ```
+pub fn if_else<I, O, E: ParseError<I>, F, G>(
+  b: bool,
+  mut first: F,
+  mut second: G,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  F: Parser<I, O, E>,
+  G: Parser<I, O, E>,
+{
+  move |input: I| {
+    if b {
+      first.parse(input)
+    } else {
+      second.parse(input)
+    }
+  }
+}
+
 /// Tries to apply its parser without consuming the input.
 ///
 /// ```rust
@@ -454,6 +950,59 @@ where
   complete(f)(input)
 }
 
+/// Records a label and the total size of the pending item in `pending` when
+/// the parser returns `Incomplete`.
+///
+/// The details are kept next to the `Needed` value rather than inside it, so
+/// `Err` stays small. `pending` is reset each time the parser is called. The
+/// total counts from the start of this parser's input, so that buffering
+/// layers can allocate the whole item at once. The label is kept if a nested
+/// `needed_context` sharing the same `pending` already set one.
+///
+/// ```rust
+/// # use nom::{Err, Needed, IResult, Pending};
+/// use nom::bytes::streaming::take;
+/// use nom::combinator::needed_context;
+/// use nom::number::streaming::be_u8;
+/// use nom::sequence::preceded;
+/// use std::cell::Cell;
+///
+/// let pending = Cell::new(Pending::default());
+/// let mut record = needed_context("record", &pending, preceded(be_u8, take(4u8)));
+///
+/// let res: IResult<&[u8], &[u8]> = record(&[0, 1, 2, 3, 4]);
+/// assert_eq!(res, Ok((&[][..], &[1, 2, 3, 4][..])));
+/// let res: IResult<&[u8], &[u8]> = record(&[0, 1, 2]);
+/// assert_eq!(res, Err(Err::Incomplete(Needed::new(2))));
+/// assert_eq!(pending.get().context, Some("record"));
+/// assert_eq!(pending.get().total.map(|n| n.get()), Some(5));
+/// ```
+pub fn needed_context<'a, I, O, E: ParseError<I>, F>(
+  context: &'static str,
+  pending: &'a Cell<Pending>,
+  mut f: F,
+) -> impl FnMut(I) -> IResult<I, O, E> + 'a
+where
+  I: InputLength,
+  F: Parser<I, O, E> + 'a,
+{
+  move |input: I| {
+    let len = input.input_len();
+    pending.set(Pending::default());
+    let res = f.parse(input);
+    if let Err(Err::Incomplete(needed)) = &res {
+      let mut p = pending.get();
+      p.size = needed.size();
+      p.total = needed
+        .size()
+        .and_then(|size| NonZeroUsize::new(len + size.get()));
+      p.context = p.context.or(Some(context));
+      pending.set(p);
+    }
+    res
+  }
+}
+
 /// Succeeds if all the input has been consumed by its child parser.
 ///
 /// ```rust
@@ -485,6 +1034,58 @@ where
   }
 }
 
+/// Succeeds if all the input has been consumed by its child parser, like
+/// [all_consuming], and reports the position and length of the leftover input.
+///
+/// The error is created with [FromExternalError] from the remaining input,
+/// `ErrorKind::Eof` and a [TrailingData](crate::error::TrailingData).
+///
+/// ```rust
+/// # use nom::{Err, IResult};
+/// use nom::combinator::all_consuming_trailing;
+/// use nom::character::complete::alpha1;
+/// use nom::error::{ErrorKind, ExternalError, TrailingData};
+///
+/// fn parser(i: &str) -> IResult<&str, &str, ExternalError<&str>> {
+///   all_consuming_trailing(alpha1)(i)
+/// }
+///
+/// assert!(parser("abcd").is_ok());
+///
+/// let e = match parser("abcd;;") {
+///   Err(Err::Error(e)) => e,
+///   _ => unreachable!(),
+/// };
+/// assert_eq!(e.kind, ErrorKind::Eof);
+/// let trailing = e.source.unwrap().downcast::<TrailingData>().unwrap();
+/// assert_eq!(*trailing, TrailingData { offset: 4, len: 2 });
+/// assert_eq!(trailing.to_string(), "unexpected trailing data at offset 4 (length 2)");
+/// ```
+pub fn all_consuming_trailing<I, O, E, F>(mut f: F) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  I: Clone + InputLength + Offset,
+  E: ParseError<I> + FromExternalError<I, TrailingData>,
+  F: Parser<I, O, E>,
+{
+  move |input: I| {
+    let (rest, res) = f.parse(input.clone())?;
+    let len = rest.input_len();
+    if len == 0 {
+      Ok((rest, res))
+    } else {
+      let trailing = TrailingData {
+        offset: input.offset(&rest),
+        len,
+      };
+      Err(Err::Error(E::from_external_error(
+        rest,
+        ErrorKind::Eof,
+        trailing,
+      )))
+    }
+  }
+}
+
 /// Returns the result of the child parser if it satisfies a verification function.
 ///
 /// The verification function takes as argument a reference to the output of the
@@ -541,6 +1142,144 @@ where
   verify(first, second)(input)
 }
 
+/// Returns the result of the child parser if it satisfies a verification function,
+/// otherwise builds the error from the input and the rejected value.
+///
+/// ```rust
+/// # use nom::{Err, error::{ErrorKind, FromExternalError}, IResult};
+/// use nom::combinator::{map_res, verify_with};
+/// use nom::character::complete::digit1;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum PortError<'a> {
+///   Nom(&'a str, ErrorKind),
+///   Reserved(u16),
+/// }
+/// # impl<'a> nom::error::ParseError<&'a str> for PortError<'a> {
+/// #   fn from_error_kind(i: &'a str, kind: ErrorKind) -> Self { PortError::Nom(i, kind) }
+/// #   fn append(_: &'a str, _: ErrorKind, other: Self) -> Self { other }
+/// # }
+/// # impl<'a, E> FromExternalError<&'a str, E> for PortError<'a> {
+/// #   fn from_external_error(i: &'a str, kind: ErrorKind, _: E) -> Self { PortError::Nom(i, kind) }
+/// # }
+///
+/// fn port(i: &str) -> IResult<&str, u16, PortError> {
+///   verify_with(
+///     map_res(digit1, str::parse),
+///     |p: &u16| *p >= 1024,
+///     |_, p| PortError::Reserved(p),
+///   )(i)
+/// }
+///
+/// assert_eq!(port("8080"), Ok(("", 8080)));
+/// assert_eq!(port("80"), Err(Err::Error(PortError::Reserved(80))));
+/// ```
+pub fn verify_with<I: Clone, O1, O2, E: ParseError<I>, F, G, H>(
+  mut first: F,
+  second: G,
+  error: H,
+) -> impl FnMut(I) -> IResult<I, O1, E>
+where
+  F: Parser<I, O1, E>,
+  G: Fn(&O2) -> bool,
+  H: Fn(I, O1) -> E,
+  O1: Borrow<O2>,
+  O2: ?Sized,
+{
+  move |input: I| {
+    let i = input.clone();
+    let (input, o) = first.parse(input)?;
+
+    if second(o.borrow()) {
+      Ok((input, o))
+    } else {
+      Err(Err::Error(error(i, o)))
+    }
+  }
+}
+
+/// Returns the result of the child parser if it satisfies a verification function,
+/// otherwise adds the label as context to the `ErrorKind::Verify` error.
+///
+/// ```rust
+/// # use nom::{Err, error::{ErrorKind, VerboseError, VerboseErrorKind}, IResult};
+/// use nom::combinator::verify_context;
+/// use nom::character::complete::alpha1;
+///
+/// fn name(i: &str) -> IResult<&str, &str, VerboseError<&str>> {
+///   verify_context("at most 8 letters", alpha1, |s: &str| s.len() <= 8)(i)
+/// }
+///
+/// assert_eq!(name("nom"), Ok(("", "nom")));
+/// assert_eq!(name("parsernom"), Err(Err::Error(VerboseError {
+///   errors: vec![
+///     ("parsernom", VerboseErrorKind::Nom(ErrorKind::Verify)),
+///     ("parsernom", VerboseErrorKind::Context("at most 8 letters")),
+///   ]
+/// })));
+/// ```
+pub fn verify_context<I: Clone, O1, O2, E: ContextError<I> + ParseError<I>, F, G>(
+  context: &'static str,
+  first: F,
+  second: G,
+) -> impl FnMut(I) -> IResult<I, O1, E>
+where
+  F: Parser<I, O1, E>,
+  G: Fn(&O2) -> bool,
+  O1: Borrow<O2>,
+  O2: ?Sized,
+{
+  verify_with(first, second, move |i: I, _| {
+    E::add_context(i.clone(), context, E::from_error_kind(i, ErrorKind::Verify))
+  })
+}
+
+/// Returns the result of the child parser if the input it consumed satisfies a
+/// verification function.
+///
+/// Unlike [verify], the verification function receives the recognized input
+/// instead of the output, to check properties of the raw text. Fails with
+/// `ErrorKind::VerifyRecognized` at the start of the input otherwise.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::character::complete::digit1;
+/// use nom::combinator::{map_res, verify_recognized};
+///
+/// // no leading zeros
+/// fn number(i: &str) -> IResult<&str, u32> {
+///   verify_recognized(map_res(digit1, str::parse), |s: &str| s == "0" || !s.starts_with('0'))(i)
+/// }
+///
+/// assert_eq!(number("120;"), Ok((";", 120)));
+/// assert_eq!(number("0"), Ok(("", 0)));
+/// assert_eq!(number("012"), Err(Err::Error(Error::new("012", ErrorKind::VerifyRecognized))));
+/// ```
+pub fn verify_recognized<I, I2, O, E: ParseError<I>, F, G>(
+  mut parser: F,
+  verify: G,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  I: Clone + Offset + Slice<RangeTo<usize>> + Borrow<I2>,
+  I2: ?Sized,
+  F: Parser<I, O, E>,
+  G: Fn(&I2) -> bool,
+{
+  move |input: I| {
+    let (i, o) = parser.parse(input.clone())?;
+    let index = input.offset(&i);
+
+    if verify(input.slice(..index).borrow()) {
+      Ok((i, o))
+    } else {
+      Err(Err::Error(E::from_error_kind(
+        input,
+        ErrorKind::VerifyRecognized,
+      )))
+    }
+  }
+}
+
 /// Returns the provided value if the child parser succeeds.
 ///
 /// ```rust
@@ -727,6 +1466,102 @@ where
   consumed(parser)(input)
 }
 
+/// If the child parser was successful, return the consumed input with `\r\n` and
+/// lone `\r` line endings replaced by `\n`.
+///
+/// Works like [recognize](fn.recognize.html), and borrows the consumed input if
+/// it does not contain any `\r`.
+///
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, IResult};
+/// use nom::combinator::normalize_newlines;
+/// use nom::bytes::complete::{tag, take_until};
+/// use nom::sequence::delimited;
+/// use std::borrow::Cow;
+///
+/// fn block(i: &str) -> IResult<&str, Cow<str>> {
+///   delimited(tag("{"), normalize_newlines(take_until("}")), tag("}"))(i)
+/// }
+///
+/// assert_eq!(block("{a\r\nb\rc}"), Ok(("", Cow::Owned(String::from("a\nb\nc")))));
+/// assert_eq!(block("{a\nb}"), Ok(("", Cow::Borrowed("a\nb"))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn normalize_newlines<'a, O, E: ParseError<&'a str>, F>(
+  mut parser: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, crate::lib::std::borrow::Cow<'a, str>, E>
+where
+  F: Parser<&'a str, O, E>,
+{
+  use crate::lib::std::borrow::Cow;
+  use crate::lib::std::string::String;
+
+  move |input: &'a str| {
+    let (i, _) = parser.parse(input)?;
+    let consumed = &input[..input.offset(i)];
+    if !consumed.contains('\r') {
+      return Ok((i, Cow::Borrowed(consumed)));
+    }
+
+    let mut res = String::with_capacity(consumed.len());
+    let mut chars = consumed.chars().peekable();
+    while let Some(c) = chars.next() {
+      if c == '\r' {
+        res.push('\n');
+        if chars.peek() == Some(&'\n') {
+          chars.next();
+        }
+      } else {
+        res.push(c);
+      }
+    }
+    Ok((i, Cow::Owned(res)))
+  }
+}
+
+/// Removes the line terminators from the output of the child parser, as done
+/// when unfolding email headers.
+///
+/// `\r\n`, `\n` and lone `\r` are removed, while the whitespace starting the
+/// continuation lines is kept. The output is borrowed if it does not contain
+/// any line terminator.
+///
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, IResult};
+/// use nom::combinator::unfold;
+/// use nom::character::complete::folded_line;
+/// use std::borrow::Cow;
+///
+/// fn header(i: &str) -> IResult<&str, Cow<str>> {
+///   unfold(folded_line)(i)
+/// }
+///
+/// assert_eq!(header("Subject: a\r\n long\r\n\tone\r\n"), Ok(("", Cow::Owned(String::from("Subject: a long\tone")))));
+/// assert_eq!(header("To: b\r\n"), Ok(("", Cow::Borrowed("To: b"))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn unfold<'a, I, E: ParseError<I>, F>(
+  mut parser: F,
+) -> impl FnMut(I) -> IResult<I, crate::lib::std::borrow::Cow<'a, str>, E>
+where
+  F: Parser<I, &'a str, E>,
+{
+  use crate::lib::std::borrow::Cow;
+  use crate::lib::std::string::String;
+
+  move |input: I| {
+    let (i, o) = parser.parse(input)?;
+    if !o.contains(&['\r', '\n'][..]) {
+      return Ok((i, Cow::Borrowed(o)));
+    }
+
+    let res: String = o.chars().filter(|&c| c != '\r' && c != '\n').collect();
+    Ok((i, Cow::Owned(res)))
+  }
+}
+
 /// transforms an error to failure
 ///
 /// ```rust
@@ -858,6 +1693,14 @@ impl<I: Clone, E, F> ParserIterator<I, E, F> {
       State::Incomplete(i) => Err(Err::Incomplete(i)),
     }
   }
+
+  /// Returns the input that was not consumed yet.
+  ///
+  /// If the parser returned `Incomplete`, this is where parsing should resume once
+  /// more data is available.
+  pub fn remaining(&self) -> &I {
+    &self.input
+  }
 }
 
 impl<'a, Input, Output, Error, F> core::iter::Iterator for &'a mut ParserIterator<Input, Error, F>
@@ -1119,6 +1962,172 @@ mod tests {
     );
   }
 
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn test_normalize_newlines() {
+    use crate::bytes::complete::take;
+    let mut parser = normalize_newlines::<_, (&str, ErrorKind), _>(take(5u8));
+
+    assert_eq!(parser("a\r\r\nbc"), Ok(("c", "a\n\nb".into())));
+    assert_eq!(parser("a\r"), Err(Err::Error(("a\r", ErrorKind::Eof))));
+  }
+
+  #[test]
+  fn limit_test() {
+    use crate::bytes::complete::tag;
+
+    let mut parser = limit(3, tag("ab"));
+    assert_parse!(parser(&b"abcd"[..]), Ok((&b"cd"[..], &b"ab"[..])));
+
+    let mut parser = limit(3, take(4u8));
+    assert_parse!(
+      parser(&b"abcd"[..]),
+      Err(Err::Error((&b"abc"[..], ErrorKind::Eof)))
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn with_progress_test() {
+    use crate::bytes::complete::take;
+    use crate::lib::std::vec::Vec;
+    use crate::multi::many0;
+
+    let input = &[0u8; 10][..];
+    let mut reports = Vec::new();
+    let res: IResult<_, _, (_, ErrorKind)> = many0(with_progress(
+      input.len(),
+      4,
+      |consumed, total| reports.push((consumed, total)),
+      take(3u8),
+    ))(input);
+    assert_eq!(res.map(|(i, v)| (i.len(), v.len())), Ok((1, 3)));
+    assert_eq!(reports, [(6, 10)]);
+
+    let mut reports = Vec::new();
+    let res: IResult<_, _, (_, ErrorKind)> = many0(with_progress(
+      input.len(),
+      0,
+      |consumed, _| reports.push(consumed),
+      take(5u8),
+    ))(input);
+    assert!(res.is_ok());
+    assert_eq!(reports, [5, 10]);
+
+    let mut reports = Vec::new();
+    let mut parser = many0(with_progress(
+      input.len(),
+      4,
+      |consumed, _| reports.push(consumed),
+      take::<_, _, (_, ErrorKind)>(2u8),
+    ));
+    assert!(parser(input).is_ok());
+    assert!(parser(input).is_ok());
+    drop(parser);
+    assert_eq!(reports, [4, 8, 10, 4, 8, 10]);
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn cancellable_test() {
+    use crate::bytes::complete::take;
+    use crate::multi::many0;
+
+    let cancelled = AtomicBool::new(false);
+    let mut parser = many0(cancellable(
+      &cancelled,
+      with_progress(
+        9,
+        0,
+        |consumed, _| cancelled.store(consumed >= 6, Ordering::Relaxed),
+        take(3u8),
+      ),
+    ));
+    assert_parse!(
+      parser(&b"abcdefghi"[..]),
+      Err(Err::Failure((&b"ghi"[..], ErrorKind::Cancelled)))
+    );
+  }
+
+  #[test]
+  fn flags_test() {
+    let from_bits = |b: u8| if b < 8 { Some(b) } else { None };
+    let mut strict = flags(u8, from_bits);
+    assert_parse!(strict(&b"\x07"[..]), Ok((&b""[..], 7)));
+    assert_parse!(
+      strict(&b"\x0f"[..]),
+      Err(Err::Error((&b"\x0f"[..], ErrorKind::Flags)))
+    );
+
+    let mut truncate = flags(u8, |b: u8| Some(b & 7));
+    assert_parse!(truncate(&b"\x0f"[..]), Ok((&b""[..], 7)));
+
+    let mut lossy = flags_lossy(7, u8, |b| b);
+    assert_parse!(lossy(&b"\x0f"[..]), Ok((&b""[..], (7, 8))));
+  }
+
+  #[test]
+  fn verify_with_test() {
+    let mut parser = verify_with(u8, |b: &u8| *b < 128, |i, _| (i, ErrorKind::Char));
+    assert_parse!(parser(&b"\x05"[..]), Ok((&b""[..], 5)));
+    assert_parse!(
+      parser(&b"\xff\x01"[..]),
+      Err(Err::Error((&b"\xff\x01"[..], ErrorKind::Char)))
+    );
+  }
+
+  #[test]
+  fn if_else_test() {
+    let parser = |b| if_else(b, u8, value(0, take(2u8)));
+    assert_parse!(parser(true)(&b"abc"[..]), Ok((&b"bc"[..], b'a')));
+    assert_parse!(parser(false)(&b"abc"[..]), Ok((&b"c"[..], 0)));
+    assert_parse!(
+      parser(false)(&b"a"[..]),
+      Err(Err::Error((&b"a"[..], ErrorKind::Eof)))
+    );
+  }
+
+  #[test]
+  fn opt_default_test() {
+    let mut parser = opt_default(u8);
+    assert_parse!(parser(&b"\x05a"[..]), Ok((&b"a"[..], 5)));
+    assert_parse!(parser(&b""[..]), Ok((&b""[..], 0)));
+
+    let mut parser = opt_or(7, cut(u8));
+    assert_parse!(
+      parser(&b""[..]),
+      Err(Err::Failure((&b""[..], ErrorKind::Eof)))
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn test_needed_context_nested() {
+    use crate::bytes::streaming::take;
+    use crate::sequence::pair;
+    let pending = Cell::new(Pending::default());
+    let mut parser = needed_context(
+      "message",
+      &pending,
+      pair(take(2u8), needed_context("body", &pending, take(4u8))),
+    );
+
+    let res: IResult<_, _, (&[u8], ErrorKind)> = parser(&b"abcd"[..]);
+    assert_eq!(res, Err(Err::Incomplete(Needed::new(2))));
+    assert_eq!(pending.get().size, Needed::new(2).size());
+    assert_eq!(pending.get().total, Needed::new(6).size());
+    assert_eq!(pending.get().context, Some("body"));
+    assert_eq!(
+      format!("{}", pending.get()),
+      "Parsing requires 2 bytes/chars in body (6 in total)"
+    );
+
+    // the details of a previous call are not kept
+    let res: IResult<_, _, (&[u8], ErrorKind)> = parser(&b"abcdef"[..]);
+    assert!(res.is_ok());
+    assert_eq!(pending.get(), Pending::default());
+  }
+
   #[test]
   #[cfg(feature = "std")]
   fn test_into() {
@@ -1133,4 +2142,181 @@ mod tests {
 
     assert_eq!(result, Ok((&b"defg"[..], vec![97, 98, 99])));
   }
+
+  #[test]
+  fn all_consuming_trailing_test() {
+    #[derive(Debug, PartialEq)]
+    struct TrailingError(Option<TrailingData>);
+
+    impl<I> ParseError<I> for TrailingError {
+      fn from_error_kind(_: I, _: ErrorKind) -> Self {
+        TrailingError(None)
+      }
+
+      fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        other
+      }
+    }
+
+    impl<I> FromExternalError<I, TrailingData> for TrailingError {
+      fn from_external_error(_: I, _: ErrorKind, e: TrailingData) -> Self {
+        TrailingError(Some(e))
+      }
+    }
+
+    let mut parser = all_consuming_trailing(crate::bytes::complete::tag("ab"));
+    assert_eq!(parser(&b"ab"[..]), Ok((&b""[..], &b"ab"[..])));
+    assert_eq!(
+      parser(&b"abcde"[..]),
+      Err(Err::Error(TrailingError(Some(TrailingData {
+        offset: 2,
+        len: 3
+      }))))
+    );
+    assert_eq!(parser(&b"b"[..]), Err(Err::Error(TrailingError(None))));
+
+    let mut parser =
+      all_consuming_trailing::<_, _, (&str, ErrorKind), _>(crate::bytes::complete::tag("ab"));
+    assert_eq!(parser("abc"), Err(Err::Error(("c", ErrorKind::Eof))));
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn budget_test() {
+    use crate::bytes::complete::tag;
+    use crate::multi::{count, length_count, many0};
+    use crate::number::complete::be_u64;
+
+    let budget = Budget::new(5);
+    let mut parser = many0(budgeted_by(&budget, |s: &&str| s.len(), tag("ab")));
+    assert_eq!(
+      parser("ababab"),
+      Err::<(&str, Vec<&str>), _>(Err::Failure(("ab", ErrorKind::TooLarge)))
+    );
+    assert_eq!(budget.remaining(), 1);
+    assert!(budget.spend(1));
+    assert!(!budget.spend(1));
+
+    let budget = Budget::new(2);
+    let mut a = count(budgeted(&budget, tag("a")), 2);
+    let mut b = count(budgeted(&budget, tag("b")), 1);
+    let res: IResult<_, _> = a("aab");
+    assert_eq!(res, Ok(("b", vec!["a", "a"])));
+    let res: IResult<_, _> = b("b");
+    assert_eq!(
+      res,
+      Err(Err::Failure(error_position!("b", ErrorKind::TooLarge)))
+    );
+    // huge counts must not be allocated before the budget is charged
+    let budget = Budget::new(2);
+    let mut c = count(budgeted(&budget, tag("a")), usize::MAX);
+    let res: IResult<_, _> = c("aaa");
+    assert_eq!(
+      res,
+      Err(Err::Failure(error_position!("a", ErrorKind::TooLarge)))
+    );
+
+    let budget = Budget::new(2);
+    let mut l = length_count(be_u64, budgeted(&budget, tag("a")));
+    let res: IResult<_, _> = l(&b"\xff\xff\xff\xff\xff\xff\xff\xffaaa"[..]);
+    assert_eq!(
+      res,
+      Err(Err::Failure(error_position!(
+        &b"a"[..],
+        ErrorKind::TooLarge
+      )))
+    );
+  }
+
+  #[test]
+  fn verify_recognized_test() {
+    use crate::character::complete::alpha1;
+
+    fn keyword(i: &[u8]) -> IResult<&[u8], usize> {
+      verify_recognized(map(alpha1, |s: &[u8]| s.len()), |s: &[u8]| {
+        s != &b"let"[..] && s != &b"fn"[..]
+      })(i)
+    }
+
+    assert_eq!(keyword(&b"letter "[..]), Ok((&b" "[..], 6)));
+    assert_eq!(
+      keyword(&b"let x"[..]),
+      Err(Err::Error(error_position!(
+        &b"let x"[..],
+        ErrorKind::VerifyRecognized
+      )))
+    );
+    assert_eq!(
+      keyword(&b"1"[..]),
+      Err(Err::Error(error_position!(&b"1"[..], ErrorKind::Alpha)))
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn map_parser_at_keeps_context() {
+    use crate::bytes::complete::{tag, take_until};
+    use crate::character::complete::{char, digit1};
+    use crate::error::{context, VerboseError, VerboseErrorKind};
+    use crate::sequence::{delimited, preceded};
+
+    fn quoted(i: &str) -> IResult<&str, &str, VerboseError<&str>> {
+      map_parser_at(
+        delimited(tag("\""), take_until("\""), tag("\"")),
+        context("number", preceded(char::<_, VerboseError<_>>('-'), digit1)),
+        |offset| offset + 1,
+      )(i)
+    }
+
+    assert_eq!(quoted("\"-12\";"), Ok((";", "12")));
+    assert_eq!(
+      quoted("\"-a\";"),
+      Err(Err::Error(VerboseError {
+        errors: vec![
+          ("a\";", VerboseErrorKind::Nom(ErrorKind::Digit)),
+          ("-a\";", VerboseErrorKind::Context("number")),
+        ]
+      }))
+    );
+    assert_eq!(
+      quoted("\"1\";"),
+      Err(Err::Error(VerboseError {
+        errors: vec![
+          ("1\";", VerboseErrorKind::Char('-')),
+          ("1\";", VerboseErrorKind::Context("number")),
+        ]
+      }))
+    );
+
+    let mut unit =
+      map_parser_at::<_, _, _, (&str, ErrorKind), (), _, _, _>(take(2usize), char('-'), |o| o);
+    assert_eq!(unit("ab"), Err(Err::Error(("ab", ErrorKind::MapRes))));
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn map_parser_at_keeps_tag() {
+    use crate::bytes::complete::{tag, take_until};
+    use crate::error::{context, VerboseError, VerboseErrorKind};
+    use crate::sequence::delimited;
+
+    fn quoted(i: &str) -> IResult<&str, &str, VerboseError<&str>> {
+      map_parser_at(
+        delimited(tag("\""), take_until("\""), tag("\"")),
+        context("keyword", tag::<_, _, VerboseError<_>>("let")),
+        |offset| offset + 1,
+      )(i)
+    }
+
+    assert_eq!(quoted("\"let\";"), Ok((";", "let")));
+    assert_eq!(
+      quoted("\"var\";"),
+      Err(Err::Error(VerboseError {
+        errors: vec![
+          ("var\";", VerboseErrorKind::Tag("let".to_string())),
+          ("var\";", VerboseErrorKind::Context("keyword")),
+        ]
+      }))
+    );
+  }
 }
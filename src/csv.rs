@@ -0,0 +1,120 @@
+//! Parsers for CSV data
+//!
+//! [csv_field] recognizes one field following the rules of RFC 4180: a field
+//! can be enclosed in quotes, and then contain delimiters, line breaks and
+//! doubled quotes. Records can be parsed by separating fields with the
+//! delimiter, like with [separated_list1](crate::multi::separated_list1).
+//!
+//! Those parsers work on complete input.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::ops::{Range, RangeFrom, RangeTo};
+use crate::quoted::{quoted_unescaped, Escape};
+use crate::traits::{AsChar, ExtendInto, InputIter, InputLength, Slice};
+
+/// Recognizes a CSV field and returns its unescaped content.
+///
+/// A field starting with `quote` ends at the next single `quote`, and a doubled
+/// `quote` inside it produces one `quote`. Other fields end at the `delimiter`,
+/// at a line break or at the end of the input. The delimiter and the line break
+/// are not consumed.
+///
+/// Following RFC 4180, a `quote` inside an unquoted field, anything but a delimiter
+/// or a line break after a quoted field, and a quoted field without its closing
+/// quote are errors with `ErrorKind::QuotedString`.
+///
+/// The content is collected into the input's [ExtendInto] accumulator, a
+/// `String` for `&str` and a `Vec<u8>` for `&[u8]`.
+///
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::csv::csv_field;
+/// use nom::multi::separated_list1;
+/// use nom::character::complete::char;
+///
+/// fn record(i: &str) -> IResult<&str, Vec<String>> {
+///   separated_list1(char(','), csv_field(',', '"'))(i)
+/// }
+///
+/// assert_eq!(
+///   record("a,\"b,\"\"c\"\"\nd\",,e\r\nf"),
+///   Ok(("\r\nf", vec!["a".to_string(), "b,\"c\"\nd".to_string(), "".to_string(), "e".to_string()]))
+/// );
+/// assert_eq!(record("a\"b"), Err(Err::Error(Error::new("\"b", ErrorKind::QuotedString))));
+/// assert_eq!(record("\"a\"b"), Err(Err::Error(Error::new("b", ErrorKind::QuotedString))));
+/// ```
+pub fn csv_field<I, E, ExtendItem, Output>(
+  delimiter: char,
+  quote: char,
+) -> impl Fn(I) -> IResult<I, Output, E>
+where
+  I: Clone + InputIter + InputLength,
+  I: Slice<Range<usize>> + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  I: ExtendInto<Item = ExtendItem, Extender = Output>,
+  <I as InputIter>::Item: AsChar + Copy,
+  Output: crate::traits::Sink<char>,
+  E: ParseError<I>,
+{
+  let quoted = quoted_unescaped(quote, Escape::Doubled, true);
+
+  move |input: I| {
+    let starts_quoted = input
+      .iter_elements()
+      .next()
+      .map(|c| c.as_char() == quote)
+      .unwrap_or(false);
+
+    if starts_quoted {
+      let (i, res) = quoted(input)?;
+      return match i.iter_elements().next().map(AsChar::as_char) {
+        None | Some('\r') | Some('\n') => Ok((i, res)),
+        Some(c) if c == delimiter => Ok((i, res)),
+        Some(_) => Err(Err::Error(E::from_error_kind(i, ErrorKind::QuotedString))),
+      };
+    }
+
+    let end = input
+      .position(|c| {
+        let c = c.as_char();
+        c == delimiter || c == quote || c == '\r' || c == '\n'
+      })
+      .unwrap_or_else(|| input.input_len());
+    let rest = input.slice(end..);
+    if rest.iter_elements().next().map(|c| c.as_char() == quote) == Some(true) {
+      return Err(Err::Error(E::from_error_kind(
+        rest,
+        ErrorKind::QuotedString,
+      )));
+    }
+
+    let mut res = input.new_builder();
+    input.slice(..end).extend_into(&mut res);
+    Ok((rest, res))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+  use crate::lib::std::vec::Vec;
+
+  #[test]
+  fn csv_field_bytes() {
+    let p = csv_field::<_, Error<&[u8]>, _, Vec<u8>>(';', '\'');
+    assert_eq!(p(&b"a,b;c"[..]), Ok((&b";c"[..], b"a,b".to_vec())));
+    assert_eq!(p(&b"'a;''b'''\n"[..]), Ok((&b"\n"[..], b"a;'b'".to_vec())));
+    assert_eq!(p(&b"''"[..]), Ok((&b""[..], Vec::new())));
+    assert_eq!(p(&b";"[..]), Ok((&b";"[..], Vec::new())));
+    assert_eq!(
+      p(&b"'a;b"[..]),
+      Err(Err::Error(Error::new(
+        &b"'a;b"[..],
+        ErrorKind::QuotedString
+      )))
+    );
+  }
+}
@@ -0,0 +1,438 @@
+//! Parsers for RFC 3339 dates and times
+//!
+//! RFC 3339 defines the profile of ISO 8601 used by most internet protocols and
+//! log formats, like `1985-04-12T23:20:50.52Z`. The parsers of this module check
+//! the ranges of all components, including the number of days in the month, and
+//! return them in the [Date], [Time] and [DateTime] structures.
+//!
+//! With the `chrono` feature, [DateTime::to_chrono] converts the result to a
+//! `chrono::DateTime`.
+//!
+//! Those parsers work on complete input.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::ops::RangeFrom;
+use crate::traits::{AsChar, InputIter, Slice};
+
+/// A calendar date
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Date {
+  /// Year, from 0 to 9999
+  pub year: u16,
+  /// Month, from 1 to 12
+  pub month: u8,
+  /// Day of the month, from 1 to 31
+  pub day: u8,
+}
+
+/// A time of the day, without offset
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Time {
+  /// Hour, from 0 to 23
+  pub hour: u8,
+  /// Minute, from 0 to 59
+  pub minute: u8,
+  /// Second, from 0 to 59, or 60 for a leap second
+  pub second: u8,
+  /// Fraction of the second, in nanoseconds. Digits past the nanosecond are ignored
+  pub nanosecond: u32,
+}
+
+/// A date and a time, with an offset from UTC
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DateTime {
+  /// Date
+  pub date: Date,
+  /// Local time
+  pub time: Time,
+  /// Offset from UTC, in minutes east of Greenwich
+  pub offset: i16,
+}
+
+/// Indicates how a time with 60 seconds is handled
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LeapSecond {
+  /// Second 60 is an error
+  Reject,
+  /// Second 60 is accepted. With an offset, [date_time] also checks that it
+  /// happens at 23:59 UTC, where leap seconds are inserted
+  Accept,
+}
+
+/// Returns the number of days in a month of the proleptic Gregorian calendar
+fn days_in_month(year: u16, month: u8) -> u8 {
+  match month {
+    2 => match (year % 4, year % 100, year % 400) {
+      (0, 0, 0) => 29,
+      (0, 0, _) => 28,
+      (0, _, _) => 29,
+      _ => 28,
+    },
+    4 | 6 | 9 | 11 => 30,
+    _ => 31,
+  }
+}
+
+fn error<I, E>(input: &I, pos: usize) -> Err<E>
+where
+  I: Slice<RangeFrom<usize>>,
+  E: ParseError<I>,
+{
+  Err::Error(E::from_error_kind(input.slice(pos..), ErrorKind::DateTime))
+}
+
+fn char_at<I>(input: &I, pos: usize) -> Option<char>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+{
+  input
+    .slice(pos..)
+    .iter_elements()
+    .next()
+    .map(AsChar::as_char)
+}
+
+/// Reads exactly `count` digits, and checks that their value is between `min` and `max`
+fn number<I, E>(input: &I, pos: usize, count: usize, min: u32, max: u32) -> Result<u32, Err<E>>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let mut it = input.slice(pos..).iter_elements();
+  let mut value = 0;
+  for _ in 0..count {
+    match it.next().and_then(|c| c.as_char().to_digit(10)) {
+      Some(d) => value = value * 10 + d,
+      None => return Err(error(input, pos)),
+    }
+  }
+
+  if value < min || value > max {
+    Err(error(input, pos))
+  } else {
+    Ok(value)
+  }
+}
+
+/// Checks that the separator `c` is at `pos`
+fn separator<I, E>(input: &I, pos: usize, c: char) -> Result<(), Err<E>>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  if char_at(input, pos) == Some(c) {
+    Ok(())
+  } else {
+    Err(error(input, pos))
+  }
+}
+
+fn date_at<I, E>(input: &I, pos: usize) -> Result<(Date, usize), Err<E>>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let year = number(input, pos, 4, 0, 9999)? as u16;
+  separator(input, pos + 4, '-')?;
+  let month = number(input, pos + 5, 2, 1, 12)? as u8;
+  separator(input, pos + 7, '-')?;
+  let day = number(input, pos + 8, 2, 1, u32::from(days_in_month(year, month)))? as u8;
+
+  Ok((Date { year, month, day }, pos + 10))
+}
+
+fn time_at<I, E>(input: &I, pos: usize, leap: LeapSecond) -> Result<(Time, usize), Err<E>>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let hour = number(input, pos, 2, 0, 23)? as u8;
+  separator(input, pos + 2, ':')?;
+  let minute = number(input, pos + 3, 2, 0, 59)? as u8;
+  separator(input, pos + 5, ':')?;
+  let max_second = if leap == LeapSecond::Accept { 60 } else { 59 };
+  let second = number(input, pos + 6, 2, 0, max_second)? as u8;
+
+  let mut end = pos + 8;
+  let mut nanosecond = 0;
+  if char_at(input, end) == Some('.') {
+    let mut digits = 0;
+    for c in input.slice(end + 1..).iter_elements() {
+      match c.as_char().to_digit(10) {
+        Some(d) => {
+          if digits < 9 {
+            nanosecond = nanosecond * 10 + d;
+          }
+          digits += 1;
+        }
+        None => break,
+      }
+    }
+
+    if digits == 0 {
+      return Err(error(input, end + 1));
+    }
+    for _ in digits..9 {
+      nanosecond *= 10;
+    }
+    end += 1 + digits;
+  }
+
+  Ok((
+    Time {
+      hour,
+      minute,
+      second,
+      nanosecond,
+    },
+    end,
+  ))
+}
+
+fn offset_at<I, E>(input: &I, pos: usize) -> Result<(i16, usize), Err<E>>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let sign = match char_at(input, pos) {
+    Some('Z') | Some('z') => return Ok((0, pos + 1)),
+    Some('+') => 1,
+    Some('-') => -1,
+    _ => return Err(error(input, pos)),
+  };
+
+  let hours = number(input, pos + 1, 2, 0, 23)? as i16;
+  separator(input, pos + 3, ':')?;
+  let minutes = number(input, pos + 4, 2, 0, 59)? as i16;
+
+  Ok((sign * (hours * 60 + minutes), pos + 6))
+}
+
+/// Recognizes a full date, like `2021-02-28`.
+///
+/// The day is checked against the number of days of the month, including leap years.
+/// The error points to the invalid component.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::datetime::{date, Date};
+///
+/// fn parser(i: &str) -> IResult<&str, Date> {
+///   date(i)
+/// }
+///
+/// assert_eq!(parser("2020-02-29"), Ok(("", Date { year: 2020, month: 2, day: 29 })));
+/// assert_eq!(parser("2021-02-29"), Err(Err::Error(Error::new("29", ErrorKind::DateTime))));
+/// assert_eq!(parser("2021-13-01"), Err(Err::Error(Error::new("13-01", ErrorKind::DateTime))));
+/// ```
+pub fn date<I, E>(input: I) -> IResult<I, Date, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let (date, end) = date_at(&input, 0)?;
+  Ok((input.slice(end..), date))
+}
+
+/// Recognizes a time without offset, like `23:20:50.52`.
+///
+/// The fraction of the second is optional. The argument indicates if leap seconds are accepted.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::datetime::{time, LeapSecond, Time};
+///
+/// fn parser(i: &str) -> IResult<&str, Time> {
+///   time(LeapSecond::Reject)(i)
+/// }
+///
+/// assert_eq!(parser("23:20:50.52"), Ok(("", Time { hour: 23, minute: 20, second: 50, nanosecond: 520_000_000 })));
+/// assert_eq!(parser("23:59:60"), Err(Err::Error(Error::new("60", ErrorKind::DateTime))));
+/// assert_eq!(parser("24:00:00"), Err(Err::Error(Error::new("24:00:00", ErrorKind::DateTime))));
+/// ```
+pub fn time<I, E>(leap: LeapSecond) -> impl Fn(I) -> IResult<I, Time, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let (time, end) = time_at(&input, 0, leap)?;
+    Ok((input.slice(end..), time))
+  }
+}
+
+/// Recognizes an offset from UTC, `Z` or like `+02:00`, and returns it in minutes.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::datetime::offset;
+///
+/// fn parser(i: &str) -> IResult<&str, i16> {
+///   offset(i)
+/// }
+///
+/// assert_eq!(parser("Z"), Ok(("", 0)));
+/// assert_eq!(parser("-05:30"), Ok(("", -330)));
+/// assert_eq!(parser("+0200"), Err(Err::Error(Error::new("00", ErrorKind::DateTime))));
+/// ```
+pub fn offset<I, E>(input: I) -> IResult<I, i16, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let (offset, end) = offset_at(&input, 0)?;
+  Ok((input.slice(end..), offset))
+}
+
+/// Recognizes an RFC 3339 timestamp, like `1985-04-12T23:20:50.52Z`.
+///
+/// The date and the time can be separated by `T`, `t` or a space. The argument indicates
+/// if leap seconds are accepted. If they are, a second 60 must be at 23:59 UTC.
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::datetime::{date_time, Date, DateTime, LeapSecond, Time};
+///
+/// fn parser(i: &str) -> IResult<&str, DateTime> {
+///   date_time(LeapSecond::Accept)(i)
+/// }
+///
+/// assert_eq!(
+///   parser("1990-12-31T15:59:60-08:00"),
+///   Ok(("", DateTime {
+///     date: Date { year: 1990, month: 12, day: 31 },
+///     time: Time { hour: 15, minute: 59, second: 60, nanosecond: 0 },
+///     offset: -480,
+///   }))
+/// );
+/// assert_eq!(
+///   parser("1990-12-31T15:59:60Z"),
+///   Err(Err::Error(Error::new("60Z", ErrorKind::DateTime)))
+/// );
+/// ```
+pub fn date_time<I, E>(leap: LeapSecond) -> impl Fn(I) -> IResult<I, DateTime, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let (date, pos) = date_at(&input, 0)?;
+    match char_at(&input, pos) {
+      Some('T') | Some('t') | Some(' ') => {}
+      _ => return Err(error(&input, pos)),
+    }
+    let (time, end) = time_at(&input, pos + 1, leap)?;
+    let (offset, end) = offset_at(&input, end)?;
+
+    let utc_minutes = i32::from(time.hour) * 60 + i32::from(time.minute) - i32::from(offset);
+    if time.second == 60 && utc_minutes.rem_euclid(24 * 60) != 24 * 60 - 1 {
+      return Err(error(&input, pos + 7));
+    }
+
+    Ok((input.slice(end..), DateTime { date, time, offset }))
+  }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "chrono")))]
+impl DateTime {
+  /// Converts to a `chrono::DateTime`, returning `None` if chrono cannot represent it.
+  ///
+  /// A leap second is represented by chrono as a nanosecond value of at least
+  /// 1 000 000 000 in the 59th second.
+  pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    use chrono::{NaiveDate, TimeZone};
+
+    let (second, nanosecond) = if self.time.second == 60 {
+      (59, self.time.nanosecond + 1_000_000_000)
+    } else {
+      (u32::from(self.time.second), self.time.nanosecond)
+    };
+
+    let naive = NaiveDate::from_ymd_opt(
+      i32::from(self.date.year),
+      u32::from(self.date.month),
+      u32::from(self.date.day),
+    )?
+    .and_hms_nano_opt(
+      u32::from(self.time.hour),
+      u32::from(self.time.minute),
+      second,
+      nanosecond,
+    )?;
+
+    chrono::FixedOffset::east_opt(i32::from(self.offset) * 60)?
+      .from_local_datetime(&naive)
+      .single()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  #[test]
+  fn date_time_errors() {
+    let p = date_time::<_, Error<&[u8]>>(LeapSecond::Reject);
+    assert_eq!(
+      p(&b"2000-02-29 00:00:00.1234567891+14:00 "[..]).map(|(i, dt)| (
+        i,
+        dt.time.nanosecond,
+        dt.offset
+      )),
+      Ok((&b" "[..], 123_456_789, 840))
+    );
+    assert_eq!(
+      p(&b"1900-02-29T00:00:00Z"[..]),
+      Err(Err::Error(Error::new(
+        &b"29T00:00:00Z"[..],
+        ErrorKind::DateTime
+      )))
+    );
+    assert_eq!(
+      p(&b"2000-01-01_00:00:00Z"[..]),
+      Err(Err::Error(Error::new(
+        &b"_00:00:00Z"[..],
+        ErrorKind::DateTime
+      )))
+    );
+    assert_eq!(
+      p(&b"2000-01-01T00:00:00.Z"[..]),
+      Err(Err::Error(Error::new(&b"Z"[..], ErrorKind::DateTime)))
+    );
+    assert_eq!(
+      p(&b"2000-01-01T00:00:00"[..]),
+      Err(Err::Error(Error::new(&b""[..], ErrorKind::DateTime)))
+    );
+    assert_eq!(
+      p(&b"2000-01-01T00:00:00+24:00"[..]),
+      Err(Err::Error(Error::new(&b"24:00"[..], ErrorKind::DateTime)))
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "chrono")]
+  fn date_time_chrono() {
+    let p = date_time::<_, Error<&str>>(LeapSecond::Accept);
+    let (_, dt) = p("2016-12-31T23:59:60.5Z").unwrap();
+    let converted = dt.to_chrono().unwrap();
+    assert_eq!(converted.timestamp(), 1_483_228_799);
+    assert_eq!(converted.timestamp_subsec_nanos(), 1_500_000_000);
+  }
+}
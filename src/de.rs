@@ -0,0 +1,472 @@
+//! Deserialization of serde types from a nom grammar
+//!
+//! A grammar describes the structure of a format by producing a [Value]: a
+//! sequence, a map or a primitive. That value implements serde's
+//! `Deserializer`, so any type deriving `Deserialize` can be read from the
+//! format with [from_parser].
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use nom::{IResult, Parser};
+//! use nom::bytes::complete::{tag, take_while1};
+//! use nom::character::complete::{char, digit1, line_ending};
+//! use nom::combinator::map;
+//! use nom::multi::many0;
+//! use nom::sequence::{separated_pair, terminated};
+//! use nom::de::{from_parser, Value};
+//!
+//! #[derive(Debug, PartialEq, Deserialize)]
+//! struct Config {
+//!   name: String,
+//!   port: u16,
+//! }
+//!
+//! // each line is a `key=value` entry, with numbers or strings as values
+//! fn value(i: &str) -> IResult<&str, Value> {
+//!   nom::branch::alt((
+//!     map(digit1, |s: &str| Value::U64(s.parse().unwrap())),
+//!     map(take_while1(|c: char| c != '\n'), Value::from),
+//!   ))(i)
+//! }
+//!
+//! fn config(i: &str) -> IResult<&str, Value> {
+//!   let entry = separated_pair(map(take_while1(|c: char| c != '='), Value::from), char('='), value);
+//!   map(many0(terminated(entry, line_ending)), Value::Map)(i)
+//! }
+//!
+//! let c: Config = from_parser("name=server\nport=8080\n", config).unwrap();
+//! assert_eq!(c, Config { name: "server".to_string(), port: 8080 });
+//! ```
+
+use crate::error::ErrorKind;
+use crate::internal::{Err, Parser};
+use crate::lib::std::borrow::Cow;
+use crate::lib::std::boxed::Box;
+use crate::lib::std::fmt;
+use crate::lib::std::string::{String, ToString};
+use crate::lib::std::vec::{IntoIter, Vec};
+use crate::traits::{InputLength, Offset};
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{
+  self, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+
+/// Document structure produced by a grammar, following the serde data model
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+  /// No data, like `()` or a unit struct
+  Unit,
+  /// A boolean
+  Bool(bool),
+  /// A signed integer
+  I64(i64),
+  /// An unsigned integer
+  U64(u64),
+  /// A floating point number
+  F64(f64),
+  /// A character
+  Char(char),
+  /// A string, borrowed from the input when possible
+  Str(Cow<'a, str>),
+  /// A byte string, borrowed from the input when possible
+  Bytes(Cow<'a, [u8]>),
+  /// An optional value
+  Option(Option<Box<Value<'a>>>),
+  /// A sequence of values, for sequences, tuples or structs
+  Seq(Vec<Value<'a>>),
+  /// A list of key-value pairs, for maps or structs
+  Map(Vec<(Value<'a>, Value<'a>)>),
+}
+
+impl<'a> From<bool> for Value<'a> {
+  fn from(b: bool) -> Self {
+    Value::Bool(b)
+  }
+}
+
+impl<'a> From<i64> for Value<'a> {
+  fn from(i: i64) -> Self {
+    Value::I64(i)
+  }
+}
+
+impl<'a> From<u64> for Value<'a> {
+  fn from(u: u64) -> Self {
+    Value::U64(u)
+  }
+}
+
+impl<'a> From<f64> for Value<'a> {
+  fn from(f: f64) -> Self {
+    Value::F64(f)
+  }
+}
+
+impl<'a> From<char> for Value<'a> {
+  fn from(c: char) -> Self {
+    Value::Char(c)
+  }
+}
+
+impl<'a> From<&'a str> for Value<'a> {
+  fn from(s: &'a str) -> Self {
+    Value::Str(Cow::Borrowed(s))
+  }
+}
+
+impl<'a> From<String> for Value<'a> {
+  fn from(s: String) -> Self {
+    Value::Str(Cow::Owned(s))
+  }
+}
+
+impl<'a> From<Cow<'a, str>> for Value<'a> {
+  fn from(s: Cow<'a, str>) -> Self {
+    Value::Str(s)
+  }
+}
+
+impl<'a> From<&'a [u8]> for Value<'a> {
+  fn from(b: &'a [u8]) -> Self {
+    Value::Bytes(Cow::Borrowed(b))
+  }
+}
+
+impl<'a> From<Vec<Value<'a>>> for Value<'a> {
+  fn from(v: Vec<Value<'a>>) -> Self {
+    Value::Seq(v)
+  }
+}
+
+impl<'a> From<Vec<(Value<'a>, Value<'a>)>> for Value<'a> {
+  fn from(v: Vec<(Value<'a>, Value<'a>)>) -> Self {
+    Value::Map(v)
+  }
+}
+
+impl<'a, T: Into<Value<'a>>> From<Option<T>> for Value<'a> {
+  fn from(o: Option<T>) -> Self {
+    Value::Option(o.map(|v| Box::new(v.into())))
+  }
+}
+
+/// Error returned by [from_parser] and by the `Deserializer` implementation of [Value]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+  /// The grammar did not match the input
+  Parse {
+    /// Kind of the parser that failed
+    kind: ErrorKind,
+    /// Position of the error in the input
+    offset: usize,
+  },
+  /// The grammar matched, but not all the input was consumed
+  TrailingData {
+    /// Position of the first unparsed element
+    offset: usize,
+  },
+  /// The value produced by the grammar does not match the requested type
+  Message(String),
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Error::Parse { kind, offset } => write!(
+        f,
+        "parse error at offset {}: {}",
+        offset,
+        kind.description()
+      ),
+      Error::TrailingData { offset } => write!(f, "unexpected data at offset {}", offset),
+      Error::Message(m) => f.write_str(m),
+    }
+  }
+}
+
+impl de::StdError for Error {}
+
+impl de::Error for Error {
+  fn custom<T: fmt::Display>(msg: T) -> Self {
+    Error::Message(msg.to_string())
+  }
+}
+
+/// Applies the grammar to the whole input, then deserializes a `T` from the
+/// resulting [Value]
+pub fn from_parser<'a, I, T, F>(input: I, mut parser: F) -> Result<T, Error>
+where
+  I: Clone + InputLength + Offset,
+  T: de::Deserialize<'a>,
+  F: Parser<I, Value<'a>, crate::error::Error<I>>,
+{
+  match parser.parse(input.clone()) {
+    Ok((rest, value)) => {
+      if rest.input_len() != 0 {
+        return Err(Error::TrailingData {
+          offset: input.offset(&rest),
+        });
+      }
+      T::deserialize(value)
+    }
+    Err(Err::Error(e)) | Err(Err::Failure(e)) => Err(Error::Parse {
+      kind: e.code,
+      offset: input.offset(&e.input),
+    }),
+    Err(Err::Incomplete(_)) => Err(Error::Parse {
+      kind: ErrorKind::Complete,
+      offset: input.input_len(),
+    }),
+  }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value<'de> {
+  type Deserializer = Self;
+
+  fn into_deserializer(self) -> Self {
+    self
+  }
+}
+
+fn seq<'de, V: Visitor<'de>>(values: Vec<Value<'de>>, visitor: V) -> Result<V::Value, Error> {
+  let mut access = SeqDeserializer::<IntoIter<Value<'de>>, Error>::new(values.into_iter());
+  let res = visitor.visit_seq(&mut access)?;
+  access.end()?;
+  Ok(res)
+}
+
+fn map<'de, V: Visitor<'de>>(
+  entries: Vec<(Value<'de>, Value<'de>)>,
+  visitor: V,
+) -> Result<V::Value, Error> {
+  let mut access = MapDeserializer::new(entries.into_iter());
+  let res = visitor.visit_map(&mut access)?;
+  access.end()?;
+  Ok(res)
+}
+
+impl<'de> Deserializer<'de> for Value<'de> {
+  type Error = Error;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    match self {
+      Value::Unit => visitor.visit_unit(),
+      Value::Bool(b) => visitor.visit_bool(b),
+      Value::I64(i) => visitor.visit_i64(i),
+      Value::U64(u) => visitor.visit_u64(u),
+      Value::F64(f) => visitor.visit_f64(f),
+      Value::Char(c) => visitor.visit_char(c),
+      Value::Str(Cow::Borrowed(s)) => visitor.visit_borrowed_str(s),
+      Value::Str(Cow::Owned(s)) => visitor.visit_string(s),
+      Value::Bytes(Cow::Borrowed(b)) => visitor.visit_borrowed_bytes(b),
+      Value::Bytes(Cow::Owned(b)) => visitor.visit_byte_buf(b),
+      Value::Option(None) => visitor.visit_none(),
+      Value::Option(Some(v)) => visitor.visit_some(*v),
+      Value::Seq(v) => seq(v, visitor),
+      Value::Map(v) => map(v, visitor),
+    }
+  }
+
+  fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    match self {
+      Value::Unit | Value::Option(None) => visitor.visit_none(),
+      Value::Option(Some(v)) => visitor.visit_some(*v),
+      v => visitor.visit_some(v),
+    }
+  }
+
+  fn deserialize_newtype_struct<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  fn deserialize_enum<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    _variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    match self {
+      Value::Map(mut entries) if entries.len() == 1 => {
+        let (variant, content) = entries.remove(0);
+        visitor.visit_enum(Enum {
+          variant,
+          content: Some(content),
+        })
+      }
+      variant => visitor.visit_enum(Enum {
+        variant,
+        content: None,
+      }),
+    }
+  }
+
+  forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf unit unit_struct seq tuple
+    tuple_struct map struct identifier ignored_any
+  }
+}
+
+/// Enum variant: its name, and its content for non unit variants
+struct Enum<'de> {
+  variant: Value<'de>,
+  content: Option<Value<'de>>,
+}
+
+impl<'de> EnumAccess<'de> for Enum<'de> {
+  type Error = Error;
+  type Variant = Variant<'de>;
+
+  fn variant_seed<V: DeserializeSeed<'de>>(
+    self,
+    seed: V,
+  ) -> Result<(V::Value, Self::Variant), Error> {
+    let variant = seed.deserialize(self.variant)?;
+    Ok((variant, Variant(self.content)))
+  }
+}
+
+/// Content of an enum variant
+struct Variant<'de>(Option<Value<'de>>);
+
+impl<'de> VariantAccess<'de> for Variant<'de> {
+  type Error = Error;
+
+  fn unit_variant(self) -> Result<(), Error> {
+    match self.0 {
+      None | Some(Value::Unit) => Ok(()),
+      Some(_) => Err(de::Error::custom("expected a unit variant")),
+    }
+  }
+
+  fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+    match self.0 {
+      Some(v) => seed.deserialize(v),
+      None => Err(de::Error::custom("expected a newtype variant")),
+    }
+  }
+
+  fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+    match self.0 {
+      Some(v) => v.deserialize_any(visitor),
+      None => Err(de::Error::custom("expected a tuple variant")),
+    }
+  }
+
+  fn struct_variant<V: Visitor<'de>>(
+    self,
+    _fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    match self.0 {
+      Some(v) => v.deserialize_any(visitor),
+      None => Err(de::Error::custom("expected a struct variant")),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::branch::alt;
+  use crate::bytes::complete::{tag, take_while1};
+  use crate::character::complete::{char, digit1, multispace0};
+  use crate::combinator::{map, opt, recognize, value};
+  use crate::multi::separated_list0;
+  use crate::sequence::{delimited, pair, preceded, separated_pair};
+  use crate::IResult;
+  use serde::Deserialize;
+
+  // a small JSON-like format with bare identifiers as strings
+  fn item(i: &str) -> IResult<&str, Value> {
+    delimited(
+      multispace0,
+      alt((
+        value(Value::Unit, tag("null")),
+        value(Value::Bool(true), tag("true")),
+        value(Value::Bool(false), tag("false")),
+        map(recognize(pair(char('-'), digit1)), |s: &str| {
+          Value::I64(s.parse().unwrap())
+        }),
+        map(digit1, |s: &str| Value::U64(s.parse().unwrap())),
+        map(take_while1(|c: char| c.is_alphabetic()), Value::from),
+        map(
+          delimited(char('['), separated_list0(char(','), item), char(']')),
+          Value::Seq,
+        ),
+        map(
+          delimited(
+            char('{'),
+            separated_list0(char(','), separated_pair(item, char(':'), item)),
+            char('}'),
+          ),
+          Value::Map,
+        ),
+      )),
+      multispace0,
+    )(i)
+  }
+
+  #[derive(Debug, PartialEq, Deserialize)]
+  enum Shape {
+    Point,
+    Circle(u32),
+    Rect { w: u32, h: u32 },
+  }
+
+  #[derive(Debug, PartialEq, Deserialize)]
+  struct Scene<'a> {
+    name: &'a str,
+    offset: (i32, i32),
+    shapes: Vec<Shape>,
+    visible: Option<bool>,
+    layer: Option<u8>,
+  }
+
+  #[test]
+  fn derive() {
+    let input = "{ name: main, offset: [-1, 2], visible: null, layer: 3,
+      shapes: [Point, {Circle: 4}, {Rect: {w: 1, h: 2}}] }";
+    let scene: Scene = from_parser(input, item).unwrap();
+
+    assert_eq!(
+      scene,
+      Scene {
+        name: "main",
+        offset: (-1, 2),
+        shapes: vec![Shape::Point, Shape::Circle(4), Shape::Rect { w: 1, h: 2 }],
+        visible: None,
+        layer: Some(3),
+      }
+    );
+  }
+
+  #[test]
+  fn errors() {
+    assert_eq!(
+      from_parser::<_, Vec<u8>, _>("[1, 2] x", item),
+      Err(Error::TrailingData { offset: 7 })
+    );
+    assert_eq!(
+      from_parser::<_, Vec<u8>, _>("[1, ", item),
+      // the error comes from the last branch of `alt`
+      Err(Error::Parse {
+        kind: ErrorKind::Char,
+        offset: 0
+      })
+    );
+    assert!(matches!(
+      from_parser::<_, Vec<u8>, _>("[1, 300]", item),
+      Err(Error::Message(_))
+    ));
+    assert_eq!(
+      from_parser::<_, Option<u8>, _>("1", preceded(opt(char('+')), item)),
+      Ok(Some(1))
+    );
+  }
+}
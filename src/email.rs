@@ -0,0 +1,418 @@
+//! Parsers for email addresses
+//!
+//! Those parsers follow the `addr-spec` grammar of RFC 5322, the address of a
+//! mailbox like `jane.doe@example.com`, and its components. They return slices
+//! of the input, including the quotes of a quoted local part and the brackets
+//! of a domain literal.
+//!
+//! Comments, folding whitespace and the obsolete syntax are not accepted, and
+//! only ASCII characters are allowed.
+//!
+//! Those parsers work on complete input.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::iter::Peekable;
+use crate::lib::std::ops::{RangeFrom, RangeTo};
+use crate::traits::{AsChar, InputIter, Slice};
+
+/// An email address, as returned by [addr_spec]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct AddrSpec<I> {
+  /// Part before the `@`, a dot-atom or a quoted string
+  pub local_part: I,
+  /// Part after the `@`, a dot-atom or a domain literal
+  pub domain: I,
+}
+
+/// Printable characters allowed in atoms
+fn is_atext(c: char) -> bool {
+  c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c)
+}
+
+/// Printable characters allowed in quoted strings, except `\` and `"`
+fn is_qtext(c: char) -> bool {
+  c == '!' || ('#'..='[').contains(&c) || (']'..='~').contains(&c)
+}
+
+/// Printable characters allowed in domain literals, except `[`, `]` and `\`
+fn is_dtext(c: char) -> bool {
+  ('!'..='Z').contains(&c) || ('^'..='~').contains(&c)
+}
+
+/// Position in the ASCII prefix of the input
+struct Scanner<It: Iterator<Item = char>> {
+  it: Peekable<It>,
+  pos: usize,
+}
+
+impl<It: Iterator<Item = char>> Scanner<It> {
+  fn peek(&mut self) -> Option<char> {
+    self.it.peek().cloned()
+  }
+
+  fn bump(&mut self) {
+    if self.it.next().is_some() {
+      self.pos += 1;
+    }
+  }
+
+  fn eat(&mut self, c: char) -> bool {
+    if self.peek() == Some(c) {
+      self.bump();
+      true
+    } else {
+      false
+    }
+  }
+
+  fn eat_while(&mut self, f: fn(char) -> bool) -> usize {
+    let start = self.pos;
+    loop {
+      match self.peek() {
+        Some(c) if f(c) => self.bump(),
+        _ => break,
+      }
+    }
+    self.pos - start
+  }
+
+  /// `atext+ ("." atext+)*`
+  fn dot_atom(&mut self) -> Result<(), usize> {
+    loop {
+      if self.eat_while(is_atext) == 0 {
+        return Err(self.pos);
+      }
+      if !self.eat('.') {
+        return Ok(());
+      }
+    }
+  }
+
+  /// `"` followed by printable characters or quoted pairs, and `"`
+  fn quoted_string(&mut self) -> Result<(), usize> {
+    if !self.eat('"') {
+      return Err(self.pos);
+    }
+    loop {
+      match self.peek() {
+        Some('"') => {
+          self.bump();
+          return Ok(());
+        }
+        Some('\\') => {
+          self.bump();
+          match self.peek() {
+            Some(c) if c == ' ' || c == '\t' || c.is_ascii_graphic() => self.bump(),
+            _ => return Err(self.pos),
+          }
+        }
+        Some(c) if is_qtext(c) || c == ' ' || c == '\t' => self.bump(),
+        _ => return Err(self.pos),
+      }
+    }
+  }
+
+  /// `[` followed by printable characters, and `]`
+  fn domain_literal(&mut self) -> Result<(), usize> {
+    if !self.eat('[') {
+      return Err(self.pos);
+    }
+    self.eat_while(is_dtext);
+    if self.eat(']') {
+      Ok(())
+    } else {
+      Err(self.pos)
+    }
+  }
+
+  fn local_part(&mut self) -> Result<(), usize> {
+    if self.peek() == Some('"') {
+      self.quoted_string()
+    } else {
+      self.dot_atom()
+    }
+  }
+
+  fn domain(&mut self) -> Result<(), usize> {
+    if self.peek() == Some('[') {
+      self.domain_literal()
+    } else {
+      self.dot_atom()
+    }
+  }
+}
+
+fn scanner<I>(input: &I) -> Scanner<impl Iterator<Item = char> + '_>
+where
+  I: InputIter,
+  <I as InputIter>::Item: AsChar,
+{
+  Scanner {
+    it: input
+      .iter_elements()
+      .map(AsChar::as_char)
+      .take_while(char::is_ascii)
+      .peekable(),
+    pos: 0,
+  }
+}
+
+/// Splits the input at the end position returned by a scanning function, or
+/// returns an `ErrorKind::Email` error at the position where it failed
+fn split<I, E>(input: I, res: Result<usize, usize>) -> IResult<I, I, E>
+where
+  I: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  E: ParseError<I>,
+{
+  match res {
+    Ok(end) => Ok((input.slice(end..), input.slice(..end))),
+    Err(pos) => Err(Err::Error(E::from_error_kind(
+      input.slice(pos..),
+      ErrorKind::Email,
+    ))),
+  }
+}
+
+/// Recognizes a dot-atom: atoms separated by single dots, like `jane.doe`.
+///
+/// An atom is made of letters, digits and the characters ``!#$%&'*+-/=?^_`{|}~``.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::email::dot_atom;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   dot_atom(i)
+/// }
+///
+/// assert_eq!(parser("jane.doe+tag@example.com"), Ok(("@example.com", "jane.doe+tag")));
+/// assert_eq!(parser("jane..doe"), Err(Err::Error(Error::new(".doe", ErrorKind::Email))));
+/// ```
+pub fn dot_atom<I, E>(input: I) -> IResult<I, I, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = {
+    let mut s = scanner(&input);
+    s.dot_atom().map(|_| s.pos)
+  };
+  split(input, res)
+}
+
+/// Recognizes a quoted string, like `"jane doe"`, with its quotes.
+///
+/// The characters are printable ASCII characters, spaces and tabs, and `\`
+/// followed by one of them.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::email::quoted_string;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   quoted_string(i)
+/// }
+///
+/// assert_eq!(parser(r#""jane \"jd\" doe"@x"#), Ok(("@x", r#""jane \"jd\" doe""#)));
+/// assert_eq!(parser("\"jane"), Err(Err::Error(Error::new("", ErrorKind::Email))));
+/// ```
+pub fn quoted_string<I, E>(input: I) -> IResult<I, I, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = {
+    let mut s = scanner(&input);
+    s.quoted_string().map(|_| s.pos)
+  };
+  split(input, res)
+}
+
+/// Recognizes a domain literal, like `[192.0.2.1]`, with its brackets.
+///
+/// The content is made of printable ASCII characters other than `[`, `]` and
+/// `\`, and is not validated further.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::email::domain_literal;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   domain_literal(i)
+/// }
+///
+/// assert_eq!(parser("[IPv6:2001:db8::1]>"), Ok((">", "[IPv6:2001:db8::1]")));
+/// assert_eq!(parser("[a[b]"), Err(Err::Error(Error::new("[b]", ErrorKind::Email))));
+/// ```
+pub fn domain_literal<I, E>(input: I) -> IResult<I, I, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = {
+    let mut s = scanner(&input);
+    s.domain_literal().map(|_| s.pos)
+  };
+  split(input, res)
+}
+
+/// Recognizes the local part of an address, a [dot_atom] or a [quoted_string].
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::email::local_part;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   local_part(i)
+/// }
+///
+/// assert_eq!(parser("jane.doe@x"), Ok(("@x", "jane.doe")));
+/// assert_eq!(parser("\"jane doe\"@x"), Ok(("@x", "\"jane doe\"")));
+/// ```
+pub fn local_part<I, E>(input: I) -> IResult<I, I, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = {
+    let mut s = scanner(&input);
+    s.local_part().map(|_| s.pos)
+  };
+  split(input, res)
+}
+
+/// Recognizes the domain of an address, a [dot_atom] or a [domain_literal].
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::email::domain;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   domain(i)
+/// }
+///
+/// assert_eq!(parser("example.com>"), Ok((">", "example.com")));
+/// assert_eq!(parser("[192.0.2.1]"), Ok(("", "[192.0.2.1]")));
+/// ```
+pub fn domain<I, E>(input: I) -> IResult<I, I, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = {
+    let mut s = scanner(&input);
+    s.domain().map(|_| s.pos)
+  };
+  split(input, res)
+}
+
+/// Recognizes an address: a [local_part], `@` and a [domain].
+///
+/// It returns an `ErrorKind::Email` error at the position of the first
+/// character that does not follow the grammar.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::email::{addr_spec, AddrSpec};
+///
+/// fn parser(i: &str) -> IResult<&str, AddrSpec<&str>> {
+///   addr_spec(i)
+/// }
+///
+/// assert_eq!(
+///   parser("jane.doe@example.com>"),
+///   Ok((">", AddrSpec { local_part: "jane.doe", domain: "example.com" }))
+/// );
+/// assert_eq!(
+///   parser("\"jane@home\"@[192.0.2.1]"),
+///   Ok(("", AddrSpec { local_part: "\"jane@home\"", domain: "[192.0.2.1]" }))
+/// );
+/// assert_eq!(parser("jane.doe"), Err(Err::Error(Error::new("", ErrorKind::Email))));
+/// assert_eq!(parser("jane@.com"), Err(Err::Error(Error::new(".com", ErrorKind::Email))));
+/// ```
+pub fn addr_spec<I, E>(input: I) -> IResult<I, AddrSpec<I>, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = {
+    let mut s = scanner(&input);
+    s.local_part().and_then(|_| {
+      let at = s.pos;
+      if !s.eat('@') {
+        return Err(at);
+      }
+      s.domain().map(|_| (at, s.pos))
+    })
+  };
+
+  match res {
+    Ok((at, end)) => {
+      let local_part = input.slice(..at);
+      let domain = input.slice(at + 1..).slice(..end - at - 1);
+      Ok((input.slice(end..), AddrSpec { local_part, domain }))
+    }
+    Err(pos) => Err(Err::Error(E::from_error_kind(
+      input.slice(pos..),
+      ErrorKind::Email,
+    ))),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  #[test]
+  fn addr_spec_grammar() {
+    let p = addr_spec::<_, Error<&[u8]>>;
+    assert_eq!(
+      p(&b"!#$%&'*+-/=?^_`{|}~@a-b.c"[..]),
+      Ok((
+        &b""[..],
+        AddrSpec {
+          local_part: &b"!#$%&'*+-/=?^_`{|}~"[..],
+          domain: &b"a-b.c"[..]
+        }
+      ))
+    );
+    assert_eq!(
+      p(&b"\"a\\\tb\"@c d"[..]),
+      Ok((
+        &b" d"[..],
+        AddrSpec {
+          local_part: &b"\"a\\\tb\""[..],
+          domain: &b"c"[..]
+        }
+      ))
+    );
+    assert_eq!(
+      p(&b".jane@x"[..]),
+      Err(Err::Error(Error::new(&b".jane@x"[..], ErrorKind::Email)))
+    );
+    assert_eq!(
+      p(&b"jane.@x"[..]),
+      Err(Err::Error(Error::new(&b"@x"[..], ErrorKind::Email)))
+    );
+    assert_eq!(
+      p(&b"\"a\nb\"@x"[..]),
+      Err(Err::Error(Error::new(&b"\nb\"@x"[..], ErrorKind::Email)))
+    );
+    assert_eq!(
+      p(&b"jane@[a\\b]"[..]),
+      Err(Err::Error(Error::new(&b"\\b]"[..], ErrorKind::Email)))
+    );
+    assert_eq!(
+      addr_spec::<_, Error<&str>>("jöhn@x"),
+      Err(Err::Error(Error::new("öhn@x", ErrorKind::Email)))
+    );
+  }
+}
@@ -5,6 +5,8 @@
 
 use crate::internal::Parser;
 use crate::lib::std::fmt;
+use crate::lib::std::ops::RangeFrom;
+use crate::traits::{Compare, Offset, Slice};
 
 /// This trait must be implemented by the error type of a nom parser.
 ///
@@ -22,11 +24,29 @@ pub trait ParseError<I>: Sized {
   /// through a parse tree, accumulating error context on the way
   fn append(input: I, kind: ErrorKind, other: Self) -> Self;
 
+  /// Combines an existing error with the index of the element a repetition
+  /// combinator like [count](crate::multi::count) was parsing when it failed.
+  /// The default implementation calls [append](ParseError::append)
+  fn append_element(input: I, kind: ErrorKind, _index: usize, other: Self) -> Self {
+    Self::append(input, kind, other)
+  }
+
   /// Creates an error from an input position and an expected character
   fn from_char(input: I, _: char) -> Self {
     Self::from_error_kind(input, ErrorKind::Char)
   }
 
+  /// Creates an error from an input position and the pattern expected by
+  /// [tag](crate::bytes::tag) or [tag_no_case](crate::bytes::tag_no_case).
+  /// The pattern can be written with [Compare::fmt_tag]. The default
+  /// implementation returns an `ErrorKind::Tag` error
+  fn from_tag<T>(input: I, _expected: &T) -> Self
+  where
+    I: Compare<T>,
+  {
+    Self::from_error_kind(input, ErrorKind::Tag)
+  }
+
   /// Combines two existing errors. This function is used to compare errors
   /// generated in various branches of [alt]
   fn or(self, other: Self) -> Self {
@@ -55,6 +75,7 @@ pub trait FromExternalError<I, E> {
 
 /// default error type, only contains the error' location and code
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Error<I> {
   /// position of the error in the input data
   pub input: I,
@@ -145,9 +166,13 @@ pub fn append_error<I, E: ParseError<I>>(input: I, kind: ErrorKind, other: E) ->
 /// This error type accumulates errors and their position when backtracking
 /// through a parse tree. With some post processing (cf `examples/json.rs`),
 /// it can be used to display user friendly error messages
+///
+/// With the `serde` feature, it can be serialized, but not deserialized
+/// since the contexts are static strings
 #[cfg(feature = "alloc")]
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerboseError<I> {
   /// List of errors accumulated by `VerboseError`, containing the affected
   /// part of input data, and some context
@@ -157,14 +182,19 @@ pub struct VerboseError<I> {
 #[cfg(feature = "alloc")]
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 /// Error context for `VerboseError`
 pub enum VerboseErrorKind {
   /// Static string added by the `context` function
   Context(&'static str),
   /// Indicates which character was expected by the `char` function
   Char(char),
+  /// Indicates which pattern was expected by the `tag` functions
+  Tag(crate::lib::std::string::String),
   /// Error kind given by various nom parsers
   Nom(ErrorKind),
+  /// Error kind of a repetition combinator and index of the element that failed
+  Element(ErrorKind, usize),
 }
 
 #[cfg(feature = "alloc")]
@@ -181,11 +211,46 @@ impl<I> ParseError<I> for VerboseError<I> {
     other
   }
 
+  fn append_element(input: I, kind: ErrorKind, index: usize, mut other: Self) -> Self {
+    other
+      .errors
+      .push((input, VerboseErrorKind::Element(kind, index)));
+    other
+  }
+
   fn from_char(input: I, c: char) -> Self {
     VerboseError {
       errors: vec![(input, VerboseErrorKind::Char(c))],
     }
   }
+
+  fn from_tag<T>(input: I, expected: &T) -> Self
+  where
+    I: Compare<T>,
+  {
+    use crate::lib::std::string::ToString;
+
+    let tag = DisplayTag::<I, T>(expected, core::marker::PhantomData).to_string();
+    let kind = if tag.is_empty() {
+      VerboseErrorKind::Nom(ErrorKind::Tag)
+    } else {
+      VerboseErrorKind::Tag(tag)
+    };
+    VerboseError {
+      errors: vec![(input, kind)],
+    }
+  }
+}
+
+/// Formats a pattern with [Compare::fmt_tag]
+#[cfg(feature = "alloc")]
+struct DisplayTag<'a, I, T>(&'a T, core::marker::PhantomData<I>);
+
+#[cfg(feature = "alloc")]
+impl<'a, I: Compare<T>, T> fmt::Display for DisplayTag<'a, I, T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    I::fmt_tag(self.0, f)
+  }
 }
 
 #[cfg(feature = "alloc")]
@@ -214,7 +279,9 @@ impl<I: fmt::Display> fmt::Display for VerboseError<I> {
       match error {
         VerboseErrorKind::Nom(e) => writeln!(f, "{:?} at: {}", e, input)?,
         VerboseErrorKind::Char(c) => writeln!(f, "expected '{}' at: {}", c, input)?,
+        VerboseErrorKind::Tag(t) => writeln!(f, "expected `{}` at: {}", t, input)?,
         VerboseErrorKind::Context(s) => writeln!(f, "in section '{}', at: {}", s, input)?,
+        VerboseErrorKind::Element(e, n) => writeln!(f, "{:?} at element {} of: {}", e, n, input)?,
       }
     }
 
@@ -225,6 +292,446 @@ impl<I: fmt::Display> fmt::Display for VerboseError<I> {
 #[cfg(feature = "std")]
 impl<I: fmt::Debug + fmt::Display> std::error::Error for VerboseError<I> {}
 
+/// Frame of an error, as returned by [ErrorFrames]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ErrorFrame<'a, I> {
+  /// Input at which the frame was recorded
+  pub input: &'a I,
+  /// Kind of the error, `None` for a frame added by [context]
+  pub kind: Option<ErrorKind>,
+  /// Label added by [context]
+  pub context: Option<&'static str>,
+  /// Character expected by the `char` parser
+  pub expected: Option<char>,
+  /// Pattern expected by the `tag` parsers
+  pub expected_tag: Option<&'a str>,
+  /// Index of the element that failed in a repetition combinator
+  pub element: Option<usize>,
+}
+
+impl<'a, I> ErrorFrame<'a, I> {
+  fn new(input: &'a I, kind: Option<ErrorKind>) -> Self {
+    ErrorFrame {
+      input,
+      kind,
+      context: None,
+      expected: None,
+      expected_tag: None,
+      element: None,
+    }
+  }
+
+  /// Position of the frame in the `original` input of the parser
+  pub fn offset(&self, original: &I) -> usize
+  where
+    I: Offset,
+  {
+    original.offset(self.input)
+  }
+}
+
+/// Iterator over the frames of an error, returned by [ErrorFrames::frames]
+pub struct Frames<'a, I> {
+  inner: FramesInner<'a, I>,
+}
+
+enum FramesInner<'a, I> {
+  Single(Option<ErrorFrame<'a, I>>),
+  #[cfg(feature = "alloc")]
+  Verbose(crate::lib::std::slice::Iter<'a, (I, VerboseErrorKind)>),
+}
+
+impl<'a, I> Iterator for Frames<'a, I> {
+  type Item = ErrorFrame<'a, I>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match &mut self.inner {
+      FramesInner::Single(frame) => frame.take(),
+      #[cfg(feature = "alloc")]
+      FramesInner::Verbose(it) => it.next().map(|(input, kind)| match kind {
+        VerboseErrorKind::Nom(k) => ErrorFrame::new(input, Some(*k)),
+        VerboseErrorKind::Char(c) => ErrorFrame {
+          expected: Some(*c),
+          ..ErrorFrame::new(input, Some(ErrorKind::Char))
+        },
+        VerboseErrorKind::Tag(t) => ErrorFrame {
+          expected_tag: Some(t),
+          ..ErrorFrame::new(input, Some(ErrorKind::Tag))
+        },
+        VerboseErrorKind::Context(ctx) => ErrorFrame {
+          context: Some(ctx),
+          ..ErrorFrame::new(input, None)
+        },
+        VerboseErrorKind::Element(k, index) => ErrorFrame {
+          element: Some(*index),
+          ..ErrorFrame::new(input, Some(*k))
+        },
+      }),
+    }
+  }
+}
+
+/// Structured access to the positions, kinds and contexts recorded by an error
+///
+/// ```rust
+/// # use nom::{Err, IResult};
+/// use nom::character::complete::{alpha1, char, digit1};
+/// use nom::error::{context, ErrorFrames, ErrorKind, VerboseError};
+/// use nom::sequence::{pair, preceded};
+///
+/// fn version(i: &str) -> IResult<&str, (&str, &str), VerboseError<&str>> {
+///   pair(alpha1, context("number", preceded(char('-'), digit1)))(i)
+/// }
+///
+/// let input = "app-x";
+/// let e = match version(input) {
+///   Err(Err::Error(e)) => e,
+///   _ => unreachable!(),
+/// };
+///
+/// let deepest = e.deepest().unwrap();
+/// assert_eq!((deepest.offset(&input), deepest.kind), (4, Some(ErrorKind::Digit)));
+///
+/// let labels: Vec<_> = e.frames().filter_map(|f| f.context.map(|c| (f.offset(&input), c))).collect();
+/// assert_eq!(labels, vec![(3, "number")]);
+/// ```
+pub trait ErrorFrames<I> {
+  /// Iterates over the frames, from the deepest one to the outermost
+  fn frames(&self) -> Frames<'_, I>;
+
+  /// Returns the deepest frame, where the parser failed
+  fn deepest(&self) -> Option<ErrorFrame<'_, I>> {
+    self.frames().next()
+  }
+}
+
+impl<I> ErrorFrames<I> for Error<I> {
+  fn frames(&self) -> Frames<'_, I> {
+    Frames {
+      inner: FramesInner::Single(Some(ErrorFrame::new(&self.input, Some(self.code)))),
+    }
+  }
+}
+
+impl<I> ErrorFrames<I> for (I, ErrorKind) {
+  fn frames(&self) -> Frames<'_, I> {
+    Frames {
+      inner: FramesInner::Single(Some(ErrorFrame::new(&self.0, Some(self.1)))),
+    }
+  }
+}
+
+/// `()` does not record any frame
+impl<I> ErrorFrames<I> for () {
+  fn frames(&self) -> Frames<'_, I> {
+    Frames {
+      inner: FramesInner::Single(None),
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+impl<I> ErrorFrames<I> for VerboseError<I> {
+  fn frames(&self) -> Frames<'_, I> {
+    Frames {
+      inner: FramesInner::Verbose(self.errors.iter()),
+    }
+  }
+}
+
+/// Error type carrying the application errors returned to [map_res] and
+/// similar combinators, to mix domain validation with parsing
+///
+/// Any error convertible to `Box<dyn std::error::Error + Send + Sync>` can be
+/// raised, which includes `anyhow::Error`, `eyre::Report` and the standard
+/// library errors. It is available as [std::error::Error::source], and this
+/// type can itself be converted to those error types once the input is owned,
+/// with [ExternalError::map_input].
+///
+/// ```rust
+/// # use nom::{Err, IResult};
+/// use nom::character::complete::{alpha1, char, digit1};
+/// use nom::combinator::map_res;
+/// use nom::error::{context, ErrorKind, ExternalError};
+/// use nom::sequence::separated_pair;
+///
+/// fn port(i: &str) -> IResult<&str, u16, ExternalError<&str>> {
+///   context("port", map_res(digit1, |s: &str| match s.parse::<u16>() {
+///     Ok(0) => Err("port 0 is reserved".into()),
+///     Ok(port) => Ok(port),
+///     Err(e) => Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>),
+///   }))(i)
+/// }
+///
+/// let e = match separated_pair(alpha1, char(':'), port)("localhost:0") {
+///   Err(Err::Error(e)) => e,
+///   _ => unreachable!(),
+/// };
+/// assert_eq!(e.kind, ErrorKind::MapRes);
+/// assert_eq!(e.input, "0");
+/// assert_eq!(e.contexts, vec![("0", "port")]);
+/// assert_eq!(e.to_string(), "error MapRes at: 0: port 0 is reserved");
+///
+/// // convert to an owned error, that can be wrapped in `anyhow::Error`
+/// let e: Box<dyn std::error::Error + Send + Sync> = Box::new(e.map_input(str::to_owned));
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub struct ExternalError<I> {
+  /// position of the error in the input data
+  pub input: I,
+  /// nom error code
+  pub kind: ErrorKind,
+  /// contexts added by the [context] combinator, from the innermost one
+  pub contexts: crate::lib::std::vec::Vec<(I, &'static str)>,
+  /// error returned by the application code, if any
+  pub source: Option<crate::lib::std::boxed::Box<dyn std::error::Error + Send + Sync>>,
+}
+
+#[cfg(feature = "std")]
+impl<I> ExternalError<I> {
+  /// Converts the input positions, for example to owned values
+  pub fn map_input<J, F: Fn(I) -> J>(self, f: F) -> ExternalError<J> {
+    ExternalError {
+      input: f(self.input),
+      kind: self.kind,
+      contexts: self
+        .contexts
+        .into_iter()
+        .map(|(i, ctx)| (f(i), ctx))
+        .collect(),
+      source: self.source,
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl<I> ParseError<I> for ExternalError<I> {
+  fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+    ExternalError {
+      input,
+      kind,
+      contexts: crate::lib::std::vec::Vec::new(),
+      source: None,
+    }
+  }
+
+  fn append(_: I, _: ErrorKind, other: Self) -> Self {
+    other
+  }
+}
+
+#[cfg(feature = "std")]
+impl<I> ContextError<I> for ExternalError<I> {
+  fn add_context(input: I, ctx: &'static str, mut other: Self) -> Self {
+    other.contexts.push((input, ctx));
+    other
+  }
+}
+
+#[cfg(feature = "std")]
+impl<I, E> FromExternalError<I, E> for ExternalError<I>
+where
+  E: Into<crate::lib::std::boxed::Box<dyn std::error::Error + Send + Sync>>,
+{
+  fn from_external_error(input: I, kind: ErrorKind, e: E) -> Self {
+    ExternalError {
+      source: Some(e.into()),
+      ..Self::from_error_kind(input, kind)
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl<I: fmt::Display> fmt::Display for ExternalError<I> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "error {:?} at: {}", self.kind, self.input)?;
+    if let Some(source) = &self.source {
+      write!(f, ": {}", source)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(feature = "std")]
+impl<I: fmt::Debug + fmt::Display> std::error::Error for ExternalError<I> {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match &self.source {
+      Some(e) => Some(&**e),
+      None => None,
+    }
+  }
+}
+
+#[cfg(all(feature = "std", backtrace))]
+static CAPTURE_BACKTRACES: core::sync::atomic::AtomicBool =
+  core::sync::atomic::AtomicBool::new(false);
+
+/// Enables or disables the capture of backtraces by [BacktraceError], disabled by default
+#[cfg(all(feature = "std", backtrace))]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+pub fn capture_backtraces(enabled: bool) {
+  CAPTURE_BACKTRACES.store(enabled, core::sync::atomic::Ordering::Relaxed);
+}
+
+/// Error wrapper capturing the Rust call stack where the error was created
+///
+/// The backtrace is captured when the inner error is created, by
+/// [from_error_kind](ParseError::from_error_kind), [from_char](ParseError::from_char),
+/// [from_tag](ParseError::from_tag) or
+/// [from_external_error](FromExternalError::from_external_error), and only if it
+/// was enabled with [capture_backtraces]. Combining errors keeps the first backtrace.
+///
+/// ```rust
+/// # use nom::{Err, IResult};
+/// use nom::character::complete::digit1;
+/// use nom::error::{capture_backtraces, BacktraceError, Error, ErrorKind};
+///
+/// fn number(i: &str) -> IResult<&str, &str, BacktraceError<Error<&str>>> {
+///   digit1(i)
+/// }
+///
+/// capture_backtraces(true);
+/// let e = match number("abc") {
+///   Err(Err::Error(e)) => e,
+///   _ => unreachable!(),
+/// };
+/// assert_eq!(e.inner, Error::new("abc", ErrorKind::Digit));
+/// assert!(e.backtrace().is_some());
+/// ```
+#[cfg(all(feature = "std", backtrace))]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub struct BacktraceError<E> {
+  /// wrapped error
+  pub inner: E,
+  backtrace: std::backtrace::Backtrace,
+}
+
+#[cfg(all(feature = "std", backtrace))]
+impl<E> BacktraceError<E> {
+  /// Wraps an error, capturing a backtrace if enabled
+  pub fn new(inner: E) -> Self {
+    let backtrace = if CAPTURE_BACKTRACES.load(core::sync::atomic::Ordering::Relaxed) {
+      std::backtrace::Backtrace::force_capture()
+    } else {
+      std::backtrace::Backtrace::disabled()
+    };
+    BacktraceError { inner, backtrace }
+  }
+
+  /// Returns the backtrace, if one was captured
+  pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+    match self.backtrace.status() {
+      std::backtrace::BacktraceStatus::Captured => Some(&self.backtrace),
+      _ => None,
+    }
+  }
+
+  fn map<F: FnOnce(E) -> E>(self, f: F) -> Self {
+    BacktraceError {
+      inner: f(self.inner),
+      backtrace: self.backtrace,
+    }
+  }
+}
+
+#[cfg(all(feature = "std", backtrace))]
+impl<I, E: ParseError<I>> ParseError<I> for BacktraceError<E> {
+  fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+    BacktraceError::new(E::from_error_kind(input, kind))
+  }
+
+  fn append(input: I, kind: ErrorKind, other: Self) -> Self {
+    other.map(|e| E::append(input, kind, e))
+  }
+
+  fn append_element(input: I, kind: ErrorKind, index: usize, other: Self) -> Self {
+    other.map(|e| E::append_element(input, kind, index, e))
+  }
+
+  fn from_char(input: I, c: char) -> Self {
+    BacktraceError::new(E::from_char(input, c))
+  }
+
+  fn from_tag<T>(input: I, expected: &T) -> Self
+  where
+    I: Compare<T>,
+  {
+    BacktraceError::new(E::from_tag(input, expected))
+  }
+
+  fn or(self, other: Self) -> Self {
+    let inner = self.inner;
+    other.map(|e| inner.or(e))
+  }
+}
+
+#[cfg(all(feature = "std", backtrace))]
+impl<I, E: ContextError<I>> ContextError<I> for BacktraceError<E> {
+  fn add_context(input: I, ctx: &'static str, other: Self) -> Self {
+    other.map(|e| E::add_context(input, ctx, e))
+  }
+}
+
+#[cfg(all(feature = "std", backtrace))]
+impl<I, E, E2: FromExternalError<I, E>> FromExternalError<I, E> for BacktraceError<E2> {
+  fn from_external_error(input: I, kind: ErrorKind, e: E) -> Self {
+    BacktraceError::new(E2::from_external_error(input, kind, e))
+  }
+}
+
+#[cfg(all(feature = "std", backtrace))]
+impl<I, E: ErrorFrames<I>> ErrorFrames<I> for BacktraceError<E> {
+  fn frames(&self) -> Frames<'_, I> {
+    self.inner.frames()
+  }
+}
+
+#[cfg(all(feature = "std", backtrace))]
+impl<E: fmt::Display> fmt::Display for BacktraceError<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.inner)?;
+    if let Some(backtrace) = self.backtrace() {
+      write!(f, "\n\nbacktrace:\n{}", backtrace)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(all(feature = "std", backtrace))]
+impl<E: std::error::Error> std::error::Error for BacktraceError<E> {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    self.inner.source()
+  }
+}
+
+/// Trailing input left by [all_consuming_trailing](crate::combinator::all_consuming_trailing)
+///
+/// It is passed to the error type through [FromExternalError].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrailingData {
+  /// offset of the remaining input from the start of the parsed input
+  pub offset: usize,
+  /// length of the remaining input
+  pub len: usize,
+}
+
+impl fmt::Display for TrailingData {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "unexpected trailing data at offset {} (length {})",
+      self.offset, self.len
+    )
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TrailingData {}
+
 use crate::internal::{Err, IResult};
 
 /// Create a new error from an input position, a static string and an existing error.
@@ -245,123 +752,357 @@ where
   }
 }
 
-/// Transforms a `VerboseError` into a trace with input position information
-#[cfg(feature = "alloc")]
-#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
-pub fn convert_error<I: core::ops::Deref<Target = str>>(
-  input: I,
-  e: VerboseError<I>,
-) -> crate::lib::std::string::String {
-  use crate::lib::std::fmt::Write;
-  use crate::traits::Offset;
+/// Re-anchors an error returned by a parser that ran over a derived buffer
+/// (unescaped text, decoded data...) to the original input.
+///
+/// `map` translates an offset in `buffer` to an offset in `original`. Each
+/// frame of the error is moved to the original input, keeping its kind,
+/// context and element index. The deepest frame also keeps the character or
+/// pattern it expected, through [ParseError::from_char] and
+/// [ParseError::from_tag], but since [ParseError] cannot add them to an
+/// existing error, those of the outer frames are dropped. An error recording
+/// no frame, like `()`, becomes an `ErrorKind::MapRes` error at the position
+/// of the start of the buffer.
+///
+/// ```rust
+/// # use nom::{Err, error::{reanchor, Error, ErrorKind}, IResult};
+/// use nom::character::complete::{char, digit1};
+/// use nom::sequence::terminated;
+///
+/// let original = "1_0_0_x";
+/// // the parser runs over the input without separators
+/// let buffer: String = original.chars().filter(|c| *c != '_').collect();
+/// let res: IResult<&str, &str> = terminated(digit1, char(';'))(&buffer[..]);
+///
+/// // each character is followed by a separator in the original
+/// let e: Err<Error<&str>> = reanchor(original, &buffer[..], res.unwrap_err(), |offset| offset * 2);
+/// assert_eq!(e, Err::Error(Error::new("x", ErrorKind::Char)));
+/// ```
+pub fn reanchor<I, B, E1, E, M>(original: I, buffer: B, err: Err<E1>, map: M) -> Err<E>
+where
+  I: Slice<RangeFrom<usize>> + for<'t> Compare<&'t str>,
+  B: Offset,
+  E1: ErrorFrames<B>,
+  E: ParseError<I> + ContextError<I>,
+  M: Fn(usize) -> usize,
+{
+  err.map(|e| {
+    let mut res: Option<E> = None;
+    for frame in e.frames() {
+      let input = original.slice(map(frame.offset(&buffer))..);
+      res = Some(match (res, frame.kind) {
+        (Some(other), None) => match frame.context {
+          Some(ctx) => E::add_context(input, ctx, other),
+          None => other,
+        },
+        (Some(other), Some(kind)) => match frame.element {
+          Some(index) => E::append_element(input, kind, index, other),
+          None => E::append(input, kind, other),
+        },
+        (None, kind) => match (frame.expected, frame.expected_tag) {
+          (Some(c), _) => E::from_char(input, c),
+          (None, Some(t)) => E::from_tag(input, &t),
+          (None, None) => E::from_error_kind(input, kind.unwrap_or(ErrorKind::MapRes)),
+        },
+      });
+    }
+    res.unwrap_or_else(|| E::from_error_kind(original.slice(map(0)..), ErrorKind::MapRes))
+  })
+}
 
-  let mut result = crate::lib::std::string::String::new();
+/// Location of an error frame in the input, passed to [ErrorRenderer::frame]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameLocation<'a> {
+  /// Position of the frame in the input, in bytes
+  pub offset: usize,
+  /// Line of the frame, starting at 1
+  pub line: usize,
+  /// Column of the frame in its line, in bytes, starting at 1
+  pub column: usize,
+  /// Text of the line containing the frame, without trailing whitespace
+  pub line_text: &'a str,
+}
 
-  for (i, (substring, kind)) in e.errors.iter().enumerate() {
-    let offset = input.offset(substring);
+impl<'a> FrameLocation<'a> {
+  fn new(input: &'a str, offset: usize) -> Self {
+    let prefix = &input.as_bytes()[..offset];
+
+    // Count the number of newlines in the first `offset` bytes of input
+    let line = prefix.iter().filter(|&&b| b == b'\n').count() + 1;
+
+    // Find the line that includes the subslice:
+    // Find the *last* newline before the substring starts
+    let line_begin = prefix
+      .iter()
+      .rev()
+      .position(|&b| b == b'\n')
+      .map(|pos| offset - pos)
+      .unwrap_or(0);
+
+    // Find the full line after that newline
+    let line_text = input[line_begin..]
+      .lines()
+      .next()
+      .unwrap_or(&input[line_begin..])
+      .trim_end();
+
+    FrameLocation {
+      offset,
+      line,
+      column: offset - line_begin + 1,
+      line_text,
+    }
+  }
+}
 
-    if input.is_empty() {
-      match kind {
-        VerboseErrorKind::Char(c) => {
-          write!(&mut result, "{}: expected '{}', got empty input\n\n", i, c)
+/// Prints the frames of an error, for [render_error]
+///
+/// Implement this trait to control the format of error messages, like adding
+/// colors or translating them. [DefaultRenderer] prints the format of
+/// [convert_error].
+///
+/// ```rust
+/// # use nom::{Err, IResult};
+/// use nom::character::complete::{char, digit1};
+/// use nom::error::{context, render_error, ErrorFrame, ErrorRenderer, FrameLocation, VerboseError};
+/// use nom::sequence::preceded;
+/// use std::fmt;
+///
+/// struct Short;
+///
+/// impl<'a> ErrorRenderer<&'a str> for Short {
+///   fn frame(
+///     &mut self,
+///     out: &mut dyn fmt::Write,
+///     _index: usize,
+///     location: Option<FrameLocation<'_>>,
+///     frame: &ErrorFrame<'_, &'a str>,
+///   ) -> fmt::Result {
+///     let (line, column) = location.map(|l| (l.line, l.column)).unwrap_or((1, 1));
+///     match (frame.context, frame.kind) {
+///       (Some(ctx), _) => writeln!(out, "{}:{}: in {}", line, column, ctx),
+///       (None, Some(kind)) => writeln!(out, "{}:{}: {}", line, column, kind.description()),
+///       (None, None) => Ok(()),
+///     }
+///   }
+/// }
+///
+/// fn number(i: &str) -> IResult<&str, &str, VerboseError<&str>> {
+///   context("number", preceded(char('#'), digit1))(i)
+/// }
+///
+/// let input = "#x";
+/// let e = match number(input) {
+///   Err(Err::Error(e)) => e,
+///   _ => unreachable!(),
+/// };
+/// let mut message = String::new();
+/// render_error(&input, &e, &mut Short, &mut message).unwrap();
+/// assert_eq!(message, "1:2: Digit\n1:1: in number\n");
+/// ```
+pub trait ErrorRenderer<I> {
+  /// Prints the frame number `index`, counted from the deepest one.
+  /// `location` is `None` if the input is empty
+  fn frame(
+    &mut self,
+    out: &mut dyn fmt::Write,
+    index: usize,
+    location: Option<FrameLocation<'_>>,
+    frame: &ErrorFrame<'_, I>,
+  ) -> fmt::Result;
+}
+
+/// Renderer printing the format of [convert_error]: the line of each frame,
+/// a caret under its position and what was expected
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DefaultRenderer;
+
+impl<I: core::ops::Deref<Target = str>> ErrorRenderer<I> for DefaultRenderer {
+  fn frame(
+    &mut self,
+    out: &mut dyn fmt::Write,
+    i: usize,
+    location: Option<FrameLocation<'_>>,
+    frame: &ErrorFrame<'_, I>,
+  ) -> fmt::Result {
+    let location = match location {
+      Some(location) => location,
+      None => {
+        return match frame {
+          ErrorFrame {
+            expected: Some(c), ..
+          } => write!(out, "{}: expected '{}', got empty input\n\n", i, c),
+          ErrorFrame {
+            expected_tag: Some(t),
+            ..
+          } => write!(out, "{}: expected `{}`, got empty input\n\n", i, t),
+          ErrorFrame {
+            context: Some(s), ..
+          } => write!(out, "{}: in {}, got empty input\n\n", i, s),
+          ErrorFrame {
+            kind: Some(e),
+            element: Some(n),
+            ..
+          } => write!(
+            out,
+            "{}: in {:?} at element {}, got empty input\n\n",
+            i, e, n
+          ),
+          ErrorFrame { kind: Some(e), .. } => write!(out, "{}: in {:?}, got empty input\n\n", i, e),
+          ErrorFrame { kind: None, .. } => Ok(()),
         }
-        VerboseErrorKind::Context(s) => write!(&mut result, "{}: in {}, got empty input\n\n", i, s),
-        VerboseErrorKind::Nom(e) => write!(&mut result, "{}: in {:?}, got empty input\n\n", i, e),
       }
-    } else {
-      let prefix = &input.as_bytes()[..offset];
-
-      // Count the number of newlines in the first `offset` bytes of input
-      let line_number = prefix.iter().filter(|&&b| b == b'\n').count() + 1;
-
-      // Find the line that includes the subslice:
-      // Find the *last* newline before the substring starts
-      let line_begin = prefix
-        .iter()
-        .rev()
-        .position(|&b| b == b'\n')
-        .map(|pos| offset - pos)
-        .unwrap_or(0);
-
-      // Find the full line after that newline
-      let line = input[line_begin..]
-        .lines()
-        .next()
-        .unwrap_or(&input[line_begin..])
-        .trim_end();
-
-      // The (1-indexed) column number is the offset of our substring into that line
-      let column_number = line.offset(substring) + 1;
-
-      match kind {
-        VerboseErrorKind::Char(c) => {
-          if let Some(actual) = substring.chars().next() {
-            write!(
-              &mut result,
-              "{i}: at line {line_number}:\n\
-               {line}\n\
-               {caret:>column$}\n\
-               expected '{expected}', found {actual}\n\n",
-              i = i,
-              line_number = line_number,
-              line = line,
-              caret = '^',
-              column = column_number,
-              expected = c,
-              actual = actual,
-            )
-          } else {
-            write!(
-              &mut result,
-              "{i}: at line {line_number}:\n\
-               {line}\n\
-               {caret:>column$}\n\
-               expected '{expected}', got end of input\n\n",
-              i = i,
-              line_number = line_number,
-              line = line,
-              caret = '^',
-              column = column_number,
-              expected = c,
-            )
-          }
-        }
-        VerboseErrorKind::Context(s) => write!(
-          &mut result,
-          "{i}: at line {line_number}, in {context}:\n\
+    };
+
+    let line_number = location.line;
+    let line = location.line_text;
+    let column_number = location.column;
+
+    match frame {
+      ErrorFrame {
+        expected: Some(c), ..
+      } => {
+        if let Some(actual) = frame.input.chars().next() {
+          write!(
+            out,
+            "{i}: at line {line_number}:\n\
              {line}\n\
-             {caret:>column$}\n\n",
-          i = i,
-          line_number = line_number,
-          context = s,
-          line = line,
-          caret = '^',
-          column = column_number,
-        ),
-        VerboseErrorKind::Nom(e) => write!(
-          &mut result,
-          "{i}: at line {line_number}, in {nom_err:?}:\n\
+             {caret:>column$}\n\
+             expected '{expected}', found {actual}\n\n",
+            i = i,
+            line_number = line_number,
+            line = line,
+            caret = '^',
+            column = column_number,
+            expected = c,
+            actual = actual,
+          )
+        } else {
+          write!(
+            out,
+            "{i}: at line {line_number}:\n\
              {line}\n\
-             {caret:>column$}\n\n",
-          i = i,
-          line_number = line_number,
-          nom_err = e,
-          line = line,
-          caret = '^',
-          column = column_number,
-        ),
+             {caret:>column$}\n\
+             expected '{expected}', got end of input\n\n",
+            i = i,
+            line_number = line_number,
+            line = line,
+            caret = '^',
+            column = column_number,
+            expected = c,
+          )
+        }
       }
+      ErrorFrame {
+        expected_tag: Some(t),
+        ..
+      } => write!(
+        out,
+        "{i}: at line {line_number}:\n\
+           {line}\n\
+           {caret:>column$}\n\
+           expected `{expected}`\n\n",
+        i = i,
+        line_number = line_number,
+        line = line,
+        caret = '^',
+        column = column_number,
+        expected = t,
+      ),
+      ErrorFrame {
+        context: Some(s), ..
+      } => write!(
+        out,
+        "{i}: at line {line_number}, in {context}:\n\
+           {line}\n\
+           {caret:>column$}\n\n",
+        i = i,
+        line_number = line_number,
+        context = s,
+        line = line,
+        caret = '^',
+        column = column_number,
+      ),
+      ErrorFrame {
+        kind: Some(e),
+        element: Some(n),
+        ..
+      } => write!(
+        out,
+        "{i}: at line {line_number}, in {nom_err:?} at element {index}:\n\
+           {line}\n\
+           {caret:>column$}\n\n",
+        i = i,
+        line_number = line_number,
+        nom_err = e,
+        index = n,
+        line = line,
+        caret = '^',
+        column = column_number,
+      ),
+      ErrorFrame { kind: Some(e), .. } => write!(
+        out,
+        "{i}: at line {line_number}, in {nom_err:?}:\n\
+           {line}\n\
+           {caret:>column$}\n\n",
+        i = i,
+        line_number = line_number,
+        nom_err = e,
+        line = line,
+        caret = '^',
+        column = column_number,
+      ),
+      ErrorFrame { kind: None, .. } => Ok(()),
     }
-    // Because `write!` to a `String` is infallible, this `unwrap` is fine.
-    .unwrap();
   }
+}
+
+/// Prints the frames of an error with a [ErrorRenderer], from the deepest one
+/// to the outermost, `input` being the whole input the parser was called on
+pub fn render_error<I, E, R>(
+  input: &I,
+  e: &E,
+  renderer: &mut R,
+  out: &mut dyn fmt::Write,
+) -> fmt::Result
+where
+  I: core::ops::Deref<Target = str>,
+  E: ErrorFrames<I>,
+  R: ErrorRenderer<I>,
+{
+  for (i, frame) in e.frames().enumerate() {
+    let location = if input.is_empty() {
+      None
+    } else {
+      Some(FrameLocation::new(
+        input,
+        Offset::offset(&**input, &**frame.input),
+      ))
+    };
+    renderer.frame(out, i, location, &frame)?;
+  }
+  Ok(())
+}
 
+/// Transforms a `VerboseError` into a trace with input position information
+///
+/// Use [render_error] with your own [ErrorRenderer] to change the format.
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn convert_error<I: core::ops::Deref<Target = str>>(
+  input: I,
+  e: VerboseError<I>,
+) -> crate::lib::std::string::String {
+  let mut result = crate::lib::std::string::String::new();
+  // Because `write!` to a `String` is infallible, this `unwrap` is fine.
+  render_error(&input, &e, &mut DefaultRenderer, &mut result).unwrap();
   result
 }
 
 /// Indicates which parser returned an error
 #[rustfmt::skip]
 #[derive(Debug,PartialEq,Eq,Hash,Clone,Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(deprecated,missing_docs)]
 pub enum ErrorKind {
   Tag,
@@ -417,6 +1158,28 @@ pub enum ErrorKind {
   Many1Count,
   Float,
   Satisfy,
+  QuotedString,
+  PercentEncoding,
+  Base64,
+  NetAddress,
+  Uuid,
+  DateTime,
+  UnknownRule,
+  Cancelled,
+  Flags,
+  ShellWord,
+  Identifier,
+  VerifyRecognized,
+  TakeTillMN,
+  DigitCount,
+  CharReference,
+  Duration,
+  SemVer,
+  Email,
+  Uri,
+  Unclosed,
+  Write,
+  LeftRecursion,
 }
 
 #[rustfmt::skip]
@@ -477,6 +1240,28 @@ pub fn error_to_u32(e: &ErrorKind) -> u32 {
     ErrorKind::Many1Count                => 73,
     ErrorKind::Float                     => 74,
     ErrorKind::Satisfy                   => 75,
+    ErrorKind::QuotedString              => 76,
+    ErrorKind::PercentEncoding           => 77,
+    ErrorKind::Base64                    => 78,
+    ErrorKind::NetAddress                => 79,
+    ErrorKind::Uuid                      => 80,
+    ErrorKind::DateTime                  => 81,
+    ErrorKind::UnknownRule               => 82,
+    ErrorKind::Cancelled                 => 83,
+    ErrorKind::Flags                     => 84,
+    ErrorKind::ShellWord                 => 85,
+    ErrorKind::Identifier                => 86,
+    ErrorKind::VerifyRecognized          => 87,
+    ErrorKind::TakeTillMN                => 88,
+    ErrorKind::DigitCount                => 89,
+    ErrorKind::CharReference             => 90,
+    ErrorKind::Duration                  => 91,
+    ErrorKind::SemVer                    => 92,
+    ErrorKind::Email                     => 93,
+    ErrorKind::Uri                       => 94,
+    ErrorKind::Unclosed                  => 95,
+    ErrorKind::Write                     => 96,
+    ErrorKind::LeftRecursion             => 97,
   }
 }
 
@@ -539,6 +1324,127 @@ impl ErrorKind {
       ErrorKind::Many1Count                => "Count occurrence of >=1 patterns",
       ErrorKind::Float                     => "Float",
       ErrorKind::Satisfy                   => "Satisfy",
+      ErrorKind::QuotedString              => "Quoted string",
+      ErrorKind::PercentEncoding           => "Percent-encoded data",
+      ErrorKind::Base64                    => "Base64",
+      ErrorKind::NetAddress                => "Network address",
+      ErrorKind::Uuid                      => "UUID",
+      ErrorKind::DateTime                  => "Date and time",
+      ErrorKind::UnknownRule               => "Unknown grammar rule",
+      ErrorKind::Cancelled                 => "Parse cancelled",
+      ErrorKind::Flags                     => "Unknown flag bits",
+      ErrorKind::ShellWord                 => "Shell word",
+      ErrorKind::Identifier                => "Identifier",
+      ErrorKind::VerifyRecognized          => "recognized input verification",
+      ErrorKind::TakeTillMN                => "TakeTillMN",
+      ErrorKind::DigitCount                => "Digit count",
+      ErrorKind::CharReference             => "Character reference",
+      ErrorKind::Duration                  => "Duration",
+      ErrorKind::SemVer                    => "Semantic version",
+      ErrorKind::Email                     => "Email address",
+      ErrorKind::Uri                       => "URI",
+      ErrorKind::Unclosed                  => "Unclosed delimiter",
+      ErrorKind::Write                     => "Writing the output failed",
+      ErrorKind::LeftRecursion             => "Left recursive grammar rule",
+    }
+  }
+}
+
+#[rustfmt::skip]
+#[allow(deprecated)]
+/// Converts a number returned by [error_to_u32] back to an ErrorKind
+///
+/// The codes are stable across versions, unknown codes are returned as error
+///
+/// ```rust
+/// # use nom::error::{error_to_u32, ErrorKind};
+/// use core::convert::TryFrom;
+///
+/// let code = error_to_u32(&ErrorKind::Digit);
+/// assert_eq!(ErrorKind::try_from(code), Ok(ErrorKind::Digit));
+/// assert_eq!(ErrorKind::try_from(1000), Err(1000));
+/// ```
+impl crate::lib::std::convert::TryFrom<u32> for ErrorKind {
+  type Error = u32;
+
+  fn try_from(code: u32) -> Result<Self, u32> {
+    match code {
+      1   => Ok(ErrorKind::Tag),
+      2   => Ok(ErrorKind::MapRes),
+      3   => Ok(ErrorKind::MapOpt),
+      4   => Ok(ErrorKind::Alt),
+      5   => Ok(ErrorKind::IsNot),
+      6   => Ok(ErrorKind::IsA),
+      7   => Ok(ErrorKind::SeparatedList),
+      8   => Ok(ErrorKind::SeparatedNonEmptyList),
+      9   => Ok(ErrorKind::Many1),
+      10  => Ok(ErrorKind::Count),
+      12  => Ok(ErrorKind::TakeUntil),
+      15  => Ok(ErrorKind::LengthValue),
+      16  => Ok(ErrorKind::TagClosure),
+      17  => Ok(ErrorKind::Alpha),
+      18  => Ok(ErrorKind::Digit),
+      19  => Ok(ErrorKind::AlphaNumeric),
+      20  => Ok(ErrorKind::Space),
+      21  => Ok(ErrorKind::MultiSpace),
+      22  => Ok(ErrorKind::LengthValueFn),
+      23  => Ok(ErrorKind::Eof),
+      27  => Ok(ErrorKind::Switch),
+      28  => Ok(ErrorKind::TagBits),
+      29  => Ok(ErrorKind::OneOf),
+      30  => Ok(ErrorKind::NoneOf),
+      40  => Ok(ErrorKind::Char),
+      41  => Ok(ErrorKind::CrLf),
+      42  => Ok(ErrorKind::RegexpMatch),
+      43  => Ok(ErrorKind::RegexpMatches),
+      44  => Ok(ErrorKind::RegexpFind),
+      45  => Ok(ErrorKind::RegexpCapture),
+      46  => Ok(ErrorKind::RegexpCaptures),
+      47  => Ok(ErrorKind::TakeWhile1),
+      48  => Ok(ErrorKind::Complete),
+      49  => Ok(ErrorKind::Fix),
+      50  => Ok(ErrorKind::Escaped),
+      51  => Ok(ErrorKind::EscapedTransform),
+      56  => Ok(ErrorKind::NonEmpty),
+      57  => Ok(ErrorKind::ManyMN),
+      59  => Ok(ErrorKind::HexDigit),
+      61  => Ok(ErrorKind::OctDigit),
+      62  => Ok(ErrorKind::Many0),
+      63  => Ok(ErrorKind::Not),
+      64  => Ok(ErrorKind::Permutation),
+      65  => Ok(ErrorKind::ManyTill),
+      66  => Ok(ErrorKind::Verify),
+      67  => Ok(ErrorKind::TakeTill1),
+      69  => Ok(ErrorKind::TakeWhileMN),
+      70  => Ok(ErrorKind::ParseTo),
+      71  => Ok(ErrorKind::TooLarge),
+      72  => Ok(ErrorKind::Many0Count),
+      73  => Ok(ErrorKind::Many1Count),
+      74  => Ok(ErrorKind::Float),
+      75  => Ok(ErrorKind::Satisfy),
+      76  => Ok(ErrorKind::QuotedString),
+      77  => Ok(ErrorKind::PercentEncoding),
+      78  => Ok(ErrorKind::Base64),
+      79  => Ok(ErrorKind::NetAddress),
+      80  => Ok(ErrorKind::Uuid),
+      81  => Ok(ErrorKind::DateTime),
+      82  => Ok(ErrorKind::UnknownRule),
+      83  => Ok(ErrorKind::Cancelled),
+      84  => Ok(ErrorKind::Flags),
+      85  => Ok(ErrorKind::ShellWord),
+      86  => Ok(ErrorKind::Identifier),
+      87  => Ok(ErrorKind::VerifyRecognized),
+      88  => Ok(ErrorKind::TakeTillMN),
+      89  => Ok(ErrorKind::DigitCount),
+      90  => Ok(ErrorKind::CharReference),
+      91  => Ok(ErrorKind::Duration),
+      92  => Ok(ErrorKind::SemVer),
+      93  => Ok(ErrorKind::Email),
+      94  => Ok(ErrorKind::Uri),
+      95  => Ok(ErrorKind::Unclosed),
+      96  => Ok(ErrorKind::Write),
+      97  => Ok(ErrorKind::LeftRecursion),
+      code => Err(code),
     }
   }
 }
@@ -674,6 +1580,160 @@ mod tests {
 
     let _result: IResult<_, _, VerboseError<&str>> = char('x')(input);
   }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn render_error_default() {
+    let input = "ab\ncd";
+    let e = Error::new(&input[4..], ErrorKind::Digit);
+    let mut message = crate::lib::std::string::String::new();
+    render_error(&input, &e, &mut DefaultRenderer, &mut message).unwrap();
+    assert_eq!(message, "0: at line 2, in Digit:\ncd\n ^\n\n");
+
+    let e = VerboseError::from_char(&input[4..], 'x');
+    assert_eq!(
+      convert_error(input, e),
+      "0: at line 2:\ncd\n ^\nexpected 'x', found d\n\n"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn error_frames() {
+    use crate::multi::count;
+    use crate::sequence::preceded;
+
+    fn pairs(i: &str) -> IResult<&str, Vec<char>, VerboseError<&str>> {
+      context("pairs", count(preceded(char('-'), char('x')), 2))(i)
+    }
+
+    let input = "-x-y";
+    let e = match pairs(input) {
+      Err(Err::Error(e)) => e,
+      res => panic!("unexpected result: {:?}", res),
+    };
+    let frames: Vec<_> = e
+      .frames()
+      .map(|f| (f.offset(&input), f.kind, f.context, f.expected, f.element))
+      .collect();
+    assert_eq!(
+      frames,
+      vec![
+        (3, Some(ErrorKind::Char), None, Some('x'), None),
+        (0, Some(ErrorKind::Count), None, None, Some(1)),
+        (0, None, Some("pairs"), None, None),
+      ]
+    );
+
+    let e: Error<&str> = Error::new(&input[1..], ErrorKind::Tag);
+    assert_eq!(e.frames().count(), 1);
+    assert_eq!(e.deepest().map(|f| f.offset(&input)), Some(1));
+    assert!(ErrorFrames::<&str>::deepest(&()).is_none());
+  }
+
+  #[test]
+  #[cfg(all(feature = "std", backtrace))]
+  fn backtrace_error() {
+    use crate::bytes::complete::tag;
+    use crate::character::complete::digit1;
+    use crate::sequence::preceded;
+
+    fn parser(i: &str) -> IResult<&str, &str, BacktraceError<VerboseError<&str>>> {
+      context("number", preceded(tag("#"), digit1))(i)
+    }
+
+    let e = match parser("#x") {
+      Err(Err::Error(e)) => e,
+      _ => unreachable!(),
+    };
+    assert_eq!(
+      e.inner.errors,
+      vec![
+        ("x", VerboseErrorKind::Nom(ErrorKind::Digit)),
+        ("#x", VerboseErrorKind::Context("number")),
+      ]
+    );
+    assert_eq!(e.deepest().map(|f| f.kind), Some(Some(ErrorKind::Digit)));
+
+    capture_backtraces(true);
+    let e = parser("#x").unwrap_err();
+    capture_backtraces(false);
+    match e {
+      Err::Error(e) => assert!(e.backtrace().is_some()),
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn tag_errors() {
+    use crate::bytes::complete::{tag, tag_no_case};
+    use crate::lib::std::string::ToString;
+
+    let input = "Content-Type: text";
+    let e = match tag::<_, _, VerboseError<&str>>("CONTENT-LENGTH")(input) {
+      Err(Err::Error(e)) => e,
+      res => panic!("unexpected result: {:?}", res),
+    };
+    assert_eq!(
+      e.errors,
+      vec![(input, VerboseErrorKind::Tag("CONTENT-LENGTH".to_string()))]
+    );
+    assert_eq!(
+      e.deepest().and_then(|f| f.expected_tag),
+      Some("CONTENT-LENGTH")
+    );
+    assert!(convert_error(input, e).contains("expected `CONTENT-LENGTH`"));
+
+    let res = tag_no_case::<_, _, VerboseError<&[u8]>>(&b"\xffA"[..])(&b"a"[..]);
+    assert_eq!(
+      res,
+      Err(Err::Error(VerboseError {
+        errors: vec![(&b"a"[..], VerboseErrorKind::Tag("\\xffA".to_string()))]
+      }))
+    );
+
+    let res = tag::<_, _, Error<&str>>('x')(input);
+    assert_eq!(res, Err(Err::Error(Error::new(input, ErrorKind::Tag))));
+
+    let partial = crate::partial::Partial::new("ab");
+    let res = crate::bytes::tag::<_, _, VerboseError<_>>("ac")(partial);
+    assert_eq!(
+      res,
+      Err(Err::Error(VerboseError {
+        errors: vec![(partial, VerboseErrorKind::Tag("ac".to_string()))]
+      }))
+    );
+  }
+
+  #[test]
+  fn error_code_round_trip() {
+    use crate::lib::std::convert::TryFrom;
+
+    for code in 0..100 {
+      if let Ok(kind) = ErrorKind::try_from(code) {
+        assert_eq!(error_to_u32(&kind), code);
+      }
+    }
+    assert_eq!(ErrorKind::try_from(0), Err(0));
+    assert_eq!(ErrorKind::try_from(81), Ok(ErrorKind::DateTime));
+  }
+
+  #[cfg(all(feature = "serde", feature = "alloc"))]
+  #[test]
+  fn deserialize_error() {
+    use crate::de::Value;
+    use serde::Deserialize;
+
+    let value = Value::Map(vec![
+      (Value::from("input"), Value::from("abc")),
+      (Value::from("code"), Value::from("Digit")),
+    ]);
+    assert_eq!(
+      Error::<&str>::deserialize(value),
+      Ok(Error::new("abc", ErrorKind::Digit))
+    );
+  }
 }
 
 /*
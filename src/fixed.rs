@@ -0,0 +1,181 @@
+//! Parsers for fixed-width fields
+//!
+//! Mainframe exports, financial messages or bank statements often use records
+//! made of fields of a fixed width, padded with spaces or zeros. [fixed_str]
+//! and [fixed_int] take exactly the width of such a field, then remove the
+//! padding and convert the content.
+//!
+//! Those parsers work on complete input: a field shorter than its width is
+//! an error.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::ops::{Range, RangeFrom, RangeTo};
+use crate::traits::{AsChar, InputIter, ParseTo, Slice};
+
+/// Returns the start and end positions of the field content, without padding
+fn trim<I>(field: &I, pad: char) -> (usize, usize)
+where
+  I: InputIter,
+  <I as InputIter>::Item: AsChar + Copy,
+{
+  let mut start = None;
+  let mut end = 0;
+  for (index, c) in field.iter_indices() {
+    if c.as_char() != pad {
+      start = start.or(Some(index));
+      end = index + c.len();
+    }
+  }
+
+  (start.unwrap_or(0), end)
+}
+
+/// Splits the input after `width` elements
+fn split<I, E>(input: &I, width: usize) -> Result<usize, Err<E>>
+where
+  I: Clone + InputIter,
+  E: ParseError<I>,
+{
+  input
+    .slice_index(width)
+    .map_err(|_| Err::Error(E::from_error_kind(input.clone(), ErrorKind::Eof)))
+}
+
+/// Recognizes a field of `width` characters and returns it without the `pad`
+/// characters at its start and end.
+///
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::fixed::fixed_str;
+///
+/// fn name(i: &str) -> IResult<&str, &str> {
+///   fixed_str(8, ' ')(i)
+/// }
+///
+/// assert_eq!(name("JOHN    DOE"), Ok(("DOE", "JOHN")));
+/// assert_eq!(name("        DOE"), Ok(("DOE", "")));
+/// assert_eq!(name("JOHN"), Err(Err::Error(Error::new("JOHN", ErrorKind::Eof))));
+/// ```
+pub fn fixed_str<I, E>(width: usize, pad: char) -> impl Fn(I) -> IResult<I, I, E>
+where
+  I: Clone + InputIter + Slice<Range<usize>> + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar + Copy,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let len = split(&input, width)?;
+    let field = input.slice(..len);
+    let (start, end) = trim(&field, pad);
+    Ok((input.slice(len..), field.slice(start..end)))
+  }
+}
+
+/// Recognizes a field of `width` characters containing an integer, padded
+/// with spaces or leading zeros.
+///
+/// The integer can start with a `+` or `-` sign. The errors point to the exact
+/// position of the problem:
+/// * a field shorter than `width` is an error with `ErrorKind::Eof`
+/// * a character that is not a digit, or an empty field, is an error with
+///   `ErrorKind::Digit`
+/// * a value that does not fit in the output type, like a negative value for
+///   an unsigned type, is an error with `ErrorKind::TooLarge` on the field
+///
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::fixed::fixed_int;
+///
+/// fn amount(i: &str) -> IResult<&str, i32> {
+///   fixed_int(6)(i)
+/// }
+///
+/// fn count(i: &str) -> IResult<&str, u8> {
+///   fixed_int(4)(i)
+/// }
+///
+/// assert_eq!(amount("000042EUR"), Ok(("EUR", 42)));
+/// assert_eq!(amount("   -17EUR"), Ok(("EUR", -17)));
+/// assert_eq!(amount("0042EUR"), Err(Err::Error(Error::new("EUR", ErrorKind::Digit))));
+/// assert_eq!(count("0300"), Err(Err::Error(Error::new("0300", ErrorKind::TooLarge))));
+/// ```
+pub fn fixed_int<I, O, E>(width: usize) -> impl Fn(I) -> IResult<I, O, E>
+where
+  I: Clone + InputIter + Slice<Range<usize>> + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  I: ParseTo<O>,
+  <I as InputIter>::Item: AsChar + Copy,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let len = split(&input, width)?;
+    let field = input.slice(..len);
+    let (start, end) = trim(&field, ' ');
+
+    let mut digits = false;
+    for (index, c) in field.slice(start..end).iter_indices() {
+      match c.as_char() {
+        '0'..='9' => digits = true,
+        '+' | '-' if index == 0 => {}
+        _ => {
+          return Err(Err::Error(E::from_error_kind(
+            input.slice(start + index..),
+            ErrorKind::Digit,
+          )))
+        }
+      }
+    }
+
+    if !digits {
+      return Err(Err::Error(E::from_error_kind(
+        input.slice(start..),
+        ErrorKind::Digit,
+      )));
+    }
+
+    match field.slice(start..end).parse_to() {
+      Some(value) => Ok((input.slice(len..), value)),
+      None => Err(Err::Error(E::from_error_kind(input, ErrorKind::TooLarge))),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  #[test]
+  fn fixed_str_padding() {
+    let p = fixed_str::<_, Error<&[u8]>>(5, '0');
+    assert_eq!(p(&b"00120;"[..]), Ok((&b";"[..], &b"12"[..])));
+    assert_eq!(p(&b"00000"[..]), Ok((&b""[..], &b""[..])));
+    assert_eq!(fixed_str::<_, Error<&str>>(3, ' ')("é a"), Ok(("", "é a")));
+  }
+
+  #[test]
+  fn fixed_int_errors() {
+    let p = fixed_int::<_, i16, Error<&str>>(5);
+    assert_eq!(p("+0012"), Ok(("", 12)));
+    assert_eq!(
+      p(" 1 2 "),
+      Err(Err::Error(Error::new(" 2 ", ErrorKind::Digit)))
+    );
+    assert_eq!(
+      p("  -  "),
+      Err(Err::Error(Error::new("-  ", ErrorKind::Digit)))
+    );
+    assert_eq!(
+      p("     "),
+      Err(Err::Error(Error::new("     ", ErrorKind::Digit)))
+    );
+    assert_eq!(p("1-2"), Err(Err::Error(Error::new("1-2", ErrorKind::Eof))));
+    assert_eq!(
+      p("99999"),
+      Err(Err::Error(Error::new("99999", ErrorKind::TooLarge)))
+    );
+  }
+}
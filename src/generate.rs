@@ -0,0 +1,378 @@
+//! Generation of valid inputs from a grammar description
+//!
+//! The generators of this module mirror nom's core parsers and combinators:
+//! where a parser recognizes a tag, a set of characters, an alternative or a
+//! repetition, the corresponding generator writes one. Describing a grammar
+//! with both produces random valid inputs, for fuzzing or property tests.
+//!
+//! The randomness comes from an [arbitrary::Unstructured], so generators
+//! integrate with `cargo fuzz` and `arbitrary` based property testing tools.
+//!
+//! ```rust
+//! use arbitrary::Unstructured;
+//! use nom::{IResult, sequence::tuple};
+//! use nom::bytes::complete::tag;
+//! use nom::character::complete::{alpha1, digit1};
+//! use nom::combinator::all_consuming;
+//! use nom::generate::{self as g, Generator};
+//!
+//! // key=value
+//! fn entry(i: &str) -> IResult<&str, (&str, &str, &str)> {
+//!   all_consuming(tuple((alpha1, tag("="), digit1)))(i)
+//! }
+//!
+//! let entry_gen = (g::alpha1(), g::tag("="), g::digit1());
+//!
+//! let mut u = Unstructured::new(&[3, 14, 15, 92, 65, 35, 89, 79, 32, 38, 46]);
+//! let input = entry_gen.generate_string(&mut u).unwrap();
+//! assert!(entry(&input).is_ok());
+//! ```
+
+use crate::lib::std::ops::RangeInclusive;
+use crate::lib::std::string::String;
+use crate::lib::std::vec::Vec;
+use arbitrary::{Result, Unstructured};
+
+/// Maximum number of repetitions for the generators without an upper bound
+pub const MAX_REPEAT: usize = 8;
+
+/// Writes inputs that a grammar accepts
+pub trait Generator {
+  /// Appends a valid input to `out`, using `u` as source of randomness
+  fn generate(&self, u: &mut Unstructured, out: &mut Vec<u8>) -> Result<()>;
+
+  /// Generates a valid input as a byte vector
+  fn generate_bytes(&self, u: &mut Unstructured) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    self.generate(u, &mut out)?;
+    Ok(out)
+  }
+
+  /// Generates a valid input as a string
+  ///
+  /// Returns `arbitrary::Error::IncorrectFormat` if the generator wrote invalid UTF-8
+  fn generate_string(&self, u: &mut Unstructured) -> Result<String> {
+    let out = self.generate_bytes(u)?;
+    String::from_utf8(out).map_err(|_| arbitrary::Error::IncorrectFormat)
+  }
+}
+
+impl<F> Generator for F
+where
+  F: Fn(&mut Unstructured, &mut Vec<u8>) -> Result<()>,
+{
+  fn generate(&self, u: &mut Unstructured, out: &mut Vec<u8>) -> Result<()> {
+    self(u, out)
+  }
+}
+
+/// Generator returned by [tag]
+#[derive(Debug, Clone)]
+pub struct Tag<T>(T);
+
+/// Writes a fixed pattern, counterpart of the `tag` parsers
+pub fn tag<T: AsRef<[u8]>>(t: T) -> Tag<T> {
+  Tag(t)
+}
+
+impl<T: AsRef<[u8]>> Generator for Tag<T> {
+  fn generate(&self, _u: &mut Unstructured, out: &mut Vec<u8>) -> Result<()> {
+    out.extend_from_slice(self.0.as_ref());
+    Ok(())
+  }
+}
+
+/// Generator returned by [one_of] and [char_range]
+#[derive(Debug, Clone)]
+pub struct Chars {
+  ranges: Vec<RangeInclusive<char>>,
+}
+
+/// Writes one of the characters of the list, counterpart of the `one_of`
+/// and `char` parsers
+pub fn one_of(list: &str) -> Chars {
+  Chars {
+    ranges: list.chars().map(|c| c..=c).collect(),
+  }
+}
+
+/// Writes a character of the range
+pub fn char_range(range: RangeInclusive<char>) -> Chars {
+  Chars {
+    ranges: vec![range],
+  }
+}
+
+impl Chars {
+  /// Adds the characters of another range
+  pub fn or(mut self, range: RangeInclusive<char>) -> Self {
+    self.ranges.push(range);
+    self
+  }
+}
+
+impl Generator for Chars {
+  fn generate(&self, u: &mut Unstructured, out: &mut Vec<u8>) -> Result<()> {
+    let range = u.choose(&self.ranges)?;
+    let c = u.int_in_range(*range.start() as u32..=*range.end() as u32)?;
+    // the surrogates are not characters, pick the start of the range instead
+    let c = core::char::from_u32(c).unwrap_or(*range.start());
+    let mut buf = [0; 4];
+    out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    Ok(())
+  }
+}
+
+/// Generator returned by [many_m_n] and the character class generators
+#[derive(Debug, Clone)]
+pub struct Repeat<G> {
+  min: usize,
+  max: usize,
+  generator: G,
+}
+
+/// Writes between `m` and `n` (inclusive) outputs of the generator,
+/// counterpart of the `many_m_n` and `take_while_m_n` parsers
+pub fn many_m_n<G: Generator>(m: usize, n: usize, generator: G) -> Repeat<G> {
+  Repeat {
+    min: m,
+    max: n,
+    generator,
+  }
+}
+
+/// Writes up to [MAX_REPEAT] outputs of the generator, counterpart of `many0`
+pub fn many0<G: Generator>(generator: G) -> Repeat<G> {
+  many_m_n(0, MAX_REPEAT, generator)
+}
+
+/// Writes between one and [MAX_REPEAT] outputs of the generator, counterpart
+/// of `many1`
+pub fn many1<G: Generator>(generator: G) -> Repeat<G> {
+  many_m_n(1, MAX_REPEAT, generator)
+}
+
+impl<G: Generator> Generator for Repeat<G> {
+  fn generate(&self, u: &mut Unstructured, out: &mut Vec<u8>) -> Result<()> {
+    let count = u.int_in_range(self.min..=self.max)?;
+    for _ in 0..count {
+      self.generator.generate(u, out)?;
+    }
+    Ok(())
+  }
+}
+
+/// Generator returned by [separated_list_m_n]
+#[derive(Debug, Clone)]
+pub struct SeparatedList<S, G> {
+  min: usize,
+  max: usize,
+  separator: S,
+  generator: G,
+}
+
+/// Writes between `m` and `n` (inclusive) outputs of the generator,
+/// separated by outputs of `separator`, counterpart of the `separated_list`
+/// parsers
+pub fn separated_list_m_n<S, G>(
+  m: usize,
+  n: usize,
+  separator: S,
+  generator: G,
+) -> SeparatedList<S, G>
+where
+  S: Generator,
+  G: Generator,
+{
+  SeparatedList {
+    min: m,
+    max: n,
+    separator,
+    generator,
+  }
+}
+
+impl<S: Generator, G: Generator> Generator for SeparatedList<S, G> {
+  fn generate(&self, u: &mut Unstructured, out: &mut Vec<u8>) -> Result<()> {
+    let count = u.int_in_range(self.min..=self.max)?;
+    for i in 0..count {
+      if i > 0 {
+        self.separator.generate(u, out)?;
+      }
+      self.generator.generate(u, out)?;
+    }
+    Ok(())
+  }
+}
+
+/// Generator returned by [opt]
+#[derive(Debug, Clone)]
+pub struct Opt<G>(G);
+
+/// Writes the output of the generator or nothing, counterpart of `opt`
+pub fn opt<G: Generator>(generator: G) -> Opt<G> {
+  Opt(generator)
+}
+
+impl<G: Generator> Generator for Opt<G> {
+  fn generate(&self, u: &mut Unstructured, out: &mut Vec<u8>) -> Result<()> {
+    if u.arbitrary()? {
+      self.0.generate(u, out)?;
+    }
+    Ok(())
+  }
+}
+
+/// Generator returned by [alt]
+#[derive(Debug, Clone)]
+pub struct Alt<T>(T);
+
+/// Writes the output of one of the generators of the tuple, counterpart of `alt`
+pub fn alt<T>(generators: T) -> Alt<T> {
+  Alt(generators)
+}
+
+macro_rules! tuple_generator(
+  ($($name:ident $index:tt),+) => (
+    impl<$($name: Generator),+> Generator for ($($name,)+) {
+      fn generate(&self, u: &mut Unstructured, out: &mut Vec<u8>) -> Result<()> {
+        $(self.$index.generate(u, out)?;)+
+        Ok(())
+      }
+    }
+
+    impl<$($name: Generator),+> Generator for Alt<($($name,)+)> {
+      fn generate(&self, u: &mut Unstructured, out: &mut Vec<u8>) -> Result<()> {
+        let choice = u.choose_index([$($index),+].len())?;
+        $(
+          if choice == $index {
+            return (self.0).$index.generate(u, out);
+          }
+        )+
+        unreachable!()
+      }
+    }
+  );
+);
+
+tuple_generator!(A 0);
+tuple_generator!(A 0, B 1);
+tuple_generator!(A 0, B 1, C 2);
+tuple_generator!(A 0, B 1, C 2, D 3);
+tuple_generator!(A 0, B 1, C 2, D 3, E 4);
+tuple_generator!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_generator!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_generator!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+tuple_generator!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+tuple_generator!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+tuple_generator!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+tuple_generator!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+/// Writes one to [MAX_REPEAT] ASCII digits, counterpart of `digit1`
+pub fn digit1() -> Repeat<Chars> {
+  many1(char_range('0'..='9'))
+}
+
+/// Writes one to [MAX_REPEAT] hexadecimal digits, counterpart of `hex_digit1`
+pub fn hex_digit1() -> Repeat<Chars> {
+  many1(char_range('0'..='9').or('a'..='f').or('A'..='F'))
+}
+
+/// Writes one to [MAX_REPEAT] ASCII letters, counterpart of `alpha1`
+pub fn alpha1() -> Repeat<Chars> {
+  many1(char_range('a'..='z').or('A'..='Z'))
+}
+
+/// Writes one to [MAX_REPEAT] ASCII letters or digits, counterpart of
+/// `alphanumeric1`
+pub fn alphanumeric1() -> Repeat<Chars> {
+  many1(char_range('a'..='z').or('A'..='Z').or('0'..='9'))
+}
+
+/// Writes up to [MAX_REPEAT] spaces and tabs, counterpart of `space0`
+pub fn space0() -> Repeat<Chars> {
+  many0(one_of(" \t"))
+}
+
+/// Writes up to [MAX_REPEAT] spaces, tabs, carriage returns and line feeds,
+/// counterpart of `multispace0`
+pub fn multispace0() -> Repeat<Chars> {
+  many0(one_of(" \t\r\n"))
+}
+
+/// Writes an end of line, counterpart of `line_ending`
+pub fn line_ending() -> Alt<(Tag<&'static str>, Tag<&'static str>)> {
+  alt((tag("\n"), tag("\r\n")))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::branch::alt as palt;
+  use crate::bytes::complete::tag as ptag;
+  use crate::character::complete::{
+    char, digit1 as pdigit1, hex_digit1 as phex, multispace0 as pms,
+  };
+  use crate::combinator::{all_consuming, opt as popt, recognize};
+  use crate::multi::separated_list1;
+  use crate::sequence::{delimited, pair, tuple};
+  use crate::IResult;
+
+  // a list of optionally negative numbers or hexadecimal literals
+  fn list(i: &str) -> IResult<&str, Vec<&str>> {
+    all_consuming(delimited(
+      char('['),
+      separated_list1(
+        tuple((pms, char(','), pms)),
+        palt((
+          recognize(pair(ptag("0x"), phex)),
+          recognize(pair(popt(char('-')), pdigit1)),
+        )),
+      ),
+      char(']'),
+    ))(i)
+  }
+
+  #[test]
+  fn round_trip() {
+    let generator = (
+      tag("["),
+      separated_list_m_n(
+        1,
+        5,
+        (multispace0(), tag(","), multispace0()),
+        alt(((tag("0x"), hex_digit1()), (opt(tag("-")), digit1()))),
+      ),
+      tag("]"),
+    );
+
+    let data: Vec<u8> = (0..4096u32)
+      .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+      .collect();
+    let mut u = Unstructured::new(&data);
+    for _ in 0..32 {
+      let input = generator.generate_string(&mut u).unwrap();
+      assert!(list(&input).is_ok(), "generated input: {:?}", input);
+    }
+  }
+
+  #[test]
+  fn chars_in_range() {
+    let data = [0xff; 64];
+    let mut u = Unstructured::new(&data);
+    let s = many_m_n(4, 4, char_range('α'..='ω'))
+      .generate_string(&mut u)
+      .unwrap();
+    assert_eq!(s.chars().count(), 4);
+    assert!(s.chars().all(|c| ('α'..='ω').contains(&c)));
+  }
+
+  #[test]
+  fn invalid_utf8() {
+    let mut u = Unstructured::new(&[]);
+    assert_eq!(
+      tag(&b"\xff"[..]).generate_string(&mut u),
+      Err(arbitrary::Error::IncorrectFormat)
+    );
+  }
+}
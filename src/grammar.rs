@@ -0,0 +1,679 @@
+//! Grammars built and interpreted at runtime
+//!
+//! A [Grammar] is a set of named [Rule]s, built with the constructors of this
+//! module, that mirror nom's combinators. Rules refer to each other by name
+//! with [rule()], which allows recursion, and each named rule that matches
+//! produces a [Node] of the syntax tree returned by [Grammar::parse].
+//!
+//! Rules are applied with the *complete* versions of the parsers. Left
+//! recursive rules are not supported: [Grammar::check] reports them, and
+//! [Grammar::parse] fails with [ErrorKind::LeftRecursion] when a rule is
+//! applied again at the same position.
+//!
+//! ```rust
+//! use nom::error::Error;
+//! use nom::grammar::{alt, digit1, many0, opt, rule, seq, tag, Grammar};
+//!
+//! let grammar = Grammar::new()
+//!   .define("list", seq(vec![tag("["), opt(rule("items")), tag("]")]))
+//!   .define("items", seq(vec![rule("item"), many0(seq(vec![tag(","), rule("item")]))]))
+//!   .define("item", alt(vec![rule("number"), rule("list")]))
+//!   .define("number", digit1());
+//! assert_eq!(grammar.check(), Ok(()));
+//!
+//! let (rest, list) = grammar.parse::<_, Error<_>>("list", "[1,[2]];").unwrap();
+//! assert_eq!(rest, ";");
+//! assert_eq!(list.span, "[1,[2]]");
+//!
+//! let items = &list.children[0];
+//! assert_eq!(items.rule, "items");
+//! assert_eq!(items.children[1].span, "[2]");
+//! assert_eq!(items.children[1].children[0].rule, "list");
+//! ```
+
+use crate::bytes::complete as bytes;
+use crate::character::complete as character;
+use crate::combinator::eof as eof_parser;
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::boxed::Box;
+use crate::lib::std::collections::BTreeMap;
+use crate::lib::std::ops::{RangeFrom, RangeTo};
+use crate::lib::std::string::{String, ToString};
+use crate::lib::std::vec::Vec;
+use crate::traits::{AsChar, Compare, FindToken, InputIter, InputLength, InputTake};
+use crate::traits::{InputTakeAtPosition, Offset, Slice};
+
+/// Element of a [Grammar]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rule {
+  /// Recognizes a literal
+  Tag(String),
+  /// Recognizes a literal, ignoring case
+  TagNoCase(String),
+  /// Recognizes one of the characters
+  OneOf(String),
+  /// Recognizes a character that is not in the list
+  NoneOf(String),
+  /// Recognizes one or more characters of the class
+  Class(CharClass),
+  /// Recognizes any character
+  AnyChar,
+  /// Succeeds at the end of input
+  Eof,
+  /// Applies the rules one after the other
+  Seq(Vec<Rule>),
+  /// Applies the first rule that succeeds
+  Alt(Vec<Rule>),
+  /// Applies a rule between `min` and `max` times
+  Repeat {
+    /// Repeated rule
+    rule: Box<Rule>,
+    /// Minimum number of repetitions
+    min: usize,
+    /// Maximum number of repetitions, unbounded if `None`
+    max: Option<usize>,
+  },
+  /// Succeeds without consuming input if the rule fails
+  Not(Box<Rule>),
+  /// Applies a rule without consuming input
+  Peek(Box<Rule>),
+  /// Applies the rule defined with that name in the grammar
+  Ref(String),
+}
+
+/// Character classes recognized by [Rule::Class]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharClass {
+  /// ASCII digits, as [digit1](crate::character::complete::digit1)
+  Digit,
+  /// ASCII hexadecimal digits, as [hex_digit1](crate::character::complete::hex_digit1)
+  HexDigit,
+  /// ASCII letters, as [alpha1](crate::character::complete::alpha1)
+  Alpha,
+  /// ASCII letters and digits, as [alphanumeric1](crate::character::complete::alphanumeric1)
+  Alphanumeric,
+  /// Spaces and tabs, as [space1](crate::character::complete::space1)
+  Space,
+  /// Spaces, tabs and line endings, as [multispace1](crate::character::complete::multispace1)
+  MultiSpace,
+}
+
+/// Recognizes a literal
+pub fn tag(t: &str) -> Rule {
+  Rule::Tag(t.to_string())
+}
+
+/// Recognizes a literal, ignoring case
+pub fn tag_no_case(t: &str) -> Rule {
+  Rule::TagNoCase(t.to_string())
+}
+
+/// Recognizes one of the characters
+pub fn one_of(list: &str) -> Rule {
+  Rule::OneOf(list.to_string())
+}
+
+/// Recognizes a character that is not in the list
+pub fn none_of(list: &str) -> Rule {
+  Rule::NoneOf(list.to_string())
+}
+
+/// Recognizes one or more ASCII digits
+pub fn digit1() -> Rule {
+  Rule::Class(CharClass::Digit)
+}
+
+/// Recognizes one or more ASCII hexadecimal digits
+pub fn hex_digit1() -> Rule {
+  Rule::Class(CharClass::HexDigit)
+}
+
+/// Recognizes one or more ASCII letters
+pub fn alpha1() -> Rule {
+  Rule::Class(CharClass::Alpha)
+}
+
+/// Recognizes one or more ASCII letters or digits
+pub fn alphanumeric1() -> Rule {
+  Rule::Class(CharClass::Alphanumeric)
+}
+
+/// Recognizes one or more spaces and tabs
+pub fn space1() -> Rule {
+  Rule::Class(CharClass::Space)
+}
+
+/// Recognizes one or more spaces, tabs and line endings
+pub fn multispace1() -> Rule {
+  Rule::Class(CharClass::MultiSpace)
+}
+
+/// Recognizes any character
+pub fn anychar() -> Rule {
+  Rule::AnyChar
+}
+
+/// Succeeds at the end of input
+pub fn eof() -> Rule {
+  Rule::Eof
+}
+
+/// Applies the rules one after the other
+pub fn seq(rules: Vec<Rule>) -> Rule {
+  Rule::Seq(rules)
+}
+
+/// Applies the first rule that succeeds
+pub fn alt(rules: Vec<Rule>) -> Rule {
+  Rule::Alt(rules)
+}
+
+/// Applies a rule zero or more times
+pub fn many0(rule: Rule) -> Rule {
+  many_m_n(0, None, rule)
+}
+
+/// Applies a rule one or more times
+pub fn many1(rule: Rule) -> Rule {
+  many_m_n(1, None, rule)
+}
+
+/// Applies a rule between `min` and `max` times, `max` being unbounded if `None`
+pub fn many_m_n(min: usize, max: Option<usize>, rule: Rule) -> Rule {
+  Rule::Repeat {
+    rule: Box::new(rule),
+    min,
+    max,
+  }
+}
+
+/// Applies a rule if it succeeds
+pub fn opt(rule: Rule) -> Rule {
+  many_m_n(0, Some(1), rule)
+}
+
+/// Succeeds without consuming input if the rule fails
+pub fn not(rule: Rule) -> Rule {
+  Rule::Not(Box::new(rule))
+}
+
+/// Applies a rule without consuming input
+pub fn peek(rule: Rule) -> Rule {
+  Rule::Peek(Box::new(rule))
+}
+
+/// Applies the rule defined with that name in the grammar
+pub fn rule(name: &str) -> Rule {
+  Rule::Ref(name.to_string())
+}
+
+/// Match of a named rule
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node<I> {
+  /// Name of the rule
+  pub rule: String,
+  /// Part of the input recognized by the rule
+  pub span: I,
+  /// Matches of the named rules referenced by this rule
+  pub children: Vec<Node<I>>,
+}
+
+/// Problem found by [Grammar::check]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GrammarError<'a> {
+  /// A rule refers to this name, which is not defined
+  Undefined(&'a str),
+  /// This rule can apply itself again without consuming input
+  LeftRecursive(&'a str),
+}
+
+/// Set of named rules
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Grammar {
+  rules: BTreeMap<String, Rule>,
+}
+
+impl Grammar {
+  /// Creates an empty grammar
+  pub fn new() -> Self {
+    Grammar::default()
+  }
+
+  /// Defines a rule, replacing the previous one with the same name
+  pub fn define(mut self, name: &str, rule: Rule) -> Self {
+    self.rules.insert(name.to_string(), rule);
+    self
+  }
+
+  /// Returns the rule defined with that name
+  pub fn get(&self, name: &str) -> Option<&Rule> {
+    self.rules.get(name)
+  }
+
+  /// Verifies that every rule referenced by name is defined, and that no
+  /// rule is left recursive
+  ///
+  /// Returns the first undefined name, or the first left recursive rule
+  /// otherwise.
+  pub fn check(&self) -> Result<(), GrammarError<'_>> {
+    fn undefined<'a>(grammar: &Grammar, rule: &'a Rule) -> Option<&'a str> {
+      match rule {
+        Rule::Ref(name) if !grammar.rules.contains_key(name) => Some(name),
+        Rule::Seq(rules) | Rule::Alt(rules) => rules.iter().find_map(|r| undefined(grammar, r)),
+        Rule::Repeat { rule, .. } | Rule::Not(rule) | Rule::Peek(rule) => undefined(grammar, rule),
+        _ => None,
+      }
+    }
+
+    if let Some(name) = self.rules.values().find_map(|r| undefined(self, r)) {
+      return Err(GrammarError::Undefined(name));
+    }
+
+    // rules that can match without consuming input, computed as a fixed point
+    let mut nullable = BTreeMap::new();
+    loop {
+      let mut changed = false;
+      for (name, rule) in &self.rules {
+        if !nullable.contains_key(name.as_str()) && self.nullable(rule, &nullable) {
+          nullable.insert(name.as_str(), ());
+          changed = true;
+        }
+      }
+      if !changed {
+        break;
+      }
+    }
+
+    // a rule is left recursive if it can be reached from itself through
+    // rules applied at the same position
+    for name in self.rules.keys() {
+      let mut visited = Vec::new();
+      let mut stack = Vec::new();
+      self.left_refs(&self.rules[name], &nullable, &mut stack);
+      while let Some(next) = stack.pop() {
+        if next == name.as_str() {
+          return Err(GrammarError::LeftRecursive(name));
+        }
+        if !visited.contains(&next) {
+          visited.push(next);
+          if let Some(rule) = self.rules.get(next) {
+            self.left_refs(rule, &nullable, &mut stack);
+          }
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Returns true if the rule can succeed without consuming input
+  fn nullable(&self, rule: &Rule, nullable: &BTreeMap<&str, ()>) -> bool {
+    match rule {
+      Rule::Tag(t) | Rule::TagNoCase(t) => t.is_empty(),
+      Rule::OneOf(_) | Rule::NoneOf(_) | Rule::Class(_) | Rule::AnyChar => false,
+      Rule::Eof | Rule::Not(_) | Rule::Peek(_) => true,
+      Rule::Seq(rules) => rules.iter().all(|r| self.nullable(r, nullable)),
+      Rule::Alt(rules) => rules.iter().any(|r| self.nullable(r, nullable)),
+      Rule::Repeat { rule, min, .. } => *min == 0 || self.nullable(rule, nullable),
+      Rule::Ref(name) => nullable.contains_key(name.as_str()),
+    }
+  }
+
+  /// Pushes the names of the rules that can be applied at the start of `rule`
+  fn left_refs<'a>(&self, rule: &'a Rule, nullable: &BTreeMap<&str, ()>, refs: &mut Vec<&'a str>) {
+    match rule {
+      Rule::Ref(name) => refs.push(name),
+      Rule::Seq(rules) => {
+        for r in rules {
+          self.left_refs(r, nullable, refs);
+          if !self.nullable(r, nullable) {
+            break;
+          }
+        }
+      }
+      Rule::Alt(rules) => {
+        for r in rules {
+          self.left_refs(r, nullable, refs);
+        }
+      }
+      Rule::Repeat { rule, .. } | Rule::Not(rule) | Rule::Peek(rule) => {
+        self.left_refs(rule, nullable, refs)
+      }
+      _ => {}
+    }
+  }
+
+  /// Applies the rule named `start` and returns its match
+  ///
+  /// If a referenced rule is not defined, this returns `Err::Failure` with
+  /// [ErrorKind::UnknownRule], and if a rule is applied again at the same
+  /// position, which would recurse forever, it returns `Err::Failure` with
+  /// [ErrorKind::LeftRecursion].
+  pub fn parse<I, E>(&self, start: &str, input: I) -> IResult<I, Node<I>, E>
+  where
+    I: Clone + Offset + Slice<RangeTo<usize>> + Slice<RangeFrom<usize>>,
+    I: InputTake + InputLength + InputIter + InputTakeAtPosition,
+    I: for<'a> Compare<&'a str>,
+    <I as InputIter>::Item: AsChar + Copy,
+    <I as InputTakeAtPosition>::Item: AsChar + Clone,
+    for<'a> &'a str: FindToken<<I as InputIter>::Item>,
+    E: ParseError<I>,
+  {
+    let mut nodes = Vec::with_capacity(1);
+    let mut active = Vec::new();
+    let (i, ()) = self.apply_named(start, input, &mut nodes, &mut active)?;
+    match nodes.pop() {
+      Some(node) => Ok((i, node)),
+      None => unreachable!("a named rule pushes its node when it succeeds"),
+    }
+  }
+
+  /// Applies the rule named `name`, keeping in `active` the named rules being
+  /// applied with the length of their input, to detect left recursion
+  fn apply_named<'g, I, E>(
+    &'g self,
+    name: &str,
+    input: I,
+    nodes: &mut Vec<Node<I>>,
+    active: &mut Vec<(&'g str, usize)>,
+  ) -> IResult<I, (), E>
+  where
+    I: Clone + Offset + Slice<RangeTo<usize>> + Slice<RangeFrom<usize>>,
+    I: InputTake + InputLength + InputIter + InputTakeAtPosition,
+    I: for<'a> Compare<&'a str>,
+    <I as InputIter>::Item: AsChar + Copy,
+    <I as InputTakeAtPosition>::Item: AsChar + Clone,
+    for<'a> &'a str: FindToken<<I as InputIter>::Item>,
+    E: ParseError<I>,
+  {
+    let (name, rule) = match self.rules.get_key_value(name) {
+      Some(entry) => entry,
+      None => {
+        return Err(Err::Failure(E::from_error_kind(
+          input,
+          ErrorKind::UnknownRule,
+        )))
+      }
+    };
+
+    let position = (name.as_str(), input.input_len());
+    if active.contains(&position) {
+      return Err(Err::Failure(E::from_error_kind(
+        input,
+        ErrorKind::LeftRecursion,
+      )));
+    }
+
+    active.push(position);
+    let mut children = Vec::new();
+    let res = self.apply(rule, input.clone(), &mut children, active);
+    active.pop();
+    let (i, ()) = res?;
+    nodes.push(Node {
+      rule: name.to_string(),
+      span: input.slice(..input.offset(&i)),
+      children,
+    });
+    Ok((i, ()))
+  }
+
+  /// Applies a rule, pushing the matches of named rules to `nodes`
+  ///
+  /// On error, `nodes` can contain matches from the failed rule, so
+  /// backtracking rules truncate it.
+  fn apply<'g, I, E>(
+    &'g self,
+    rule: &Rule,
+    input: I,
+    nodes: &mut Vec<Node<I>>,
+    active: &mut Vec<(&'g str, usize)>,
+  ) -> IResult<I, (), E>
+  where
+    I: Clone + Offset + Slice<RangeTo<usize>> + Slice<RangeFrom<usize>>,
+    I: InputTake + InputLength + InputIter + InputTakeAtPosition,
+    I: for<'a> Compare<&'a str>,
+    <I as InputIter>::Item: AsChar + Copy,
+    <I as InputTakeAtPosition>::Item: AsChar + Clone,
+    for<'a> &'a str: FindToken<<I as InputIter>::Item>,
+    E: ParseError<I>,
+  {
+    let i = match rule {
+      Rule::Tag(t) => bytes::tag(t.as_str())(input)?.0,
+      Rule::TagNoCase(t) => bytes::tag_no_case(t.as_str())(input)?.0,
+      Rule::OneOf(list) => character::one_of(list.as_str())(input)?.0,
+      Rule::NoneOf(list) => character::none_of(list.as_str())(input)?.0,
+      Rule::Class(class) => match class {
+        CharClass::Digit => character::digit1(input)?.0,
+        CharClass::HexDigit => character::hex_digit1(input)?.0,
+        CharClass::Alpha => character::alpha1(input)?.0,
+        CharClass::Alphanumeric => character::alphanumeric1(input)?.0,
+        CharClass::Space => character::space1(input)?.0,
+        CharClass::MultiSpace => character::multispace1(input)?.0,
+      },
+      Rule::AnyChar => character::anychar(input)?.0,
+      Rule::Eof => eof_parser(input)?.0,
+      Rule::Seq(rules) => {
+        let mut i = input;
+        for rule in rules {
+          i = self.apply(rule, i, nodes, active)?.0;
+        }
+        i
+      }
+      Rule::Alt(rules) => {
+        let len = nodes.len();
+        let mut error: Option<E> = None;
+        for rule in rules {
+          match self.apply(rule, input.clone(), nodes, active) {
+            Err(Err::Error(e)) => {
+              nodes.truncate(len);
+              error = Some(match error {
+                Some(prev) => prev.or(e),
+                None => e,
+              });
+            }
+            res => return res,
+          }
+        }
+        return Err(Err::Error(match error {
+          Some(e) => E::append(input, ErrorKind::Alt, e),
+          None => E::from_error_kind(input, ErrorKind::Alt),
+        }));
+      }
+      Rule::Repeat { rule, min, max } => {
+        let mut i = input;
+        let mut count = 0;
+        while *max != Some(count) {
+          let len = nodes.len();
+          match self.apply(rule, i.clone(), nodes, active) {
+            Ok((i1, ())) => {
+              count += 1;
+              // a rule matching the empty string would repeat forever
+              let progress = i1.input_len() != i.input_len();
+              i = i1;
+              if !progress {
+                break;
+              }
+            }
+            Err(Err::Error(e)) => {
+              nodes.truncate(len);
+              if count < *min {
+                return Err(Err::Error(E::append(i, ErrorKind::ManyMN, e)));
+              }
+              break;
+            }
+            Err(e) => return Err(e),
+          }
+        }
+        if count < *min {
+          return Err(Err::Error(E::from_error_kind(i, ErrorKind::ManyMN)));
+        }
+        i
+      }
+      Rule::Not(rule) => {
+        let len = nodes.len();
+        let res = self.apply(rule, input.clone(), nodes, active);
+        nodes.truncate(len);
+        match res {
+          Ok(_) => return Err(Err::Error(E::from_error_kind(input, ErrorKind::Not))),
+          Err(Err::Error(_)) => input,
+          Err(e) => return Err(e),
+        }
+      }
+      Rule::Peek(rule) => {
+        let len = nodes.len();
+        let res = self.apply(rule, input.clone(), nodes, active);
+        nodes.truncate(len);
+        res?;
+        input
+      }
+      Rule::Ref(name) => self.apply_named(name, input, nodes, active)?.0,
+    };
+    Ok((i, ()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  fn expression() -> Grammar {
+    Grammar::new()
+      .define(
+        "sum",
+        seq(vec![
+          rule("product"),
+          many0(seq(vec![one_of("+-"), rule("product")])),
+        ]),
+      )
+      .define(
+        "product",
+        seq(vec![
+          rule("atom"),
+          many0(seq(vec![one_of("*/"), rule("atom")])),
+        ]),
+      )
+      .define(
+        "atom",
+        alt(vec![
+          rule("number"),
+          seq(vec![tag("("), rule("sum"), tag(")")]),
+        ]),
+      )
+      .define("number", digit1())
+  }
+
+  fn names<I>(node: &Node<I>) -> Vec<&str> {
+    node.children.iter().map(|n| n.rule.as_str()).collect()
+  }
+
+  #[test]
+  fn recursion() {
+    let grammar = expression();
+    let (rest, sum) = grammar.parse::<_, Error<_>>("sum", "1+2*(3-4)").unwrap();
+    assert_eq!(rest, "");
+    assert_eq!(names(&sum), vec!["product", "product"]);
+
+    let product = &sum.children[1];
+    assert_eq!(product.span, "2*(3-4)");
+    assert_eq!(names(product), vec!["atom", "atom"]);
+    assert_eq!(names(&product.children[1]), vec!["sum"]);
+
+    let (rest, _) = grammar.parse::<_, Error<_>>("sum", &b"(1)x"[..]).unwrap();
+    assert_eq!(rest, &b"x"[..]);
+  }
+
+  #[test]
+  fn backtracking_discards_nodes() {
+    let grammar = Grammar::new()
+      .define(
+        "call",
+        alt(vec![seq(vec![rule("name"), tag("()")]), rule("name")]),
+      )
+      .define("name", alpha1());
+    let (rest, call) = grammar.parse::<_, Error<_>>("call", "f(").unwrap();
+    assert_eq!(rest, "(");
+    assert_eq!(names(&call), vec!["name"]);
+  }
+
+  #[test]
+  fn errors() {
+    let grammar = expression();
+    assert_eq!(
+      grammar.parse::<_, Error<_>>("sum", "(1"),
+      Err(Err::Error(Error::new("", ErrorKind::Tag)))
+    );
+
+    let grammar = grammar.define("number", seq(vec![digit1(), rule("exponent")]));
+    assert_eq!(grammar.check(), Err(GrammarError::Undefined("exponent")));
+    assert_eq!(
+      grammar.parse::<_, Error<_>>("sum", "1e2"),
+      Err(Err::Failure(Error::new("e2", ErrorKind::UnknownRule)))
+    );
+  }
+
+  #[test]
+  fn left_recursion() {
+    let grammar = Grammar::new()
+      .define(
+        "sum",
+        alt(vec![seq(vec![rule("sum"), tag("+"), digit1()]), digit1()]),
+      )
+      .define("list", seq(vec![opt(tag(",")), rule("items")]))
+      .define("items", seq(vec![many0(tag(" ")), rule("list")]))
+      .define("nested", seq(vec![tag("("), rule("nested"), tag(")")]));
+    assert_eq!(grammar.check(), Err(GrammarError::LeftRecursive("items")));
+    assert_eq!(
+      grammar.clone().define("items", rule("nested")).check(),
+      Err(GrammarError::LeftRecursive("sum"))
+    );
+    assert_eq!(
+      grammar
+        .clone()
+        .define("items", rule("nested"))
+        .define("sum", digit1())
+        .check(),
+      Ok(())
+    );
+
+    assert_eq!(
+      grammar.parse::<_, Error<_>>("sum", "1+2"),
+      Err(Err::Failure(Error::new("1+2", ErrorKind::LeftRecursion)))
+    );
+    assert_eq!(
+      grammar.parse::<_, Error<_>>("list", ",,"),
+      Err(Err::Failure(Error::new("", ErrorKind::LeftRecursion)))
+    );
+  }
+
+  #[test]
+  fn repetitions() {
+    let grammar = Grammar::new()
+      .define(
+        "word",
+        seq(vec![many_m_n(2, Some(3), one_of("ab")), not(tag("c"))]),
+      )
+      .define("empty", many1(opt(tag("x"))));
+    assert_eq!(
+      grammar
+        .parse::<_, Error<_>>("word", "aba;")
+        .map(|(i, n)| (i, n.span)),
+      Ok((";", "aba"))
+    );
+    assert_eq!(
+      grammar.parse::<_, Error<_>>("word", "abac"),
+      Err(Err::Error(Error::new("c", ErrorKind::Not)))
+    );
+    assert_eq!(
+      grammar.parse::<_, Error<_>>("word", "a;"),
+      Err(Err::Error(Error::new(";", ErrorKind::OneOf)))
+    );
+    assert_eq!(
+      grammar
+        .parse::<_, Error<_>>("empty", "y")
+        .map(|(i, n)| (i, n.span)),
+      Ok(("y", ""))
+    );
+  }
+}
@@ -0,0 +1,349 @@
+//! Parsers decoding hexadecimal strings
+//!
+//! Hash fingerprints, MAC addresses or test vectors are usually written as
+//! a run of hexadecimal digit pairs, sometimes separated by characters like
+//! `:` or spaces. [hex_bytes] and [hex_bytes_n] decode such a run to the
+//! corresponding bytes, while [uuid] recognizes the textual form of UUIDs.
+//!
+//! Those parsers work on complete input.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::ops::RangeFrom;
+use crate::traits::{AsChar, Compare, CompareResult, FindToken, InputIter, Slice};
+
+/// Decodes up to `max` bytes, passing each of them to `push`.
+///
+/// Returns the number of decoded bytes and the position following the last one
+fn decode<I, T, E, F>(
+  input: &I,
+  separators: &T,
+  max: usize,
+  mut push: F,
+) -> Result<(usize, usize), Err<E>>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar + Copy,
+  T: FindToken<char>,
+  E: ParseError<I>,
+  F: FnMut(u8),
+{
+  let mut it = input.iter_indices().peekable();
+  let mut count = 0;
+  let mut end = 0;
+
+  while count < max {
+    // a separator is only consumed if a byte follows it
+    if count > 0 {
+      if let Some((_, c)) = it.peek() {
+        if separators.find_token(c.as_char()) {
+          it.next();
+        }
+      }
+    }
+
+    let (index, high) = match it.next() {
+      Some((index, c)) => match c.as_char().to_digit(16) {
+        Some(d) => (index, d),
+        None if count == 0 => break,
+        None => return Ok((count, end)),
+      },
+      None => break,
+    };
+
+    match it.next() {
+      Some((next, c)) => match c.as_char().to_digit(16) {
+        Some(low) => {
+          push((high << 4 | low) as u8);
+          count += 1;
+          end = next + c.len();
+        }
+        None => {
+          return Err(Err::Error(E::from_error_kind(
+            input.slice(index..),
+            ErrorKind::HexDigit,
+          )))
+        }
+      },
+      None => {
+        return Err(Err::Error(E::from_error_kind(
+          input.slice(index..),
+          ErrorKind::HexDigit,
+        )))
+      }
+    }
+  }
+
+  if count == 0 {
+    return Err(Err::Error(E::from_error_kind(
+      input.clone(),
+      ErrorKind::HexDigit,
+    )));
+  }
+
+  Ok((count, end))
+}
+
+/// Recognizes a run of hexadecimal digit pairs and decodes it to bytes.
+///
+/// The argument is the list of characters accepted between two bytes, like
+/// `":"` or `" \t"`. Use `""` to accept no separator. A separator that is not
+/// followed by a byte is left in the remaining input.
+///
+/// An odd number of digits is an error on the unpaired digit, and an input
+/// that does not start with a byte is an error.
+///
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::hex::hex_bytes;
+///
+/// fn fingerprint(i: &str) -> IResult<&str, Vec<u8>> {
+///   hex_bytes(":")(i)
+/// }
+///
+/// assert_eq!(fingerprint("de:ad:BE:ef"), Ok(("", vec![0xde, 0xad, 0xbe, 0xef])));
+/// assert_eq!(fingerprint("cafe: x"), Ok((": x", vec![0xca, 0xfe])));
+/// assert_eq!(fingerprint("de:a:be"), Err(Err::Error(Error::new("a:be", ErrorKind::HexDigit))));
+/// assert_eq!(fingerprint("xyz"), Err(Err::Error(Error::new("xyz", ErrorKind::HexDigit))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn hex_bytes<I, T, E>(
+  separators: T,
+) -> impl Fn(I) -> IResult<I, crate::lib::std::vec::Vec<u8>, E>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar + Copy,
+  T: FindToken<char>,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let mut res = crate::lib::std::vec::Vec::new();
+    let (_, end) = decode(&input, &separators, usize::MAX, |b| res.push(b))?;
+    Ok((input.slice(end..), res))
+  }
+}
+
+/// Recognizes a fixed number of hexadecimal digit pairs and decodes them to
+/// a byte array.
+///
+/// The number of bytes is the length of the array type `A`, like `[u8; 6]`
+/// for a MAC address. Separators work like in [hex_bytes]. Parsing stops once
+/// the array is filled, and fewer bytes than the array length is an error.
+///
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::hex::hex_bytes_n;
+///
+/// fn mac(i: &str) -> IResult<&str, [u8; 6]> {
+///   hex_bytes_n(":-")(i)
+/// }
+///
+/// assert_eq!(mac("00:1b-63:84:45:e6 eth0"), Ok((" eth0", [0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6])));
+/// assert_eq!(mac("00:1b:63"), Err(Err::Error(Error::new("00:1b:63", ErrorKind::HexDigit))));
+/// ```
+pub fn hex_bytes_n<I, A, T, E>(separators: T) -> impl Fn(I) -> IResult<I, A, E>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar + Copy,
+  A: Default + AsMut<[u8]>,
+  T: FindToken<char>,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let mut res = A::default();
+    let buf = res.as_mut();
+    let len = buf.len();
+    let mut index = 0;
+    let (count, end) = decode(&input, &separators, len, |b| {
+      buf[index] = b;
+      index += 1;
+    })?;
+
+    if count < len {
+      return Err(Err::Error(E::from_error_kind(input, ErrorKind::HexDigit)));
+    }
+
+    Ok((input.slice(end..), res))
+  }
+}
+
+/// Decodes the 36 characters of a hyphenated UUID
+fn uuid_bytes<I>(input: &I, start: usize) -> Option<([u8; 16], usize)>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+{
+  let mut res = [0; 16];
+  let mut it = input.slice(start..).iter_elements().map(AsChar::as_char);
+  let mut high = None;
+  let mut count = 0;
+
+  for index in 0..36 {
+    let c = it.next()?;
+    if index == 8 || index == 13 || index == 18 || index == 23 {
+      if c != '-' {
+        return None;
+      }
+      continue;
+    }
+
+    let d = c.to_digit(16)?;
+    match high.take() {
+      None => high = Some(d),
+      Some(h) => {
+        res[count] = (h << 4 | d) as u8;
+        count += 1;
+      }
+    }
+  }
+
+  // a longer last group is not a valid UUID
+  match it.next() {
+    Some(c) if c.is_ascii_hexdigit() => None,
+    _ => Some((res, start + 36)),
+  }
+}
+
+/// Recognizes a UUID in its hyphenated form, like `67e55044-10b1-426f-9247-bb680e5fe0c8`,
+/// and returns its bytes.
+///
+/// The UUID can also be enclosed in braces, or prefixed by `urn:uuid:`. Hexadecimal
+/// digits can be uppercase or lowercase.
+///
+/// # Example
+///
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::hex::uuid;
+///
+/// fn parser(i: &str) -> IResult<&str, [u8; 16]> {
+///   uuid(i)
+/// }
+///
+/// let bytes = [
+///   0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f,
+///   0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8,
+/// ];
+/// assert_eq!(parser("67e55044-10b1-426f-9247-bb680e5fe0c8 ok"), Ok((" ok", bytes)));
+/// assert_eq!(parser("{67E55044-10B1-426F-9247-BB680E5FE0C8}"), Ok(("", bytes)));
+/// assert_eq!(parser("urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"), Ok(("", bytes)));
+/// assert_eq!(parser("67e55044-10b1-426f-9247"), Err(Err::Error(Error::new("67e55044-10b1-426f-9247", ErrorKind::Uuid))));
+/// ```
+pub fn uuid<I, E>(input: I) -> IResult<I, [u8; 16], E>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>> + Compare<&'static str>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = if input.compare("{") == CompareResult::Ok {
+    uuid_bytes(&input, 1).and_then(|(bytes, end)| {
+      match input
+        .slice(end..)
+        .iter_elements()
+        .next()
+        .map(AsChar::as_char)
+      {
+        Some('}') => Some((bytes, end + 1)),
+        _ => None,
+      }
+    })
+  } else if input.compare_no_case("urn:uuid:") == CompareResult::Ok {
+    uuid_bytes(&input, 9)
+  } else {
+    uuid_bytes(&input, 0)
+  };
+
+  match res {
+    Some((bytes, end)) => Ok((input.slice(end..), bytes)),
+    None => Err(Err::Error(E::from_error_kind(input, ErrorKind::Uuid))),
+  }
+}
+
+/// Recognizes a UUID like [uuid], and returns it as a [uuid::Uuid](::uuid::Uuid).
+///
+/// # Example
+///
+/// ```
+/// # use nom::IResult;
+/// use nom::hex::uuid_value;
+/// use uuid::Uuid;
+///
+/// fn parser(i: &str) -> IResult<&str, Uuid> {
+///   uuid_value(i)
+/// }
+///
+/// assert_eq!(
+///   parser("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+///   Ok(("", Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8)))
+/// );
+/// ```
+#[cfg(feature = "uuid")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "uuid")))]
+pub fn uuid_value<I, E>(input: I) -> IResult<I, ::uuid::Uuid, E>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>> + Compare<&'static str>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let (i, bytes) = uuid(input)?;
+  Ok((i, ::uuid::Uuid::from_bytes(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn hex_bytes_separators() {
+    let p = hex_bytes::<_, _, Error<&[u8]>>(&b" \t"[..]);
+    assert_eq!(
+      p(&b"00 01\tfF 2"[..]),
+      Err(Err::Error(Error::new(&b"2"[..], ErrorKind::HexDigit)))
+    );
+    assert_eq!(p(&b"0001 ff  aa"[..]), Ok((&b"  aa"[..], vec![0, 1, 0xff])));
+    assert_eq!(
+      p(&b"abc;"[..]),
+      Err(Err::Error(Error::new(&b"c;"[..], ErrorKind::HexDigit)))
+    );
+    assert_eq!(
+      hex_bytes::<_, _, Error<&str>>("")("0a:0b"),
+      Ok((":0b", vec![0x0a]))
+    );
+  }
+
+  #[test]
+  fn hex_bytes_n_length() {
+    let p = hex_bytes_n::<_, [u8; 2], _, Error<&str>>("");
+    assert_eq!(p("abcdef"), Ok(("ef", [0xab, 0xcd])));
+    assert_eq!(
+      p("ab"),
+      Err(Err::Error(Error::new("ab", ErrorKind::HexDigit)))
+    );
+    assert_eq!(p(""), Err(Err::Error(Error::new("", ErrorKind::HexDigit))));
+  }
+
+  #[test]
+  fn uuid_invalid() {
+    let p = uuid::<_, Error<&[u8]>>;
+    let valid = &b"{00000000-0000-0000-0000-0000000000ff}"[..];
+    assert_eq!(p(valid).map(|(_, b)| b[15]), Ok(0xff));
+    for case in [
+      &b"{00000000-0000-0000-0000-0000000000ff"[..],
+      &b"00000000-0000-0000-0000-0000000000ff0"[..],
+      &b"00000000-0000-0000-00000-000000000ff"[..],
+      &b"0000000g-0000-0000-0000-0000000000ff"[..],
+      &b"urn:uuid:{00000000-0000-0000-0000-0000000000ff}"[..],
+    ]
+    .iter()
+    {
+      assert_eq!(p(case), Err(Err::Error(Error::new(*case, ErrorKind::Uuid))));
+    }
+  }
+}
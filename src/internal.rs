@@ -44,6 +44,93 @@ impl<I, O, E> Finish<I, O, E> for IResult<I, O, E> {
   }
 }
 
+/// Helper methods to manipulate a parser's result
+///
+/// ```rust
+/// # use nom::{IResult, IResultExt};
+/// use nom::character::complete::digit1;
+/// use nom::error::ErrorKind;
+///
+/// fn number(i: &str) -> IResult<&str, &str> {
+///   digit1(i)
+/// }
+///
+/// assert_eq!(number("123;").map_output(str::len), Ok((";", 3)));
+/// assert_eq!(number("123;").map_remaining(str::len), Ok((1, "123")));
+/// assert_eq!(number("123;").unwrap_output(), "123");
+/// assert_eq!(number("abc").err_kind(), Some(ErrorKind::Digit));
+/// ```
+pub trait IResultExt<I, O, E>: Sized {
+  /// Applies a function to the output, leaving the remaining input and errors untouched
+  fn map_output<O2, F: FnOnce(O) -> O2>(self, f: F) -> IResult<I, O2, E>;
+
+  /// Applies a function to the remaining input, leaving the output and errors untouched
+  fn map_remaining<I2, F: FnOnce(I) -> I2>(self, f: F) -> IResult<I2, O, E>;
+
+  /// Returns the kind of the deepest error, if the result is `Err::Error` or `Err::Failure`
+  fn err_kind(&self) -> Option<ErrorKind>
+  where
+    E: error::ErrorFrames<I>;
+
+  /// Returns the output, panicking with `msg` if the parser failed or did
+  /// not consume the whole input
+  ///
+  /// This is mostly useful in tests.
+  fn expect_complete(self, msg: &str) -> O
+  where
+    I: crate::traits::InputLength + fmt::Debug,
+    E: fmt::Debug;
+
+  /// Returns the output, panicking if the parser failed
+  ///
+  /// This is mostly useful in tests.
+  fn unwrap_output(self) -> O
+  where
+    E: fmt::Debug;
+}
+
+impl<I, O, E> IResultExt<I, O, E> for IResult<I, O, E> {
+  fn map_output<O2, F: FnOnce(O) -> O2>(self, f: F) -> IResult<I, O2, E> {
+    self.map(|(i, o)| (i, f(o)))
+  }
+
+  fn map_remaining<I2, F: FnOnce(I) -> I2>(self, f: F) -> IResult<I2, O, E> {
+    self.map(|(i, o)| (f(i), o))
+  }
+
+  fn err_kind(&self) -> Option<ErrorKind>
+  where
+    E: error::ErrorFrames<I>,
+  {
+    match self {
+      Err(Err::Error(e)) | Err(Err::Failure(e)) => e.deepest().and_then(|frame| frame.kind),
+      _ => None,
+    }
+  }
+
+  fn expect_complete(self, msg: &str) -> O
+  where
+    I: crate::traits::InputLength + fmt::Debug,
+    E: fmt::Debug,
+  {
+    match self {
+      Ok((i, o)) if i.input_len() == 0 => o,
+      Ok((i, _)) => panic!("{}: remaining input: {:?}", msg, i),
+      Err(e) => panic!("{}: {:?}", msg, e),
+    }
+  }
+
+  fn unwrap_output(self) -> O
+  where
+    E: fmt::Debug,
+  {
+    match self {
+      Ok((_, o)) => o,
+      Err(e) => panic!("called `unwrap_output()` on an error: {:?}", e),
+    }
+  }
+}
+
 /// Contains information on needed data if a parser returned `Incomplete`
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[allow(missing_doc_code_examples)]
@@ -54,6 +141,22 @@ pub enum Needed {
   Size(NonZeroUsize),
 }
 
+/// Details on the item a parser could not finish
+///
+/// They are recorded by the [needed_context](crate::combinator::needed_context)
+/// combinator, next to the `Needed` value, to keep `Needed` small.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[allow(missing_doc_code_examples)]
+pub struct Pending {
+  /// Additional data required, if known
+  pub size: Option<NonZeroUsize>,
+  /// Total size of the pending item, from the start of the outermost
+  /// labelled parser, if known
+  pub total: Option<NonZeroUsize>,
+  /// Label of the innermost labelled parser that needs the data
+  pub context: Option<&'static str>,
+}
+
 impl Needed {
   /// Creates `Needed` instance, returns `Needed::Unknown` if the argument is zero
   pub fn new(s: usize) -> Self {
@@ -65,7 +168,15 @@ impl Needed {
 
   /// Indicates if we know how many bytes we need
   pub fn is_known(&self) -> bool {
-    *self != Unknown
+    self.size().is_some()
+  }
+
+  /// Returns the additional data size required, if known
+  pub fn size(&self) -> Option<NonZeroUsize> {
+    match self {
+      Unknown => None,
+      Size(n) => Some(*n),
+    }
   }
 
   /// Maps a `Needed` to `Needed` by applying a function to a contained `Size` value.
@@ -78,6 +189,31 @@ impl Needed {
   }
 }
 
+impl fmt::Display for Needed {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.size() {
+      Some(u) => write!(f, "Parsing requires {} bytes/chars", u),
+      None => write!(f, "Parsing requires more data"),
+    }
+  }
+}
+
+impl fmt::Display for Pending {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.size {
+      Some(u) => write!(f, "Parsing requires {} bytes/chars", u)?,
+      None => write!(f, "Parsing requires more data")?,
+    }
+    if let Some(context) = self.context {
+      write!(f, " in {}", context)?;
+    }
+    if let Some(total) = self.total {
+      write!(f, " ({} in total)", total)?;
+    }
+    Ok(())
+  }
+}
+
 /// The `Err` enum indicates the parser was not successful
 ///
 /// It has three cases:
@@ -115,6 +251,48 @@ impl<E> Err<E> {
     }
   }
 
+  /// Tests if the result is a recoverable Error
+  pub fn is_error(&self) -> bool {
+    if let Err::Error(_) = self {
+      true
+    } else {
+      false
+    }
+  }
+
+  /// Tests if the result is an unrecoverable Failure
+  pub fn is_failure(&self) -> bool {
+    if let Err::Failure(_) = self {
+      true
+    } else {
+      false
+    }
+  }
+
+  /// Returns the inner error of an Error or Failure, or `None` if Incomplete
+  pub fn into_error(self) -> Option<E> {
+    match self {
+      Err::Incomplete(_) => None,
+      Err::Error(e) | Err::Failure(e) => Some(e),
+    }
+  }
+
+  /// Returns the needed data size if Incomplete
+  pub fn needed(&self) -> Option<Needed> {
+    match self {
+      Err::Incomplete(n) => Some(*n),
+      _ => None,
+    }
+  }
+
+  /// Turns a recoverable Error into a Failure, to stop backtracking
+  pub fn cut(self) -> Self {
+    match self {
+      Err::Error(e) => Err::Failure(e),
+      e => e,
+    }
+  }
+
   /// Applies the given function to the inner error
   pub fn map<E2, F>(self, f: F) -> Err<E2>
   where
@@ -150,6 +328,16 @@ impl<T> Err<(T, ErrorKind)> {
   }
 }
 
+impl<T> Err<error::Error<T>> {
+  /// Maps `Err<error::Error<T>>` to `Err<error::Error<U>>` with the given `F: T -> U`
+  pub fn map_input<U, F>(self, f: F) -> Err<error::Error<U>>
+  where
+    F: FnOnce(T) -> U,
+  {
+    self.map(|e| error::Error::new(f(e.input), e.code))
+  }
+}
+
 #[cfg(feature = "alloc")]
 use crate::lib::std::{borrow::ToOwned, string::String, vec::Vec};
 #[cfg(feature = "alloc")]
@@ -178,8 +366,7 @@ where
 {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     match self {
-      Err::Incomplete(Needed::Size(u)) => write!(f, "Parsing requires {} bytes/chars", u),
-      Err::Incomplete(Needed::Unknown) => write!(f, "Parsing requires more data"),
+      Err::Incomplete(n) => write!(f, "{}", n),
       Err::Failure(c) => write!(f, "Parsing Failure: {:?}", c),
       Err::Error(c) => write!(f, "Parsing Error: {:?}", c),
     }
@@ -423,6 +610,27 @@ mod tests {
   use super::*;
   use crate::error::ErrorKind;
 
+  #[test]
+  fn iresult_ext_test() {
+    let res: IResult<&[u8], u8, (&[u8], ErrorKind)> = Ok((&b""[..], 1));
+    assert_eq!(res.clone().map_output(u32::from), Ok((&b""[..], 1u32)));
+    assert_eq!(res.expect_complete("number"), 1);
+
+    let res: IResult<&[u8], u8, (&[u8], ErrorKind)> =
+      Err(Err::Failure((&b"a"[..], ErrorKind::Digit)));
+    assert_eq!(res.err_kind(), Some(ErrorKind::Digit));
+
+    let res: IResult<&[u8], u8, (&[u8], ErrorKind)> = Err(Err::Incomplete(Needed::Unknown));
+    assert_eq!(res.err_kind(), None);
+  }
+
+  #[test]
+  #[should_panic(expected = "number: remaining input")]
+  fn expect_complete_remaining() {
+    let res: IResult<&str, u8> = Ok((";", 1));
+    res.expect_complete("number");
+  }
+
   #[doc(hidden)]
   #[macro_export]
   macro_rules! assert_size (
@@ -446,4 +654,20 @@ mod tests {
     let e = Err::Error(1);
     assert_eq!(e.map(|v| v + 1), Err::Error(2));
   }
+
+  #[test]
+  fn err_helpers_test() {
+    let e: Err<error::Error<&str>> = Err::Error(error::Error::new("abc", ErrorKind::Tag));
+    assert!(e.is_error() && !e.is_failure() && !e.is_incomplete());
+    assert_eq!(e.needed(), None);
+
+    let e = e.map_input(str::len).cut();
+    assert!(e.is_failure());
+    assert_eq!(e.into_error(), Some(error::Error::new(3, ErrorKind::Tag)));
+
+    let e: Err<()> = Err::Incomplete(Needed::new(2));
+    assert_eq!(e.needed(), Some(Needed::new(2)));
+    assert_eq!(e.clone().cut(), Err::Incomplete(Needed::new(2)));
+    assert_eq!(e.into_error(), None);
+  }
 }
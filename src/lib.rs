@@ -411,17 +411,35 @@
 #[cfg(feature = "alloc")]
 #[macro_use]
 extern crate alloc;
+#[cfg(feature = "arbitrary")]
+pub extern crate arbitrary;
+#[cfg(feature = "arrayvec")]
+pub extern crate arrayvec;
 #[cfg(feature = "bitvec")]
 pub extern crate bitvec;
+#[cfg(feature = "chrono")]
+pub extern crate chrono;
+#[cfg(feature = "codespan")]
+pub extern crate codespan_reporting;
 #[cfg(doctest)]
 extern crate doc_comment;
 #[cfg(feature = "lexical")]
 extern crate lexical_core;
 extern crate memchr;
+#[cfg(feature = "parallel")]
+extern crate rayon;
 #[cfg(feature = "regexp")]
 pub extern crate regex;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "smallvec")]
+pub extern crate smallvec;
 #[cfg(nightly)]
 extern crate test;
+#[cfg(feature = "codec")]
+pub extern crate tokio_util;
+#[cfg(feature = "uuid")]
+pub extern crate uuid;
 
 #[cfg(doctest)]
 doc_comment::doctest!("../README.md");
@@ -442,7 +460,7 @@ pub mod lib {
 
     #[cfg(feature = "alloc")]
     #[doc(hidden)]
-    pub use alloc::{borrow, boxed, string, vec};
+    pub use alloc::{borrow, boxed, collections, string, vec};
 
     #[doc(hidden)]
     pub use core::{cmp, convert, fmt, iter, mem, ops, option, result, slice, str};
@@ -510,6 +528,78 @@ pub mod bits;
 #[macro_use]
 pub mod character;
 
+pub mod quoted;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub mod csv;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub mod grammar;
+
+pub mod hex;
+
+pub mod fixed;
+
+pub mod bom;
+
+pub mod units;
+
+#[cfg(feature = "base64")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "base64")))]
+pub mod base64;
+
+#[cfg(feature = "net")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "net")))]
+pub mod net;
+
+#[cfg(feature = "datetime")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "datetime")))]
+pub mod datetime;
+
+#[cfg(feature = "entities")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "entities")))]
+pub mod entities;
+
+#[cfg(feature = "email")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "email")))]
+pub mod email;
+
+#[cfg(feature = "semver")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "semver")))]
+pub mod semver;
+
+#[cfg(feature = "uri")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "uri")))]
+pub mod uri;
+
+#[cfg(feature = "codec")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "codec")))]
+pub mod codec;
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[cfg_attr(
+  feature = "docsrs",
+  doc(cfg(all(feature = "serde", feature = "alloc")))
+)]
+pub mod de;
+
+#[cfg(all(feature = "arbitrary", feature = "alloc"))]
+#[cfg_attr(
+  feature = "docsrs",
+  doc(cfg(all(feature = "arbitrary", feature = "alloc")))
+)]
+pub mod generate;
+
+#[cfg(feature = "codespan")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "codespan")))]
+pub mod codespan;
+
+#[cfg(feature = "parallel")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "parallel")))]
+pub mod parallel;
+
 #[cfg(feature = "regexp")]
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "regexp")))]
 #[macro_use]
@@ -520,6 +610,12 @@ mod str;
 #[macro_use]
 pub mod number;
 
+pub mod partial;
+pub mod resume;
+pub mod ring;
+#[macro_use]
+pub mod testing;
+
 #[cfg(feature = "docsrs")]
 #[cfg_attr(feature = "docsrs", cfg_attr(feature = "docsrs", doc = include_str!("../doc/nom_recipes.md")))]
 pub mod recipes {}
@@ -11,6 +11,21 @@ use crate::lib::std::vec::Vec;
 use crate::traits::{InputLength, InputTake, ToUsize};
 use core::num::NonZeroUsize;
 
+/// Don't pre-allocate more than 64KiB when calling `Vec::with_capacity`.
+///
+/// Pre-allocating memory is a nice optimization but count fields can't
+/// always be trusted, and a [Budget](crate::combinator::Budget) is only
+/// charged once elements are parsed. We clamp initial capacities to some
+/// reasonable amount, to prevent a bogus count from triggering an OOM.
+#[cfg(feature = "alloc")]
+const MAX_INITIAL_CAPACITY_BYTES: usize = 65536;
+
+#[cfg(feature = "alloc")]
+fn initial_capacity<O>(count: usize) -> usize {
+  let max = MAX_INITIAL_CAPACITY_BYTES / crate::lib::std::mem::size_of::<O>().max(1);
+  count.min(max)
+}
+
 /// Repeats the embedded parser until it fails
 /// and returns the results in a `Vec`.
 ///
@@ -109,7 +124,8 @@ where
   E: ParseError<I>,
 {
   move |mut i: I| match f.parse(i.clone()) {
-    Err(Err::Error(err)) => Err(Err::Error(E::append(i, ErrorKind::Many1, err))),
+    Err(Err::Error(err)) => Err(Err::Error(E::append_element(i, ErrorKind::Many1, 0, err))),
+    Err(Err::Failure(err)) => Err(Err::Failure(E::append_element(i, ErrorKind::Many1, 0, err))),
     Err(e) => Err(e),
     Ok((i1, o)) => {
       let mut acc = crate::lib::std::vec::Vec::with_capacity(4);
@@ -119,6 +135,15 @@ where
       loop {
         match f.parse(i.clone()) {
           Err(Err::Error(_)) => return Ok((i, acc)),
+          Err(Err::Failure(err)) => {
+            let index = acc.len();
+            return Err(Err::Failure(E::append_element(
+              i,
+              ErrorKind::Many1,
+              index,
+              err,
+            )));
+          }
           Err(e) => return Err(e),
           Ok((i1, o)) => {
             if i1 == i {
@@ -217,6 +242,59 @@ where
   many_till(f, g)(i)
 }
 
+/// Applies the parser `f` until the parser `g` would succeed, without
+/// consuming the input recognized by `g`. Returns the results of `f` in a `Vec`.
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::multi::many_until_peek;
+/// use nom::bytes::complete::tag;
+/// use nom::character::complete::{alpha1, multispace0};
+/// use nom::sequence::{delimited, terminated};
+///
+/// fn block(s: &str) -> IResult<&str, Vec<&str>> {
+///   delimited(tag("{"), many_until_peek(terminated(alpha1, multispace0), tag("}")), tag("}"))(s)
+/// };
+///
+/// assert_eq!(block("{a b c}d"), Ok(("d", vec!["a", "b", "c"])));
+/// assert_eq!(block("{}"), Ok(("", vec![])));
+/// assert_eq!(block("{a 1}"), Err(Err::Error(Error::new("1}", ErrorKind::Alpha))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn many_until_peek<I, O, P, E, F, G>(
+  mut f: F,
+  mut g: G,
+) -> impl FnMut(I) -> IResult<I, Vec<O>, E>
+where
+  I: Clone + PartialEq,
+  F: Parser<I, O, E>,
+  G: Parser<I, P, E>,
+  E: ParseError<I>,
+{
+  move |mut i: I| {
+    let mut res = crate::lib::std::vec::Vec::new();
+    loop {
+      match g.parse(i.clone()) {
+        Ok(_) => return Ok((i, res)),
+        Err(Err::Error(_)) => match f.parse(i.clone()) {
+          Err(Err::Error(err)) => return Err(Err::Error(E::append(i, ErrorKind::ManyTill, err))),
+          Err(e) => return Err(e),
+          Ok((i1, o)) => {
+            // loop trip must always consume (otherwise infinite loops)
+            if i1 == i {
+              return Err(Err::Error(E::from_error_kind(i1, ErrorKind::ManyTill)));
+            }
+
+            res.push(o);
+            i = i1;
+          }
+        },
+        Err(e) => return Err(e),
+      }
+    }
+  }
+}
+
 /// Alternates between two parsers to produce
 /// a list of elements.
 /// # Arguments
@@ -255,6 +333,14 @@ where
 
     match f.parse(i.clone()) {
       Err(Err::Error(_)) => return Ok((i, res)),
+      Err(Err::Failure(e)) => {
+        return Err(Err::Failure(E::append_element(
+          i,
+          ErrorKind::SeparatedList,
+          0,
+          e,
+        )))
+      }
       Err(e) => return Err(e),
       Ok((i1, o)) => {
         res.push(o);
@@ -273,6 +359,15 @@ where
 
           match f.parse(i1.clone()) {
             Err(Err::Error(_)) => return Ok((i, res)),
+            Err(Err::Failure(e)) => {
+              let index = res.len();
+              return Err(Err::Failure(E::append_element(
+                i1,
+                ErrorKind::SeparatedList,
+                index,
+                e,
+              )));
+            }
             Err(e) => return Err(e),
             Ok((i2, o)) => {
               res.push(o);
@@ -338,6 +433,14 @@ where
 
     // Parse the first element
     match f.parse(i.clone()) {
+      Err(Err::Failure(e)) => {
+        return Err(Err::Failure(E::append_element(
+          i,
+          ErrorKind::SeparatedList,
+          0,
+          e,
+        )))
+      }
       Err(e) => return Err(e),
       Ok((i1, o)) => {
         res.push(o);
@@ -356,6 +459,15 @@ where
 
           match f.parse(i1.clone()) {
             Err(Err::Error(_)) => return Ok((i, res)),
+            Err(Err::Failure(e)) => {
+              let index = res.len();
+              return Err(Err::Failure(E::append_element(
+                i1,
+                ErrorKind::SeparatedList,
+                index,
+                e,
+              )));
+            }
             Err(e) => return Err(e),
             Ok((i2, o)) => {
               res.push(o);
@@ -382,6 +494,97 @@ where
   separated_list1(sep, f)(i)
 }
 
+/// Alternates between two parsers like [separated_list0], and also returns the
+/// outputs of the separators.
+///
+/// There is always one separator less than elements: a separator that is not
+/// followed by an element is not consumed. The separator spans can be kept by
+/// wrapping it with [recognize](crate::combinator::recognize) or
+/// [consumed](crate::combinator::consumed).
+/// # Arguments
+/// * `sep` Parses the separator between list elements.
+/// * `f` Parses the elements of the list.
+///
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::multi::separated_list_with_seps;
+/// use nom::bytes::complete::tag;
+/// use nom::branch::alt;
+///
+/// fn parser(s: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
+///   separated_list_with_seps(alt((tag("|"), tag("&"))), tag("abc"))(s)
+/// }
+///
+/// assert_eq!(parser("abc|abc&abc"), Ok(("", (vec!["abc", "abc", "abc"], vec!["|", "&"]))));
+/// assert_eq!(parser("abc|def"), Ok(("|def", (vec!["abc"], vec![]))));
+/// assert_eq!(parser("def"), Ok(("def", (vec![], vec![]))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn separated_list_with_seps<I, O, O2, E, F, G>(
+  mut sep: G,
+  mut f: F,
+) -> impl FnMut(I) -> IResult<I, (Vec<O>, Vec<O2>), E>
+where
+  I: Clone + PartialEq,
+  F: Parser<I, O, E>,
+  G: Parser<I, O2, E>,
+  E: ParseError<I>,
+{
+  move |mut i: I| {
+    let mut res = Vec::new();
+    let mut seps = Vec::new();
+
+    match f.parse(i.clone()) {
+      Err(Err::Error(_)) => return Ok((i, (res, seps))),
+      Err(Err::Failure(e)) => {
+        return Err(Err::Failure(E::append_element(
+          i,
+          ErrorKind::SeparatedList,
+          0,
+          e,
+        )))
+      }
+      Err(e) => return Err(e),
+      Ok((i1, o)) => {
+        res.push(o);
+        i = i1;
+      }
+    }
+
+    loop {
+      match sep.parse(i.clone()) {
+        Err(Err::Error(_)) => return Ok((i, (res, seps))),
+        Err(e) => return Err(e),
+        Ok((i1, s)) => {
+          if i1 == i {
+            return Err(Err::Error(E::from_error_kind(i1, ErrorKind::SeparatedList)));
+          }
+
+          match f.parse(i1.clone()) {
+            Err(Err::Error(_)) => return Ok((i, (res, seps))),
+            Err(Err::Failure(e)) => {
+              let index = res.len();
+              return Err(Err::Failure(E::append_element(
+                i1,
+                ErrorKind::SeparatedList,
+                index,
+                e,
+              )));
+            }
+            Err(e) => return Err(e),
+            Ok((i2, o)) => {
+              res.push(o);
+              seps.push(s);
+              i = i2;
+            }
+          }
+        }
+      }
+    }
+  }
+}
+
 /// Repeats the embedded parser `n` times or until it fails
 /// and returns the results in a `Vec`. Fails if the
 /// embedded parser does not succeed at least `m` times.
@@ -418,7 +621,7 @@ where
   E: ParseError<I>,
 {
   move |mut input: I| {
-    let mut res = crate::lib::std::vec::Vec::with_capacity(min);
+    let mut res = crate::lib::std::vec::Vec::with_capacity(initial_capacity::<O>(min));
 
     for count in 0..max {
       match parse.parse(input.clone()) {
@@ -433,11 +636,24 @@ where
         }
         Err(Err::Error(e)) => {
           if count < min {
-            return Err(Err::Error(E::append(input, ErrorKind::ManyMN, e)));
+            return Err(Err::Error(E::append_element(
+              input,
+              ErrorKind::ManyMN,
+              count,
+              e,
+            )));
           } else {
             return Ok((input, res));
           }
         }
+        Err(Err::Failure(e)) => {
+          return Err(Err::Failure(E::append_element(
+            input,
+            ErrorKind::ManyMN,
+            count,
+            e,
+          )));
+        }
         Err(e) => {
           return Err(e);
         }
@@ -617,9 +833,9 @@ where
 {
   move |i: I| {
     let mut input = i.clone();
-    let mut res = crate::lib::std::vec::Vec::with_capacity(count);
+    let mut res = crate::lib::std::vec::Vec::with_capacity(initial_capacity::<O>(count));
 
-    for _ in 0..count {
+    for index in 0..count {
       let input_ = input.clone();
       match f.parse(input_) {
         Ok((i, o)) => {
@@ -627,7 +843,15 @@ where
           input = i;
         }
         Err(Err::Error(e)) => {
-          return Err(Err::Error(E::append(i, ErrorKind::Count, e)));
+          return Err(Err::Error(E::append_element(i, ErrorKind::Count, index, e)));
+        }
+        Err(Err::Failure(e)) => {
+          return Err(Err::Failure(E::append_element(
+            i,
+            ErrorKind::Count,
+            index,
+            e,
+          )));
         }
         Err(e) => {
           return Err(e);
@@ -691,6 +915,71 @@ where
   }
 }
 
+/// Repeats the embedded parser until it fails, and writes its results
+/// into the given sink.
+///
+/// The sink can be a mutable reference to a `String` or `Vec<u8>` (or to their
+/// `arrayvec` and `smallvec` counterparts) when the parser produces chars, bytes
+/// or slices, or a writer wrapped in [FmtSink](crate::FmtSink) or
+/// [IoSink](crate::IoSink). This
+/// avoids building an intermediate collection when the results only need to be
+/// written somewhere else.
+///
+/// If the sink cannot store a result, this returns a `Failure` with `ErrorKind::TooLarge`,
+/// or `ErrorKind::Write` if the underlying writer returned an error.
+/// # Arguments
+/// * `f` The parser to apply.
+/// * `sink` The sink receiving the results
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::multi::many0_into;
+/// use nom::branch::alt;
+/// use nom::bytes::complete::tag;
+/// use nom::combinator::value;
+///
+/// fn parser<'a>(s: &'a str, out: &mut String) -> IResult<&'a str, ()> {
+///   many0_into(alt((tag("abc"), value("-", tag(" ")))), out)(s)
+/// }
+///
+/// let mut out = String::new();
+/// assert_eq!(parser("abc abc123", &mut out), Ok(("123", ())));
+/// assert_eq!(out, "abc-abc");
+/// ```
+pub fn many0_into<I, O, E, F, S>(mut f: F, mut sink: S) -> impl FnMut(I) -> IResult<I, (), E>
+where
+  I: Clone + PartialEq,
+  F: Parser<I, O, E>,
+  S: crate::traits::Sink<O>,
+  E: ParseError<I>,
+{
+  move |i: I| {
+    let mut input = i;
+
+    loop {
+      let i_ = input.clone();
+      match f.parse(i_) {
+        Ok((i, o)) => {
+          // loop trip must always consume (otherwise infinite loops)
+          if i == input {
+            return Err(Err::Error(E::from_error_kind(input, ErrorKind::Many0)));
+          }
+
+          if let Err(kind) = sink.extend_from(o) {
+            return Err(Err::Failure(E::from_error_kind(input, kind)));
+          }
+          input = i;
+        }
+        Err(Err::Error(_)) => {
+          return Ok((input, ()));
+        }
+        Err(e) => {
+          return Err(e);
+        }
+      }
+    }
+  }
+}
+
 /// Applies a parser until it fails and accumulates
 /// the results using a given function and initial value.
 /// # Arguments
@@ -860,6 +1149,187 @@ where
   fold_many1(f, init, g)(i)
 }
 
+/// Alternates between two parsers like [separated_list0], and accumulates
+/// the elements using a given function and initial value, without
+/// allocating a `Vec`.
+/// # Arguments
+/// * `sep` Parses the separator between list elements.
+/// * `f` Parses the elements of the list.
+/// * `init` The initial value.
+/// * `g` The function that combines a result of `f` with
+///   the current accumulator.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed, IResult};
+/// use nom::multi::separated_fold0;
+/// use nom::character::complete::{char, digit1};
+/// use nom::combinator::map_res;
+///
+/// fn sum(s: &str) -> IResult<&str, u32> {
+///   separated_fold0(char(','), map_res(digit1, str::parse), 0, |acc, n: u32| acc + n)(s)
+/// }
+///
+/// assert_eq!(sum("1,2,3"), Ok(("", 6)));
+/// assert_eq!(sum("1,2,x"), Ok((",x", 3)));
+/// assert_eq!(sum("x"), Ok(("x", 0)));
+/// ```
+pub fn separated_fold0<I, O, O2, E, F, G, H, R>(
+  mut sep: G,
+  mut f: F,
+  init: R,
+  mut g: H,
+) -> impl FnMut(I) -> IResult<I, R, E>
+where
+  I: Clone + InputLength,
+  F: Parser<I, O, E>,
+  G: Parser<I, O2, E>,
+  H: FnMut(R, O) -> R,
+  E: ParseError<I>,
+  R: Clone,
+{
+  move |mut i: I| {
+    let mut acc = init.clone();
+
+    match f.parse(i.clone()) {
+      Err(Err::Error(_)) => return Ok((i, acc)),
+      Err(Err::Failure(e)) => {
+        return Err(Err::Failure(E::append_element(
+          i,
+          ErrorKind::SeparatedList,
+          0,
+          e,
+        )))
+      }
+      Err(e) => return Err(e),
+      Ok((i1, o)) => {
+        acc = g(acc, o);
+        i = i1;
+      }
+    }
+
+    let mut index = 1;
+    loop {
+      match sep.parse(i.clone()) {
+        Err(Err::Error(_)) => return Ok((i, acc)),
+        Err(e) => return Err(e),
+        Ok((i1, _)) => {
+          // infinite loop check: the parser must always consume
+          if i1.input_len() == i.input_len() {
+            return Err(Err::Error(E::from_error_kind(i1, ErrorKind::SeparatedList)));
+          }
+
+          match f.parse(i1.clone()) {
+            Err(Err::Error(_)) => return Ok((i, acc)),
+            Err(Err::Failure(e)) => {
+              return Err(Err::Failure(E::append_element(
+                i1,
+                ErrorKind::SeparatedList,
+                index,
+                e,
+              )));
+            }
+            Err(e) => return Err(e),
+            Ok((i2, o)) => {
+              acc = g(acc, o);
+              index += 1;
+              i = i2;
+            }
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Alternates between two parsers like [separated_list1], and accumulates
+/// the elements using a given function and initial value, without
+/// allocating a `Vec`. Fails if the element parser does not produce at
+/// least one element.
+/// # Arguments
+/// * `sep` Parses the separator between list elements.
+/// * `f` Parses the elements of the list.
+/// * `init` The initial value.
+/// * `g` The function that combines a result of `f` with
+///   the current accumulator.
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, Needed, IResult};
+/// use nom::multi::separated_fold1;
+/// use nom::character::complete::{alpha1, char};
+///
+/// fn count(s: &str) -> IResult<&str, usize> {
+///   separated_fold1(char(','), alpha1, 0, |acc, _| acc + 1)(s)
+/// }
+///
+/// assert_eq!(count("a,b,c"), Ok(("", 3)));
+/// assert_eq!(count("a,1"), Ok((",1", 1)));
+/// assert_eq!(count("1"), Err(Err::Error(Error::new("1", ErrorKind::Alpha))));
+/// ```
+pub fn separated_fold1<I, O, O2, E, F, G, H, R>(
+  mut sep: G,
+  mut f: F,
+  init: R,
+  mut g: H,
+) -> impl FnMut(I) -> IResult<I, R, E>
+where
+  I: Clone + InputLength,
+  F: Parser<I, O, E>,
+  G: Parser<I, O2, E>,
+  H: FnMut(R, O) -> R,
+  E: ParseError<I>,
+  R: Clone,
+{
+  move |mut i: I| {
+    let mut acc = init.clone();
+
+    match f.parse(i.clone()) {
+      Err(Err::Failure(e)) => {
+        return Err(Err::Failure(E::append_element(
+          i,
+          ErrorKind::SeparatedList,
+          0,
+          e,
+        )))
+      }
+      Err(e) => return Err(e),
+      Ok((i1, o)) => {
+        acc = g(acc, o);
+        i = i1;
+      }
+    }
+
+    let mut index = 1;
+    loop {
+      match sep.parse(i.clone()) {
+        Err(Err::Error(_)) => return Ok((i, acc)),
+        Err(e) => return Err(e),
+        Ok((i1, _)) => {
+          // infinite loop check: the parser must always consume
+          if i1.input_len() == i.input_len() {
+            return Err(Err::Error(E::from_error_kind(i1, ErrorKind::SeparatedList)));
+          }
+
+          match f.parse(i1.clone()) {
+            Err(Err::Error(_)) => return Ok((i, acc)),
+            Err(Err::Failure(e)) => {
+              return Err(Err::Failure(E::append_element(
+                i1,
+                ErrorKind::SeparatedList,
+                index,
+                e,
+              )));
+            }
+            Err(e) => return Err(e),
+            Ok((i2, o)) => {
+              acc = g(acc, o);
+              index += 1;
+              i = i2;
+            }
+          }
+        }
+      }
+    }
+  }
+}
+
 /// Applies a parser `n` times or until it fails and accumulates
 /// the results using a given function and initial value.
 /// Fails if the embedded parser does not succeed at least `m`
@@ -926,11 +1396,24 @@ where
         //FInputXMError: handle failure properly
         Err(Err::Error(err)) => {
           if count < min {
-            return Err(Err::Error(E::append(input, ErrorKind::ManyMN, err)));
+            return Err(Err::Error(E::append_element(
+              input,
+              ErrorKind::ManyMN,
+              count,
+              err,
+            )));
           } else {
             break;
           }
         }
+        Err(Err::Failure(err)) => {
+          return Err(Err::Failure(E::append_element(
+            input,
+            ErrorKind::ManyMN,
+            count,
+            err,
+          )));
+        }
         Err(e) => return Err(e),
       }
     }
@@ -1101,7 +1584,7 @@ where
     let mut input = i.clone();
     let mut res = Vec::new();
 
-    for _ in 0..count.to_usize() {
+    for index in 0..count.to_usize() {
       let input_ = input.clone();
       match g.parse(input_) {
         Ok((i, o)) => {
@@ -1109,7 +1592,15 @@ where
           input = i;
         }
         Err(Err::Error(e)) => {
-          return Err(Err::Error(E::append(i, ErrorKind::Count, e)));
+          return Err(Err::Error(E::append_element(i, ErrorKind::Count, index, e)));
+        }
+        Err(Err::Failure(e)) => {
+          return Err(Err::Failure(E::append_element(
+            i,
+            ErrorKind::Count,
+            index,
+            e,
+          )));
         }
         Err(e) => {
           return Err(e);
@@ -1120,3 +1611,147 @@ where
     Ok((input, res))
   }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+  use super::*;
+  use crate::bytes::complete::tag;
+  use crate::character::complete::digit1;
+  use crate::combinator::cut;
+  use crate::error::{VerboseError, VerboseErrorKind};
+  use crate::sequence::terminated;
+
+  #[test]
+  fn element_index_in_errors() {
+    fn numbers(i: &str) -> IResult<&str, Vec<&str>, VerboseError<&str>> {
+      count(terminated(digit1, tag(";")), 3)(i)
+    }
+
+    let res = numbers("1;2;x;");
+    assert_eq!(
+      res,
+      Err(Err::Error(VerboseError {
+        errors: vec![
+          ("x;", VerboseErrorKind::Nom(ErrorKind::Digit)),
+          ("1;2;x;", VerboseErrorKind::Element(ErrorKind::Count, 2)),
+        ]
+      }))
+    );
+
+    fn list(i: &str) -> IResult<&str, Vec<&str>, VerboseError<&str>> {
+      separated_list0(tag(","), cut(digit1))(i)
+    }
+
+    let res = list("1,2,3,x");
+    assert_eq!(
+      res,
+      Err(Err::Failure(VerboseError {
+        errors: vec![
+          ("x", VerboseErrorKind::Nom(ErrorKind::Digit)),
+          ("x", VerboseErrorKind::Element(ErrorKind::SeparatedList, 3)),
+        ]
+      }))
+    );
+
+    fn items(i: &str) -> IResult<&str, Vec<&str>, VerboseError<&str>> {
+      many1(terminated(digit1, cut(tag(";"))))(i)
+    }
+
+    for &(input, index) in &[("1;2;3x", 2), ("1x", 0)] {
+      assert_eq!(
+        items(input),
+        Err(Err::Failure(VerboseError {
+          errors: vec![
+            ("x", VerboseErrorKind::Tag(";".to_string())),
+            (
+              &input[input.len() - 2..],
+              VerboseErrorKind::Element(ErrorKind::Many1, index)
+            ),
+          ]
+        }))
+      );
+    }
+
+    let expected = Err(Err::Failure(VerboseError {
+      errors: vec![
+        ("x", VerboseErrorKind::Nom(ErrorKind::Digit)),
+        ("x", VerboseErrorKind::Element(ErrorKind::SeparatedList, 0)),
+      ],
+    }));
+    assert_eq!(list("x"), expected);
+    let res: IResult<_, _, VerboseError<&str>> = separated_list1(tag(","), cut(digit1))("x");
+    assert_eq!(res, expected);
+  }
+
+  #[test]
+  fn separated_list_with_seps_test() {
+    use crate::combinator::consumed;
+
+    fn list(i: &str) -> IResult<&str, (Vec<&str>, Vec<(&str, char)>)> {
+      separated_list_with_seps(consumed(crate::character::complete::one_of(",;")), digit1)(i)
+    }
+
+    assert_eq!(
+      list("1,2;3,"),
+      Ok((",", (vec!["1", "2", "3"], vec![(",", ','), (";", ';')])))
+    );
+    assert_eq!(list(""), Ok(("", (vec![], vec![]))));
+
+    fn empty_sep(i: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
+      separated_list_with_seps(tag(""), digit1)(i)
+    }
+    assert_eq!(
+      empty_sep("1a"),
+      Err(Err::Error(error_position!("a", ErrorKind::SeparatedList)))
+    );
+  }
+
+  #[test]
+  fn separated_fold_test() {
+    use crate::character::complete::char;
+    use crate::combinator::map_res;
+
+    fn sum(i: &str) -> IResult<&str, u32> {
+      separated_fold1(
+        char(','),
+        cut(map_res(digit1, str::parse)),
+        0,
+        |acc, n: u32| acc + n,
+      )(i)
+    }
+
+    assert_eq!(sum("200,100,250;"), Ok((";", 550)));
+    assert_eq!(
+      sum("1,2,x"),
+      Err(Err::Failure(error_position!("x", ErrorKind::Digit)))
+    );
+    assert_eq!(
+      sum(";"),
+      Err(Err::Failure(error_position!(";", ErrorKind::Digit)))
+    );
+
+    fn verbose_sum(i: &str) -> IResult<&str, u32, VerboseError<&str>> {
+      separated_fold0(
+        char(','),
+        cut(map_res(digit1, str::parse)),
+        0,
+        |acc, n: u32| acc + n,
+      )(i)
+    }
+    assert_eq!(
+      verbose_sum("x"),
+      Err(Err::Failure(VerboseError {
+        errors: vec![
+          ("x", VerboseErrorKind::Nom(ErrorKind::Digit)),
+          ("x", VerboseErrorKind::Element(ErrorKind::SeparatedList, 0)),
+        ]
+      }))
+    );
+
+    fn count(i: &str) -> IResult<&str, usize> {
+      separated_fold0(char(','), digit1, 0, |acc, _| acc + 1)(i)
+    }
+    assert_eq!(count("1,22,333,"), Ok((",", 3)));
+    assert_eq!(count(""), Ok(("", 0)));
+  }
+}
@@ -0,0 +1,390 @@
+//! Parsers for network addresses
+//!
+//! IP addresses, CIDR prefixes and MAC addresses appear in configuration
+//! files and logs. The parsers of this module follow the textual forms of
+//! the RFCs strictly: IPv4 octets with leading zeros, IPv6 addresses with
+//! multiple `::` or too many groups, and out of range prefixes are rejected.
+//!
+//! Those parsers work on complete input.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::ops::RangeFrom;
+use crate::traits::{AsChar, InputIter, Slice};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Longest textual form handled, a CIDR prefix on an IPv6 address with embedded IPv4
+const MAX_LEN: usize = 64;
+
+/// Copies the leading ASCII characters of the input, so the addresses can be
+/// parsed on bytes. Since they are all ASCII, a length in this buffer is also
+/// a valid offset in the input.
+fn ascii_prefix<I>(input: &I, buf: &mut [u8; MAX_LEN]) -> usize
+where
+  I: InputIter,
+  <I as InputIter>::Item: AsChar,
+{
+  let mut len = 0;
+  for c in input.iter_elements().take(MAX_LEN) {
+    let c = c.as_char();
+    if !c.is_ascii() {
+      break;
+    }
+    buf[len] = c as u8;
+    len += 1;
+  }
+  len
+}
+
+/// Runs a parser on the ASCII prefix of the input
+fn parse<I, O, E, F>(input: I, f: F) -> IResult<I, O, E>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+  F: Fn(&[u8]) -> Option<(O, usize)>,
+{
+  let mut buf = [0; MAX_LEN];
+  let len = ascii_prefix(&input, &mut buf);
+  match f(&buf[..len]) {
+    Some((o, consumed)) => Ok((input.slice(consumed..), o)),
+    None => Err(Err::Error(E::from_error_kind(input, ErrorKind::NetAddress))),
+  }
+}
+
+/// Number of leading bytes matching the predicate
+fn count_while(b: &[u8], f: impl Fn(u8) -> bool) -> usize {
+  b.iter().take_while(|&&c| f(c)).count()
+}
+
+fn dec_octet(b: &[u8]) -> Option<(u8, usize)> {
+  let n = count_while(b, |c| c.is_ascii_digit());
+  if n == 0 || n > 3 || (n > 1 && b[0] == b'0') {
+    return None;
+  }
+  let value = b[..n]
+    .iter()
+    .fold(0u16, |acc, c| acc * 10 + u16::from(c - b'0'));
+  if value > 255 {
+    None
+  } else {
+    Some((value as u8, n))
+  }
+}
+
+fn ipv4_bytes(b: &[u8]) -> Option<([u8; 4], usize)> {
+  let mut res = [0; 4];
+  let mut pos = 0;
+  for (i, octet) in res.iter_mut().enumerate() {
+    if i > 0 {
+      if b.get(pos) != Some(&b'.') {
+        return None;
+      }
+      pos += 1;
+    }
+    let (value, n) = dec_octet(&b[pos..])?;
+    *octet = value;
+    pos += n;
+  }
+  Some((res, pos))
+}
+
+fn h16(b: &[u8]) -> Option<(u16, usize)> {
+  let n = count_while(b, |c| c.is_ascii_hexdigit());
+  if n == 0 || n > 4 {
+    return None;
+  }
+  let value = b[..n].iter().fold(0u16, |acc, &c| {
+    acc << 4 | (c as char).to_digit(16).unwrap_or(0) as u16
+  });
+  Some((value, n))
+}
+
+fn ipv6_bytes(b: &[u8]) -> Option<([u16; 8], usize)> {
+  let mut groups = [0u16; 8];
+  let mut count = 0;
+  let mut gap = None;
+  let mut pos = 0;
+  let mut need_group = false;
+
+  if b.starts_with(b"::") {
+    gap = Some(0);
+    pos = 2;
+  }
+
+  while count < 8 {
+    if b[pos..].starts_with(b":") {
+      return None;
+    }
+
+    // an IPv4 address can replace the last two groups
+    if count <= 6 {
+      if let Some((v4, n)) = ipv4_bytes(&b[pos..]) {
+        groups[count] = u16::from(v4[0]) << 8 | u16::from(v4[1]);
+        groups[count + 1] = u16::from(v4[2]) << 8 | u16::from(v4[3]);
+        count += 2;
+        pos += n;
+        need_group = false;
+        break;
+      }
+    }
+
+    match h16(&b[pos..]) {
+      Some((group, n)) => {
+        groups[count] = group;
+        count += 1;
+        pos += n;
+        need_group = false;
+      }
+      None => break,
+    }
+
+    if gap.is_none() && b[pos..].starts_with(b"::") {
+      gap = Some(count);
+      pos += 2;
+    } else if count < 8 && b[pos..].starts_with(b":") {
+      pos += 1;
+      need_group = true;
+    } else {
+      break;
+    }
+  }
+
+  match gap {
+    _ if need_group => None,
+    None if count < 8 => None,
+    Some(_) if count == 8 => None,
+    None => Some((groups, pos)),
+    Some(gap) => {
+      let mut res = [0u16; 8];
+      res[..gap].copy_from_slice(&groups[..gap]);
+      res[8 - (count - gap)..].copy_from_slice(&groups[gap..count]);
+      Some((res, pos))
+    }
+  }
+}
+
+fn ip_bytes(b: &[u8]) -> Option<(IpAddr, usize)> {
+  ipv4_bytes(b)
+    .map(|(v4, n)| (IpAddr::V4(Ipv4Addr::from(v4)), n))
+    .or_else(|| ipv6_bytes(b).map(|(v6, n)| (IpAddr::V6(Ipv6Addr::from(v6)), n)))
+}
+
+/// Recognizes an IPv4 address in dotted decimal form.
+///
+/// Octets with leading zeros are rejected, since some tools read them as octal.
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::net::ipv4;
+/// use std::net::Ipv4Addr;
+///
+/// fn parser(i: &str) -> IResult<&str, Ipv4Addr> {
+///   ipv4(i)
+/// }
+///
+/// assert_eq!(parser("192.168.1.1:80"), Ok((":80", Ipv4Addr::new(192, 168, 1, 1))));
+/// assert_eq!(parser("192.168.1.256"), Err(Err::Error(Error::new("192.168.1.256", ErrorKind::NetAddress))));
+/// assert_eq!(parser("192.168.01.1"), Err(Err::Error(Error::new("192.168.01.1", ErrorKind::NetAddress))));
+/// ```
+pub fn ipv4<I, E>(input: I) -> IResult<I, Ipv4Addr, E>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  parse(input, |b| {
+    ipv4_bytes(b).map(|(v4, n)| (Ipv4Addr::from(v4), n))
+  })
+}
+
+/// Recognizes an IPv6 address.
+///
+/// A single `::` can replace one or more groups of zeros, and the last two groups
+/// can be written as an IPv4 address.
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::net::ipv6;
+/// use std::net::Ipv6Addr;
+///
+/// fn parser(i: &str) -> IResult<&str, Ipv6Addr> {
+///   ipv6(i)
+/// }
+///
+/// assert_eq!(parser("fe80::1%eth0"), Ok(("%eth0", Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1))));
+/// assert_eq!(parser("::ffff:10.0.0.1"), Ok(("", Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0x0a00, 1))));
+/// assert_eq!(parser("1::2::3"), Err(Err::Error(Error::new("1::2::3", ErrorKind::NetAddress))));
+/// ```
+pub fn ipv6<I, E>(input: I) -> IResult<I, Ipv6Addr, E>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  parse(input, |b| {
+    ipv6_bytes(b).map(|(v6, n)| (Ipv6Addr::from(v6), n))
+  })
+}
+
+/// Recognizes an IPv4 or IPv6 address.
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::net::ip;
+/// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+///
+/// fn parser(i: &str) -> IResult<&str, IpAddr> {
+///   ip(i)
+/// }
+///
+/// assert_eq!(parser("10.0.0.1"), Ok(("", IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)))));
+/// assert_eq!(parser("::1"), Ok(("", IpAddr::V6(Ipv6Addr::LOCALHOST))));
+/// ```
+pub fn ip<I, E>(input: I) -> IResult<I, IpAddr, E>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  parse(input, ip_bytes)
+}
+
+/// Recognizes an IP address followed by a `/` and a prefix length, which cannot
+/// exceed 32 for IPv4 and 128 for IPv6.
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::net::cidr;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// fn parser(i: &str) -> IResult<&str, (IpAddr, u8)> {
+///   cidr(i)
+/// }
+///
+/// assert_eq!(parser("10.0.0.0/8"), Ok(("", (IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)), 8))));
+/// assert_eq!(parser("10.0.0.0/33"), Err(Err::Error(Error::new("10.0.0.0/33", ErrorKind::NetAddress))));
+/// ```
+pub fn cidr<I, E>(input: I) -> IResult<I, (IpAddr, u8), E>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  parse(input, |b| {
+    let (addr, n) = ip_bytes(b)?;
+    if b.get(n) != Some(&b'/') {
+      return None;
+    }
+    let (prefix, len) = dec_octet(&b[n + 1..])?;
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    if prefix > max {
+      None
+    } else {
+      Some(((addr, prefix), n + 1 + len))
+    }
+  })
+}
+
+/// Recognizes a MAC address, as 6 pairs of hexadecimal digits separated
+/// by `:` or `-`. The same separator must be used in the whole address.
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::net::mac;
+///
+/// fn parser(i: &str) -> IResult<&str, [u8; 6]> {
+///   mac(i)
+/// }
+///
+/// assert_eq!(parser("00-1b-63-84-45-E6"), Ok(("", [0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6])));
+/// assert_eq!(parser("00:1b-63:84:45:e6"), Err(Err::Error(Error::new("00:1b-63:84:45:e6", ErrorKind::NetAddress))));
+/// ```
+pub fn mac<I, E>(input: I) -> IResult<I, [u8; 6], E>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  parse(input, |b| {
+    let separator = *b.get(2)?;
+    if separator != b':' && separator != b'-' {
+      return None;
+    }
+
+    let mut res = [0; 6];
+    for (i, byte) in res.iter_mut().enumerate() {
+      let pos = i * 3;
+      if i > 0 && b.get(pos - 1) != Some(&separator) {
+        return None;
+      }
+      let pair = b.get(pos..pos + 2)?;
+      if !pair.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+      }
+      *byte = h16(pair)?.0 as u8;
+    }
+    Some((res, 17))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  #[test]
+  fn ipv6_matches_std() {
+    let cases = [
+      "::",
+      "::1",
+      "1::",
+      "1:2:3:4:5:6:7:8",
+      "1:2:3:4:5:6:7::",
+      "1::8",
+      "1:2:3:4:5:6:1.2.3.4",
+      "1:2:3:4:5::1.2.3.4",
+      "::1.2.3.4",
+      "1:2:3:4:5:6:7:1.2.3.4",
+      "1:2:3:4:5:6:7:8:9",
+      "1:2:3:4:5:6:7::8",
+      "1:::2",
+      ":1::2",
+      "1:2",
+      "12345::",
+      "1::2::3",
+      "::ffff:1.2.3",
+      "::01.2.3.4",
+      "1:",
+    ];
+
+    for case in cases.iter() {
+      let expected = case.parse::<Ipv6Addr>().ok();
+      let res = ipv6::<_, Error<&str>>(*case)
+        .ok()
+        .filter(|(rest, _)| rest.is_empty())
+        .map(|(_, addr)| addr);
+      assert_eq!(res, expected, "{}", case);
+    }
+  }
+
+  #[test]
+  fn net_bytes() {
+    assert_eq!(
+      ip::<_, Error<&[u8]>>(&b"1:2::1.2.3.4]"[..]),
+      Ok((
+        &b"]"[..],
+        IpAddr::V6(Ipv6Addr::new(1, 2, 0, 0, 0, 0, 0x0102, 0x0304))
+      ))
+    );
+    assert_eq!(
+      cidr::<_, Error<&[u8]>>(&b"::/129"[..]),
+      Err(Err::Error(Error::new(
+        &b"::/129"[..],
+        ErrorKind::NetAddress
+      )))
+    );
+    assert_eq!(
+      mac::<_, Error<&[u8]>>(&b"00:11:22:33:44:5"[..]),
+      Err(Err::Error(Error::new(
+        &b"00:11:22:33:44:5"[..],
+        ErrorKind::NetAddress
+      )))
+    );
+  }
+}
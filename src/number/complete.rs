@@ -10,6 +10,11 @@ use crate::lib::std::ops::{RangeFrom, RangeTo};
 use crate::sequence::{pair, tuple};
 use crate::traits::{AsChar, InputIter, InputLength, InputTakeAtPosition};
 use crate::traits::{Offset, Slice};
+#[cfg(feature = "alloc")]
+use crate::{
+  lib::std::vec::Vec,
+  traits::{AsBytes, InputTake},
+};
 
 /// Recognizes an unsigned 1 byte integer.
 ///
@@ -1539,6 +1544,186 @@ where
   }
 }
 
+/// Recognizes `count` big endian numbers of the same type, like
+/// [count](crate::multi::count) with [be_u16], but converting them in bulk.
+///
+/// *Complete version*: Returns an error if there is not enough input data.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// use nom::number::complete::be_vec;
+///
+/// let parser = |s| {
+///   be_vec::<u16, _, (_, ErrorKind)>(2)(s)
+/// };
+///
+/// assert_eq!(parser(&b"\x00\x01\x00\x02abc"[..]), Ok((&b"abc"[..], vec![1, 2])));
+/// assert_eq!(parser(&b"\x00\x01\x00"[..]), Err(Err::Error((&b"\x00\x01\x00"[..], ErrorKind::Eof))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn be_vec<T, I, E: ParseError<I>>(count: usize) -> impl Fn(I) -> IResult<I, Vec<T>, E>
+where
+  T: super::BinaryNumber,
+  I: AsBytes + InputTake + InputLength,
+{
+  move |input: I| {
+    let len = match count.checked_mul(T::SIZE) {
+      Some(len) => len,
+      None => return Err(Err::Error(make_error(input, ErrorKind::TooLarge))),
+    };
+    if input.input_len() < len {
+      return Err(Err::Error(make_error(input, ErrorKind::Eof)));
+    }
+    let (i, o) = input.take_split(len);
+    let res = super::read_vec(&o.as_bytes()[..len], true);
+    Ok((i, res))
+  }
+}
+
+/// Recognizes `count` little endian numbers of the same type, like
+/// [count](crate::multi::count) with [le_f32], but converting them in bulk.
+///
+/// *Complete version*: Returns an error if there is not enough input data.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// use nom::number::complete::le_vec;
+///
+/// let parser = |s| {
+///   le_vec::<f32, _, (_, ErrorKind)>(2)(s)
+/// };
+///
+/// assert_eq!(parser(&b"\x00\x00\x80\x3f\x00\x00\x00\x40abc"[..]), Ok((&b"abc"[..], vec![1.0, 2.0])));
+/// assert_eq!(parser(&b"\x00\x00\x80\x3f\x00"[..]), Err(Err::Error((&b"\x00\x00\x80\x3f\x00"[..], ErrorKind::Eof))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn le_vec<T, I, E: ParseError<I>>(count: usize) -> impl Fn(I) -> IResult<I, Vec<T>, E>
+where
+  T: super::BinaryNumber,
+  I: AsBytes + InputTake + InputLength,
+{
+  move |input: I| {
+    let len = match count.checked_mul(T::SIZE) {
+      Some(len) => len,
+      None => return Err(Err::Error(make_error(input, ErrorKind::TooLarge))),
+    };
+    if input.input_len() < len {
+      return Err(Err::Error(make_error(input, ErrorKind::Eof)));
+    }
+    let (i, o) = input.take_split(len);
+    let res = super::read_vec(&o.as_bytes()[..len], false);
+    Ok((i, res))
+  }
+}
+
+/// Recognizes a floating point number written with a custom decimal separator,
+/// and returns a f64.
+///
+/// If `grouping` is set, that separator is accepted between the digits of the
+/// integer part, like the thousands separator in `1.234,5`. It must differ
+/// from `decimal`.
+///
+/// *Complete version*: Can parse until the end of input.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// use nom::number::complete::double_localized;
+///
+/// let parser = |s| {
+///   double_localized::<_, (&str, ErrorKind)>(',', Some('.'))(s)
+/// };
+///
+/// assert_eq!(parser("1.234,5"), Ok(("", 1234.5)));
+/// assert_eq!(parser("-0,25e2"), Ok(("", -25.0)));
+/// assert_eq!(parser("12.34.5;"), Ok((";", 12345.0)));
+/// assert_eq!(parser("1.;"), Ok((".;", 1.0)));
+/// assert_eq!(parser("abc"), Err(Err::Error(("abc", ErrorKind::Float))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn double_localized<T, E: ParseError<T>>(
+  decimal: char,
+  grouping: Option<char>,
+) -> impl Fn(T) -> IResult<T, f64, E>
+where
+  T: Slice<RangeFrom<usize>> + InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  move |input: T| {
+    let float = super::scan_localized_float(&input, decimal, grouping);
+    if float.len == 0 {
+      return Err(Err::Error(E::from_error_kind(input, ErrorKind::Float)));
+    }
+    match float.normalized.parse() {
+      Ok(n) => Ok((input.slice(float.len..), n)),
+      Err(_) => Err(Err::Error(E::from_error_kind(input, ErrorKind::Float))),
+    }
+  }
+}
+
+/// Recognizes a hexadecimal floating point literal and returns a f32.
+///
+/// The literal is written like in C99, `0x` or `0X`, hexadecimal digits with an
+/// optional `.`, and a mandatory binary exponent introduced by `p` or `P`, with
+/// an optional sign. The result is rounded to the nearest f32.
+///
+/// *Complete version*: Can parse until the end of input.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// use nom::number::complete::hex_float;
+///
+/// let parser = |s| {
+///   hex_float::<_, (&str, ErrorKind)>(s)
+/// };
+///
+/// assert_eq!(parser("0x1.8p3"), Ok(("", 12.0)));
+/// assert_eq!(parser("-0X.4p-2;"), Ok((";", -0.0625)));
+/// assert_eq!(parser("0x1.8"), Err(Err::Error(("0x1.8", ErrorKind::Float))));
+/// ```
+pub fn hex_float<T, E: ParseError<T>>(input: T) -> IResult<T, f32, E>
+where
+  T: Slice<RangeFrom<usize>> + InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  let float = super::scan_hex_float(&input);
+  if float.len == 0 {
+    return Err(Err::Error(E::from_error_kind(input, ErrorKind::Float)));
+  }
+  let value = f32::from_bits(float.to_bits(23, 8) as u32);
+  Ok((input.slice(float.len..), value))
+}
+
+/// Recognizes a hexadecimal floating point literal and returns a f64.
+///
+/// The literal is written like in C99, `0x` or `0X`, hexadecimal digits with an
+/// optional `.`, and a mandatory binary exponent introduced by `p` or `P`, with
+/// an optional sign. The result is rounded to the nearest f64.
+///
+/// *Complete version*: Can parse until the end of input.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// use nom::number::complete::hex_double;
+///
+/// let parser = |s| {
+///   hex_double::<_, (&str, ErrorKind)>(s)
+/// };
+///
+/// assert_eq!(parser("0x1.8p3"), Ok(("", 12.0)));
+/// assert_eq!(parser("-0X.4p-2;"), Ok((";", -0.0625)));
+/// assert_eq!(parser("0x1.8"), Err(Err::Error(("0x1.8", ErrorKind::Float))));
+/// ```
+pub fn hex_double<T, E: ParseError<T>>(input: T) -> IResult<T, f64, E>
+where
+  T: Slice<RangeFrom<usize>> + InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  let float = super::scan_hex_float(&input);
+  if float.len == 0 {
+    return Err(Err::Error(E::from_error_kind(input, ErrorKind::Float)));
+  }
+  let value = f64::from_bits(float.to_bits(52, 11));
+  Ok((input.slice(float.len..), value))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -1900,4 +2085,123 @@ mod tests {
       Err(Err::Failure(("", ErrorKind::Digit)))
     );
   }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn double_localized_test() {
+    let french = double_localized(',', Some(' '));
+    assert_parse!(french(&b"1 234 567,89"[..]), Ok((&b""[..], 1_234_567.89)));
+    assert_parse!(french(&b",5e-1 "[..]), Ok((&b" "[..], 0.05)));
+    assert_parse!(french(&b"12 ,5"[..]), Ok((&b" ,5"[..], 12.0)));
+    assert_parse!(french(&b"3e"[..]), Ok((&b"e"[..], 3.0)));
+    assert_parse!(
+      french(&b"-,"[..]),
+      Err(Err::Error((&b"-,"[..], ErrorKind::Float)))
+    );
+
+    let swiss = double_localized('.', Some('\''));
+    assert_parse!(swiss("1'000.5"), Ok(("", 1000.5)));
+    assert_parse!(swiss("'1"), Err(Err::Error(("'1", ErrorKind::Float))));
+  }
+
+  #[test]
+  fn hex_float_test() {
+    assert_parse!(hex_double("0x1p0"), Ok(("", 1.0)));
+    assert_parse!(hex_double("0x.8P+1"), Ok(("", 1.0)));
+    assert_parse!(
+      hex_double("0x1.fffffffffffffp1023"),
+      Ok(("", core::f64::MAX))
+    );
+    assert_parse!(hex_double("0x1p1024"), Ok(("", core::f64::INFINITY)));
+    assert_parse!(
+      hex_double("0x1.fffffffffffff8p1023"),
+      Ok(("", core::f64::INFINITY))
+    );
+    assert_parse!(hex_double("0x1p-1074"), Ok(("", f64::from_bits(1))));
+    assert_parse!(hex_double("0x1p-1075"), Ok(("", 0.0)));
+    assert_parse!(hex_double("0x1.0000001p-1075"), Ok(("", f64::from_bits(1))));
+    assert_parse!(
+      hex_double("0x1.ffffffffffffep-1023"),
+      Ok(("", f64::from_bits(0x000f_ffff_ffff_ffff)))
+    );
+    assert_parse!(
+      hex_double("0x1.fffffffffffffp-1023"),
+      Ok(("", core::f64::MIN_POSITIVE))
+    );
+    assert_parse!(hex_double("0x1p-1022"), Ok(("", core::f64::MIN_POSITIVE)));
+    // ties are rounded to even
+    assert_parse!(hex_double("0x1.00000000000008p0"), Ok(("", 1.0)));
+    assert_parse!(
+      hex_double("0x1.00000000000018p0"),
+      Ok(("", 1.0 + 2.0 * core::f64::EPSILON))
+    );
+    assert_parse!(
+      hex_double("0x1.000000000000080000000000001p0"),
+      Ok(("", 1.0 + core::f64::EPSILON))
+    );
+    assert_parse!(hex_double("0x10000000000000000000p-76"), Ok(("", 1.0)));
+    assert_parse!(hex_double("0x00000000000000000000.01p8"), Ok(("", 1.0)));
+    assert_parse!(hex_double("0x0p99999999999"), Ok(("", 0.0)));
+    assert_parse!(hex_double("-0x0p0"), Ok(("", -0.0)));
+    assert_parse!(
+      hex_double("0xp1"),
+      Err(Err::Error(("0xp1", ErrorKind::Float)))
+    );
+    assert_parse!(
+      hex_double("0x1p"),
+      Err(Err::Error(("0x1p", ErrorKind::Float)))
+    );
+    assert_parse!(
+      hex_double("1p1"),
+      Err(Err::Error(("1p1", ErrorKind::Float)))
+    );
+
+    assert_parse!(
+      hex_float(&b"0x1.fffffep127"[..]),
+      Ok((&b""[..], core::f32::MAX))
+    );
+    assert_parse!(
+      hex_float(&b"0x1p128"[..]),
+      Ok((&b""[..], core::f32::INFINITY))
+    );
+    assert_parse!(hex_float(&b"0x1.000001p0"[..]), Ok((&b""[..], 1.0)));
+    assert_parse!(
+      hex_float(&b"0x1.000003p0"[..]),
+      Ok((&b""[..], 1.0 + 2.0 * core::f32::EPSILON))
+    );
+    assert_parse!(
+      hex_float(&b"0x1p-149"[..]),
+      Ok((&b""[..], f32::from_bits(1)))
+    );
+    assert_parse!(
+      hex_float(&b"-0x1.8p-149"[..]),
+      Ok((&b""[..], -f32::from_bits(2)))
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn bulk_vec_test() {
+    use crate::multi::count;
+
+    let input: Vec<u8> = (0..=255).chain(0..=255).collect();
+    // compare the bits, the input contains NaNs
+    let (i, v): (&[u8], Vec<f32>) = le_vec::<_, _, (_, ErrorKind)>(128)(&input[..]).unwrap();
+    let (j, w) = count(le_f32::<_, (_, ErrorKind)>, 128)(&input[..]).unwrap();
+    assert_eq!(i, j);
+    assert!(v
+      .iter()
+      .zip(w.iter())
+      .all(|(a, b)| a.to_bits() == b.to_bits()));
+    let res: IResult<&[u8], Vec<i64>> = be_vec(64)(&input[..]);
+    assert_eq!(res, count(be_i64, 64)(&input[..]));
+    let res: IResult<&[u8], Vec<u8>> = le_vec(3)(&input[..]);
+    assert_eq!(res, Ok((&input[3..], vec![0, 1, 2])));
+
+    assert_parse!(le_vec::<u16, _, _>(0)(&b"ab"[..]), Ok((&b"ab"[..], vec![])));
+    assert_parse!(
+      le_vec::<u64, _, _>(usize::MAX / 4)(&b"ab"[..]),
+      Err(Err::Error((&b"ab"[..], ErrorKind::TooLarge)))
+    );
+  }
 }
@@ -6,6 +6,17 @@ mod macros;
 pub mod complete;
 pub mod streaming;
 
+use crate::error::ParseError;
+use crate::internal::IResult;
+use crate::lib::std::ops::RangeFrom;
+#[cfg(feature = "alloc")]
+use crate::lib::std::string::String;
+#[cfg(feature = "alloc")]
+use crate::lib::std::vec::Vec;
+#[cfg(feature = "alloc")]
+use crate::traits::{AsBytes, InputTake};
+use crate::traits::{AsChar, InputIter, InputLength, InputMode, Slice};
+
 /// Configurable endianness
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Endianness {
@@ -16,3 +27,1099 @@ pub enum Endianness {
   /// Will match the host's endianness
   Native,
 }
+
+dispatch_mode! {
+  /// Recognizes an unsigned 1 byte integer.
+  ///
+  /// Uses [streaming::be_u8] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_u8] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_u8;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_u8::<_, Error<_>>(&b"\x00abc"[..]), Ok((&b"abc"[..], 0x0)));
+  /// assert_eq!(be_u8::<_, Error<_>>(&b""[..]), Err(Err::Error(Error::new(&b""[..], ErrorKind::Eof))));
+  /// assert_eq!(be_u8::<_, Error<_>>(Partial::new(&b""[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_u8<I, E: ParseError<I>>(input: I) -> IResult<I, u8, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian unsigned 2 bytes integer.
+  ///
+  /// Uses [streaming::be_u16] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_u16] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_u16;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_u16::<_, Error<_>>(&b"\x00\x03abcefg"[..]), Ok((&b"abcefg"[..], 0x0003)));
+  /// assert_eq!(
+  ///   be_u16::<_, Error<_>>(&b"\x01"[..]),
+  ///   Err(Err::Error(Error::new(&[0x01][..], ErrorKind::Eof)))
+  /// );
+  /// assert_eq!(be_u16::<_, Error<_>>(Partial::new(&b"\x01"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_u16<I, E: ParseError<I>>(input: I) -> IResult<I, u16, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian unsigned 3 byte integer.
+  ///
+  /// Uses [streaming::be_u24] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_u24] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_u24;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_u24::<_, Error<_>>(&b"\x00\x01\x02abc"[..]), Ok((&b"abc"[..], 0x102)));
+  /// assert_eq!(be_u24::<_, Error<_>>(&b"\x00\x01"[..]), Err(Err::Error(Error::new(&b"\x00\x01"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_u24::<_, Error<_>>(Partial::new(&b"\x00\x01"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_u24<I, E: ParseError<I>>(input: I) -> IResult<I, u32, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian unsigned 4 bytes integer.
+  ///
+  /// Uses [streaming::be_u32] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_u32] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_u32;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_u32::<_, Error<_>>(&b"\x00\x01\x02\x03abc"[..]), Ok((&b"abc"[..], 0x10203)));
+  /// assert_eq!(be_u32::<_, Error<_>>(&b"\x00\x01\x02"[..]), Err(Err::Error(Error::new(&b"\x00\x01\x02"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_u32::<_, Error<_>>(Partial::new(&b"\x00\x01\x02"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_u32<I, E: ParseError<I>>(input: I) -> IResult<I, u32, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian unsigned 8 bytes integer.
+  ///
+  /// Uses [streaming::be_u64] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_u64] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_u64;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_u64::<_, Error<_>>(&b"\x00\x01\x02\x03\x04\x05\x06\x07abc"[..]), Ok((&b"abc"[..], 0x1020304050607)));
+  /// assert_eq!(be_u64::<_, Error<_>>(&b"\x00\x01\x02\x03\x04\x05\x06"[..]), Err(Err::Error(Error::new(&b"\x00\x01\x02\x03\x04\x05\x06"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_u64::<_, Error<_>>(Partial::new(&b"\x00\x01\x02\x03\x04\x05\x06"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_u64<I, E: ParseError<I>>(input: I) -> IResult<I, u64, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian unsigned 16 bytes integer.
+  ///
+  /// Uses [streaming::be_u128] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_u128] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_u128;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_u128::<_, Error<_>>(&b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0fabc"[..]), Ok((&b"abc"[..], 0x102030405060708090a0b0c0d0e0f)));
+  /// assert_eq!(be_u128::<_, Error<_>>(&b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..]), Err(Err::Error(Error::new(&b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_u128::<_, Error<_>>(Partial::new(&b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_u128<I, E: ParseError<I>>(input: I) -> IResult<I, u128, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a signed 1 byte integer.
+  ///
+  /// Uses [streaming::be_i8] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_i8] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_i8;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_i8::<_, Error<_>>(&b"\xffabc"[..]), Ok((&b"abc"[..], -0x1)));
+  /// assert_eq!(be_i8::<_, Error<_>>(&b""[..]), Err(Err::Error(Error::new(&b""[..], ErrorKind::Eof))));
+  /// assert_eq!(be_i8::<_, Error<_>>(Partial::new(&b""[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_i8<I, E: ParseError<I>>(input: I) -> IResult<I, i8, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian signed 2 bytes integer.
+  ///
+  /// Uses [streaming::be_i16] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_i16] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_i16;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_i16::<_, Error<_>>(&b"\xff\x01abc"[..]), Ok((&b"abc"[..], -0xff)));
+  /// assert_eq!(be_i16::<_, Error<_>>(&b"\xff"[..]), Err(Err::Error(Error::new(&b"\xff"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_i16::<_, Error<_>>(Partial::new(&b"\xff"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_i16<I, E: ParseError<I>>(input: I) -> IResult<I, i16, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian signed 3 bytes integer.
+  ///
+  /// Uses [streaming::be_i24] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_i24] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_i24;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_i24::<_, Error<_>>(&b"\xff\x01\x02abc"[..]), Ok((&b"abc"[..], -0xfefe)));
+  /// assert_eq!(be_i24::<_, Error<_>>(&b"\xff\x01"[..]), Err(Err::Error(Error::new(&b"\xff\x01"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_i24::<_, Error<_>>(Partial::new(&b"\xff\x01"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_i24<I, E: ParseError<I>>(input: I) -> IResult<I, i32, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian signed 4 bytes integer.
+  ///
+  /// Uses [streaming::be_i32] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_i32] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_i32;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_i32::<_, Error<_>>(&b"\xff\x01\x02\x03abc"[..]), Ok((&b"abc"[..], -0xfefdfd)));
+  /// assert_eq!(be_i32::<_, Error<_>>(&b"\xff\x01\x02"[..]), Err(Err::Error(Error::new(&b"\xff\x01\x02"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_i32::<_, Error<_>>(Partial::new(&b"\xff\x01\x02"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_i32<I, E: ParseError<I>>(input: I) -> IResult<I, i32, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian signed 8 bytes integer.
+  ///
+  /// Uses [streaming::be_i64] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_i64] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_i64;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_i64::<_, Error<_>>(&b"\xff\x01\x02\x03\x04\x05\x06\x07abc"[..]), Ok((&b"abc"[..], -0xfefdfcfbfaf9f9)));
+  /// assert_eq!(be_i64::<_, Error<_>>(&b"\xff\x01\x02\x03\x04\x05\x06"[..]), Err(Err::Error(Error::new(&b"\xff\x01\x02\x03\x04\x05\x06"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_i64::<_, Error<_>>(Partial::new(&b"\xff\x01\x02\x03\x04\x05\x06"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_i64<I, E: ParseError<I>>(input: I) -> IResult<I, i64, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian signed 16 bytes integer.
+  ///
+  /// Uses [streaming::be_i128] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_i128] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_i128;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_i128::<_, Error<_>>(&b"\xff\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0fabc"[..]), Ok((&b"abc"[..], -0xfefdfcfbfaf9f8f7f6f5f4f3f2f1f1)));
+  /// assert_eq!(be_i128::<_, Error<_>>(&b"\xff\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..]), Err(Err::Error(Error::new(&b"\xff\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_i128::<_, Error<_>>(Partial::new(&b"\xff\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_i128<I, E: ParseError<I>>(input: I) -> IResult<I, i128, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian 4 bytes floating point number.
+  ///
+  /// Uses [streaming::be_f32] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_f32] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_f32;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_f32::<_, Error<_>>(&b"\x41\x48\x00\x00abc"[..]), Ok((&b"abc"[..], 12.5)));
+  /// assert_eq!(be_f32::<_, Error<_>>(&b"\x41\x48\x00"[..]), Err(Err::Error(Error::new(&b"\x41\x48\x00"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_f32::<_, Error<_>>(Partial::new(&b"\x41\x48\x00"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_f32<I, E: ParseError<I>>(input: I) -> IResult<I, f32, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a big endian 8 bytes floating point number.
+  ///
+  /// Uses [streaming::be_f64] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_f64] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::be_f64;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(be_f64::<_, Error<_>>(&b"\x40\x29\x00\x00\x00\x00\x00\x00abc"[..]), Ok((&b"abc"[..], 12.5)));
+  /// assert_eq!(be_f64::<_, Error<_>>(&b"\x40\x29\x00\x00\x00\x00\x00"[..]), Err(Err::Error(Error::new(&b"\x40\x29\x00\x00\x00\x00\x00"[..], ErrorKind::Eof))));
+  /// assert_eq!(be_f64::<_, Error<_>>(Partial::new(&b"\x40\x29\x00\x00\x00\x00\x00"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn be_f64<I, E: ParseError<I>>(input: I) -> IResult<I, f64, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes an unsigned 1 byte integer.
+  ///
+  /// Uses [streaming::le_u8] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_u8] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_u8;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_u8::<_, Error<_>>(&b"\x00abc"[..]), Ok((&b"abc"[..], 0x0)));
+  /// assert_eq!(le_u8::<_, Error<_>>(&b""[..]), Err(Err::Error(Error::new(&b""[..], ErrorKind::Eof))));
+  /// assert_eq!(le_u8::<_, Error<_>>(Partial::new(&b""[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_u8<I, E: ParseError<I>>(input: I) -> IResult<I, u8, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian unsigned 2 bytes integer.
+  ///
+  /// Uses [streaming::le_u16] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_u16] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_u16;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_u16::<_, Error<_>>(&b"\x00\x01abc"[..]), Ok((&b"abc"[..], 0x100)));
+  /// assert_eq!(le_u16::<_, Error<_>>(&b"\x00"[..]), Err(Err::Error(Error::new(&b"\x00"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_u16::<_, Error<_>>(Partial::new(&b"\x00"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_u16<I, E: ParseError<I>>(input: I) -> IResult<I, u16, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian unsigned 3 byte integer.
+  ///
+  /// Uses [streaming::le_u24] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_u24] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_u24;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_u24::<_, Error<_>>(&b"\x00\x01\x02abc"[..]), Ok((&b"abc"[..], 0x20100)));
+  /// assert_eq!(le_u24::<_, Error<_>>(&b"\x00\x01"[..]), Err(Err::Error(Error::new(&b"\x00\x01"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_u24::<_, Error<_>>(Partial::new(&b"\x00\x01"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_u24<I, E: ParseError<I>>(input: I) -> IResult<I, u32, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian unsigned 4 bytes integer.
+  ///
+  /// Uses [streaming::le_u32] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_u32] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_u32;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_u32::<_, Error<_>>(&b"\x00\x01\x02\x03abc"[..]), Ok((&b"abc"[..], 0x3020100)));
+  /// assert_eq!(le_u32::<_, Error<_>>(&b"\x00\x01\x02"[..]), Err(Err::Error(Error::new(&b"\x00\x01\x02"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_u32::<_, Error<_>>(Partial::new(&b"\x00\x01\x02"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_u32<I, E: ParseError<I>>(input: I) -> IResult<I, u32, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian unsigned 8 bytes integer.
+  ///
+  /// Uses [streaming::le_u64] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_u64] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_u64;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_u64::<_, Error<_>>(&b"\x00\x01\x02\x03\x04\x05\x06\x07abc"[..]), Ok((&b"abc"[..], 0x706050403020100)));
+  /// assert_eq!(le_u64::<_, Error<_>>(&b"\x00\x01\x02\x03\x04\x05\x06"[..]), Err(Err::Error(Error::new(&b"\x00\x01\x02\x03\x04\x05\x06"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_u64::<_, Error<_>>(Partial::new(&b"\x00\x01\x02\x03\x04\x05\x06"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_u64<I, E: ParseError<I>>(input: I) -> IResult<I, u64, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian unsigned 16 bytes integer.
+  ///
+  /// Uses [streaming::le_u128] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_u128] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_u128;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_u128::<_, Error<_>>(&b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0fabc"[..]), Ok((&b"abc"[..], 0xf0e0d0c0b0a09080706050403020100)));
+  /// assert_eq!(le_u128::<_, Error<_>>(&b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..]), Err(Err::Error(Error::new(&b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_u128::<_, Error<_>>(Partial::new(&b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_u128<I, E: ParseError<I>>(input: I) -> IResult<I, u128, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a signed 1 byte integer.
+  ///
+  /// Uses [streaming::le_i8] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_i8] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_i8;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_i8::<_, Error<_>>(&b"\xffabc"[..]), Ok((&b"abc"[..], -0x1)));
+  /// assert_eq!(le_i8::<_, Error<_>>(&b""[..]), Err(Err::Error(Error::new(&b""[..], ErrorKind::Eof))));
+  /// assert_eq!(le_i8::<_, Error<_>>(Partial::new(&b""[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_i8<I, E: ParseError<I>>(input: I) -> IResult<I, i8, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian signed 2 bytes integer.
+  ///
+  /// Uses [streaming::le_i16] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_i16] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_i16;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_i16::<_, Error<_>>(&b"\xff\x01abc"[..]), Ok((&b"abc"[..], 0x1ff)));
+  /// assert_eq!(le_i16::<_, Error<_>>(&b"\xff"[..]), Err(Err::Error(Error::new(&b"\xff"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_i16::<_, Error<_>>(Partial::new(&b"\xff"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_i16<I, E: ParseError<I>>(input: I) -> IResult<I, i16, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian signed 3 bytes integer.
+  ///
+  /// Uses [streaming::le_i24] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_i24] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_i24;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_i24::<_, Error<_>>(&b"\xff\x01\x02abc"[..]), Ok((&b"abc"[..], 0x201ff)));
+  /// assert_eq!(le_i24::<_, Error<_>>(&b"\xff\x01"[..]), Err(Err::Error(Error::new(&b"\xff\x01"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_i24::<_, Error<_>>(Partial::new(&b"\xff\x01"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_i24<I, E: ParseError<I>>(input: I) -> IResult<I, i32, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian signed 4 bytes integer.
+  ///
+  /// Uses [streaming::le_i32] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_i32] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_i32;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_i32::<_, Error<_>>(&b"\xff\x01\x02\x03abc"[..]), Ok((&b"abc"[..], 0x30201ff)));
+  /// assert_eq!(le_i32::<_, Error<_>>(&b"\xff\x01\x02"[..]), Err(Err::Error(Error::new(&b"\xff\x01\x02"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_i32::<_, Error<_>>(Partial::new(&b"\xff\x01\x02"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_i32<I, E: ParseError<I>>(input: I) -> IResult<I, i32, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian signed 8 bytes integer.
+  ///
+  /// Uses [streaming::le_i64] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_i64] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_i64;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_i64::<_, Error<_>>(&b"\xff\x01\x02\x03\x04\x05\x06\x07abc"[..]), Ok((&b"abc"[..], 0x7060504030201ff)));
+  /// assert_eq!(le_i64::<_, Error<_>>(&b"\xff\x01\x02\x03\x04\x05\x06"[..]), Err(Err::Error(Error::new(&b"\xff\x01\x02\x03\x04\x05\x06"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_i64::<_, Error<_>>(Partial::new(&b"\xff\x01\x02\x03\x04\x05\x06"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_i64<I, E: ParseError<I>>(input: I) -> IResult<I, i64, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian signed 16 bytes integer.
+  ///
+  /// Uses [streaming::le_i128] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_i128] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_i128;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_i128::<_, Error<_>>(&b"\xff\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0fabc"[..]), Ok((&b"abc"[..], 0xf0e0d0c0b0a090807060504030201ff)));
+  /// assert_eq!(le_i128::<_, Error<_>>(&b"\xff\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..]), Err(Err::Error(Error::new(&b"\xff\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_i128::<_, Error<_>>(Partial::new(&b"\xff\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_i128<I, E: ParseError<I>>(input: I) -> IResult<I, i128, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian 4 bytes floating point number.
+  ///
+  /// Uses [streaming::le_f32] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_f32] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_f32;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_f32::<_, Error<_>>(&b"\x00\x00\x48\x41abc"[..]), Ok((&b"abc"[..], 12.5)));
+  /// assert_eq!(le_f32::<_, Error<_>>(&b"\x00\x00\x48"[..]), Err(Err::Error(Error::new(&b"\x00\x00\x48"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_f32::<_, Error<_>>(Partial::new(&b"\x00\x00\x48"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_f32<I, E: ParseError<I>>(input: I) -> IResult<I, f32, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a little endian 8 bytes floating point number.
+  ///
+  /// Uses [streaming::le_f64] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_f64] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_f64;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(le_f64::<_, Error<_>>(&b"\x00\x00\x00\x00\x00\x00\x29\x40abc"[..]), Ok((&b"abc"[..], 12.5)));
+  /// assert_eq!(le_f64::<_, Error<_>>(&b"\x00\x00\x00\x00\x00\x00\x29"[..]), Err(Err::Error(Error::new(&b"\x00\x00\x00\x00\x00\x00\x29"[..], ErrorKind::Eof))));
+  /// assert_eq!(le_f64::<_, Error<_>>(Partial::new(&b"\x00\x00\x00\x00\x00\x00\x29"[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn le_f64<I, E: ParseError<I>>(input: I) -> IResult<I, f64, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes an unsigned 1 byte integer
+  ///
+  /// Uses [streaming::u8] if the input is [partial](crate::partial::Partial),
+  /// and [complete::u8] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::u8;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(u8::<_, Error<_>>(&b"\x00abc"[..]), Ok((&b"abc"[..], 0x0)));
+  /// assert_eq!(u8::<_, Error<_>>(&b""[..]), Err(Err::Error(Error::new(&b""[..], ErrorKind::Eof))));
+  /// assert_eq!(u8::<_, Error<_>>(Partial::new(&b""[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn u8<I, E: ParseError<I>>(input: I) -> IResult<I, u8, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a signed 1 byte integer
+  ///
+  /// Uses [streaming::i8] if the input is [partial](crate::partial::Partial),
+  /// and [complete::i8] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::i8;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(i8::<_, Error<_>>(&b"\xffabc"[..]), Ok((&b"abc"[..], -0x1)));
+  /// assert_eq!(i8::<_, Error<_>>(&b""[..]), Err(Err::Error(Error::new(&b""[..], ErrorKind::Eof))));
+  /// assert_eq!(i8::<_, Error<_>>(Partial::new(&b""[..])), Err(Err::Incomplete(Needed::new(1))));
+  /// ```
+  pub fn i8<I, E: ParseError<I>>(input: I) -> IResult<I, i8, E>
+  where
+    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength + InputMode,
+}
+
+/// Numbers with a fixed size binary representation, read in bulk by
+/// [be_vec] and [le_vec]
+pub trait BinaryNumber: Sized {
+  /// Size of the number in bytes
+  const SIZE: usize;
+
+  /// Reads a big endian number from exactly `SIZE` bytes
+  fn from_be_slice(bytes: &[u8]) -> Self;
+
+  /// Reads a little endian number from exactly `SIZE` bytes
+  fn from_le_slice(bytes: &[u8]) -> Self;
+}
+
+macro_rules! binary_number_impls {
+  ($($t:ty => $size:expr),*) => {
+    $(
+      impl BinaryNumber for $t {
+        const SIZE: usize = $size;
+
+        #[inline]
+        fn from_be_slice(bytes: &[u8]) -> Self {
+          let mut b = [0u8; $size];
+          b.copy_from_slice(bytes);
+          <$t>::from_be_bytes(b)
+        }
+
+        #[inline]
+        fn from_le_slice(bytes: &[u8]) -> Self {
+          let mut b = [0u8; $size];
+          b.copy_from_slice(bytes);
+          <$t>::from_le_bytes(b)
+        }
+      }
+    )*
+  };
+}
+
+binary_number_impls! {
+  u8 => 1, u16 => 2, u32 => 4, u64 => 8, u128 => 16,
+  i8 => 1, i16 => 2, i32 => 4, i64 => 8, i128 => 16,
+  f32 => 4, f64 => 8
+}
+
+/// Converts `bytes`, of a length multiple of `T::SIZE`, to numbers
+///
+/// The conversion is a plain copy when the endianness matches the target,
+/// and a byte swap otherwise, that the compiler vectorizes.
+#[cfg(feature = "alloc")]
+fn read_vec<T: BinaryNumber>(bytes: &[u8], big_endian: bool) -> Vec<T> {
+  let chunks = bytes.chunks_exact(T::SIZE);
+  if big_endian {
+    chunks.map(T::from_be_slice).collect()
+  } else {
+    chunks.map(T::from_le_slice).collect()
+  }
+}
+
+dispatch_mode! {
+  /// Recognizes `count` big endian numbers of the same type.
+  ///
+  /// Uses [streaming::be_vec] if the input is [partial](crate::partial::Partial),
+  /// and [complete::be_vec] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::Error, Needed};
+  /// use nom::number::be_vec;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(
+  ///   be_vec::<u16, _, Error<_>>(2)(&b"\x00\x01\x00\x02abc"[..]),
+  ///   Ok((&b"abc"[..], vec![1, 2]))
+  /// );
+  /// assert_eq!(
+  ///   be_vec::<u16, _, Error<_>>(2)(Partial::new(&b"\x00\x01\x00"[..])),
+  ///   Err(Err::Incomplete(Needed::new(1)))
+  /// );
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+  pub fn be_vec<T, I, E: ParseError<I>>(count: usize) -> impl Fn(I) -> IResult<I, Vec<T>, E>
+  where
+    T: BinaryNumber,
+    I: AsBytes + InputTake + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes `count` little endian numbers of the same type.
+  ///
+  /// Uses [streaming::le_vec] if the input is [partial](crate::partial::Partial),
+  /// and [complete::le_vec] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::le_vec;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(
+  ///   le_vec::<u16, _, Error<_>>(2)(&b"\x01\x00\x02\x00abc"[..]),
+  ///   Ok((&b"abc"[..], vec![1, 2]))
+  /// );
+  /// assert_eq!(
+  ///   le_vec::<u16, _, Error<_>>(2)(Partial::new(&b"\x01\x00\x02"[..])),
+  ///   Err(Err::Incomplete(Needed::new(1)))
+  /// );
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+  pub fn le_vec<T, I, E: ParseError<I>>(count: usize) -> impl Fn(I) -> IResult<I, Vec<T>, E>
+  where
+    T: BinaryNumber,
+    I: AsBytes + InputTake + InputLength + InputMode,
+}
+
+dispatch_mode! {
+  /// Recognizes a floating point number written with a custom decimal separator,
+  /// and optional grouping separator between the digits of the integer part.
+  ///
+  /// Uses [streaming::double_localized] if the input is [partial](crate::partial::Partial),
+  /// and [complete::double_localized] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::Error, Needed};
+  /// use nom::number::double_localized;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(double_localized::<_, Error<_>>(',', None)("3,14"), Ok(("", 3.14)));
+  /// assert_eq!(
+  ///   double_localized::<_, Error<_>>(',', None)(Partial::new("3,14")),
+  ///   Err(Err::Incomplete(Needed::new(1)))
+  /// );
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+  pub fn double_localized<T, E: ParseError<T>>(
+    decimal: char,
+    grouping: Option<char>,
+  ) -> impl Fn(T) -> IResult<T, f64, E>
+  where
+    T: Slice<RangeFrom<usize>> + InputIter + InputLength + InputMode,
+    <T as InputIter>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes a hexadecimal floating point literal, like `0x1.8p3`, and returns a f32.
+  ///
+  /// Uses [streaming::hex_float] if the input is [partial](crate::partial::Partial),
+  /// and [complete::hex_float] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::{Error, ErrorKind}, Needed};
+  /// use nom::number::hex_float;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(hex_float::<_, Error<_>>("0x1.8p3"), Ok(("", 12.0)));
+  /// assert_eq!(
+  ///   hex_float::<_, Error<_>>(Partial::new("0x1.8p3")),
+  ///   Err(Err::Incomplete(Needed::new(1)))
+  /// );
+  /// ```
+  pub fn hex_float<T, E: ParseError<T>>(input: T) -> IResult<T, f32, E>
+  where
+    T: Slice<RangeFrom<usize>> + InputIter + InputLength + InputMode,
+    <T as InputIter>::Item: AsChar,
+}
+
+dispatch_mode! {
+  /// Recognizes a hexadecimal floating point literal, like `0x1.8p3`, and returns a f64.
+  ///
+  /// Uses [streaming::hex_double] if the input is [partial](crate::partial::Partial),
+  /// and [complete::hex_double] otherwise.
+  ///
+  /// ```rust
+  /// # use nom::{Err, error::Error, Needed};
+  /// use nom::number::hex_double;
+  /// use nom::partial::Partial;
+  ///
+  /// assert_eq!(hex_double::<_, Error<_>>("0x1.8p3"), Ok(("", 12.0)));
+  /// assert_eq!(
+  ///   hex_double::<_, Error<_>>(Partial::new("0x1.8p3")),
+  ///   Err(Err::Incomplete(Needed::new(1)))
+  /// );
+  /// ```
+  pub fn hex_double<T, E: ParseError<T>>(input: T) -> IResult<T, f64, E>
+  where
+    T: Slice<RangeFrom<usize>> + InputIter + InputLength + InputMode,
+    <T as InputIter>::Item: AsChar,
+}
+
+/// Result of scanning a hexadecimal float
+struct HexFloat {
+  /// length of the recognized number in the input, 0 if there is none
+  len: usize,
+  negative: bool,
+  /// the value is `mantissa * 2^exponent`, with `sticky` set if non zero
+  /// digits were dropped from the mantissa
+  mantissa: u64,
+  exponent: i64,
+  sticky: bool,
+  /// the scan looked past the end of the input
+  reached_end: bool,
+}
+
+fn scan_hex_float<T>(input: &T) -> HexFloat
+where
+  T: Slice<RangeFrom<usize>> + InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  let mut scanner = Scanner {
+    input,
+    reached_end: false,
+  };
+  let mut float = HexFloat {
+    len: 0,
+    negative: false,
+    mantissa: 0,
+    exponent: 0,
+    sticky: false,
+    reached_end: false,
+  };
+  let mut pos = 0;
+
+  if let Some((c @ '+', l)) | Some((c @ '-', l)) = scanner.peek(pos) {
+    float.negative = c == '-';
+    pos += l;
+  }
+
+  match scanner.peek(pos) {
+    Some(('0', l)) => match scanner.peek(pos + l) {
+      Some(('x', l2)) | Some(('X', l2)) => pos += l + l2,
+      _ => {
+        float.reached_end = scanner.reached_end;
+        return float;
+      }
+    },
+    _ => {
+      float.reached_end = scanner.reached_end;
+      return float;
+    }
+  }
+
+  let mut digits = 0;
+  let mut fraction = false;
+  while let Some((c, l)) = scanner.peek(pos) {
+    if c == '.' && !fraction {
+      fraction = true;
+    } else if let Some(d) = c.to_digit(16) {
+      // keep 60 to 64 significant bits, the rest only matters for rounding
+      if float.mantissa >> 60 == 0 {
+        float.mantissa = float.mantissa << 4 | u64::from(d);
+        if fraction {
+          float.exponent -= 4;
+        }
+      } else {
+        if !fraction {
+          float.exponent += 4;
+        }
+        float.sticky |= d != 0;
+      }
+      digits += 1;
+    } else {
+      break;
+    }
+    pos += l;
+  }
+
+  // the binary exponent is mandatory
+  match scanner.peek(pos) {
+    Some(('p', l)) | Some(('P', l)) if digits > 0 => pos += l,
+    _ => {
+      float.reached_end = scanner.reached_end;
+      return float;
+    }
+  }
+  let mut negative_exponent = false;
+  if let Some((c @ '+', l)) | Some((c @ '-', l)) = scanner.peek(pos) {
+    negative_exponent = c == '-';
+    pos += l;
+  }
+  let mut exponent: i64 = 0;
+  let mut exponent_digits = 0;
+  while let Some((c, l)) = scanner.peek(pos) {
+    match c.to_digit(10) {
+      // larger exponents overflow or underflow anyway
+      Some(d) => exponent = (exponent * 10 + i64::from(d)).min(1 << 20),
+      None => break,
+    }
+    pos += l;
+    exponent_digits += 1;
+  }
+
+  float.reached_end = scanner.reached_end;
+  if exponent_digits > 0 {
+    float.len = pos;
+    float.exponent += if negative_exponent {
+      -exponent
+    } else {
+      exponent
+    };
+  }
+  float
+}
+
+impl HexFloat {
+  /// Returns the bits of the closest floating point number, rounding ties to even,
+  /// for a format with `fraction_bits` bits of fraction and `exponent_bits` bits of
+  /// exponent
+  fn to_bits(&self, fraction_bits: u32, exponent_bits: u32) -> u64 {
+    let sign = if self.negative {
+      1u64 << (fraction_bits + exponent_bits)
+    } else {
+      0
+    };
+    if self.mantissa == 0 {
+      return sign;
+    }
+
+    let max_exponent = (1i64 << exponent_bits) - 1;
+    let bias = max_exponent >> 1;
+    let zeros = self.mantissa.leading_zeros();
+    // the value is 1.m * 2^exponent, with m the 63 bits following the first one
+    let m = self.mantissa << zeros;
+    let mut biased = self.exponent + 63 - i64::from(zeros) + bias;
+    if biased >= max_exponent {
+      return sign | (max_exponent as u64) << fraction_bits;
+    }
+
+    let mut drop = i64::from(63 - fraction_bits);
+    if biased <= 0 {
+      // subnormal number
+      drop += 1 - biased;
+      biased = 0;
+    }
+    if drop > 64 {
+      return sign;
+    }
+
+    let m = u128::from(m);
+    let mut kept = (m >> drop) as u64;
+    let half = (m >> (drop - 1)) & 1 == 1;
+    let rest = m & ((1 << (drop - 1)) - 1) != 0 || self.sticky;
+    if half && (rest || kept & 1 == 1) {
+      kept += 1;
+    }
+
+    if biased == 0 {
+      // rounding up to the smallest normal number sets the exponent bit
+      return sign | kept;
+    }
+    if kept >> (fraction_bits + 1) != 0 {
+      kept >>= 1;
+      biased += 1;
+      if biased >= max_exponent {
+        return sign | (max_exponent as u64) << fraction_bits;
+      }
+    }
+    sign | (biased as u64) << fraction_bits | (kept & ((1 << fraction_bits) - 1))
+  }
+}
+
+/// Result of scanning a localized float
+#[cfg(feature = "alloc")]
+struct LocalizedFloat {
+  /// length of the recognized number in the input, 0 if there is none
+  len: usize,
+  /// the number rewritten with `.` as decimal separator and no grouping
+  normalized: String,
+  /// the scan looked past the end of the input
+  reached_end: bool,
+}
+
+#[cfg(feature = "alloc")]
+fn scan_localized_float<T>(input: &T, decimal: char, grouping: Option<char>) -> LocalizedFloat
+where
+  T: Slice<RangeFrom<usize>> + InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  let mut scanner = Scanner {
+    input,
+    reached_end: false,
+  };
+
+  let mut normalized = String::new();
+  let mut pos = 0;
+
+  if let Some((c @ '+', l)) | Some((c @ '-', l)) = scanner.peek(pos) {
+    normalized.push(c);
+    pos += l;
+  }
+
+  // integer part, a grouping separator is only accepted between two digits
+  let mut int_digits = 0;
+  loop {
+    match scanner.peek(pos) {
+      Some((c, l)) if c.is_ascii_digit() => {
+        normalized.push(c);
+        pos += l;
+        int_digits += 1;
+      }
+      Some((c, l)) if int_digits > 0 && Some(c) == grouping => match scanner.peek(pos + l) {
+        Some((d, _)) if d.is_ascii_digit() => pos += l,
+        _ => break,
+      },
+      _ => break,
+    }
+  }
+
+  let mut frac_digits = 0;
+  if let Some((c, l)) = scanner.peek(pos) {
+    if c == decimal {
+      let mut frac_pos = pos + l;
+      let mut frac = String::from(".");
+      while let Some((d, l)) = scanner.peek(frac_pos) {
+        if !d.is_ascii_digit() {
+          break;
+        }
+        frac.push(d);
+        frac_pos += l;
+        frac_digits += 1;
+      }
+      if int_digits > 0 || frac_digits > 0 {
+        normalized.push_str(&frac);
+        pos = frac_pos;
+      }
+    }
+  }
+
+  if int_digits == 0 && frac_digits == 0 {
+    return LocalizedFloat {
+      len: 0,
+      normalized,
+      reached_end: scanner.reached_end,
+    };
+  }
+
+  // the exponent is only recognized if it has digits
+  if let Some((c @ 'e', l)) | Some((c @ 'E', l)) = scanner.peek(pos) {
+    let mut exp_pos = pos + l;
+    let mut exponent = String::new();
+    exponent.push(c);
+    if let Some((s @ '+', l)) | Some((s @ '-', l)) = scanner.peek(exp_pos) {
+      exponent.push(s);
+      exp_pos += l;
+    }
+    let mut exp_digits = 0;
+    while let Some((d, l)) = scanner.peek(exp_pos) {
+      if !d.is_ascii_digit() {
+        break;
+      }
+      exponent.push(d);
+      exp_pos += l;
+      exp_digits += 1;
+    }
+    if exp_digits > 0 {
+      normalized.push_str(&exponent);
+      pos = exp_pos;
+    }
+  }
+
+  LocalizedFloat {
+    len: pos,
+    normalized,
+    reached_end: scanner.reached_end,
+  }
+}
+
+struct Scanner<'a, T> {
+  input: &'a T,
+  reached_end: bool,
+}
+
+impl<'a, T> Scanner<'a, T>
+where
+  T: Slice<RangeFrom<usize>> + InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  /// Returns the character at `pos` and its length in the input
+  fn peek(&mut self, pos: usize) -> Option<(char, usize)> {
+    if pos >= self.input.input_len() {
+      self.reached_end = true;
+      None
+    } else {
+      let rest = self.input.slice(pos..);
+      let mut chars = rest.iter_indices();
+      let c = chars.next()?.1.as_char();
+      let len = chars
+        .next()
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| rest.input_len());
+      Some((c, len))
+    }
+  }
+}
@@ -9,6 +9,11 @@ use crate::lib::std::ops::{RangeFrom, RangeTo};
 use crate::sequence::{pair, tuple};
 use crate::traits::{AsChar, InputIter, InputLength, InputTakeAtPosition};
 use crate::traits::{Offset, Slice};
+#[cfg(feature = "alloc")]
+use crate::{
+  lib::std::vec::Vec,
+  traits::{AsBytes, InputTake},
+};
 
 /// Recognizes an unsigned 1 byte integer.
 ///
@@ -1516,6 +1521,194 @@ where
   }
 }
 
+/// Recognizes `count` big endian numbers of the same type, like
+/// [count](crate::multi::count) with [be_u16], but converting them in bulk.
+///
+/// *Streaming version*: Will return `Err(nom::Err::Incomplete(_))` if there is not enough data.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// use nom::number::streaming::be_vec;
+///
+/// let parser = |s| {
+///   be_vec::<u16, _, (_, ErrorKind)>(2)(s)
+/// };
+///
+/// assert_eq!(parser(&b"\x00\x01\x00\x02abc"[..]), Ok((&b"abc"[..], vec![1, 2])));
+/// assert_eq!(parser(&b"\x00\x01\x00"[..]), Err(Err::Incomplete(Needed::new(1))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn be_vec<T, I, E: ParseError<I>>(count: usize) -> impl Fn(I) -> IResult<I, Vec<T>, E>
+where
+  T: super::BinaryNumber,
+  I: AsBytes + InputTake + InputLength,
+{
+  move |input: I| {
+    let len = match count.checked_mul(T::SIZE) {
+      Some(len) => len,
+      None => return Err(Err::Error(E::from_error_kind(input, ErrorKind::TooLarge))),
+    };
+    if input.input_len() < len {
+      return Err(Err::Incomplete(Needed::new(len - input.input_len())));
+    }
+    let (i, o) = input.take_split(len);
+    let res = super::read_vec(&o.as_bytes()[..len], true);
+    Ok((i, res))
+  }
+}
+
+/// Recognizes `count` little endian numbers of the same type, like
+/// [count](crate::multi::count) with [le_f32], but converting them in bulk.
+///
+/// *Streaming version*: Will return `Err(nom::Err::Incomplete(_))` if there is not enough data.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// use nom::number::streaming::le_vec;
+///
+/// let parser = |s| {
+///   le_vec::<f32, _, (_, ErrorKind)>(2)(s)
+/// };
+///
+/// assert_eq!(parser(&b"\x00\x00\x80\x3f\x00\x00\x00\x40abc"[..]), Ok((&b"abc"[..], vec![1.0, 2.0])));
+/// assert_eq!(parser(&b"\x00\x00\x80\x3f\x00"[..]), Err(Err::Incomplete(Needed::new(3))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn le_vec<T, I, E: ParseError<I>>(count: usize) -> impl Fn(I) -> IResult<I, Vec<T>, E>
+where
+  T: super::BinaryNumber,
+  I: AsBytes + InputTake + InputLength,
+{
+  move |input: I| {
+    let len = match count.checked_mul(T::SIZE) {
+      Some(len) => len,
+      None => return Err(Err::Error(E::from_error_kind(input, ErrorKind::TooLarge))),
+    };
+    if input.input_len() < len {
+      return Err(Err::Incomplete(Needed::new(len - input.input_len())));
+    }
+    let (i, o) = input.take_split(len);
+    let res = super::read_vec(&o.as_bytes()[..len], false);
+    Ok((i, res))
+  }
+}
+
+/// Recognizes a floating point number written with a custom decimal separator,
+/// and returns a f64.
+///
+/// If `grouping` is set, that separator is accepted between the digits of the
+/// integer part, like the thousands separator in `1.234,5`. It must differ
+/// from `decimal`.
+///
+/// *Streaming version*: Will return `Err(nom::Err::Incomplete(_))` if it reaches the end of input.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// use nom::number::streaming::double_localized;
+///
+/// let parser = |s| {
+///   double_localized::<_, (&str, ErrorKind)>(',', Some('.'))(s)
+/// };
+///
+/// assert_eq!(parser("1.234,5;"), Ok((";", 1234.5)));
+/// assert_eq!(parser("-0,25e2;"), Ok((";", -25.0)));
+/// assert_eq!(parser("1.234"), Err(Err::Incomplete(Needed::new(1))));
+/// assert_eq!(parser("abc"), Err(Err::Error(("abc", ErrorKind::Float))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn double_localized<T, E: ParseError<T>>(
+  decimal: char,
+  grouping: Option<char>,
+) -> impl Fn(T) -> IResult<T, f64, E>
+where
+  T: Slice<RangeFrom<usize>> + InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  move |input: T| {
+    let float = super::scan_localized_float(&input, decimal, grouping);
+    if float.reached_end {
+      return Err(Err::Incomplete(Needed::new(1)));
+    }
+    if float.len == 0 {
+      return Err(Err::Error(E::from_error_kind(input, ErrorKind::Float)));
+    }
+    match float.normalized.parse() {
+      Ok(n) => Ok((input.slice(float.len..), n)),
+      Err(_) => Err(Err::Error(E::from_error_kind(input, ErrorKind::Float))),
+    }
+  }
+}
+
+/// Recognizes a hexadecimal floating point literal and returns a f32.
+///
+/// The literal is written like in C99, `0x` or `0X`, hexadecimal digits with an
+/// optional `.`, and a mandatory binary exponent introduced by `p` or `P`, with
+/// an optional sign. The result is rounded to the nearest f32.
+///
+/// *Streaming version*: Will return `Err(nom::Err::Incomplete(_))` if it reaches the end of input.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// use nom::number::streaming::hex_float;
+///
+/// let parser = |s| {
+///   hex_float::<_, (&str, ErrorKind)>(s)
+/// };
+///
+/// assert_eq!(parser("0x1.8p3;"), Ok((";", 12.0)));
+/// assert_eq!(parser("-0X.4p-2;"), Ok((";", -0.0625)));
+/// assert_eq!(parser("0x1.8p3"), Err(Err::Incomplete(Needed::new(1))));
+/// ```
+pub fn hex_float<T, E: ParseError<T>>(input: T) -> IResult<T, f32, E>
+where
+  T: Slice<RangeFrom<usize>> + InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  let float = super::scan_hex_float(&input);
+  if float.reached_end {
+    return Err(Err::Incomplete(Needed::new(1)));
+  }
+  if float.len == 0 {
+    return Err(Err::Error(E::from_error_kind(input, ErrorKind::Float)));
+  }
+  let value = f32::from_bits(float.to_bits(23, 8) as u32);
+  Ok((input.slice(float.len..), value))
+}
+
+/// Recognizes a hexadecimal floating point literal and returns a f64.
+///
+/// The literal is written like in C99, `0x` or `0X`, hexadecimal digits with an
+/// optional `.`, and a mandatory binary exponent introduced by `p` or `P`, with
+/// an optional sign. The result is rounded to the nearest f64.
+///
+/// *Streaming version*: Will return `Err(nom::Err::Incomplete(_))` if it reaches the end of input.
+/// ```rust
+/// # use nom::{Err, error::ErrorKind, Needed};
+/// use nom::number::streaming::hex_double;
+///
+/// let parser = |s| {
+///   hex_double::<_, (&str, ErrorKind)>(s)
+/// };
+///
+/// assert_eq!(parser("0x1.8p3;"), Ok((";", 12.0)));
+/// assert_eq!(parser("-0X.4p-2;"), Ok((";", -0.0625)));
+/// assert_eq!(parser("0x1.8p3"), Err(Err::Incomplete(Needed::new(1))));
+/// ```
+pub fn hex_double<T, E: ParseError<T>>(input: T) -> IResult<T, f64, E>
+where
+  T: Slice<RangeFrom<usize>> + InputIter + InputLength,
+  <T as InputIter>::Item: AsChar,
+{
+  let float = super::scan_hex_float(&input);
+  if float.reached_end {
+    return Err(Err::Incomplete(Needed::new(1)));
+  }
+  if float.len == 0 {
+    return Err(Err::Error(E::from_error_kind(input, ErrorKind::Float)));
+  }
+  let value = f64::from_bits(float.to_bits(52, 11));
+  Ok((input.slice(float.len..), value))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -0,0 +1,214 @@
+//! Parallel parsing of independent records
+//!
+//! Large files are often a sequence of records that can be parsed without
+//! looking at their neighbours: lines of a log, length-prefixed messages.
+//! [parse_records] splits the input with a cheap boundary scanner, a parser
+//! that only recognizes the extent of a record, then parses the records in
+//! parallel on the rayon thread pool.
+//!
+//! The outputs are returned in the order of the records. If some records
+//! fail, all the errors are returned, with their offset in the whole input.
+
+use crate::error::{ErrorFrames, ErrorKind, ParseError};
+use crate::internal::{Err, IResult, Parser};
+use crate::lib::std::fmt;
+use crate::traits::{InputLength, Offset};
+use rayon::prelude::*;
+
+/// Error returned by [parse_records] for one record
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordError<E> {
+  /// Index of the record, or of the record that the scanner could not delimit
+  pub index: usize,
+  /// Position of the error in the whole input: the position of its deepest
+  /// frame, or the start of the record for `Err::Incomplete`
+  pub offset: usize,
+  /// Error returned by the scanner or the parser
+  pub error: Err<E>,
+}
+
+impl<E: fmt::Debug> fmt::Display for RecordError<E> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "record {} failed at offset {}: {:?}",
+      self.index, self.offset, self.error
+    )
+  }
+}
+
+impl<E: fmt::Debug> std::error::Error for RecordError<E> {}
+
+fn record_error<I, E>(original: &I, start: I, index: usize, error: Err<E>) -> RecordError<E>
+where
+  I: Offset,
+  E: ErrorFrames<I>,
+{
+  let offset = match &error {
+    Err::Error(e) | Err::Failure(e) => e.deepest().map(|frame| frame.offset(original)),
+    Err::Incomplete(_) => None,
+  }
+  .unwrap_or_else(|| original.offset(&start));
+
+  RecordError {
+    index,
+    offset,
+    error,
+  }
+}
+
+/// Splits `input` in records with `scanner`, then applies a parser to each
+/// record in parallel.
+///
+/// `scanner` is applied repeatedly until the input is empty, and returns the
+/// slice containing the next record. `make_parser` creates the record parser,
+/// it is called once per batch of records handled by a thread, so parsers
+/// keeping state can be used.
+///
+/// Returns the outputs in the order of the records, or every error, sorted by
+/// record. If the scanner fails, the records before that point are still
+/// parsed and their errors reported.
+///
+/// ```rust
+/// # use nom::{Err, IResult};
+/// use nom::character::complete::{char, digit1, line_ending, not_line_ending};
+/// use nom::combinator::{all_consuming, map_res};
+/// use nom::error::{Error, ErrorKind};
+/// use nom::parallel::{parse_records, RecordError};
+/// use nom::sequence::terminated;
+///
+/// fn line(i: &str) -> IResult<&str, &str> {
+///   terminated(not_line_ending, line_ending)(i)
+/// }
+///
+/// fn number(i: &str) -> IResult<&str, u32> {
+///   all_consuming(map_res(digit1, str::parse))(i)
+/// }
+///
+/// assert_eq!(parse_records("1\n22\n333\n", line, || number), Ok(vec![1, 22, 333]));
+///
+/// let input = "1\nx\n3\n4y\n";
+/// assert_eq!(
+///   parse_records(input, line, || number),
+///   Err(vec![
+///     RecordError { index: 1, offset: 2, error: Err::Error(Error::new("x", ErrorKind::Digit)) },
+///     RecordError { index: 3, offset: 7, error: Err::Error(Error::new("y", ErrorKind::Eof)) },
+///   ])
+/// );
+/// ```
+pub fn parse_records<I, O, E, S, M, P>(
+  input: I,
+  mut scanner: S,
+  make_parser: M,
+) -> Result<Vec<O>, Vec<RecordError<E>>>
+where
+  I: Clone + Offset + InputLength + Send + Sync,
+  O: Send,
+  E: ParseError<I> + ErrorFrames<I> + Send,
+  S: Parser<I, I, E>,
+  M: Fn() -> P + Sync,
+  P: Parser<I, O, E>,
+{
+  let mut records = Vec::new();
+  let mut scan_error = None;
+  let mut i = input.clone();
+  while i.input_len() > 0 {
+    match scanner.parse(i.clone()) {
+      Ok((rest, record)) => {
+        if rest.input_len() == i.input_len() {
+          // the scanner must make progress
+          scan_error = Some(record_error(
+            &input,
+            i.clone(),
+            records.len(),
+            Err::Error(E::from_error_kind(i, ErrorKind::Many1)),
+          ));
+          break;
+        }
+        records.push(record);
+        i = rest;
+      }
+      Err(e) => {
+        scan_error = Some(record_error(&input, i, records.len(), e));
+        break;
+      }
+    }
+  }
+
+  let results: Vec<IResult<I, O, E>> = records
+    .par_iter()
+    .map_init(&make_parser, |parser, record| parser.parse(record.clone()))
+    .collect();
+
+  let mut outputs = Vec::with_capacity(results.len());
+  let mut errors = Vec::new();
+  for (index, (result, record)) in results.into_iter().zip(records).enumerate() {
+    match result {
+      Ok((_, o)) => outputs.push(o),
+      Err(e) => errors.push(record_error(&input, record, index, e)),
+    }
+  }
+  errors.extend(scan_error);
+
+  if errors.is_empty() {
+    Ok(outputs)
+  } else {
+    Err(errors)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::bytes::complete::{tag, take};
+  use crate::internal::Needed;
+  use crate::multi::length_data;
+  use crate::number::complete::{be_u16, be_u8};
+  use crate::sequence::preceded;
+
+  fn message(i: &[u8]) -> IResult<&[u8], &[u8], (&[u8], ErrorKind)> {
+    length_data(be_u8)(i)
+  }
+
+  fn record(i: &[u8]) -> IResult<&[u8], u16, (&[u8], ErrorKind)> {
+    preceded(tag(&[0][..]), be_u16)(i)
+  }
+
+  #[test]
+  fn length_prefixed_records() {
+    let mut input = Vec::new();
+    for n in 0..1000u16 {
+      input.extend_from_slice(&[3, 0]);
+      input.extend_from_slice(&n.to_be_bytes());
+    }
+    let res = parse_records(&input[..], message, || record);
+    assert_eq!(res, Ok((0..1000).collect()));
+
+    // one record is invalid, and the last one is truncated
+    input[4 * 500 + 1] = 1;
+    input.pop();
+    let errors = parse_records(&input[..], message, || record).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert_eq!((errors[0].index, errors[0].offset), (500, 4 * 500 + 1));
+    assert_eq!(
+      errors[0].error,
+      Err::Error((&input[4 * 500 + 1..4 * 501], ErrorKind::Tag))
+    );
+    assert_eq!((errors[1].index, errors[1].offset), (999, 4 * 999));
+    assert_eq!(errors[1].error, Err::Incomplete(Needed::new(1)));
+  }
+
+  #[test]
+  fn scanner_progress() {
+    let res: Result<Vec<&[u8]>, Vec<RecordError<(&[u8], ErrorKind)>>> =
+      parse_records(&b"abc"[..], take(0usize), || take(1usize));
+    assert_eq!(
+      res,
+      Err(vec![RecordError {
+        index: 0,
+        offset: 0,
+        error: Err::Error((&b"abc"[..], ErrorKind::Many1)),
+      }])
+    );
+  }
+}
@@ -0,0 +1,310 @@
+//! Input wrapper selecting the streaming behaviour of parsers
+//!
+//! nom provides each basic parser in two versions: the `complete` one treats
+//! the end of the input as the end of the data, while the `streaming` one
+//! returns `Err::Incomplete` if it cannot decide without more data.
+//!
+//! The parsers at the root of the `bytes`, `character` and `number` modules
+//! select their behaviour from the input instead: slices are complete, and
+//! the [Partial] wrapper marks the input as partial, so a single grammar
+//! can handle both cases. Once the last chunk of data is received,
+//! [Partial::into_complete] switches the input back to the complete behaviour.
+//!
+//! ```rust
+//! use nom::{Err, Needed, IResult};
+//! use nom::character::digit1;
+//! use nom::partial::Partial;
+//!
+//! fn number<I>(i: I) -> IResult<I, I>
+//! where
+//!   I: nom::InputTakeAtPosition<Item = char> + nom::InputMode,
+//! {
+//!   digit1(i)
+//! }
+//!
+//! assert_eq!(number("123"), Ok(("", "123")));
+//! assert_eq!(number(Partial::new("123")), Err(Err::Incomplete(Needed::new(1))));
+//! assert_eq!(
+//!   number(Partial::new("123").into_complete()),
+//!   Ok((Partial::new("").into_complete(), Partial::new("123").into_complete()))
+//! );
+//! ```
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::IResult;
+use crate::lib::std::fmt;
+use crate::lib::std::ops::Deref;
+use crate::traits::{
+  AsBytes, Compare, CompareResult, ExtendInto, FindSubstring, InputIter, InputLength, InputMode,
+  InputTake, InputTakeAtPosition, Offset, ParseTo, Slice,
+};
+
+/// Marks an input as partial: more data can follow its end
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Partial<I> {
+  input: I,
+  partial: bool,
+}
+
+impl<I> Partial<I> {
+  /// Wraps an input that can be followed by more data
+  pub fn new(input: I) -> Self {
+    Partial {
+      input,
+      partial: true,
+    }
+  }
+
+  /// Indicates that no more data will follow the input
+  pub fn into_complete(self) -> Self {
+    Partial {
+      input: self.input,
+      partial: false,
+    }
+  }
+
+  /// Returns the wrapped input
+  pub fn into_inner(self) -> I {
+    self.input
+  }
+
+  fn with<J>(&self, input: J) -> Partial<J> {
+    Partial {
+      input,
+      partial: self.partial,
+    }
+  }
+
+  fn wrap<E: ParseError<Self>>(
+    &self,
+    res: IResult<I, I, (I, ErrorKind)>,
+  ) -> IResult<Self, Self, E> {
+    match res {
+      Ok((i, o)) => Ok((self.with(i), self.with(o))),
+      Err(e) => Err(e.map(|(i, kind)| E::from_error_kind(self.with(i), kind))),
+    }
+  }
+}
+
+impl<I> Deref for Partial<I> {
+  type Target = I;
+
+  fn deref(&self) -> &I {
+    &self.input
+  }
+}
+
+impl<I> InputMode for Partial<I> {
+  #[inline]
+  fn is_partial(&self) -> bool {
+    self.partial
+  }
+}
+
+impl<I: InputLength> InputLength for Partial<I> {
+  #[inline]
+  fn input_len(&self) -> usize {
+    self.input.input_len()
+  }
+}
+
+impl<I: Offset> Offset for Partial<I> {
+  #[inline]
+  fn offset(&self, second: &Self) -> usize {
+    self.input.offset(&second.input)
+  }
+}
+
+impl<I: AsBytes> AsBytes for Partial<I> {
+  #[inline]
+  fn as_bytes(&self) -> &[u8] {
+    self.input.as_bytes()
+  }
+}
+
+impl<I: InputIter> InputIter for Partial<I> {
+  type Item = I::Item;
+  type Iter = I::Iter;
+  type IterElem = I::IterElem;
+
+  #[inline]
+  fn iter_indices(&self) -> Self::Iter {
+    self.input.iter_indices()
+  }
+
+  #[inline]
+  fn iter_elements(&self) -> Self::IterElem {
+    self.input.iter_elements()
+  }
+
+  #[inline]
+  fn position<P>(&self, predicate: P) -> Option<usize>
+  where
+    P: Fn(Self::Item) -> bool,
+  {
+    self.input.position(predicate)
+  }
+
+  #[inline]
+  fn slice_index(&self, count: usize) -> Result<usize, crate::Needed> {
+    self.input.slice_index(count)
+  }
+}
+
+impl<I: InputTake> InputTake for Partial<I> {
+  #[inline]
+  fn take(&self, count: usize) -> Self {
+    self.with(self.input.take(count))
+  }
+
+  #[inline]
+  fn take_split(&self, count: usize) -> (Self, Self) {
+    let (suffix, prefix) = self.input.take_split(count);
+    (self.with(suffix), self.with(prefix))
+  }
+}
+
+impl<I: InputTakeAtPosition> InputTakeAtPosition for Partial<I> {
+  type Item = I::Item;
+
+  fn split_at_position<P, E: ParseError<Self>>(&self, predicate: P) -> IResult<Self, Self, E>
+  where
+    P: Fn(Self::Item) -> bool,
+  {
+    self.wrap(self.input.split_at_position(predicate))
+  }
+
+  fn split_at_position1<P, E: ParseError<Self>>(
+    &self,
+    predicate: P,
+    e: ErrorKind,
+  ) -> IResult<Self, Self, E>
+  where
+    P: Fn(Self::Item) -> bool,
+  {
+    self.wrap(self.input.split_at_position1(predicate, e))
+  }
+
+  fn split_at_position_complete<P, E: ParseError<Self>>(
+    &self,
+    predicate: P,
+  ) -> IResult<Self, Self, E>
+  where
+    P: Fn(Self::Item) -> bool,
+  {
+    self.wrap(self.input.split_at_position_complete(predicate))
+  }
+
+  fn split_at_position1_complete<P, E: ParseError<Self>>(
+    &self,
+    predicate: P,
+    e: ErrorKind,
+  ) -> IResult<Self, Self, E>
+  where
+    P: Fn(Self::Item) -> bool,
+  {
+    self.wrap(self.input.split_at_position1_complete(predicate, e))
+  }
+}
+
+impl<I: Compare<T>, T> Compare<T> for Partial<I> {
+  #[inline]
+  fn compare(&self, t: T) -> CompareResult {
+    self.input.compare(t)
+  }
+
+  #[inline]
+  fn compare_no_case(&self, t: T) -> CompareResult {
+    self.input.compare_no_case(t)
+  }
+
+  #[inline]
+  fn fmt_tag(t: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    I::fmt_tag(t, f)
+  }
+}
+
+impl<I: FindSubstring<T>, T> FindSubstring<T> for Partial<I> {
+  #[inline]
+  fn find_substring(&self, substr: T) -> Option<usize> {
+    self.input.find_substring(substr)
+  }
+}
+
+impl<I: ParseTo<R>, R> ParseTo<R> for Partial<I> {
+  #[inline]
+  fn parse_to(&self) -> Option<R> {
+    self.input.parse_to()
+  }
+}
+
+impl<I: Slice<R>, R> Slice<R> for Partial<I> {
+  #[inline]
+  fn slice(&self, range: R) -> Self {
+    self.with(self.input.slice(range))
+  }
+}
+
+impl<I: ExtendInto> ExtendInto for Partial<I> {
+  type Item = I::Item;
+  type Extender = I::Extender;
+
+  #[inline]
+  fn new_builder(&self) -> Self::Extender {
+    self.input.new_builder()
+  }
+
+  #[inline]
+  fn extend_into(&self, acc: &mut Self::Extender) {
+    self.input.extend_into(acc)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::bytes::{tag, take_until};
+  use crate::character::{digit1, line_ending};
+  use crate::error::Error;
+  use crate::sequence::{delimited, pair};
+  use crate::{Err, Needed};
+
+  fn header<I>(i: I) -> IResult<I, (I, I), Error<I>>
+  where
+    I: InputTake
+      + InputLength
+      + InputIter
+      + InputMode
+      + InputTakeAtPosition
+      + Compare<&'static str>
+      + FindSubstring<&'static str>
+      + Slice<crate::lib::std::ops::Range<usize>>
+      + Slice<crate::lib::std::ops::RangeFrom<usize>>
+      + Slice<crate::lib::std::ops::RangeTo<usize>>
+      + Clone,
+    <I as InputTakeAtPosition>::Item: crate::traits::AsChar,
+  {
+    pair(take_until(":"), delimited(tag(": "), digit1, line_ending))(i)
+  }
+
+  #[test]
+  fn same_grammar_both_modes() {
+    assert_eq!(header("Length: 12\n"), Ok(("", ("Length", "12"))));
+    assert_eq!(
+      header("Length: 12"),
+      Err(Err::Error(Error::new("", crate::error::ErrorKind::CrLf)))
+    );
+
+    assert_eq!(
+      header(Partial::new("Length: 12")),
+      Err(Err::Incomplete(Needed::new(1)))
+    );
+    assert_eq!(
+      header(Partial::new("Leng")),
+      Err(Err::Incomplete(Needed::Unknown))
+    );
+    let (rest, (name, value)) = header(Partial::new("Length: 12\r\nbody")).unwrap();
+    assert_eq!((*rest, *name, *value), ("body", "Length", "12"));
+    assert!(rest.is_partial());
+  }
+}
@@ -0,0 +1,468 @@
+//! Parsers recognizing quoted strings
+//!
+//! Quoted strings appear in most text formats, with small variations: which
+//! characters can be used as quotes, how a quote is escaped inside the string,
+//! and whether the string can span multiple lines. [quoted] recognizes such a
+//! string and returns its raw content, while [quoted_unescaped] also processes
+//! the escape sequences. [shell_words] splits a command line into words
+//! following the quoting rules of POSIX shells.
+//!
+//! Those parsers work on complete input: a string missing its closing quote
+//! is an error.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::ops::{Range, RangeFrom};
+#[cfg(feature = "alloc")]
+use crate::lib::std::{string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use crate::traits::InputLength;
+use crate::traits::{AsChar, FindToken, InputIter, Slice};
+
+/// Indicates how a quote character can be included in a quoted string
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Escape {
+  /// No escape mechanism: the string ends at the first closing quote,
+  /// like TOML literal strings
+  None,
+  /// A backslash escapes the next character, like in JSON or C strings
+  Backslash,
+  /// A quote is escaped by doubling it, like in SQL or CSV
+  Doubled,
+}
+
+/// Finds the limits of a quoted string.
+///
+/// Returns the opening quote, the start and end positions of the content
+/// and the position following the closing quote
+fn scan<I, T, E>(
+  input: &I,
+  quotes: &T,
+  escape: Escape,
+  newlines: bool,
+) -> Result<(char, usize, usize, usize), Err<E>>
+where
+  I: Clone + InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar + Copy,
+  T: FindToken<char>,
+  E: ParseError<I>,
+{
+  let mut it = input.iter_indices().peekable();
+
+  let (quote, start) = match it.next() {
+    Some((_, c)) if quotes.find_token(c.as_char()) => (c.as_char(), c.len()),
+    _ => {
+      return Err(Err::Error(E::from_error_kind(
+        input.clone(),
+        ErrorKind::QuotedString,
+      )))
+    }
+  };
+
+  let mut escaped = false;
+  while let Some((index, c)) = it.next() {
+    if escaped {
+      escaped = false;
+      continue;
+    }
+
+    match c.as_char() {
+      '\\' if escape == Escape::Backslash => escaped = true,
+      ch if ch == quote => {
+        if escape == Escape::Doubled {
+          if let Some(&(_, next)) = it.peek() {
+            if next.as_char() == quote {
+              it.next();
+              continue;
+            }
+          }
+        }
+        return Ok((quote, start, index, index + c.len()));
+      }
+      '\n' | '\r' if !newlines => {
+        return Err(Err::Error(E::from_error_kind(
+          input.slice(index..),
+          ErrorKind::QuotedString,
+        )))
+      }
+      _ => {}
+    }
+  }
+
+  Err(Err::Error(E::from_error_kind(
+    input.clone(),
+    ErrorKind::QuotedString,
+  )))
+}
+
+/// Recognizes a quoted string and returns its content, without the quotes.
+///
+/// * The first argument is the list of accepted quote characters. The string must
+///   end with the same character it started with
+/// * The second argument indicates how quotes can be escaped inside the string
+/// * The third argument indicates if the string can contain `\n` or `\r` characters
+///   (a newline escaped with a backslash is always accepted)
+///
+/// The escape sequences are left as is in the returned content.
+///
+/// *Complete version*: a string without a closing quote is an error.
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::quoted::{quoted, Escape};
+///
+/// fn parser(input: &str) -> IResult<&str, &str> {
+///   quoted("\"'", Escape::Backslash, false)(input)
+/// }
+///
+/// assert_eq!(parser(r#""a\"b" c"#), Ok((" c", r#"a\"b"#)));
+/// assert_eq!(parser("'abc'"), Ok(("", "abc")));
+/// assert_eq!(parser("\"abc'"), Err(Err::Error(Error::new("\"abc'", ErrorKind::QuotedString))));
+/// assert_eq!(parser("\"a\nb\""), Err(Err::Error(Error::new("\nb\"", ErrorKind::QuotedString))));
+/// ```
+pub fn quoted<I, T, E>(quotes: T, escape: Escape, newlines: bool) -> impl Fn(I) -> IResult<I, I, E>
+where
+  I: Clone + InputIter + Slice<Range<usize>> + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar + Copy,
+  T: FindToken<char>,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let (_, start, end, next) = scan(&input, &quotes, escape, newlines)?;
+    Ok((input.slice(next..), input.slice(start..end)))
+  }
+}
+
+/// Recognizes a quoted string and returns its content with the escape sequences
+/// processed.
+///
+/// The arguments are the same as for [quoted]. With `Escape::Doubled`, a doubled
+/// quote produces a single quote. With `Escape::Backslash`, `\n`, `\r`, `\t` and `\0`
+/// produce the corresponding control character, and a backslash followed by any
+/// other character produces that character.
+///
+/// The content is collected into the input's [ExtendInto](crate::ExtendInto)
+/// accumulator, a `String` for `&str` and a `Vec<u8>` for `&[u8]`.
+///
+/// *Complete version*: a string without a closing quote is an error.
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::quoted::{quoted_unescaped, Escape};
+///
+/// fn sql(input: &str) -> IResult<&str, String> {
+///   quoted_unescaped("'", Escape::Doubled, true)(input)
+/// }
+///
+/// fn c(input: &str) -> IResult<&str, String> {
+///   quoted_unescaped("\"", Escape::Backslash, false)(input)
+/// }
+///
+/// assert_eq!(sql("'it''s'"), Ok(("", String::from("it's"))));
+/// assert_eq!(c(r#""a\tb\"c""#), Ok(("", String::from("a\tb\"c"))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn quoted_unescaped<I, T, E, ExtendItem, Output>(
+  quotes: T,
+  escape: Escape,
+  newlines: bool,
+) -> impl Fn(I) -> IResult<I, Output, E>
+where
+  I: Clone + InputIter + Slice<Range<usize>> + Slice<RangeFrom<usize>>,
+  I: crate::traits::ExtendInto<Item = ExtendItem, Extender = Output>,
+  <I as InputIter>::Item: AsChar + Copy,
+  Output: crate::traits::Sink<char>,
+  T: FindToken<char>,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let (quote, start, end, next) = scan(&input, &quotes, escape, newlines)?;
+    let content = input.slice(start..end);
+    let mut res = content.new_builder();
+
+    let mut run = 0;
+    let mut it = content.iter_indices();
+    while let Some((index, c)) = it.next() {
+      let c = c.as_char();
+      let is_escape = match escape {
+        Escape::None => false,
+        Escape::Backslash => c == '\\',
+        Escape::Doubled => c == quote,
+      };
+
+      if is_escape {
+        // scan() guarantees that escapes are always followed by a character
+        if let Some((escaped_index, escaped)) = it.next() {
+          content.slice(run..index).extend_into(&mut res);
+          let control = match escaped.as_char() {
+            'n' if escape == Escape::Backslash => Some('\n'),
+            'r' if escape == Escape::Backslash => Some('\r'),
+            't' if escape == Escape::Backslash => Some('\t'),
+            '0' if escape == Escape::Backslash => Some('\0'),
+            _ => None,
+          };
+          // other escaped items are copied as is with the next run, so
+          // byte input is never re-encoded
+          run = match control {
+            Some(control) => {
+              if let Err(kind) = res.extend_from(control) {
+                return Err(Err::Failure(E::from_error_kind(
+                  content.slice(index..),
+                  kind,
+                )));
+              }
+              escaped_index + escaped.len()
+            }
+            None => escaped_index,
+          };
+        }
+      }
+    }
+    content.slice(run..end - start).extend_into(&mut res);
+
+    Ok((input.slice(next..), res))
+  }
+}
+
+/// Returns the position following the blanks and line continuations
+/// at the start of the input
+#[cfg(feature = "alloc")]
+fn skip_blanks<I: InputIter<Item = char> + InputLength>(input: &I) -> usize {
+  let mut it = input.iter_indices().peekable();
+  while let Some(&(index, c)) = it.peek() {
+    match c {
+      ' ' | '\t' | '\r' => {
+        it.next();
+      }
+      '\\' => {
+        it.next();
+        match it.next() {
+          Some((_, '\n')) => {}
+          _ => return index,
+        }
+      }
+      _ => return index,
+    }
+  }
+  input.input_len()
+}
+
+/// Unescapes the word at the start of the input, returning it with its length
+#[cfg(feature = "alloc")]
+fn scan_word<I, E>(input: &I) -> Result<(String, usize), Err<E>>
+where
+  I: Clone + InputIter<Item = char> + InputLength + Slice<RangeFrom<usize>>,
+  E: ParseError<I>,
+{
+  let error = |index: usize, kind| Err(Err::Error(E::from_error_kind(input.slice(index..), kind)));
+  let mut it = input.iter_indices().peekable();
+  let mut word = String::new();
+  let mut found = false;
+
+  while let Some(&(index, c)) = it.peek() {
+    match c {
+      ' ' | '\t' | '\r' | '\n' => break,
+      '\\' => {
+        it.next();
+        match it.next() {
+          Some((_, '\n')) => continue,
+          Some((_, c)) => word.push(c),
+          None => return error(index, ErrorKind::Escaped),
+        }
+      }
+      '\'' => {
+        it.next();
+        loop {
+          match it.next() {
+            Some((_, '\'')) => break,
+            Some((_, c)) => word.push(c),
+            None => return error(index, ErrorKind::QuotedString),
+          }
+        }
+      }
+      '"' => {
+        it.next();
+        loop {
+          match it.next() {
+            Some((_, '"')) => break,
+            Some((_, '\\')) => match it.next() {
+              Some((_, '\n')) => {}
+              Some((_, c)) if "$`\"\\".contains(c) => word.push(c),
+              Some((_, c)) => {
+                word.push('\\');
+                word.push(c);
+              }
+              None => return error(index, ErrorKind::QuotedString),
+            },
+            Some((_, c)) => word.push(c),
+            None => return error(index, ErrorKind::QuotedString),
+          }
+        }
+      }
+      c => {
+        it.next();
+        word.push(c);
+      }
+    }
+    found = true;
+  }
+
+  if !found {
+    return error(0, ErrorKind::ShellWord);
+  }
+  let len = it
+    .peek()
+    .map(|(index, _)| *index)
+    .unwrap_or_else(|| input.input_len());
+  Ok((word, len))
+}
+
+/// Recognizes a word of a command line and returns it unescaped.
+///
+/// The word follows the quoting rules of POSIX shells: it ends at the first
+/// unquoted whitespace, a backslash escapes the next character, single quotes
+/// keep their content as is and double quotes only process the `\$`, `` \` ``,
+/// `\"` and `\\` escapes. Quoted and unquoted parts are concatenated, and a
+/// backslash followed by a newline is removed. There is no variable or glob
+/// expansion.
+///
+/// *Complete version*: an unclosed quote returns `ErrorKind::QuotedString`
+/// and a trailing backslash `ErrorKind::Escaped`.
+/// ```
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::quoted::shell_word;
+///
+/// fn parser(input: &str) -> IResult<&str, String> {
+///   shell_word(input)
+/// }
+///
+/// assert_eq!(parser(r#"a"b c"'\d' e"#), Ok((" e", String::from("ab c\\d"))));
+/// assert_eq!(parser(r"a\ b"), Ok(("", String::from("a b"))));
+/// assert_eq!(parser(" a"), Err(Err::Error(Error::new(" a", ErrorKind::ShellWord))));
+/// assert_eq!(parser("a'b"), Err(Err::Error(Error::new("'b", ErrorKind::QuotedString))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn shell_word<I, E>(input: I) -> IResult<I, String, E>
+where
+  I: Clone + InputIter<Item = char> + InputLength + Slice<RangeFrom<usize>>,
+  E: ParseError<I>,
+{
+  let (word, len) = scan_word(&input)?;
+  Ok((input.slice(len..), word))
+}
+
+/// Splits a command line into words, following the rules of [shell_word].
+///
+/// The words are separated by spaces and tabs, and the line ends at the first
+/// unquoted newline, which is not consumed. An empty line returns no words.
+/// ```
+/// # use nom::IResult;
+/// use nom::quoted::shell_words;
+///
+/// fn parser(input: &str) -> IResult<&str, Vec<String>> {
+///   shell_words(input)
+/// }
+///
+/// assert_eq!(
+///   parser("cp  'my file' \"other \\\"file\"\nls"),
+///   Ok(("\nls", vec![String::from("cp"), String::from("my file"), String::from("other \"file")]))
+/// );
+/// assert_eq!(parser("  "), Ok(("", vec![])));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn shell_words<I, E>(input: I) -> IResult<I, Vec<String>, E>
+where
+  I: Clone + InputIter<Item = char> + InputLength + Slice<RangeFrom<usize>>,
+  E: ParseError<I>,
+{
+  let mut words = Vec::new();
+  let mut i = input;
+  loop {
+    i = i.slice(skip_blanks(&i)..);
+    match i.iter_elements().next() {
+      None | Some('\n') => return Ok((i, words)),
+      Some(_) => {
+        let (word, len) = scan_word(&i)?;
+        words.push(word);
+        i = i.slice(len..);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  #[test]
+  fn quoted_no_escape() {
+    let p = quoted::<_, _, Error<&str>>("'", Escape::None, true);
+    assert_eq!(p("'a\\'b'"), Ok(("b'", "a\\")));
+    assert_eq!(p("'a\nb'"), Ok(("", "a\nb")));
+    assert_eq!(
+      p("abc"),
+      Err(Err::Error(Error::new("abc", ErrorKind::QuotedString)))
+    );
+    assert_eq!(
+      p(""),
+      Err(Err::Error(Error::new("", ErrorKind::QuotedString)))
+    );
+  }
+
+  #[test]
+  fn quoted_doubled() {
+    let p = quoted::<_, _, Error<&[u8]>>("\"", Escape::Doubled, false);
+    assert_eq!(
+      p(&b"\"a\"\"b\"\"\",c"[..]),
+      Ok((&b",c"[..], &b"a\"\"b\"\""[..]))
+    );
+    assert_eq!(p(&b"\"\""[..]), Ok((&b""[..], &b""[..])));
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn quoted_unescaped_bytes() {
+    use crate::lib::std::vec::Vec;
+
+    let p = quoted_unescaped::<_, _, Error<&[u8]>, _, Vec<u8>>("\"", Escape::Backslash, false);
+    assert_eq!(
+      p(&b"\"a\\\\b\\\nc\\x\""[..]),
+      Ok((&b""[..], b"a\\b\ncx".to_vec()))
+    );
+    assert_eq!(
+      p(&b"\"\xc3\xa9\\\xc3\xa9\\\xe9\""[..]),
+      Ok((&b""[..], b"\xc3\xa9\xc3\xa9\xe9".to_vec()))
+    );
+    assert_eq!(
+      p(&b"\"abc\\\""[..]),
+      Err(Err::Error(Error::new(
+        &b"\"abc\\\""[..],
+        ErrorKind::QuotedString
+      )))
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn shell_words_quoting() {
+    use crate::lib::std::vec::Vec;
+
+    let p = shell_words::<_, Error<&str>>;
+    let words = |v: &[&str]| v.iter().map(|s| String::from(*s)).collect::<Vec<_>>();
+    assert_eq!(
+      p("a\tb \\\n c\\\nd \"\" ''\r\n"),
+      Ok(("\n", words(&["a", "b", "cd", "", ""])))
+    );
+    assert_eq!(p(r#""\a\$" '\$'"#), Ok(("", words(&["\\a$", "\\$"]))));
+    assert_eq!(
+      p("a \"b"),
+      Err(Err::Error(Error::new("\"b", ErrorKind::QuotedString)))
+    );
+    assert_eq!(
+      p("a b\\"),
+      Err(Err::Error(Error::new("\\", ErrorKind::Escaped)))
+    );
+  }
+}
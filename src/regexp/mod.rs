@@ -9,7 +9,7 @@ pub mod str {
   #[cfg(feature = "alloc")]
   use crate::lib::std::vec::Vec;
   use crate::traits::{InputLength, Slice};
-  use crate::{Err, IResult};
+  use crate::{Err, IResult, Needed};
 
   /// Compares the input with a regular expression and returns the
   /// whole input if a match is found.
@@ -116,6 +116,48 @@ pub mod str {
     }
   }
 
+  /// Compares the input with a regular expression and returns the
+  /// first match, streaming version.
+  ///
+  /// The first match is returned only if it ends before the end of the
+  /// input, and it is taken as final: a match that more data could still
+  /// extend or replace, like `a.*b` on `axbyy` or `a|ab` on `ab`, gives the
+  /// result for the current input, which can differ from the result on the
+  /// whole stream.
+  ///
+  /// Returns `Err::Incomplete(Needed::Unknown)` if there is no match, or if
+  /// the match reaches the end of the input. The regular expression engine
+  /// cannot tell whether more data could still produce a match, so this
+  /// never returns an error, even for an anchored pattern that already
+  /// failed at the start of the input: bound the amount of buffered data,
+  /// and apply the complete version once the end of the stream is reached.
+  ///
+  /// Requires the `regexp` feature.
+  /// # Example
+  ///
+  /// ```
+  /// # use nom::{Err, Needed, error::ErrorKind};
+  /// # use nom::regexp::str::re_find_streaming;
+  /// # fn main() {
+  /// let re = regex::Regex::new(r"\d+").unwrap();
+  /// let parser = re_find_streaming::<(&str, ErrorKind)>(re);
+  /// assert_eq!(parser("abc2019-10"), Ok(("-10", "2019")));
+  /// assert_eq!(parser("abc2019"), Err(Err::Incomplete(Needed::Unknown)));
+  /// assert_eq!(parser("abc"), Err(Err::Incomplete(Needed::Unknown)));
+  /// # }
+  /// ```
+  #[cfg(feature = "regexp")]
+  #[cfg_attr(feature = "docsrs", doc(cfg(feature = "regexp")))]
+  pub fn re_find_streaming<'a, E>(re: Regex) -> impl Fn(&'a str) -> IResult<&'a str, &'a str, E>
+  where
+    E: ParseError<&'a str>,
+  {
+    move |i| match re.find(i) {
+      Some(m) if m.end() < i.len() => Ok((i.slice(m.end()..), i.slice(m.start()..m.end()))),
+      _ => Err(Err::Incomplete(Needed::Unknown)),
+    }
+  }
+
   /// Compares the input with a regular expression and returns
   /// the capture groups of the first match in a `Vec`.
   ///
@@ -160,6 +202,60 @@ pub mod str {
     }
   }
 
+  /// Compares the input with a regular expression and returns
+  /// the capture groups of the first match in a `Vec`, streaming version.
+  ///
+  /// The first match is returned only if it ends before the end of the
+  /// input, and it is taken as final: a match that more data could still
+  /// extend or replace, like `a.*b` on `axbyy` or `a|ab` on `ab`, gives the
+  /// result for the current input, which can differ from the result on the
+  /// whole stream.
+  ///
+  /// Returns `Err::Incomplete(Needed::Unknown)` if there is no match, or if
+  /// the match reaches the end of the input. The regular expression engine
+  /// cannot tell whether more data could still produce a match, so this
+  /// never returns an error, even for an anchored pattern that already
+  /// failed at the start of the input: bound the amount of buffered data,
+  /// and apply the complete version once the end of the stream is reached.
+  ///
+  /// Requires the `regexp` feature.
+  /// # Example
+  ///
+  /// ```
+  /// # use nom::{Err, Needed, error::ErrorKind};
+  /// # use nom::regexp::str::re_capture_streaming;
+  /// # fn main() {
+  /// let re = regex::Regex::new(r"(a)(\d+)").unwrap();
+  /// let parser = re_capture_streaming::<(&str, ErrorKind)>(re);
+  /// assert_eq!(parser("a12b"), Ok(("b", vec!["a12", "a", "12"])));
+  /// assert_eq!(parser("ba12"), Err(Err::Incomplete(Needed::Unknown)));
+  /// # }
+  /// ```
+  #[cfg(all(feature = "regexp", feature = "alloc"))]
+  #[cfg_attr(
+    feature = "docsrs",
+    doc(cfg(all(feature = "regexp", feature = "alloc")))
+  )]
+  pub fn re_capture_streaming<'a, E>(
+    re: Regex,
+  ) -> impl Fn(&'a str) -> IResult<&'a str, Vec<&'a str>, E>
+  where
+    E: ParseError<&'a str>,
+  {
+    move |i| match re.captures(i) {
+      Some(c) if c.get(0).map(|m| m.end() < i.len()).unwrap_or(false) => {
+        let v: Vec<_> = c
+          .iter()
+          .flatten()
+          .map(|m| i.slice(m.start()..m.end()))
+          .collect();
+        let end = c.get(0).unwrap().end();
+        Ok((i.slice(end..), v))
+      }
+      _ => Err(Err::Incomplete(Needed::Unknown)),
+    }
+  }
+
   /// Compares the input with a regular expression and returns
   /// the capture groups of all matches in a nested `Vec`.
   ///
@@ -246,6 +342,30 @@ pub mod str {
       assert_eq!(rm("2015-09-07blah"), Ok(("blah", "2015-09-07")));
     }
 
+    #[test]
+    fn re_find_streaming_str() {
+      let re = Regex::new(r"^\d{4}-\d{2}-\d{2}").unwrap();
+      let rm = re_find_streaming(re);
+      assert_parse!(rm("2015-09-07blah"), Ok(("blah", "2015-09-07")));
+      assert_parse!(rm("2015-09-07"), Err(Err::Incomplete(Needed::Unknown)));
+      assert_parse!(rm("2015-09"), Err(Err::Incomplete(Needed::Unknown)));
+      // an anchored pattern that failed at the start still needs more data
+      assert_parse!(rm("blah2015-09-07 "), Err(Err::Incomplete(Needed::Unknown)));
+    }
+
+    #[test]
+    fn re_find_streaming_str_no_match() {
+      let re = Regex::new(r"\d+;").unwrap();
+      let rm = re_find_streaming(re);
+      assert_parse!(rm("abc"), Err(Err::Incomplete(Needed::Unknown)));
+      assert_parse!(rm("abc12"), Err(Err::Incomplete(Needed::Unknown)));
+      assert_parse!(rm("abc12;d"), Ok(("d", "12;")));
+      // a match ending before the end of the input is final
+      let re = Regex::new(r"a.*b").unwrap();
+      let rm = re_find_streaming(re);
+      assert_parse!(rm("axbyy"), Ok(("yy", "axb")));
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn re_matches_str() {
@@ -321,7 +441,7 @@ pub mod bytes {
   #[cfg(feature = "alloc")]
   use crate::lib::std::vec::Vec;
   use crate::traits::{InputLength, Slice};
-  use crate::{Err, IResult};
+  use crate::{Err, IResult, Needed};
 
   /// Compares the input with a regular expression and returns the
   /// whole input if a match is found.
@@ -426,6 +546,48 @@ pub mod bytes {
     }
   }
 
+  /// Compares the input with a regular expression and returns the
+  /// first match, streaming version.
+  ///
+  /// The first match is returned only if it ends before the end of the
+  /// input, and it is taken as final: a match that more data could still
+  /// extend or replace, like `a.*b` on `axbyy` or `a|ab` on `ab`, gives the
+  /// result for the current input, which can differ from the result on the
+  /// whole stream.
+  ///
+  /// Returns `Err::Incomplete(Needed::Unknown)` if there is no match, or if
+  /// the match reaches the end of the input. The regular expression engine
+  /// cannot tell whether more data could still produce a match, so this
+  /// never returns an error, even for an anchored pattern that already
+  /// failed at the start of the input: bound the amount of buffered data,
+  /// and apply the complete version once the end of the stream is reached.
+  ///
+  /// Requires the `regexp` feature.
+  /// # Example
+  ///
+  /// ```
+  /// # use nom::{Err, Needed, error::ErrorKind};
+  /// # use nom::regexp::bytes::re_find_streaming;
+  /// # fn main() {
+  /// let re = regex::bytes::Regex::new(r"\d+").unwrap();
+  /// let parser = re_find_streaming::<(&[u8], ErrorKind)>(re);
+  /// assert_eq!(parser(&b"abc2019-10"[..]), Ok((&b"-10"[..], &b"2019"[..])));
+  /// assert_eq!(parser(&b"abc2019"[..]), Err(Err::Incomplete(Needed::Unknown)));
+  /// assert_eq!(parser(&b"abc"[..]), Err(Err::Incomplete(Needed::Unknown)));
+  /// # }
+  /// ```
+  #[cfg(feature = "regexp")]
+  #[cfg_attr(feature = "docsrs", doc(cfg(feature = "regexp")))]
+  pub fn re_find_streaming<'a, E>(re: Regex) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], &'a [u8], E>
+  where
+    E: ParseError<&'a [u8]>,
+  {
+    move |i| match re.find(i) {
+      Some(m) if m.end() < i.len() => Ok((i.slice(m.end()..), i.slice(m.start()..m.end()))),
+      _ => Err(Err::Incomplete(Needed::Unknown)),
+    }
+  }
+
   /// Compares the input with a regular expression and returns
   /// the capture groups of the first match in a `Vec`.
   ///
@@ -470,6 +632,60 @@ pub mod bytes {
     }
   }
 
+  /// Compares the input with a regular expression and returns
+  /// the capture groups of the first match in a `Vec`, streaming version.
+  ///
+  /// The first match is returned only if it ends before the end of the
+  /// input, and it is taken as final: a match that more data could still
+  /// extend or replace, like `a.*b` on `axbyy` or `a|ab` on `ab`, gives the
+  /// result for the current input, which can differ from the result on the
+  /// whole stream.
+  ///
+  /// Returns `Err::Incomplete(Needed::Unknown)` if there is no match, or if
+  /// the match reaches the end of the input. The regular expression engine
+  /// cannot tell whether more data could still produce a match, so this
+  /// never returns an error, even for an anchored pattern that already
+  /// failed at the start of the input: bound the amount of buffered data,
+  /// and apply the complete version once the end of the stream is reached.
+  ///
+  /// Requires the `regexp` feature.
+  /// # Example
+  ///
+  /// ```
+  /// # use nom::{Err, Needed, error::ErrorKind};
+  /// # use nom::regexp::bytes::re_capture_streaming;
+  /// # fn main() {
+  /// let re = regex::bytes::Regex::new(r"(a)(\d+)").unwrap();
+  /// let parser = re_capture_streaming::<(&[u8], ErrorKind)>(re);
+  /// assert_eq!(parser(&b"a12b"[..]), Ok((&b"b"[..], vec![&b"a12"[..], &b"a"[..], &b"12"[..]])));
+  /// assert_eq!(parser(&b"ba12"[..]), Err(Err::Incomplete(Needed::Unknown)));
+  /// # }
+  /// ```
+  #[cfg(all(feature = "regexp", feature = "alloc"))]
+  #[cfg_attr(
+    feature = "docsrs",
+    doc(cfg(all(feature = "regexp", feature = "alloc")))
+  )]
+  pub fn re_capture_streaming<'a, E>(
+    re: Regex,
+  ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], Vec<&'a [u8]>, E>
+  where
+    E: ParseError<&'a [u8]>,
+  {
+    move |i| match re.captures(i) {
+      Some(c) if c.get(0).map(|m| m.end() < i.len()).unwrap_or(false) => {
+        let v: Vec<_> = c
+          .iter()
+          .flatten()
+          .map(|m| i.slice(m.start()..m.end()))
+          .collect();
+        let end = c.get(0).unwrap().end();
+        Ok((i.slice(end..), v))
+      }
+      _ => Err(Err::Incomplete(Needed::Unknown)),
+    }
+  }
+
   /// Compares the input with a regular expression and returns
   /// the capture groups of all matches in a nested `Vec`.
   ///
@@ -564,6 +780,45 @@ pub mod bytes {
       );
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn re_capture_streaming_bytes() {
+      let re = Regex::new(r"([[:alpha:]]+)\s+((\d+).(\d+).(\d+))").unwrap();
+      let rm = re_capture_streaming(re);
+      assert_parse!(
+        rm(&b"blah nom 0.3.11 pouet"[..]),
+        Ok((
+          &b" pouet"[..],
+          vec![
+            &b"nom 0.3.11"[..],
+            &b"nom"[..],
+            &b"0.3.11"[..],
+            &b"0"[..],
+            &b"3"[..],
+            &b"11"[..]
+          ]
+        ))
+      );
+      assert_parse!(
+        rm(&b"blah nom 0.3.11"[..]),
+        Err(Err::Incomplete(Needed::Unknown))
+      );
+      assert_parse!(rm(&b"blah"[..]), Err(Err::Incomplete(Needed::Unknown)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn re_capture_streaming_bytes_anchored() {
+      let re = Regex::new(r"^(a)(\d+)").unwrap();
+      let rm = re_capture_streaming(re);
+      assert_parse!(
+        rm(&b"a12b"[..]),
+        Ok((&b"b"[..], vec![&b"a12"[..], &b"a"[..], &b"12"[..]]))
+      );
+      assert_parse!(rm(&b"ba12b"[..]), Err(Err::Incomplete(Needed::Unknown)));
+      assert_parse!(rm(&b""[..]), Err(Err::Incomplete(Needed::Unknown)));
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn re_matches_bytes() {
@@ -0,0 +1,270 @@
+//! Resumable parsers for streaming input
+//!
+//! When a streaming parser returns `Err::Incomplete`, the usual approach is to
+//! read more data and to apply the parser again from the start of the item.
+//! For large items arriving in small chunks, the same data is parsed again and
+//! again.
+//!
+//! The parsers of this module save their progress in a [State] when they return
+//! `Err::Incomplete`, and continue from there when they are called again with
+//! the same state. The new input must start at the same position as the previous
+//! one, and contain the previous data followed by the new data. The state is
+//! reset once the parser succeeds or fails.
+//!
+//! ```rust
+//! use nom::{Err, error::Error};
+//! use nom::resume::{take_until, State};
+//!
+//! let mut state = State::new();
+//! let mut buffer = String::new();
+//! let mut line = None;
+//!
+//! for chunk in ["hel", "lo, wo", "rld\r", "\nnext"].iter() {
+//!   buffer.push_str(chunk);
+//!   // each call only searches the data received since the previous one
+//!   match take_until::<_, _, Error<&str>>(&buffer[..], "\r\n", &mut state) {
+//!     Ok((_, l)) => {
+//!       line = Some(l.to_string());
+//!       break;
+//!     }
+//!     Err(Err::Incomplete(_)) => continue,
+//!     Err(e) => panic!("parse error: {:?}", e),
+//!   }
+//! }
+//!
+//! assert_eq!(line, Some(String::from("hello, world")));
+//! ```
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult, Needed, Parser};
+use crate::lib::std::ops::RangeFrom;
+#[cfg(feature = "alloc")]
+use crate::lib::std::vec::Vec;
+use crate::traits::{FindSubstring, InputLength, InputTake, Offset, Slice};
+
+/// Progress of a resumable parser
+#[derive(Debug, Clone, PartialEq)]
+pub struct State<T> {
+  offset: usize,
+  value: Option<T>,
+}
+
+impl<T> State<T> {
+  /// Creates a state for a parser starting a new item
+  pub fn new() -> Self {
+    State {
+      offset: 0,
+      value: None,
+    }
+  }
+
+  /// Returns the number of input elements already handled for the current item
+  pub fn offset(&self) -> usize {
+    self.offset
+  }
+
+  /// Forgets the progress, so the next call starts a new item
+  pub fn reset(&mut self) {
+    self.offset = 0;
+    self.value = None;
+  }
+
+  /// Returns the input to resume from, or starts over if the input is shorter
+  /// than what was already handled
+  fn resume_from<I>(&mut self, input: &I) -> I
+  where
+    I: InputLength + Slice<RangeFrom<usize>>,
+  {
+    if self.offset > input.input_len() {
+      self.reset();
+    }
+    input.slice(self.offset..)
+  }
+}
+
+impl<T> Default for State<T> {
+  fn default() -> Self {
+    State::new()
+  }
+}
+
+/// Repeats the embedded parser and folds its results, like
+/// [fold_many0](crate::multi::fold_many0), saving the accumulated value in
+/// `state` if the input is incomplete.
+///
+/// `init` is only called when a new item starts.
+pub fn fold_many0<I, O, E, F, H, G, R>(
+  input: I,
+  mut f: F,
+  mut init: H,
+  mut g: G,
+  state: &mut State<R>,
+) -> IResult<I, R, E>
+where
+  I: Clone + InputLength + Offset + Slice<RangeFrom<usize>>,
+  F: Parser<I, O, E>,
+  H: FnMut() -> R,
+  G: FnMut(R, O) -> R,
+  E: ParseError<I>,
+{
+  let mut i = state.resume_from(&input);
+  let mut acc = match state.value.take() {
+    Some(acc) => acc,
+    None => init(),
+  };
+
+  loop {
+    match f.parse(i.clone()) {
+      Ok((i1, o)) => {
+        // loop trip must always consume (otherwise infinite loops)
+        if i1.input_len() == i.input_len() {
+          state.reset();
+          return Err(Err::Error(E::from_error_kind(i, ErrorKind::Many0)));
+        }
+
+        acc = g(acc, o);
+        i = i1;
+      }
+      Err(Err::Error(_)) => {
+        state.reset();
+        return Ok((i, acc));
+      }
+      Err(Err::Incomplete(needed)) => {
+        state.offset = input.offset(&i);
+        state.value = Some(acc);
+        return Err(Err::Incomplete(needed));
+      }
+      Err(e) => {
+        state.reset();
+        return Err(e);
+      }
+    }
+  }
+}
+
+/// Repeats the embedded parser and collects its results in a `Vec`, like
+/// [many0](crate::multi::many0), saving the results in `state` if the input
+/// is incomplete.
+///
+/// The results are kept between calls, so they must not borrow the input.
+///
+/// ```rust
+/// use nom::{Err, Needed, IResult};
+/// use nom::number::streaming::be_u16;
+/// use nom::resume::{many0, State};
+///
+/// let mut state = State::new();
+///
+/// let res: IResult<_, _> = many0(&[0, 1, 0, 2, 0][..], be_u16, &mut state);
+/// assert_eq!(res, Err(Err::Incomplete(Needed::new(1))));
+/// assert_eq!(state.offset(), 4);
+///
+/// // only the bytes after the first 4 are parsed again
+/// let res: IResult<_, _> = many0(&[0, 1, 0, 2, 0, 3, 0xff][..], be_u16, &mut state);
+/// assert_eq!(res, Err(Err::Incomplete(Needed::new(1))));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+pub fn many0<I, O, E, F>(input: I, f: F, state: &mut State<Vec<O>>) -> IResult<I, Vec<O>, E>
+where
+  I: Clone + InputLength + Offset + Slice<RangeFrom<usize>>,
+  F: Parser<I, O, E>,
+  E: ParseError<I>,
+{
+  fold_many0(
+    input,
+    f,
+    || Vec::with_capacity(4),
+    |mut acc, o| {
+      acc.push(o);
+      acc
+    },
+    state,
+  )
+}
+
+/// Returns the input slice up to the first occurrence of the pattern, like
+/// [take_until](crate::bytes::streaming::take_until), without searching
+/// again the part of the input that was already searched.
+///
+/// ```rust
+/// use nom::{Err, Needed, IResult};
+/// use nom::resume::{take_until, State};
+///
+/// let mut state = State::new();
+///
+/// let res: IResult<_, _> = take_until("hello, wor", "\r\n", &mut state);
+/// assert_eq!(res, Err(Err::Incomplete(Needed::Unknown)));
+/// assert_eq!(state.offset(), 9);
+///
+/// let res: IResult<_, _> = take_until("hello, world\r\n", "\r\n", &mut state);
+/// assert_eq!(res, Ok(("\r\n", "hello, world")));
+/// assert_eq!(state.offset(), 0);
+/// ```
+pub fn take_until<T, I, E>(input: I, tag: T, state: &mut State<()>) -> IResult<I, I, E>
+where
+  I: InputTake + InputLength + FindSubstring<T> + Slice<RangeFrom<usize>>,
+  T: InputLength,
+  E: ParseError<I>,
+{
+  let tag_len = tag.input_len();
+  match state.resume_from(&input).find_substring(tag) {
+    Some(index) => {
+      let end = state.offset + index;
+      state.reset();
+      Ok(input.take_split(end))
+    }
+    None => {
+      // the end of the input could be the start of the pattern
+      state.offset = input
+        .input_len()
+        .saturating_sub(tag_len.saturating_sub(1))
+        .max(state.offset);
+      Err(Err::Incomplete(Needed::Unknown))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::bytes::streaming::tag;
+  use crate::error::Error;
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn many0_resume_calls() {
+    let mut calls = 0;
+    let mut state = State::new();
+    let mut parser = |i| {
+      calls += 1;
+      tag::<_, _, Error<&str>>("ab")(i)
+    };
+
+    assert_eq!(
+      many0(&"ababa"[..], &mut parser, &mut state),
+      Err(Err::Incomplete(Needed::new(1)))
+    );
+    assert_eq!(
+      many0(&"ababab;"[..], &mut parser, &mut state),
+      Ok((";", vec!["ab", "ab", "ab"]))
+    );
+    // 3 calls for the first input, 2 for the second one
+    assert_eq!(calls, 5);
+    assert_eq!(state, State::new());
+  }
+
+  #[test]
+  fn take_until_split_pattern() {
+    let mut state = State::new();
+    assert_eq!(
+      take_until::<_, _, Error<&[u8]>>(&b"abc\r"[..], &b"\r\n"[..], &mut state),
+      Err(Err::Incomplete(Needed::Unknown))
+    );
+    assert_eq!(state.offset(), 3);
+    assert_eq!(
+      take_until::<_, _, Error<&[u8]>>(&b"abc\r\n"[..], &b"\r\n"[..], &mut state),
+      Ok((&b"\r\n"[..], &b"abc"[..]))
+    );
+  }
+}
@@ -0,0 +1,421 @@
+//! Fixed capacity ring buffer usable as streaming input
+//!
+//! A [RingBuffer] stores bytes in a storage allocated once: a producer
+//! appends data at the back with [RingBuffer::write], and parsers consume it
+//! from the front. The buffered data can wrap around the end of the storage,
+//! so it is exposed to parsers as a [RingInput], made of up to two slices.
+//!
+//! Streaming parsers return `Err::Incomplete` when they need more data, but
+//! a ring buffer cannot hold more than its capacity: [RingBuffer::parse]
+//! reports an item that would not fit with a `TooLarge` failure instead.
+//!
+//! ```rust
+//! use nom::{Err, IResult, error::ErrorKind};
+//! use nom::number::streaming::be_u16;
+//! use nom::ring::{RingBuffer, RingInput};
+//!
+//! fn length(i: RingInput) -> IResult<RingInput, u16, (RingInput, ErrorKind)> {
+//!   be_u16(i)
+//! }
+//!
+//! let mut storage = [0u8; 3];
+//! let mut buffer = RingBuffer::new(&mut storage[..]);
+//!
+//! assert_eq!(buffer.write(&[0, 1, 0]), 3);
+//! assert_eq!(buffer.parse(length), Ok(1));
+//!
+//! // the second value wraps around the end of the storage
+//! assert_eq!(buffer.write(&[2]), 1);
+//! assert_eq!(buffer.parse(length), Ok(2));
+//! assert!(buffer.is_empty());
+//! ```
+
+use crate::error::ErrorKind;
+use crate::internal::{Err, IResult, Needed};
+use crate::lib::std::iter::{Chain, Copied, Enumerate};
+use crate::lib::std::ops::{Range, RangeFrom, RangeFull, RangeTo};
+use crate::lib::std::slice::Iter;
+use crate::traits::{InputIter, InputLength, InputTake, Offset, Slice, UnspecializedInput};
+
+/// Fixed capacity byte buffer, written at the back and consumed from the front
+///
+/// The storage can be any mutable byte slice, like an array or a `Vec<u8>`.
+#[derive(Debug)]
+pub struct RingBuffer<S> {
+  storage: S,
+  head: usize,
+  len: usize,
+}
+
+impl<S: AsRef<[u8]> + AsMut<[u8]>> RingBuffer<S> {
+  /// Creates an empty buffer using the whole storage
+  pub fn new(storage: S) -> Self {
+    RingBuffer {
+      storage,
+      head: 0,
+      len: 0,
+    }
+  }
+
+  /// Maximum number of bytes the buffer can hold
+  pub fn capacity(&self) -> usize {
+    self.storage.as_ref().len()
+  }
+
+  /// Number of bytes currently stored
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Returns true if no bytes are stored
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Number of bytes that can still be written
+  pub fn available_space(&self) -> usize {
+    self.capacity() - self.len
+  }
+
+  /// Appends as much of `data` as fits, and returns the number of bytes written
+  pub fn write(&mut self, data: &[u8]) -> usize {
+    let capacity = self.capacity();
+    let count = data.len().min(capacity - self.len);
+    if count == 0 {
+      return 0;
+    }
+
+    let tail = (self.head + self.len) % capacity;
+    let first = count.min(capacity - tail);
+    let storage = self.storage.as_mut();
+    storage[tail..tail + first].copy_from_slice(&data[..first]);
+    storage[..count - first].copy_from_slice(&data[first..count]);
+    self.len += count;
+    count
+  }
+
+  /// Removes `count` bytes from the front of the buffer
+  ///
+  /// panics if `count` is larger than the number of bytes stored
+  pub fn consume(&mut self, count: usize) {
+    assert!(
+      count <= self.len,
+      "cannot consume more than the buffered data"
+    );
+    self.len -= count;
+    self.head = if self.len == 0 {
+      0
+    } else {
+      (self.head + count) % self.capacity()
+    };
+  }
+
+  /// Removes all the stored bytes
+  pub fn clear(&mut self) {
+    self.head = 0;
+    self.len = 0;
+  }
+
+  /// Returns the stored bytes as parser input
+  pub fn input(&self) -> RingInput<'_> {
+    let storage = self.storage.as_ref();
+    let end = self.head + self.len;
+    if end <= storage.len() {
+      RingInput::new(&storage[self.head..end], &[])
+    } else {
+      RingInput::new(&storage[self.head..], &storage[..end - storage.len()])
+    }
+  }
+
+  /// Applies a parser to the stored bytes, and consumes the bytes it used
+  /// if it succeeds
+  ///
+  /// Errors indicate the offset from the front of the buffer at which they
+  /// happened. If the parser needs more data than the buffer could ever hold,
+  /// this returns `Err::Failure((len, ErrorKind::TooLarge))` instead of
+  /// `Err::Incomplete`.
+  pub fn parse<O, F>(&mut self, mut parser: F) -> Result<O, Err<(usize, ErrorKind)>>
+  where
+    F: for<'a> FnMut(RingInput<'a>) -> IResult<RingInput<'a>, O, (RingInput<'a>, ErrorKind)>,
+  {
+    let input = self.input();
+    let res = match parser(input) {
+      Ok((remaining, o)) => Ok((input.offset(&remaining), o)),
+      Err(Err::Error((i, kind))) => Err(Err::Error((input.offset(&i), kind))),
+      Err(Err::Failure((i, kind))) => Err(Err::Failure((input.offset(&i), kind))),
+      Err(Err::Incomplete(needed)) => Err(self.incomplete(needed)),
+    };
+
+    res.map(|(consumed, o)| {
+      self.consume(consumed);
+      o
+    })
+  }
+
+  fn incomplete(&self, needed: Needed) -> Err<(usize, ErrorKind)> {
+    let fits = match needed.size() {
+      None => self.len < self.capacity(),
+      Some(n) => n.get() <= self.available_space(),
+    };
+
+    if fits {
+      Err::Incomplete(needed)
+    } else {
+      Err::Failure((self.len, ErrorKind::TooLarge))
+    }
+  }
+}
+
+/// Bytes stored in a [RingBuffer], as seen by parsers
+///
+/// The data is split in two slices when it wraps around the end of the
+/// storage.
+#[derive(Clone, Copy, Debug)]
+pub struct RingInput<'a> {
+  first: &'a [u8],
+  second: &'a [u8],
+  position: usize,
+}
+
+impl<'a> RingInput<'a> {
+  /// Creates an input from the data at the end of the storage, followed by
+  /// the data at its start
+  pub fn new(first: &'a [u8], second: &'a [u8]) -> Self {
+    if first.is_empty() {
+      RingInput {
+        first: second,
+        second: &[],
+        position: 0,
+      }
+    } else {
+      RingInput {
+        first,
+        second,
+        position: 0,
+      }
+    }
+  }
+
+  /// Returns the data as two slices
+  pub fn as_slices(&self) -> (&'a [u8], &'a [u8]) {
+    (self.first, self.second)
+  }
+
+  /// Returns the data as a contiguous slice, if it does not wrap around
+  pub fn as_contiguous(&self) -> Option<&'a [u8]> {
+    if self.second.is_empty() {
+      Some(self.first)
+    } else {
+      None
+    }
+  }
+
+  /// Copies the data into a `Vec<u8>`
+  #[cfg(feature = "alloc")]
+  #[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+  pub fn to_vec(&self) -> crate::lib::std::vec::Vec<u8> {
+    let mut v = crate::lib::std::vec::Vec::with_capacity(self.input_len());
+    v.extend_from_slice(self.first);
+    v.extend_from_slice(self.second);
+    v
+  }
+
+  fn sub(&self, start: usize, end: usize) -> Self {
+    let split = self.first.len();
+    let (first, second) = if end <= split {
+      (&self.first[start..end], &[][..])
+    } else if start >= split {
+      (&self.second[start - split..end - split], &[][..])
+    } else {
+      (&self.first[start..], &self.second[..end - split])
+    };
+
+    RingInput {
+      first,
+      second,
+      position: self.position + start,
+    }
+  }
+}
+
+impl<'a> PartialEq for RingInput<'a> {
+  fn eq(&self, other: &Self) -> bool {
+    self.input_len() == other.input_len() && self.iter_elements().eq(other.iter_elements())
+  }
+}
+
+impl<'a> Eq for RingInput<'a> {}
+
+impl<'a, 'b> PartialEq<&'b [u8]> for RingInput<'a> {
+  fn eq(&self, other: &&'b [u8]) -> bool {
+    self.input_len() == other.len() && self.iter_elements().eq(other.iter().copied())
+  }
+}
+
+impl<'a> InputLength for RingInput<'a> {
+  #[inline]
+  fn input_len(&self) -> usize {
+    self.first.len() + self.second.len()
+  }
+}
+
+impl<'a> InputIter for RingInput<'a> {
+  type Item = u8;
+  type Iter = Enumerate<Self::IterElem>;
+  type IterElem = Copied<Chain<Iter<'a, u8>, Iter<'a, u8>>>;
+
+  #[inline]
+  fn iter_indices(&self) -> Self::Iter {
+    self.iter_elements().enumerate()
+  }
+
+  #[inline]
+  fn iter_elements(&self) -> Self::IterElem {
+    self.first.iter().chain(self.second.iter()).copied()
+  }
+
+  #[inline]
+  fn position<P>(&self, predicate: P) -> Option<usize>
+  where
+    P: Fn(Self::Item) -> bool,
+  {
+    self.iter_elements().position(predicate)
+  }
+
+  #[inline]
+  fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+    let len = self.input_len();
+    if len >= count {
+      Ok(count)
+    } else {
+      Err(Needed::new(count - len))
+    }
+  }
+}
+
+impl<'a> InputTake for RingInput<'a> {
+  #[inline]
+  fn take(&self, count: usize) -> Self {
+    self.sub(0, count)
+  }
+
+  #[inline]
+  fn take_split(&self, count: usize) -> (Self, Self) {
+    (self.sub(count, self.input_len()), self.sub(0, count))
+  }
+}
+
+impl<'a> UnspecializedInput for RingInput<'a> {}
+
+impl<'a> Offset for RingInput<'a> {
+  fn offset(&self, second: &Self) -> usize {
+    second.position - self.position
+  }
+}
+
+impl<'a> Slice<Range<usize>> for RingInput<'a> {
+  fn slice(&self, range: Range<usize>) -> Self {
+    self.sub(range.start, range.end)
+  }
+}
+
+impl<'a> Slice<RangeFrom<usize>> for RingInput<'a> {
+  fn slice(&self, range: RangeFrom<usize>) -> Self {
+    self.sub(range.start, self.input_len())
+  }
+}
+
+impl<'a> Slice<RangeTo<usize>> for RingInput<'a> {
+  fn slice(&self, range: RangeTo<usize>) -> Self {
+    self.sub(0, range.end)
+  }
+}
+
+impl<'a> Slice<RangeFull> for RingInput<'a> {
+  fn slice(&self, _: RangeFull) -> Self {
+    *self
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::bytes::streaming::{tag, take, take_while1};
+  use crate::number::streaming::be_u32;
+
+  type Res<'a, O> = IResult<RingInput<'a>, O, (RingInput<'a>, ErrorKind)>;
+
+  fn word(i: RingInput) -> Res<RingInput> {
+    let (i, _) = tag(&b"<"[..])(i)?;
+    let (i, w) = take_while1(|c: u8| c.is_ascii_alphabetic())(i)?;
+    let (i, _) = tag(&b">"[..])(i)?;
+    Ok((i, w))
+  }
+
+  fn word_vec(i: RingInput) -> Res<Vec<u8>> {
+    word(i).map(|(i, w)| (i, w.to_vec()))
+  }
+
+  fn skip_word(i: RingInput) -> Res<()> {
+    word(i).map(|(i, _)| (i, ()))
+  }
+
+  fn number(i: RingInput) -> Res<u32> {
+    be_u32(i)
+  }
+
+  fn skip_5(i: RingInput) -> Res<()> {
+    take(5usize)(i).map(|(i, _)| (i, ()))
+  }
+
+  #[test]
+  fn parse_across_wrap() {
+    let mut storage = [0u8; 8];
+    let mut buffer = RingBuffer::new(&mut storage[..]);
+
+    assert_eq!(buffer.write(b"<abc><de"), 8);
+    assert_eq!(buffer.write(b"f>"), 0);
+    assert_eq!(buffer.parse(word_vec), Ok(b"abc".to_vec()));
+    assert_eq!(buffer.len(), 3);
+
+    assert_eq!(buffer.parse(word_vec), Err(Err::Incomplete(Needed::new(1))));
+    assert_eq!(buffer.write(b"f>"), 2);
+    assert_eq!(buffer.input().as_contiguous(), None);
+    assert_eq!(buffer.parse(word_vec), Ok(b"def".to_vec()));
+    assert!(buffer.is_empty());
+  }
+
+  #[test]
+  fn error_offset() {
+    let mut storage = [0u8; 4];
+    let mut buffer = RingBuffer::new(&mut storage[..]);
+
+    buffer.write(b"<1>");
+    assert_eq!(
+      buffer.parse(skip_word),
+      Err(Err::Error((1, ErrorKind::TakeWhile1)))
+    );
+    assert_eq!(buffer.len(), 3);
+  }
+
+  #[test]
+  fn needed_exceeds_capacity() {
+    let mut storage = [0u8; 4];
+    let mut buffer = RingBuffer::new(&mut storage[..]);
+
+    buffer.write(&[1, 2]);
+    assert_eq!(buffer.parse(number), Err(Err::Incomplete(Needed::new(2))));
+    assert_eq!(
+      buffer.parse(skip_5),
+      Err(Err::Failure((2, ErrorKind::TooLarge)))
+    );
+
+    buffer.write(&[3, 4]);
+    assert_eq!(buffer.parse(number), Ok(0x0102_0304));
+    buffer.write(b"<abc");
+    assert_eq!(
+      buffer.parse(skip_word),
+      Err(Err::Failure((4, ErrorKind::TooLarge)))
+    );
+  }
+}
@@ -0,0 +1,269 @@
+//! Parser for semantic versions
+//!
+//! [version] recognizes the version strings of the Semantic Versioning 2.0.0
+//! specification, like `1.0.0-alpha.1+build.5`, as found in package manifests
+//! and lockfiles. The pre-release and build metadata parts are returned as
+//! slices of the input.
+//!
+//! This parser works on complete input.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::ops::{Range, RangeFrom};
+use crate::traits::{AsChar, InputIter, Slice};
+
+/// A semantic version
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Version<I> {
+  /// Major version
+  pub major: u64,
+  /// Minor version
+  pub minor: u64,
+  /// Patch version
+  pub patch: u64,
+  /// Pre-release identifiers, separated by dots, without the leading `-`
+  pub pre: Option<I>,
+  /// Build metadata identifiers, separated by dots, without the leading `+`
+  pub build: Option<I>,
+}
+
+fn error<I, E>(input: &I, pos: usize) -> Err<E>
+where
+  I: Slice<RangeFrom<usize>>,
+  E: ParseError<I>,
+{
+  Err::Error(E::from_error_kind(input.slice(pos..), ErrorKind::SemVer))
+}
+
+fn is_identifier_char(c: char) -> bool {
+  c.is_ascii_alphanumeric() || c == '-'
+}
+
+fn char_at<I>(input: &I, pos: usize) -> Option<char>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+{
+  input
+    .slice(pos..)
+    .iter_elements()
+    .next()
+    .map(AsChar::as_char)
+}
+
+/// ASCII characters of the input, as the positions are byte offsets
+fn chars<I>(input: &I) -> impl Iterator<Item = char> + '_
+where
+  I: InputIter,
+  <I as InputIter>::Item: AsChar,
+{
+  input
+    .iter_elements()
+    .map(AsChar::as_char)
+    .take_while(char::is_ascii)
+}
+
+/// Reads a numeric identifier without leading zeros at `pos`, returning its
+/// value and end position
+fn numeric<I, E>(input: &I, pos: usize) -> Result<(u64, usize), Err<E>>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let rest = input.slice(pos..);
+  let mut value: u64 = 0;
+  let mut len = 0;
+  for c in chars(&rest) {
+    let d = match c.to_digit(10) {
+      Some(d) => d,
+      None => break,
+    };
+    if len == 1 && value == 0 {
+      return Err(error(input, pos));
+    }
+    value = match value
+      .checked_mul(10)
+      .and_then(|v| v.checked_add(u64::from(d)))
+    {
+      Some(v) => v,
+      None => {
+        return Err(Err::Error(E::from_error_kind(
+          input.slice(pos..),
+          ErrorKind::TooLarge,
+        )))
+      }
+    };
+    len += 1;
+  }
+
+  if len == 0 {
+    Err(error(input, pos))
+  } else {
+    Ok((value, pos + len))
+  }
+}
+
+/// Reads dot separated identifiers at `pos`, returning the end position.
+/// Pre-release identifiers made of digits cannot have leading zeros
+fn identifiers<I, E>(input: &I, pos: usize, pre_release: bool) -> Result<usize, Err<E>>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let rest = input.slice(pos..);
+  let mut it = chars(&rest).peekable();
+  let mut end = pos;
+  loop {
+    let start = end;
+    let mut numeric = true;
+    while let Some(&c) = it.peek() {
+      if !is_identifier_char(c) {
+        break;
+      }
+      numeric &= c.is_ascii_digit();
+      it.next();
+      end += 1;
+    }
+
+    let empty = end == start;
+    let leading_zero = numeric && end - start > 1 && char_at(input, start) == Some('0');
+    if empty || (pre_release && leading_zero) {
+      return Err(error(input, start));
+    }
+
+    if it.peek() == Some(&'.') {
+      it.next();
+      end += 1;
+    } else {
+      return Ok(end);
+    }
+  }
+}
+
+/// Recognizes a semantic version: three numbers separated by dots, followed
+/// by optional pre-release identifiers after a `-`, and optional build
+/// metadata identifiers after a `+`.
+///
+/// It returns an `ErrorKind::SemVer` error at the position of an invalid
+/// component: a missing number, a number or a numeric pre-release identifier
+/// with leading zeros, or an empty identifier. A number that does not fit in
+/// a `u64` is an `ErrorKind::TooLarge` error.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::semver::{version, Version};
+///
+/// fn parser(i: &str) -> IResult<&str, Version<&str>> {
+///   version(i)
+/// }
+///
+/// assert_eq!(parser("1.20.3"), Ok(("", Version { major: 1, minor: 20, patch: 3, pre: None, build: None })));
+/// assert_eq!(
+///   parser("1.0.0-rc.1+build.5 "),
+///   Ok((" ", Version { major: 1, minor: 0, patch: 0, pre: Some("rc.1"), build: Some("build.5") }))
+/// );
+/// assert_eq!(parser("1.02.3"), Err(Err::Error(Error::new("02.3", ErrorKind::SemVer))));
+/// assert_eq!(parser("1.2"), Err(Err::Error(Error::new("", ErrorKind::SemVer))));
+/// assert_eq!(parser("1.2.3-"), Err(Err::Error(Error::new("", ErrorKind::SemVer))));
+/// ```
+pub fn version<I, E>(input: I) -> IResult<I, Version<I>, E>
+where
+  I: InputIter + Slice<Range<usize>> + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let mut numbers = [0; 3];
+  let mut pos = 0;
+  for (i, n) in numbers.iter_mut().enumerate() {
+    if i > 0 {
+      if char_at(&input, pos) != Some('.') {
+        return Err(error(&input, pos));
+      }
+      pos += 1;
+    }
+    let (value, end) = numeric(&input, pos)?;
+    *n = value;
+    pos = end;
+  }
+
+  let mut pre = None;
+  if char_at(&input, pos) == Some('-') {
+    let end = identifiers(&input, pos + 1, true)?;
+    pre = Some(input.slice(pos + 1..end));
+    pos = end;
+  }
+
+  let mut build = None;
+  if char_at(&input, pos) == Some('+') {
+    let end = identifiers(&input, pos + 1, false)?;
+    build = Some(input.slice(pos + 1..end));
+    pos = end;
+  }
+
+  let version = Version {
+    major: numbers[0],
+    minor: numbers[1],
+    patch: numbers[2],
+    pre,
+    build,
+  };
+  Ok((input.slice(pos..), version))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  #[test]
+  fn version_identifiers() {
+    let p = version::<_, Error<&[u8]>>;
+    assert_eq!(
+      p(&b"0.0.0-0.a-b.010a--+001.x-"[..]),
+      Ok((
+        &b""[..],
+        Version {
+          major: 0,
+          minor: 0,
+          patch: 0,
+          pre: Some(&b"0.a-b.010a--"[..]),
+          build: Some(&b"001.x-"[..]),
+        }
+      ))
+    );
+    assert_eq!(
+      p(&b"1.2.3-alpha.01"[..]),
+      Err(Err::Error(Error::new(&b"01"[..], ErrorKind::SemVer)))
+    );
+    assert_eq!(
+      p(&b"1.2.3+a..b"[..]),
+      Err(Err::Error(Error::new(&b".b"[..], ErrorKind::SemVer)))
+    );
+    assert_eq!(
+      p(&b"1.2.3-a_b"[..]),
+      Ok((
+        &b"_b"[..],
+        Version {
+          major: 1,
+          minor: 2,
+          patch: 3,
+          pre: Some(&b"a"[..]),
+          build: None,
+        }
+      ))
+    );
+    assert_eq!(
+      p(&b"1.18446744073709551616.0"[..]),
+      Err(Err::Error(Error::new(
+        &b"18446744073709551616.0"[..],
+        ErrorKind::TooLarge
+      )))
+    );
+    assert_eq!(
+      p(&b"18446744073709551615.0.0"[..]).map(|(_, v)| v.major),
+      Ok(core::u64::MAX)
+    );
+  }
+}
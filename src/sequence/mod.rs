@@ -3,8 +3,10 @@
 #[macro_use]
 mod macros;
 
-use crate::error::ParseError;
-use crate::internal::{IResult, Parser};
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult, Parser};
+use crate::lib::std::ops::RangeTo;
+use crate::traits::{InputLength, Offset, Slice};
 
 /// Gets an object from the first parser,
 /// then gets another object from the second parser.
@@ -255,9 +257,286 @@ where
   delimited(first, second, third)(input)
 }
 
+/// Like [delimited], but reports the unmatched opening delimiter if the third parser
+/// fails, with error types recording several frames like [VerboseError](crate::error::VerboseError).
+///
+/// An `ErrorKind::Unclosed` frame is appended to the error of the third parser at the
+/// position of the first parser, so the error points at the missing closing delimiter
+/// and at the opening one. Error types keeping a single frame, like the default
+/// [Error](crate::error::Error) and `(I, ErrorKind)`, drop that frame in
+/// [append](ParseError::append) and only report the error of the third parser.
+///
+/// # Arguments
+/// * `first` The first parser to apply and discard.
+/// * `second` The second parser to apply.
+/// * `third` The third parser to apply and discard.
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind, VerboseError, VerboseErrorKind}, IResult};
+/// use nom::sequence::delimited_spanned;
+/// use nom::character::complete::{alpha1, char};
+///
+/// fn parser(i: &str) -> IResult<&str, &str, VerboseError<&str>> {
+///   delimited_spanned(char('('), alpha1, char(')'))(i)
+/// }
+///
+/// assert_eq!(parser("(abc)def"), Ok(("def", "abc")));
+/// assert_eq!(
+///   parser("(abc"),
+///   Err(Err::Error(VerboseError {
+///     errors: vec![
+///       ("", VerboseErrorKind::Char(')')),
+///       ("(abc", VerboseErrorKind::Nom(ErrorKind::Unclosed)),
+///     ]
+///   }))
+/// );
+///
+/// // the default error type only keeps the error of the closing delimiter
+/// fn short(i: &str) -> IResult<&str, &str> {
+///   delimited_spanned(char('('), alpha1, char(')'))(i)
+/// }
+///
+/// assert_eq!(short("(abc"), Err(Err::Error(Error::new("", ErrorKind::Char))));
+/// ```
+pub fn delimited_spanned<I, O1, O2, O3, E: ParseError<I>, F, G, H>(
+  mut first: F,
+  mut second: G,
+  mut third: H,
+) -> impl FnMut(I) -> IResult<I, O2, E>
+where
+  I: Clone,
+  F: Parser<I, O1, E>,
+  G: Parser<I, O2, E>,
+  H: Parser<I, O3, E>,
+{
+  move |input: I| {
+    let (i, _) = first.parse(input.clone())?;
+    let (i, o2) = second.parse(i)?;
+    match third.parse(i) {
+      Ok((i, _)) => Ok((i, o2)),
+      Err(e) => Err(unclosed(input, e)),
+    }
+  }
+}
+
+/// Applies the `close` parser, recognizing the delimiter that closes a
+/// construct opened at `open`, and reports `open` if it fails, with error types
+/// recording several frames like [VerboseError](crate::error::VerboseError).
+///
+/// If `close` fails, an `ErrorKind::Unclosed` frame is appended to its error
+/// at the position of `open`. As with [delimited_spanned], error types keeping
+/// a single frame, like the default [Error](crate::error::Error), drop it and
+/// only report the error of `close`. Use it when the opening delimiter is not
+/// parsed by [delimited_spanned], like in a loop.
+///
+/// # Arguments
+/// * `open` The input at the opening delimiter.
+/// * `close` The parser recognizing the closing delimiter.
+/// ```rust
+/// # use nom::{Err, error::{ErrorKind, VerboseError, VerboseErrorKind}, IResult};
+/// use nom::sequence::closing_for;
+/// use nom::bytes::complete::take_while;
+/// use nom::character::complete::char;
+///
+/// fn string(i: &str) -> IResult<&str, &str, VerboseError<&str>> {
+///   let (rest, _) = char('"')(i)?;
+///   let (rest, content) = take_while(|c| c != '"' && c != '\n')(rest)?;
+///   let (rest, _) = closing_for(i, char('"'))(rest)?;
+///   Ok((rest, content))
+/// }
+///
+/// assert_eq!(string("\"abc\" def"), Ok((" def", "abc")));
+/// assert_eq!(
+///   string("\"abc\ndef"),
+///   Err(Err::Error(VerboseError {
+///     errors: vec![
+///       ("\ndef", VerboseErrorKind::Char('"')),
+///       ("\"abc\ndef", VerboseErrorKind::Nom(ErrorKind::Unclosed)),
+///     ]
+///   }))
+/// );
+/// ```
+pub fn closing_for<I, O, E: ParseError<I>, F>(
+  open: I,
+  mut close: F,
+) -> impl FnMut(I) -> IResult<I, O, E>
+where
+  I: Clone,
+  F: Parser<I, O, E>,
+{
+  move |input: I| close.parse(input).map_err(|e| unclosed(open.clone(), e))
+}
+
+fn unclosed<I, E: ParseError<I>>(open: I, e: Err<E>) -> Err<E> {
+  e.map(|e| E::append(open, ErrorKind::Unclosed, e))
+}
+
+/// Applies `trivia` as long as it succeeds and consumes input
+fn skip_trivia<I, O, E, T>(trivia: &mut T, mut input: I) -> Result<I, Err<E>>
+where
+  I: Clone + InputLength,
+  E: ParseError<I>,
+  T: Parser<I, O, E>,
+{
+  loop {
+    match trivia.parse(input.clone()) {
+      Ok((i, _)) if i.input_len() != input.input_len() => input = i,
+      Ok(_) | Err(Err::Error(_)) => return Ok(input),
+      Err(e) => return Err(e),
+    }
+  }
+}
+
+/// Skips the trivia around a token: whitespace, comments, or anything else
+/// recognized by `trivia`.
+///
+/// `trivia` is applied repeatedly before and after `parser`, until it fails
+/// or stops consuming input, so it only needs to recognize one space or comment.
+/// Use [Lexer] to wrap all the tokens of a grammar with the same trivia.
+///
+/// # Arguments
+/// * `trivia` The parser recognizing an element to skip.
+/// * `parser` The token parser.
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::sequence::{lexeme, delimited, preceded};
+/// use nom::branch::alt;
+/// use nom::bytes::complete::{tag, take_until};
+/// use nom::character::complete::{alpha1, multispace1, not_line_ending};
+///
+/// fn trivia(i: &str) -> IResult<&str, &str> {
+///   alt((
+///     multispace1,
+///     preceded(tag("//"), not_line_ending),
+///     delimited(tag("/*"), take_until("*/"), tag("*/")),
+///   ))(i)
+/// }
+///
+/// let mut parser = lexeme(trivia, alpha1);
+///
+/// assert_eq!(parser(" /* a */ abc // b\n def"), Ok(("def", "abc")));
+/// assert_eq!(parser("abc"), Ok(("", "abc")));
+/// assert_eq!(parser("/* a */ 123"), Err(Err::Error(Error::new("123", ErrorKind::Alpha))));
+/// ```
+pub fn lexeme<I, O1, O2, E: ParseError<I>, T, F>(
+  mut trivia: T,
+  mut parser: F,
+) -> impl FnMut(I) -> IResult<I, O2, E>
+where
+  I: Clone + InputLength,
+  T: Parser<I, O1, E>,
+  F: Parser<I, O2, E>,
+{
+  move |input: I| {
+    let input = skip_trivia(&mut trivia, input)?;
+    let (input, o) = parser.parse(input)?;
+    let input = skip_trivia(&mut trivia, input)?;
+    Ok((input, o))
+  }
+}
+
+/// Like [lexeme], but keeps the trivia around the token, for lossless parsing.
+///
+/// Returns the parts of the input recognized as leading and trailing trivia,
+/// with the token's output in between. They are empty if there is no trivia.
+///
+/// # Arguments
+/// * `trivia` The parser recognizing one element of trivia.
+/// * `parser` The token parser.
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::sequence::{with_trivia, preceded};
+/// use nom::branch::alt;
+/// use nom::bytes::complete::tag;
+/// use nom::character::complete::{alpha1, multispace1, not_line_ending};
+///
+/// fn trivia(i: &str) -> IResult<&str, &str> {
+///   alt((multispace1, preceded(tag("#"), not_line_ending)))(i)
+/// }
+///
+/// let mut parser = with_trivia(trivia, alpha1);
+///
+/// assert_eq!(parser("  abc # b\ndef"), Ok(("def", ("  ", "abc", " # b\n"))));
+/// assert_eq!(parser("abc"), Ok(("", ("", "abc", ""))));
+/// assert_eq!(parser(" 123"), Err(Err::Error(Error::new("123", ErrorKind::Alpha))));
+/// ```
+pub fn with_trivia<I, O1, O2, E: ParseError<I>, T, F>(
+  mut trivia: T,
+  mut parser: F,
+) -> impl FnMut(I) -> IResult<I, (I, O2, I), E>
+where
+  I: Clone + InputLength + Offset + Slice<RangeTo<usize>>,
+  T: Parser<I, O1, E>,
+  F: Parser<I, O2, E>,
+{
+  move |input: I| {
+    let i = skip_trivia(&mut trivia, input.clone())?;
+    let leading = input.slice(..input.offset(&i));
+    let (token_end, o) = parser.parse(i)?;
+    let i = skip_trivia(&mut trivia, token_end.clone())?;
+    let trailing = token_end.slice(..token_end.offset(&i));
+    Ok((i, (leading, o, trailing)))
+  }
+}
+
+/// Wraps the token parsers of a grammar to skip the same trivia around them.
+///
+/// ```rust
+/// # use nom::IResult;
+/// use nom::sequence::{preceded, tuple, Lexer};
+/// use nom::branch::alt;
+/// use nom::bytes::complete::tag;
+/// use nom::character::complete::{alpha1, digit1, multispace1, not_line_ending};
+///
+/// fn trivia(i: &str) -> IResult<&str, &str> {
+///   alt((multispace1, preceded(tag("#"), not_line_ending)))(i)
+/// }
+///
+/// let lexer = Lexer::new(trivia);
+/// let mut assignment = tuple((lexer.token(alpha1), lexer.token(tag("=")), lexer.token(digit1)));
+///
+/// assert_eq!(assignment("a # name\n = 1 # value\n"), Ok(("", ("a", "=", "1"))));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Lexer<T> {
+  trivia: T,
+}
+
+impl<T: Clone> Lexer<T> {
+  /// Creates a lexer skipping the elements recognized by `trivia`
+  pub fn new(trivia: T) -> Self {
+    Lexer { trivia }
+  }
+
+  /// Wraps a token parser to skip the trivia around it, as done by [lexeme]
+  pub fn token<I, O1, O2, E: ParseError<I>, F>(
+    &self,
+    parser: F,
+  ) -> impl FnMut(I) -> IResult<I, O2, E>
+  where
+    I: Clone + InputLength,
+    T: Parser<I, O1, E>,
+    F: Parser<I, O2, E>,
+  {
+    lexeme(self.trivia.clone(), parser)
+  }
+
+  /// Wraps a token parser to return the trivia around it, as done by [with_trivia]
+  pub fn token_with_trivia<I, O1, O2, E: ParseError<I>, F>(
+    &self,
+    parser: F,
+  ) -> impl FnMut(I) -> IResult<I, (I, O2, I), E>
+  where
+    I: Clone + InputLength + Offset + Slice<RangeTo<usize>>,
+    T: Parser<I, O1, E>,
+    F: Parser<I, O2, E>,
+  {
+    with_trivia(self.trivia.clone(), parser)
+  }
+}
+
 /// Helper trait for the tuple combinator.
 ///
-/// This trait is implemented for tuples of parsers of up to 21 elements.
+/// This trait is implemented for tuples of parsers of up to 32 elements.
 pub trait Tuple<I, O, E> {
   /// Parses the input and returns a tuple of results of each parser.
   fn parse(&mut self, input: I) -> IResult<I, O, E>;
@@ -319,10 +598,13 @@ macro_rules! tuple_trait_inner(
 );
 
 tuple_trait!(FnA A, FnB B, FnC C, FnD D, FnE E, FnF F, FnG G, FnH H, FnI I, FnJ J, FnK K, FnL L,
-  FnM M, FnN N, FnO O, FnP P, FnQ Q, FnR R, FnS S, FnT T, FnU U);
+  FnM M, FnN N, FnO O, FnP P, FnQ Q, FnR R, FnS S, FnT T, FnU U, FnV V, FnW W, FnX X, FnY Y,
+  FnZ Z, FnAA AA, FnAB AB, FnAC AC, FnAD AD, FnAE AE, FnAF AF);
 
 ///Applies a tuple of parsers one by one and returns their results as a tuple.
 ///
+/// Tuples of up to 32 parsers are supported, longer sequences can nest `tuple` calls.
+///
 /// ```rust
 /// # use nom::{Err, error::ErrorKind};
 /// use nom::sequence::tuple;
@@ -354,4 +636,117 @@ mod tests {
       Err(Err::Error(("123def", ErrorKind::Alpha)))
     );
   }
+
+  #[test]
+  fn delimited_spanned_nested() {
+    use crate::character::complete::{char, digit1};
+    use crate::error::{ErrorKind, VerboseError, VerboseErrorKind};
+    use crate::{Err, IResult};
+
+    fn expr(i: &str) -> IResult<&str, &str, VerboseError<&str>> {
+      crate::branch::alt((digit1, delimited_spanned(char('('), expr, char(')'))))(i)
+    }
+
+    assert_eq!(expr("((1))"), Ok(("", "1")));
+
+    // the inner group is closed, the outer one is reported
+    let input = "((1)";
+    let e = match expr(input) {
+      Err(Err::Error(e)) => e,
+      res => panic!("unexpected result: {:?}", res),
+    };
+    assert_eq!(
+      e.errors[..2],
+      [
+        ("", VerboseErrorKind::Char(')')),
+        (input, VerboseErrorKind::Nom(ErrorKind::Unclosed)),
+      ]
+    );
+
+    // errors inside the delimiters are not reported as unclosed
+    let e = match expr("(x)") {
+      Err(Err::Error(e)) => e,
+      res => panic!("unexpected result: {:?}", res),
+    };
+    assert!(e
+      .errors
+      .iter()
+      .all(|(_, kind)| *kind != VerboseErrorKind::Nom(ErrorKind::Unclosed)));
+  }
+
+  #[test]
+  fn delimited_spanned_single_frame() {
+    use crate::character::complete::{alpha1, char};
+    use crate::error::ErrorKind;
+    use crate::Err;
+
+    // the Unclosed frame is dropped by error types keeping a single frame
+    let mut parser =
+      delimited_spanned::<_, _, _, _, (&str, ErrorKind), _, _, _>(char('('), alpha1, char(')'));
+    assert_eq!(parser("(abc"), Err(Err::Error(("", ErrorKind::Char))));
+    let mut close = closing_for::<_, _, (&str, ErrorKind), _>("(abc", char(')'));
+    assert_eq!(close("abc"), Err(Err::Error(("abc", ErrorKind::Char))));
+  }
+
+  #[test]
+  fn lexeme_trivia_progress() {
+    use crate::bytes::complete::tag;
+    use crate::character::complete::{alpha1, space0};
+    use crate::combinator::cut;
+    use crate::{error::ErrorKind, Err};
+
+    let mut parser = lexeme::<_, _, _, (&str, ErrorKind), _, _>(space0, alpha1);
+    assert_eq!(parser("  abc  "), Ok(("", "abc")));
+
+    let mut parser = lexeme::<_, _, _, (&str, ErrorKind), _, _>(cut(tag("#")), alpha1);
+    assert_eq!(parser("abc"), Err(Err::Failure(("abc", ErrorKind::Tag))));
+  }
+
+  #[test]
+  #[cfg(feature = "alloc")]
+  fn with_trivia_lossless() {
+    use crate::bytes::complete::tag;
+    use crate::character::complete::{alpha1, digit1, space1};
+    use crate::error::ErrorKind;
+
+    let lexer = Lexer::new(space1::<&[u8], (&[u8], ErrorKind)>);
+    let mut assignment = tuple((
+      lexer.token_with_trivia(alpha1),
+      lexer.token_with_trivia(tag("=")),
+      lexer.token_with_trivia(digit1),
+    ));
+
+    let input = &b" a =1  ;"[..];
+    let (rest, tokens) = assignment(input).unwrap();
+    assert_eq!(rest, &b";"[..]);
+
+    // the leading trivia, tokens and trailing trivia cover the whole input
+    let mut rebuilt = crate::lib::std::vec::Vec::new();
+    for (leading, token, trailing) in [tokens.0, tokens.1, tokens.2].iter() {
+      rebuilt.extend_from_slice(leading);
+      rebuilt.extend_from_slice(token);
+      rebuilt.extend_from_slice(trailing);
+    }
+    rebuilt.extend_from_slice(rest);
+    assert_eq!(&rebuilt[..], input);
+  }
+
+  #[test]
+  fn long_tuples() {
+    use crate::error::ErrorKind;
+    use crate::number::complete::u8;
+
+    let mut input = [0u8; 33];
+    for (i, b) in input.iter_mut().enumerate() {
+      *b = i as u8;
+    }
+    let p = u8::<_, (&[u8], ErrorKind)>;
+    let mut parser = tuple((
+      p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p, p,
+      p,
+    ));
+    let (rest, o) = parser(&input[..]).unwrap();
+    assert_eq!(rest, &[32][..]);
+    assert_eq!((o.0, o.12, o.21, o.25, o.31), (0, 12, 21, 25, 31));
+  }
 }
@@ -0,0 +1,275 @@
+//! Helpers to test parsers
+//!
+//! [assert_parses!] checks the output of a parser and the input it leaves,
+//! defaulting to the whole input being consumed, [assert_error_at!] checks the
+//! position and kind of an error, and [run_cases] tests a parser against a
+//! table of inputs.
+//!
+//! ```rust
+//! use nom::{assert_error_at, assert_parses, IResult};
+//! use nom::character::complete::digit1;
+//! use nom::error::ErrorKind;
+//!
+//! fn number(i: &str) -> IResult<&str, &str> {
+//!   digit1(i)
+//! }
+//!
+//! assert_parses!(number, "123", "123");
+//! assert_parses!(number, "123;", "123", ";");
+//! assert_error_at!(number, "abc", 0, ErrorKind::Digit);
+//! ```
+
+use crate::error::{self, ErrorKind};
+use crate::internal::{Err, Needed, Parser};
+use crate::lib::std::fmt::Debug;
+use crate::traits::Offset;
+
+/// Error types that record where the error happened
+pub trait ErrorPosition<I> {
+  /// Returns the input at which the error happened, and its kind
+  ///
+  /// `None` if the error does not record it
+  fn position(&self) -> Option<(&I, ErrorKind)>;
+}
+
+impl<I> ErrorPosition<I> for error::Error<I> {
+  fn position(&self) -> Option<(&I, ErrorKind)> {
+    Some((&self.input, self.code))
+  }
+}
+
+impl<I> ErrorPosition<I> for (I, ErrorKind) {
+  fn position(&self) -> Option<(&I, ErrorKind)> {
+    Some((&self.0, self.1))
+  }
+}
+
+/// The position is the one of the innermost nom error, ignoring contexts
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "alloc")))]
+impl<I> ErrorPosition<I> for error::VerboseError<I> {
+  fn position(&self) -> Option<(&I, ErrorKind)> {
+    self.errors.iter().find_map(|(i, kind)| match kind {
+      error::VerboseErrorKind::Nom(kind) => Some((i, *kind)),
+      error::VerboseErrorKind::Char(_) => Some((i, ErrorKind::Char)),
+      error::VerboseErrorKind::Tag(_) => Some((i, ErrorKind::Tag)),
+      error::VerboseErrorKind::Context(_) | error::VerboseErrorKind::Element(..) => None,
+    })
+  }
+}
+
+/// Asserts that a parser succeeds with the expected output
+///
+/// With three arguments, the parser must consume the whole input; the
+/// fourth argument gives the expected remaining input otherwise.
+///
+/// ```rust
+/// use nom::{assert_parses, IResult};
+/// use nom::bytes::complete::tag;
+///
+/// fn abc(i: &[u8]) -> IResult<&[u8], &[u8]> {
+///   tag("abc")(i)
+/// }
+///
+/// assert_parses!(abc, &b"abc"[..], &b"abc"[..]);
+/// assert_parses!(abc, &b"abcd"[..], &b"abc"[..], &b"d"[..]);
+/// ```
+#[macro_export]
+macro_rules! assert_parses (
+  ($parser:expr, $input:expr, $expected:expr) => (
+    match $crate::Parser::parse(&mut $parser, $input) {
+      Ok((remaining, output)) => {
+        assert_eq!(output, $expected, "unexpected parser output");
+        assert!(
+          $crate::InputLength::input_len(&remaining) == 0,
+          "the parser did not consume the whole input, remaining: {:?}",
+          remaining
+        );
+      }
+      Err(e) => panic!("the parser failed: {:?}", e),
+    }
+  );
+  ($parser:expr, $input:expr, $expected:expr, $remaining:expr) => (
+    match $crate::Parser::parse(&mut $parser, $input) {
+      Ok((remaining, output)) => {
+        assert_eq!(output, $expected, "unexpected parser output");
+        assert_eq!(remaining, $remaining, "unexpected remaining input");
+      }
+      Err(e) => panic!("the parser failed: {:?}", e),
+    }
+  );
+);
+
+/// Asserts that a parser fails at the offset with the error kind
+///
+/// Both `Err::Error` and `Err::Failure` are accepted. The error type must
+/// implement [ErrorPosition](testing::ErrorPosition).
+///
+/// ```rust
+/// use nom::{assert_error_at, IResult};
+/// use nom::character::complete::{alpha1, digit1};
+/// use nom::error::ErrorKind;
+/// use nom::sequence::pair;
+///
+/// fn ident(i: &str) -> IResult<&str, (&str, &str)> {
+///   pair(alpha1, digit1)(i)
+/// }
+///
+/// assert_error_at!(ident, "abc;", 3, ErrorKind::Digit);
+/// ```
+#[macro_export]
+macro_rules! assert_error_at (
+  ($parser:expr, $input:expr, $offset:expr, $kind:expr) => (
+    {
+      let input = $input;
+      match $crate::Parser::parse(&mut $parser, input.clone()) {
+        Err($crate::Err::Error(e)) | Err($crate::Err::Failure(e)) => {
+          match $crate::testing::ErrorPosition::position(&e) {
+            Some((at, kind)) => {
+              assert_eq!($crate::Offset::offset(&input, at), $offset, "unexpected error offset");
+              assert_eq!(kind, $kind, "unexpected error kind");
+            }
+            None => panic!("the error does not record its position: {:?}", e),
+          }
+        }
+        other => panic!("expected an error, got {:?}", other),
+      }
+    }
+  );
+);
+
+/// Expected result of a parser for [run_cases]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expect<I, O> {
+  /// The parser succeeds with the output and the remaining input
+  Parsed(O, I),
+  /// The parser returns `Err::Error` or `Err::Failure` at the offset, with the error kind
+  ErrorAt(usize, ErrorKind),
+  /// The parser returns `Err::Incomplete`
+  Incomplete(Needed),
+}
+
+/// Tests a parser against a table of inputs and expected results
+///
+/// Panics on the first mismatch, indicating the index of the case.
+///
+/// ```rust
+/// use nom::IResult;
+/// use nom::character::complete::digit1;
+/// use nom::error::ErrorKind;
+/// use nom::testing::{run_cases, Expect};
+///
+/// fn number(i: &str) -> IResult<&str, &str> {
+///   digit1(i)
+/// }
+///
+/// run_cases(number, &[
+///   ("123", Expect::Parsed("123", "")),
+///   ("12;", Expect::Parsed("12", ";")),
+///   (";", Expect::ErrorAt(0, ErrorKind::Digit)),
+/// ]);
+/// ```
+pub fn run_cases<I, O, E, P>(mut parser: P, cases: &[(I, Expect<I, O>)])
+where
+  I: Clone + Offset + PartialEq + Debug,
+  O: PartialEq + Debug,
+  E: ErrorPosition<I> + Debug,
+  P: Parser<I, O, E>,
+{
+  for (index, (input, expected)) in cases.iter().enumerate() {
+    let res = parser.parse(input.clone());
+    match (res, expected) {
+      (Ok((remaining, output)), Expect::Parsed(o, r)) => {
+        assert_eq!(&output, o, "case {}: unexpected parser output", index);
+        assert_eq!(&remaining, r, "case {}: unexpected remaining input", index);
+      }
+      (Err(Err::Error(e)), Expect::ErrorAt(offset, k))
+      | (Err(Err::Failure(e)), Expect::ErrorAt(offset, k)) => match e.position() {
+        Some((at, kind)) => {
+          assert_eq!(
+            input.offset(at),
+            *offset,
+            "case {}: unexpected error offset",
+            index
+          );
+          assert_eq!(kind, *k, "case {}: unexpected error kind", index);
+        }
+        None => panic!(
+          "case {}: the error does not record its position: {:?}",
+          index, e
+        ),
+      },
+      (Err(Err::Incomplete(n)), Expect::Incomplete(needed)) => {
+        assert_eq!(&n, needed, "case {}: unexpected needed size", index);
+      }
+      (res, expected) => panic!("case {}: expected {:?}, got {:?}", index, expected, res),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::bytes::streaming::tag;
+  use crate::character::complete::{alpha1, digit1};
+  use crate::sequence::{pair, preceded};
+  use crate::IResult;
+
+  fn assignment(i: &str) -> IResult<&str, (&str, &str)> {
+    pair(alpha1, preceded(crate::bytes::complete::tag("="), digit1))(i)
+  }
+
+  #[test]
+  fn assertions() {
+    assert_parses!(assignment, "a=1", ("a", "1"));
+    assert_parses!(assignment, "a=1;", ("a", "1"), ";");
+    assert_error_at!(assignment, "ab=;", 3, ErrorKind::Digit);
+    assert_error_at!(assignment, "ab;", 2, ErrorKind::Tag);
+  }
+
+  #[test]
+  #[should_panic(expected = "did not consume the whole input")]
+  fn remaining_input_is_checked() {
+    assert_parses!(assignment, "a=1;", ("a", "1"));
+  }
+
+  fn abc(i: &[u8]) -> IResult<&[u8], &[u8]> {
+    tag("abc")(i)
+  }
+
+  #[test]
+  fn table() {
+    run_cases(
+      abc,
+      &[
+        (&b"abcd"[..], Expect::Parsed(&b"abc"[..], &b"d"[..])),
+        (&b"ab"[..], Expect::Incomplete(Needed::new(1))),
+        (&b"abd"[..], Expect::ErrorAt(0, ErrorKind::Tag)),
+      ],
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "case 1: expected Incomplete")]
+  fn table_mismatch() {
+    run_cases(
+      abc,
+      &[
+        (&b"abc"[..], Expect::Parsed(&b"abc"[..], &b""[..])),
+        (&b"abd"[..], Expect::Incomplete(Needed::Unknown)),
+      ],
+    );
+  }
+
+  #[cfg(feature = "alloc")]
+  #[test]
+  fn verbose_error_position() {
+    use crate::error::{context, VerboseError};
+
+    fn number(i: &str) -> IResult<&str, &str, VerboseError<&str>> {
+      preceded(alpha1, context("number", digit1))(i)
+    }
+
+    assert_error_at!(number, "ab;", 2, ErrorKind::Digit);
+  }
+}
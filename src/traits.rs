@@ -1,6 +1,7 @@
 //! Traits input types have to implement to work with nom combinators
 use crate::error::{ErrorKind, ParseError};
 use crate::internal::{Err, IResult, Needed};
+use crate::lib::std::fmt;
 use crate::lib::std::iter::{Copied, Enumerate};
 use crate::lib::std::ops::{Range, RangeFrom, RangeFull, RangeTo};
 use crate::lib::std::slice::Iter;
@@ -17,6 +18,9 @@ use crate::lib::std::vec::Vec;
 #[cfg(feature = "bitvec")]
 use bitvec::{prelude::*, slice::BitValIter};
 
+#[cfg(const_generics)]
+mod arrays;
+
 /// Abstract method to calculate the input length
 pub trait InputLength {
   /// Calculates the input length, as indicated by its name,
@@ -47,6 +51,38 @@ impl<'a> InputLength for (&'a [u8], usize) {
   }
 }
 
+/// A single byte, used as a one byte tag
+impl InputLength for u8 {
+  #[inline]
+  fn input_len(&self) -> usize {
+    1
+  }
+}
+
+/// A single character, used as a tag of its UTF-8 length
+impl InputLength for char {
+  #[inline]
+  fn input_len(&self) -> usize {
+    self.len_utf8()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl InputLength for String {
+  #[inline]
+  fn input_len(&self) -> usize {
+    self.len()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl InputLength for Vec<u8> {
+  #[inline]
+  fn input_len(&self) -> usize {
+    self.len()
+  }
+}
+
 #[cfg(feature = "bitvec")]
 impl<'a, O, T> InputLength for &'a BitSlice<O, T>
 where
@@ -403,6 +439,31 @@ pub trait InputIter {
   fn slice_index(&self, count: usize) -> Result<usize, Needed>;
 }
 
+/// Indicates whether more data can follow the end of the input
+///
+/// Slices are complete inputs, while the [Partial](crate::partial::Partial)
+/// wrapper marks an input as partial. The parsers at the root of the `bytes`,
+/// `character` and `number` modules use it to select their streaming or
+/// complete behaviour.
+pub trait InputMode {
+  /// Returns true if more data can follow the end of the input
+  fn is_partial(&self) -> bool;
+}
+
+impl<T> InputMode for &[T] {
+  #[inline]
+  fn is_partial(&self) -> bool {
+    false
+  }
+}
+
+impl InputMode for &str {
+  #[inline]
+  fn is_partial(&self) -> bool {
+    false
+  }
+}
+
 /// Abstracts slicing operations
 pub trait InputTake: Sized {
   /// Returns a slice of `count` bytes. panics if count > length
@@ -915,6 +976,18 @@ pub trait Compare<T> {
   /// the result. This is a temporary solution until
   /// a better one appears
   fn compare_no_case(&self, t: T) -> CompareResult;
+
+  /// Writes the pattern `t` in error messages, like the literal expected by a
+  /// failed [tag](crate::bytes::complete::tag), escaping the bytes that are not
+  /// valid UTF-8.
+  ///
+  /// The default implementation writes nothing
+  fn fmt_tag(_t: &T, _f: &mut fmt::Formatter<'_>) -> fmt::Result
+  where
+    Self: Sized,
+  {
+    Ok(())
+  }
 }
 
 fn lowercase_byte(c: u8) -> u8 {
@@ -971,6 +1044,11 @@ impl<'a, 'b> Compare<&'b [u8]> for &'a [u8] {
       CompareResult::Ok
     }
   }
+
+  #[inline]
+  fn fmt_tag(t: &&'b [u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_tag_bytes(t, f)
+  }
 }
 
 impl<
@@ -1011,6 +1089,15 @@ impl<
       CompareResult::Ok
     }
   }
+
+  fn fmt_tag(t: &O, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for b in t.iter_elements() {
+      for c in core::ascii::escape_default(b) {
+        fmt::Write::write_char(f, c as char)?;
+      }
+    }
+    Ok(())
+  }
 }
 
 impl<'a, 'b> Compare<&'b str> for &'a [u8] {
@@ -1022,6 +1109,11 @@ impl<'a, 'b> Compare<&'b str> for &'a [u8] {
   fn compare_no_case(&self, t: &'b str) -> CompareResult {
     self.compare_no_case(AsBytes::as_bytes(t))
   }
+
+  #[inline]
+  fn fmt_tag(t: &&'b str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(t)
+  }
 }
 
 impl<'a, 'b> Compare<&'b str> for &'a str {
@@ -1049,6 +1141,125 @@ impl<'a, 'b> Compare<&'b str> for &'a str {
       }
     }
   }
+
+  #[inline]
+  fn fmt_tag(t: &&'b str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(t)
+  }
+}
+
+impl Compare<u8> for &[u8] {
+  #[inline(always)]
+  fn compare(&self, t: u8) -> CompareResult {
+    match self.first() {
+      Some(&c) if c == t => CompareResult::Ok,
+      Some(_) => CompareResult::Error,
+      None => CompareResult::Incomplete,
+    }
+  }
+
+  #[inline(always)]
+  fn compare_no_case(&self, t: u8) -> CompareResult {
+    match self.first() {
+      Some(&c) if lowercase_byte(c) == lowercase_byte(t) => CompareResult::Ok,
+      Some(_) => CompareResult::Error,
+      None => CompareResult::Incomplete,
+    }
+  }
+
+  #[inline]
+  fn fmt_tag(t: &u8, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_tag_bytes(&[*t], f)
+  }
+}
+
+/// The character is compared to its UTF-8 encoding
+impl Compare<char> for &[u8] {
+  #[inline(always)]
+  fn compare(&self, t: char) -> CompareResult {
+    self.compare(t.encode_utf8(&mut [0; 4]).as_bytes())
+  }
+
+  #[inline(always)]
+  fn compare_no_case(&self, t: char) -> CompareResult {
+    self.compare_no_case(t.encode_utf8(&mut [0; 4]).as_bytes())
+  }
+
+  #[inline]
+  fn fmt_tag(t: &char, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Write::write_char(f, *t)
+  }
+}
+
+impl Compare<char> for &str {
+  #[inline(always)]
+  fn compare(&self, t: char) -> CompareResult {
+    self.compare(&*t.encode_utf8(&mut [0; 4]))
+  }
+
+  #[inline(always)]
+  fn compare_no_case(&self, t: char) -> CompareResult {
+    self.compare_no_case(&*t.encode_utf8(&mut [0; 4]))
+  }
+
+  #[inline]
+  fn fmt_tag(t: &char, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Write::write_char(f, *t)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl Compare<Vec<u8>> for &[u8] {
+  #[inline(always)]
+  fn compare(&self, t: Vec<u8>) -> CompareResult {
+    self.compare(&t[..])
+  }
+
+  #[inline(always)]
+  fn compare_no_case(&self, t: Vec<u8>) -> CompareResult {
+    self.compare_no_case(&t[..])
+  }
+
+  #[inline]
+  fn fmt_tag(t: &Vec<u8>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_tag_bytes(t, f)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl Compare<String> for &[u8] {
+  #[inline(always)]
+  fn compare(&self, t: String) -> CompareResult {
+    self.compare(t.as_bytes())
+  }
+
+  #[inline(always)]
+  fn compare_no_case(&self, t: String) -> CompareResult {
+    self.compare_no_case(t.as_bytes())
+  }
+
+  #[inline]
+  fn fmt_tag(t: &String, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(t)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl Compare<String> for &str {
+  #[inline(always)]
+  fn compare(&self, t: String) -> CompareResult {
+    self.compare(&t[..])
+  }
+
+  #[inline(always)]
+  fn compare_no_case(&self, t: String) -> CompareResult {
+    self.compare_no_case(&t[..])
+  }
+
+  #[inline]
+  fn fmt_tag(t: &String, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(t)
+  }
 }
 
 #[cfg(feature = "bitvec")]
@@ -1077,6 +1288,28 @@ where
   fn compare_no_case(&self, other: &'b BitSlice<O2, T2>) -> CompareResult {
     self.compare(other)
   }
+
+  fn fmt_tag(t: &&'b BitSlice<O2, T2>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for bit in t.iter() {
+      fmt::Write::write_char(f, if *bit { '1' } else { '0' })?;
+    }
+    Ok(())
+  }
+}
+
+/// Writes a pattern, escaping the bytes if it is not valid UTF-8
+fn fmt_tag_bytes(bytes: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+  match from_utf8(bytes) {
+    Ok(s) => f.write_str(s),
+    Err(_) => {
+      for b in bytes {
+        for c in core::ascii::escape_default(*b) {
+          fmt::Write::write_char(f, c as char)?;
+        }
+      }
+      Ok(())
+    }
+  }
 }
 
 /// Look for a token in self
@@ -1121,6 +1354,12 @@ impl<'a> FindToken<char> for &'a str {
   }
 }
 
+impl FindToken<char> for char {
+  fn find_token(&self, token: char) -> bool {
+    *self == token
+  }
+}
+
 #[cfg(feature = "bitvec")]
 impl<'a, O, T> FindToken<bool> for &'a BitSlice<O, T>
 where
@@ -1305,6 +1544,7 @@ slice_ranges_impl! {[T]}
 #[cfg(feature = "bitvec")]
 slice_ranges_impl! {BitSlice}
 
+#[cfg(not(const_generics))]
 macro_rules! array_impls {
   ($($N:expr)+) => {
     $(
@@ -1355,6 +1595,10 @@ macro_rules! array_impls {
         fn compare_no_case(&self, t: [u8;$N]) -> CompareResult {
           self.compare_no_case(&t[..])
         }
+
+        fn fmt_tag(t: &[u8; $N], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+          fmt_tag_bytes(&t[..], f)
+        }
       }
 
       impl<'a,'b> Compare<&'b [u8; $N]> for &'a [u8] {
@@ -1367,6 +1611,10 @@ macro_rules! array_impls {
         fn compare_no_case(&self, t: &'b [u8;$N]) -> CompareResult {
           self.compare_no_case(&t[..])
         }
+
+        fn fmt_tag(t: &&'b [u8; $N], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+          fmt_tag_bytes(&t[..], f)
+        }
       }
 
       impl FindToken<u8> for [u8; $N] {
@@ -1384,6 +1632,7 @@ macro_rules! array_impls {
   };
 }
 
+#[cfg(not(const_generics))]
 array_impls! {
      0  1  2  3  4  5  6  7  8  9
     10 11 12 13 14 15 16 17 18 19
@@ -1393,6 +1642,12 @@ array_impls! {
 
 /// Abstracts something which can extend an `Extend`.
 /// Used to build modified input slices in `escaped_transform`
+///
+/// The accumulator type is chosen by the input type (`String` for `&str`,
+/// `Vec<u8>` for `&[u8]`), so this trait is only implemented with the `alloc`
+/// feature. To accumulate into a caller provided collection (like an
+/// `ArrayString` or a `SmallVec` in `no_std` environments), see
+/// [escaped_transform_with](crate::bytes::complete::escaped_transform_with)
 pub trait ExtendInto {
   /// The current input type is a sequence of that `Item` type.
   ///
@@ -1523,6 +1778,301 @@ where
   }
 }
 
+pub(crate) mod sink {
+  use crate::error::ErrorKind;
+
+  /// Collection that can be extended with parts of the input, or with values
+  /// produced from it.
+  ///
+  /// Where [ExtendInto](crate::ExtendInto) lets the input type decide which
+  /// accumulator is built, `Sink` is implemented by the accumulator itself, so
+  /// combinators like `escaped_transform_with` can write into a collection
+  /// chosen by the caller, including fixed capacity ones that do not need an
+  /// allocator.
+  ///
+  /// This trait is sealed: it is implemented for `String` and `Vec<u8>` with
+  /// the `alloc` feature, for `ArrayString` and `ArrayVec` with the `arrayvec`
+  /// feature, for `SmallVec` with the `smallvec` feature, and for mutable
+  /// references to those. Other writers can be used through the
+  /// [FmtSink](crate::FmtSink) and [IoSink](crate::IoSink) wrappers.
+  pub trait Sink<T> {
+    /// Appends `item` to the collection.
+    ///
+    /// If the item could not be stored, returns `ErrorKind::TooLarge` when
+    /// there is not enough capacity left, and `ErrorKind::Write` when the
+    /// underlying writer returned an error
+    fn extend_from(&mut self, item: T) -> Result<(), ErrorKind>;
+  }
+}
+
+pub(crate) use self::sink::Sink;
+
+impl<T, S: Sink<T> + ?Sized> Sink<T> for &mut S {
+  #[inline]
+  fn extend_from(&mut self, item: T) -> Result<(), ErrorKind> {
+    (**self).extend_from(item)
+  }
+}
+
+/// Wraps a [fmt::Write](crate::lib::std::fmt::Write) implementation to use
+/// it as the sink of [escaped_transform_into](crate::bytes::complete::escaped_transform_into)
+#[derive(Debug)]
+pub struct FmtSink<W>(pub W);
+
+impl<'a, W: crate::lib::std::fmt::Write> Sink<&'a str> for FmtSink<W> {
+  #[inline]
+  fn extend_from(&mut self, item: &'a str) -> Result<(), ErrorKind> {
+    self.0.write_str(item).map_err(|_| ErrorKind::Write)
+  }
+}
+
+impl<W: crate::lib::std::fmt::Write> Sink<char> for FmtSink<W> {
+  #[inline]
+  fn extend_from(&mut self, item: char) -> Result<(), ErrorKind> {
+    self.0.write_char(item).map_err(|_| ErrorKind::Write)
+  }
+}
+
+/// Wraps an [io::Write](std::io::Write) implementation to use it as
+/// the sink of [escaped_transform_into](crate::bytes::complete::escaped_transform_into)
+///
+/// When writing fails, the parser returns `ErrorKind::Write`, and the
+/// `io::Error` is kept here, to be retrieved with [IoSink::take_error].
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub struct IoSink<W> {
+  writer: W,
+  error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoSink<W> {
+  /// Wraps a writer
+  pub fn new(writer: W) -> Self {
+    IoSink {
+      writer,
+      error: None,
+    }
+  }
+
+  /// Gets a reference to the underlying writer
+  pub fn get_ref(&self) -> &W {
+    &self.writer
+  }
+
+  /// Gets a mutable reference to the underlying writer
+  pub fn get_mut(&mut self) -> &mut W {
+    &mut self.writer
+  }
+
+  /// Returns the last error returned by the writer, if any
+  pub fn take_error(&mut self) -> Option<std::io::Error> {
+    self.error.take()
+  }
+
+  /// Unwraps the underlying writer
+  pub fn into_inner(self) -> W {
+    self.writer
+  }
+
+  fn write_all(&mut self, data: &[u8]) -> Result<(), ErrorKind> {
+    self.writer.write_all(data).map_err(|e| {
+      self.error = Some(e);
+      ErrorKind::Write
+    })
+  }
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: std::io::Write> Sink<&'a [u8]> for IoSink<W> {
+  #[inline]
+  fn extend_from(&mut self, item: &'a [u8]) -> Result<(), ErrorKind> {
+    self.write_all(item)
+  }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Sink<u8> for IoSink<W> {
+  #[inline]
+  fn extend_from(&mut self, item: u8) -> Result<(), ErrorKind> {
+    self.write_all(&[item])
+  }
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: std::io::Write> Sink<&'a str> for IoSink<W> {
+  #[inline]
+  fn extend_from(&mut self, item: &'a str) -> Result<(), ErrorKind> {
+    self.write_all(item.as_bytes())
+  }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Sink<char> for IoSink<W> {
+  #[inline]
+  fn extend_from(&mut self, item: char) -> Result<(), ErrorKind> {
+    let mut buf = [0; 4];
+    self.write_all(item.encode_utf8(&mut buf).as_bytes())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Sink<&'a str> for String {
+  #[inline]
+  fn extend_from(&mut self, item: &'a str) -> Result<(), ErrorKind> {
+    self.push_str(item);
+    Ok(())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl Sink<char> for String {
+  #[inline]
+  fn extend_from(&mut self, item: char) -> Result<(), ErrorKind> {
+    self.push(item);
+    Ok(())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Sink<&'a [u8]> for Vec<u8> {
+  #[inline]
+  fn extend_from(&mut self, item: &'a [u8]) -> Result<(), ErrorKind> {
+    self.extend_from_slice(item);
+    Ok(())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl Sink<u8> for Vec<u8> {
+  #[inline]
+  fn extend_from(&mut self, item: u8) -> Result<(), ErrorKind> {
+    self.push(item);
+    Ok(())
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl Sink<char> for Vec<u8> {
+  /// Appends the character if it is ASCII, and returns `ErrorKind::Char`
+  /// otherwise, since a byte collection has no single encoding for other
+  /// characters
+  #[inline]
+  fn extend_from(&mut self, item: char) -> Result<(), ErrorKind> {
+    if item.is_ascii() {
+      self.push(item as u8);
+      Ok(())
+    } else {
+      Err(ErrorKind::Char)
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Sink<&'a str> for Vec<u8> {
+  #[inline]
+  fn extend_from(&mut self, item: &'a str) -> Result<(), ErrorKind> {
+    self.extend_from_slice(item.as_bytes());
+    Ok(())
+  }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'a, A> Sink<&'a str> for arrayvec::ArrayString<A>
+where
+  A: arrayvec::Array<Item = u8> + Copy,
+{
+  #[inline]
+  fn extend_from(&mut self, item: &'a str) -> Result<(), ErrorKind> {
+    self.try_push_str(item).map_err(|_| ErrorKind::TooLarge)
+  }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<A> Sink<char> for arrayvec::ArrayString<A>
+where
+  A: arrayvec::Array<Item = u8> + Copy,
+{
+  #[inline]
+  fn extend_from(&mut self, item: char) -> Result<(), ErrorKind> {
+    self.try_push(item).map_err(|_| ErrorKind::TooLarge)
+  }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'a, A> Sink<&'a [u8]> for arrayvec::ArrayVec<A>
+where
+  A: arrayvec::Array<Item = u8>,
+{
+  #[inline]
+  fn extend_from(&mut self, item: &'a [u8]) -> Result<(), ErrorKind> {
+    self
+      .try_extend_from_slice(item)
+      .map_err(|_| ErrorKind::TooLarge)
+  }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<A> Sink<u8> for arrayvec::ArrayVec<A>
+where
+  A: arrayvec::Array<Item = u8>,
+{
+  #[inline]
+  fn extend_from(&mut self, item: u8) -> Result<(), ErrorKind> {
+    self.try_push(item).map_err(|_| ErrorKind::TooLarge)
+  }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'a, A> Sink<&'a str> for arrayvec::ArrayVec<A>
+where
+  A: arrayvec::Array<Item = u8>,
+{
+  #[inline]
+  fn extend_from(&mut self, item: &'a str) -> Result<(), ErrorKind> {
+    self
+      .try_extend_from_slice(item.as_bytes())
+      .map_err(|_| ErrorKind::TooLarge)
+  }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'a, A> Sink<&'a [u8]> for smallvec::SmallVec<A>
+where
+  A: smallvec::Array<Item = u8>,
+{
+  #[inline]
+  fn extend_from(&mut self, item: &'a [u8]) -> Result<(), ErrorKind> {
+    self.extend_from_slice(item);
+    Ok(())
+  }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> Sink<u8> for smallvec::SmallVec<A>
+where
+  A: smallvec::Array<Item = u8>,
+{
+  #[inline]
+  fn extend_from(&mut self, item: u8) -> Result<(), ErrorKind> {
+    self.push(item);
+    Ok(())
+  }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'a, A> Sink<&'a str> for smallvec::SmallVec<A>
+where
+  A: smallvec::Array<Item = u8>,
+{
+  #[inline]
+  fn extend_from(&mut self, item: &'a str) -> Result<(), ErrorKind> {
+    self.extend_from_slice(item.as_bytes());
+    Ok(())
+  }
+}
+
 /// Helper trait to convert numbers to usize.
 ///
 /// By default, usize implements `From<u8>` and `From<u16>` but not
@@ -1655,4 +2205,26 @@ mod tests {
     assert_eq!(a.offset(c), 0);
     assert_eq!(a.offset(d), 5);
   }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn extend_from_sinks() {
+    let mut fmt = FmtSink(String::new());
+    assert_eq!(fmt.extend_from("ab"), Ok(()));
+    assert_eq!(fmt.extend_from('c'), Ok(()));
+    assert_eq!(fmt.0, "abc");
+
+    let mut io = IoSink::new(Vec::new());
+    assert_eq!(io.extend_from(&b"ab"[..]), Ok(()));
+    assert_eq!(io.extend_from('é'), Ok(()));
+    assert_eq!(io.into_inner(), "abé".as_bytes());
+
+    let mut buf = [0u8; 2];
+    let mut full = IoSink::new(&mut buf[..]);
+    assert_eq!(full.extend_from("abc"), Err(ErrorKind::Write));
+    assert_eq!(
+      full.take_error().map(|e| e.kind()),
+      Some(std::io::ErrorKind::WriteZero)
+    );
+  }
 }
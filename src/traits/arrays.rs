@@ -0,0 +1,95 @@
+//! Implementations for byte arrays of any length, with const generics
+//!
+//! Only built with rustc 1.51 or later, older versions use the impls for
+//! arrays of up to 32 elements generated by `array_impls!`.
+
+use super::{fmt_tag_bytes, Compare, CompareResult, FindToken, InputIter, InputLength};
+use crate::internal::Needed;
+use crate::lib::std::fmt;
+use crate::lib::std::iter::{Copied, Enumerate};
+use crate::lib::std::slice::Iter;
+
+impl<const N: usize> InputLength for [u8; N] {
+  #[inline]
+  fn input_len(&self) -> usize {
+    self.len()
+  }
+}
+
+impl<const N: usize> InputLength for &[u8; N] {
+  #[inline]
+  fn input_len(&self) -> usize {
+    self.len()
+  }
+}
+
+impl<'a, const N: usize> InputIter for &'a [u8; N] {
+  type Item = u8;
+  type Iter = Enumerate<Self::IterElem>;
+  type IterElem = Copied<Iter<'a, u8>>;
+
+  fn iter_indices(&self) -> Self::Iter {
+    (&self[..]).iter_indices()
+  }
+
+  fn iter_elements(&self) -> Self::IterElem {
+    (&self[..]).iter_elements()
+  }
+
+  fn position<P>(&self, predicate: P) -> Option<usize>
+  where
+    P: Fn(Self::Item) -> bool,
+  {
+    (&self[..]).position(predicate)
+  }
+
+  fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+    (&self[..]).slice_index(count)
+  }
+}
+
+impl<const N: usize> Compare<[u8; N]> for &[u8] {
+  #[inline(always)]
+  fn compare(&self, t: [u8; N]) -> CompareResult {
+    self.compare(&t[..])
+  }
+
+  #[inline(always)]
+  fn compare_no_case(&self, t: [u8; N]) -> CompareResult {
+    self.compare_no_case(&t[..])
+  }
+
+  #[inline]
+  fn fmt_tag(t: &[u8; N], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_tag_bytes(&t[..], f)
+  }
+}
+
+impl<const N: usize> Compare<&[u8; N]> for &[u8] {
+  #[inline(always)]
+  fn compare(&self, t: &[u8; N]) -> CompareResult {
+    self.compare(&t[..])
+  }
+
+  #[inline(always)]
+  fn compare_no_case(&self, t: &[u8; N]) -> CompareResult {
+    self.compare_no_case(&t[..])
+  }
+
+  #[inline]
+  fn fmt_tag(t: &&[u8; N], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_tag_bytes(&t[..], f)
+  }
+}
+
+impl<const N: usize> FindToken<u8> for [u8; N] {
+  fn find_token(&self, token: u8) -> bool {
+    memchr::memchr(token, &self[..]).is_some()
+  }
+}
+
+impl<const N: usize> FindToken<&u8> for [u8; N] {
+  fn find_token(&self, token: &u8) -> bool {
+    self.find_token(*token)
+  }
+}
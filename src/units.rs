@@ -0,0 +1,443 @@
+//! Parsers for quantities with units
+//!
+//! Configuration files often express sizes and durations in a human readable
+//! way, like `512K`, `1.5GiB` or `1h30m`. [byte_size] converts sizes to a
+//! number of bytes, and [duration] converts durations to a
+//! [Duration](core::time::Duration).
+//!
+//! Those parsers work on complete input.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::ops::RangeFrom;
+use crate::traits::{AsChar, InputIter, Slice};
+use core::time::Duration;
+
+/// Interpretation of the `K`, `M`, `G`... prefixes used without `i`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SizeUnits {
+  /// powers of 1000, `1K` is 1000 bytes
+  Si,
+  /// powers of 1024, `1K` is 1024 bytes
+  Binary,
+}
+
+/// Most significant fractional digits kept, the others are ignored
+const MAX_FRACTION_DIGITS: u32 = 20;
+
+/// Position in the input, with the current character
+struct Cursor<It> {
+  it: It,
+  current: Option<(usize, char)>,
+  end: usize,
+}
+
+impl<It: Iterator<Item = (usize, char)>> Cursor<It> {
+  fn new(mut it: It) -> Self {
+    let current = it.next();
+    Cursor {
+      it,
+      current,
+      end: 0,
+    }
+  }
+
+  fn peek(&self) -> Option<char> {
+    self.current.map(|(_, c)| c)
+  }
+
+  /// Offset of the current character
+  fn offset(&self) -> usize {
+    self.current.map(|(i, _)| i).unwrap_or(self.end)
+  }
+
+  fn bump(&mut self) {
+    if let Some((i, c)) = self.current {
+      self.end = i + c.len_utf8();
+    }
+    self.current = self.it.next();
+  }
+
+  fn peek_is(&self, f: impl Fn(char) -> bool) -> bool {
+    match self.peek() {
+      Some(c) => f(c),
+      None => false,
+    }
+  }
+
+  fn eat(&mut self, f: impl Fn(char) -> bool) -> bool {
+    match self.peek() {
+      Some(c) if f(c) => {
+        self.bump();
+        true
+      }
+      _ => false,
+    }
+  }
+}
+
+/// Recognizes a size like `10K`, `4MiB` or `1.5 GB` and returns a number of bytes.
+///
+/// The number can have a fractional part, and is followed by an optional unit,
+/// with an optional space between them. The unit is made of a prefix (`K`, `M`,
+/// `G`, `T`, `P` or `E`, in any case), optionally followed by `i` for the binary
+/// prefixes, then by an optional `B`. Without `i`, `units` decides if the
+/// prefixes are powers of 1000 or 1024. A lone `B` is bytes.
+///
+/// The result is rounded down to a whole number of bytes. It returns an
+/// `ErrorKind::Digit` error if there is no number, and an `ErrorKind::TooLarge`
+/// error if the size does not fit in a `u64`.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::units::{byte_size, SizeUnits};
+///
+/// fn size(i: &str) -> IResult<&str, u64> {
+///   byte_size(SizeUnits::Si)(i)
+/// }
+///
+/// assert_eq!(size("10K"), Ok(("", 10_000)));
+/// assert_eq!(size("4MiB"), Ok(("", 4 * 1024 * 1024)));
+/// assert_eq!(size("1.5 GB;"), Ok((";", 1_500_000_000)));
+/// assert_eq!(size("512"), Ok(("", 512)));
+/// assert_eq!(byte_size::<_, Error<_>>(SizeUnits::Binary)("1.5k"), Ok(("", 1536)));
+/// assert_eq!(size("20EB"), Err(Err::Error(Error::new("20EB", ErrorKind::TooLarge))));
+/// assert_eq!(size("MB"), Err(Err::Error(Error::new("MB", ErrorKind::Digit))));
+/// ```
+pub fn byte_size<I, E>(units: SizeUnits) -> impl Fn(I) -> IResult<I, u64, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let mut cursor = Cursor::new(input.iter_indices().map(|(i, c)| (i, c.as_char())));
+
+    let mut integer: u128 = 0;
+    let mut int_digits = 0;
+    while let Some(d) = cursor.peek().and_then(|c| c.to_digit(10)) {
+      // saturates, any value above u64::MAX is too large
+      integer = (integer * 10 + u128::from(d)).min(u128::from(core::u64::MAX) + 1);
+      int_digits += 1;
+      cursor.bump();
+    }
+
+    let mut fraction: u128 = 0;
+    let mut scale: u128 = 1;
+    let mut frac_digits = 0;
+    if cursor.peek() == Some('.') {
+      let dot = cursor.offset();
+      cursor.bump();
+      while let Some(d) = cursor.peek().and_then(|c| c.to_digit(10)) {
+        if frac_digits < MAX_FRACTION_DIGITS {
+          fraction = fraction * 10 + u128::from(d);
+          scale *= 10;
+        }
+        frac_digits += 1;
+        cursor.bump();
+      }
+      if frac_digits == 0 && int_digits > 0 {
+        // the dot is not part of the size
+        return finish(input, dot, integer, 0, 1, 1);
+      }
+    }
+
+    if int_digits == 0 && frac_digits == 0 {
+      return Err(Err::Error(E::from_error_kind(input, ErrorKind::Digit)));
+    }
+
+    // the optional space belongs to the size only if a unit follows
+    let number_end = cursor.offset();
+    cursor.eat(|c| c == ' ');
+
+    let exponent = match cursor.peek().map(|c| c.to_ascii_uppercase()) {
+      Some('K') => 1,
+      Some('M') => 2,
+      Some('G') => 3,
+      Some('T') => 4,
+      Some('P') => 5,
+      Some('E') => 6,
+      Some('B') => 0,
+      _ => return finish(input, number_end, integer, fraction, scale, 1),
+    };
+    cursor.bump();
+
+    let mut base: u128 = match units {
+      SizeUnits::Si => 1000,
+      SizeUnits::Binary => 1024,
+    };
+    if exponent > 0 {
+      if cursor.eat(|c| c == 'i' || c == 'I') {
+        base = 1024;
+      }
+      cursor.eat(|c| c == 'B');
+    }
+
+    finish(
+      input,
+      cursor.offset(),
+      integer,
+      fraction,
+      scale,
+      base.pow(exponent),
+    )
+  }
+}
+
+/// Computes the size and splits the input
+fn finish<I, E>(
+  input: I,
+  end: usize,
+  integer: u128,
+  fraction: u128,
+  scale: u128,
+  multiplier: u128,
+) -> IResult<I, u64, E>
+where
+  I: Slice<RangeFrom<usize>>,
+  E: ParseError<I>,
+{
+  // integer is at most 2^64 and multiplier 2^60, fraction * multiplier is below 10^20 * 2^60
+  let bytes = integer * multiplier + fraction * multiplier / scale;
+  if bytes > u128::from(core::u64::MAX) {
+    return Err(Err::Error(E::from_error_kind(input, ErrorKind::TooLarge)));
+  }
+  Ok((input.slice(end..), bytes as u64))
+}
+
+/// Units of the components of a [duration]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum TimeUnit {
+  /// `ns`
+  Nanoseconds,
+  /// `us` or `µs`
+  Microseconds,
+  /// `ms`
+  Milliseconds,
+  /// `s`
+  Seconds,
+  /// `m`
+  Minutes,
+  /// `h`
+  Hours,
+  /// `d`, 24 hours
+  Days,
+  /// `w`, 7 days
+  Weeks,
+}
+
+impl TimeUnit {
+  /// All the units
+  pub const ALL: [TimeUnit; 8] = [
+    TimeUnit::Nanoseconds,
+    TimeUnit::Microseconds,
+    TimeUnit::Milliseconds,
+    TimeUnit::Seconds,
+    TimeUnit::Minutes,
+    TimeUnit::Hours,
+    TimeUnit::Days,
+    TimeUnit::Weeks,
+  ];
+
+  /// Length of the unit in nanoseconds
+  pub fn nanoseconds(self) -> u64 {
+    match self {
+      TimeUnit::Nanoseconds => 1,
+      TimeUnit::Microseconds => 1_000,
+      TimeUnit::Milliseconds => 1_000_000,
+      TimeUnit::Seconds => 1_000_000_000,
+      TimeUnit::Minutes => 60_000_000_000,
+      TimeUnit::Hours => 3_600_000_000_000,
+      TimeUnit::Days => 86_400_000_000_000,
+      TimeUnit::Weeks => 604_800_000_000_000,
+    }
+  }
+}
+
+/// Recognizes a duration like `1h30m`, `250ms` or `2d4h`.
+///
+/// A duration is a sequence of integers, each followed by a unit among `units`
+/// (see [TimeUnit] for their symbols). The units must appear from the largest
+/// to the smallest, each at most once.
+///
+/// It returns an `ErrorKind::Digit` error if the input does not start with a
+/// number, an `ErrorKind::Duration` error at the position of a missing,
+/// unknown, disallowed or misplaced unit, and an `ErrorKind::TooLarge` error
+/// if the duration does not fit in a [Duration].
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use core::time::Duration;
+/// use nom::units::{duration, TimeUnit};
+///
+/// fn timeout(i: &str) -> IResult<&str, Duration> {
+///   duration(&TimeUnit::ALL)(i)
+/// }
+///
+/// assert_eq!(timeout("1h30m"), Ok(("", Duration::from_secs(5400))));
+/// assert_eq!(timeout("250ms;"), Ok((";", Duration::from_millis(250))));
+/// assert_eq!(timeout("2d4h"), Ok(("", Duration::from_secs(187_200))));
+/// assert_eq!(timeout("30m1h"), Err(Err::Error(Error::new("h", ErrorKind::Duration))));
+/// assert_eq!(timeout("30"), Err(Err::Error(Error::new("", ErrorKind::Duration))));
+///
+/// let coarse = [TimeUnit::Hours, TimeUnit::Minutes, TimeUnit::Seconds];
+/// assert_eq!(
+///   duration::<_, Error<_>>(&coarse)("10ms"),
+///   Err(Err::Error(Error::new("ms", ErrorKind::Duration)))
+/// );
+/// ```
+pub fn duration<'a, I, E>(units: &'a [TimeUnit]) -> impl Fn(I) -> IResult<I, Duration, E> + 'a
+where
+  I: InputIter + Slice<RangeFrom<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  move |input: I| {
+    let mut cursor = Cursor::new(input.iter_indices().map(|(i, c)| (i, c.as_char())));
+    let max = u128::from(core::u64::MAX) * 1_000_000_000 + 999_999_999;
+
+    let mut total: u128 = 0;
+    let mut previous: Option<TimeUnit> = None;
+    while cursor.peek_is(|c| c.is_ascii_digit()) {
+      let mut value: u128 = 0;
+      while let Some(d) = cursor.peek().and_then(|c| c.to_digit(10)) {
+        // saturates, the duration will be too large anyway
+        value = (value * 10 + u128::from(d)).min(max + 1);
+        cursor.bump();
+      }
+
+      let unit_start = cursor.offset();
+      let unit = match cursor.peek() {
+        Some('n') => Some(TimeUnit::Nanoseconds),
+        Some('u') | Some('µ') => Some(TimeUnit::Microseconds),
+        Some('m') => Some(TimeUnit::Minutes),
+        Some('s') => Some(TimeUnit::Seconds),
+        Some('h') => Some(TimeUnit::Hours),
+        Some('d') => Some(TimeUnit::Days),
+        Some('w') => Some(TimeUnit::Weeks),
+        _ => None,
+      };
+      cursor.bump();
+      let unit = match unit {
+        Some(TimeUnit::Minutes) if cursor.eat(|c| c == 's') => Some(TimeUnit::Milliseconds),
+        Some(TimeUnit::Nanoseconds) | Some(TimeUnit::Microseconds) if !cursor.eat(|c| c == 's') => {
+          None
+        }
+        unit => unit,
+      };
+
+      // units must appear from the largest to the smallest
+      let in_order = match (previous, unit) {
+        (Some(previous), Some(unit)) => previous > unit,
+        _ => true,
+      };
+      let unit = match unit {
+        Some(unit) if in_order && units.contains(&unit) => unit,
+        _ => {
+          return Err(Err::Error(E::from_error_kind(
+            input.slice(unit_start..),
+            ErrorKind::Duration,
+          )))
+        }
+      };
+
+      total = match value
+        .checked_mul(u128::from(unit.nanoseconds()))
+        .map(|v| total + v)
+        .filter(|t| *t <= max)
+      {
+        Some(t) => t,
+        None => return Err(Err::Error(E::from_error_kind(input, ErrorKind::TooLarge))),
+      };
+      previous = Some(unit);
+    }
+
+    if previous.is_none() {
+      return Err(Err::Error(E::from_error_kind(input, ErrorKind::Digit)));
+    }
+
+    let duration = Duration::new(
+      (total / 1_000_000_000) as u64,
+      (total % 1_000_000_000) as u32,
+    );
+    Ok((input.slice(cursor.offset()..), duration))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  #[test]
+  fn byte_size_units() {
+    let si = byte_size::<_, Error<&[u8]>>(SizeUnits::Si);
+    assert_eq!(si(&b"1B"[..]), Ok((&b""[..], 1)));
+    assert_eq!(si(&b"2 kB"[..]), Ok((&b""[..], 2000)));
+    assert_eq!(si(&b"2 x"[..]), Ok((&b" x"[..], 2)));
+    assert_eq!(si(&b"2.x"[..]), Ok((&b".x"[..], 2)));
+    assert_eq!(si(&b".5K"[..]), Ok((&b""[..], 500)));
+    assert_eq!(si(&b"0.0001K"[..]), Ok((&b""[..], 0)));
+    assert_eq!(si(&b"1Kb"[..]), Ok((&b"b"[..], 1000)));
+    assert_eq!(si(&b"15EiB"[..]), Ok((&b""[..], 15 << 60)));
+    assert_eq!(
+      si(&b"18446744073709551615"[..]),
+      Ok((&b""[..], core::u64::MAX))
+    );
+    assert_eq!(
+      si(&b"18446744073709551616"[..]),
+      Err(Err::Error(Error::new(
+        &b"18446744073709551616"[..],
+        ErrorKind::TooLarge
+      )))
+    );
+    assert!(si(&b"99999999999999999999999999999999999999999999"[..]).is_err());
+    assert_eq!(
+      si(&b"."[..]),
+      Err(Err::Error(Error::new(&b"."[..], ErrorKind::Digit)))
+    );
+
+    let binary = byte_size::<_, Error<&str>>(SizeUnits::Binary);
+    assert_eq!(binary("0.1K"), Ok(("", 102)));
+    assert_eq!(binary("3G"), Ok(("", 3 << 30)));
+    assert_eq!(binary("1.00000000000000000000001E"), Ok(("", 1 << 60)));
+  }
+
+  #[test]
+  fn duration_units() {
+    let all = duration::<_, Error<&str>>(&TimeUnit::ALL);
+    assert_eq!(
+      all("1w2d3h4m5s6ms7us8ns"),
+      Ok(("", Duration::new(788_645, 6_007_008)))
+    );
+    assert_eq!(all("2µs"), Ok(("", Duration::from_micros(2))));
+    assert_eq!(all("0s "), Ok((" ", Duration::from_secs(0))));
+    assert_eq!(
+      all("1m1m"),
+      Err(Err::Error(Error::new("m", ErrorKind::Duration)))
+    );
+    assert_eq!(
+      all("1n"),
+      Err(Err::Error(Error::new("n", ErrorKind::Duration)))
+    );
+    assert_eq!(all("h"), Err(Err::Error(Error::new("h", ErrorKind::Digit))));
+    assert_eq!(
+      all("18446744073709551615s999ms"),
+      Ok(("", Duration::new(core::u64::MAX, 999_000_000)))
+    );
+    assert_eq!(
+      all("18446744073709551616s"),
+      Err(Err::Error(Error::new(
+        "18446744073709551616s",
+        ErrorKind::TooLarge
+      )))
+    );
+    assert_eq!(
+      all("99999999999999999999999999999999999999999999w"),
+      Err(Err::Error(Error::new(
+        "99999999999999999999999999999999999999999999w",
+        ErrorKind::TooLarge
+      )))
+    );
+  }
+}
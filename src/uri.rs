@@ -0,0 +1,703 @@
+//! Parsers for URI components
+//!
+//! Those parsers follow the generic syntax of RFC 3986, shared by the URIs of
+//! most protocols, like `https://jane@example.com:8080/path?query#fragment`.
+//! They return slices of the input without the delimiters. Percent-encoded
+//! characters are validated but not decoded, and the brackets of IP literals
+//! are kept in the host.
+//!
+//! Only ASCII characters are allowed. Those parsers work on complete input.
+
+use crate::error::{ErrorKind, ParseError};
+use crate::internal::{Err, IResult};
+use crate::lib::std::iter::Peekable;
+use crate::lib::std::ops::{Range, RangeFrom, RangeTo};
+use crate::traits::{AsChar, InputIter, Slice};
+
+/// Authority of a URI, as returned by [authority]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Authority<I> {
+  /// Part before the `@`, if any
+  pub userinfo: Option<I>,
+  /// Registered name, IPv4 address or IP literal with its brackets. It can be empty
+  pub host: I,
+  /// Digits after the `:` following the host, if any. They can be empty
+  pub port: Option<I>,
+}
+
+/// URI, as returned by [uri]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Uri<I> {
+  /// Scheme, like `https`
+  pub scheme: I,
+  /// Authority, if the scheme is followed by `//`
+  pub authority: Option<Authority<I>>,
+  /// Path, which can be empty
+  pub path: I,
+  /// Query after the `?`, if any
+  pub query: Option<I>,
+  /// Fragment after the `#`, if any
+  pub fragment: Option<I>,
+}
+
+impl Authority<Range<usize>> {
+  fn slice<I: Slice<Range<usize>>>(self, input: &I) -> Authority<I> {
+    Authority {
+      userinfo: self.userinfo.map(|r| input.slice(r)),
+      host: input.slice(self.host),
+      port: self.port.map(|r| input.slice(r)),
+    }
+  }
+}
+
+fn is_unreserved(c: char) -> bool {
+  c.is_ascii_alphanumeric() || "-._~".contains(c)
+}
+
+fn is_sub_delim(c: char) -> bool {
+  "!$&'()*+,;=".contains(c)
+}
+
+fn is_scheme(c: char) -> bool {
+  c.is_ascii_alphanumeric() || "+-.".contains(c)
+}
+
+fn is_userinfo(c: char) -> bool {
+  is_unreserved(c) || is_sub_delim(c) || c == ':'
+}
+
+fn is_reg_name(c: char) -> bool {
+  is_unreserved(c) || is_sub_delim(c)
+}
+
+fn is_path(c: char) -> bool {
+  is_unreserved(c) || is_sub_delim(c) || ":@/".contains(c)
+}
+
+/// Characters of queries and fragments
+fn is_query(c: char) -> bool {
+  is_path(c) || c == '?'
+}
+
+/// `DIGIT / %x31-39 DIGIT / "1" 2DIGIT / "2" %x30-34 DIGIT / "25" %x30-35`
+fn is_dec_octet(digits: &[u8]) -> bool {
+  !digits.is_empty()
+    && digits.len() <= 3
+    && digits.iter().all(u8::is_ascii_digit)
+    && (digits.len() == 1 || digits[0] != b'0')
+    && digits
+      .iter()
+      .fold(0u32, |n, d| n * 10 + u32::from(d - b'0'))
+      <= 255
+}
+
+/// Position in the ASCII prefix of the input
+struct Scanner<It: Iterator<Item = char>> {
+  it: Peekable<It>,
+  pos: usize,
+}
+
+impl<It: Iterator<Item = char>> Scanner<It> {
+  fn peek(&mut self) -> Option<char> {
+    self.it.peek().cloned()
+  }
+
+  fn bump(&mut self) {
+    if self.it.next().is_some() {
+      self.pos += 1;
+    }
+  }
+
+  fn peek_is(&mut self, f: fn(char) -> bool) -> bool {
+    match self.peek() {
+      Some(c) => f(c),
+      None => false,
+    }
+  }
+
+  fn eat(&mut self, c: char) -> bool {
+    if self.peek() == Some(c) {
+      self.bump();
+      true
+    } else {
+      false
+    }
+  }
+
+  fn eat_while(&mut self, f: fn(char) -> bool) -> usize {
+    let start = self.pos;
+    loop {
+      match self.peek() {
+        Some(c) if f(c) => self.bump(),
+        _ => break,
+      }
+    }
+    self.pos - start
+  }
+
+  /// `(c / pct-encoded)*`, fails on the `%` of an invalid percent-encoding
+  fn eat_encoded(&mut self, f: fn(char) -> bool) -> Result<(), usize> {
+    loop {
+      match self.peek() {
+        Some('%') => {
+          let pct = self.pos;
+          self.bump();
+          for _ in 0..2 {
+            if !self.peek_is(|c| c.is_ascii_hexdigit()) {
+              return Err(pct);
+            }
+            self.bump();
+          }
+        }
+        Some(c) if f(c) => self.bump(),
+        _ => return Ok(()),
+      }
+    }
+  }
+
+  /// Takes up to 4 characters matching `f`
+  fn piece(&mut self, f: fn(char) -> bool) -> ([u8; 4], usize) {
+    let mut piece = [0; 4];
+    let mut len = 0;
+    while len < 4 {
+      match self.peek() {
+        Some(c) if f(c) => {
+          piece[len] = c as u8;
+          len += 1;
+          self.bump();
+        }
+        _ => break,
+      }
+    }
+    (piece, len)
+  }
+
+  /// `ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )`
+  fn scheme(&mut self) -> Result<(), usize> {
+    if !self.peek_is(|c| c.is_ascii_alphabetic()) {
+      return Err(self.pos);
+    }
+    self.eat_while(is_scheme);
+    Ok(())
+  }
+
+  /// `IP-literal / IPv4address / reg-name`
+  fn host(&mut self) -> Result<(), usize> {
+    if !self.eat('[') {
+      return self.eat_encoded(is_reg_name);
+    }
+
+    if self.peek_is(|c| c == 'v' || c == 'V') {
+      self.ip_future()?;
+    } else {
+      self.ipv6()?;
+    }
+
+    if self.eat(']') {
+      Ok(())
+    } else {
+      Err(self.pos)
+    }
+  }
+
+  /// `"v" 1*HEXDIG "." 1*( unreserved / sub-delims / ":" )`
+  fn ip_future(&mut self) -> Result<(), usize> {
+    self.bump();
+    if self.eat_while(|c| c.is_ascii_hexdigit()) == 0 || !self.eat('.') {
+      return Err(self.pos);
+    }
+    if self.eat_while(is_userinfo) == 0 {
+      return Err(self.pos);
+    }
+    Ok(())
+  }
+
+  /// Eight groups of up to 4 hexadecimal digits separated by `:`, where `::`
+  /// can replace one or more groups, and the last two groups can be written
+  /// as an IPv4 address
+  fn ipv6(&mut self) -> Result<(), usize> {
+    let mut groups = 0;
+    let mut elided = false;
+    if self.eat(':') {
+      if !self.eat(':') {
+        return Err(self.pos);
+      }
+      elided = true;
+    }
+
+    while self.peek_is(|c| c.is_ascii_hexdigit()) {
+      let start = self.pos;
+      let (piece, len) = self.piece(|c| c.is_ascii_hexdigit());
+      if self.peek() == Some('.') {
+        if groups > 6 || !is_dec_octet(&piece[..len]) {
+          return Err(start);
+        }
+        for _ in 0..3 {
+          if !self.eat('.') {
+            return Err(self.pos);
+          }
+          let start = self.pos;
+          let (piece, len) = self.piece(|c| c.is_ascii_digit());
+          if !is_dec_octet(&piece[..len]) {
+            return Err(start);
+          }
+        }
+        groups += 2;
+        break;
+      }
+
+      groups += 1;
+      if groups > 8 {
+        return Err(start);
+      }
+      if !self.eat(':') {
+        break;
+      }
+      if self.eat(':') {
+        if elided {
+          return Err(self.pos - 2);
+        }
+        elided = true;
+      } else if !self.peek_is(|c| c.is_ascii_hexdigit()) {
+        return Err(self.pos);
+      }
+    }
+
+    if (elided && groups < 8) || (!elided && groups == 8) {
+      Ok(())
+    } else {
+      Err(self.pos)
+    }
+  }
+}
+
+/// Creates a scanner over the ASCII prefix of the input, starting at `start`
+fn scanner<I>(input: &I, start: usize) -> Scanner<impl Iterator<Item = char> + '_>
+where
+  I: InputIter,
+  <I as InputIter>::Item: AsChar,
+{
+  let mut s = Scanner {
+    it: input
+      .iter_elements()
+      .map(AsChar::as_char)
+      .take_while(char::is_ascii)
+      .peekable(),
+    pos: 0,
+  };
+  while s.pos < start {
+    s.bump();
+  }
+  s
+}
+
+fn scan_authority<I>(input: &I, start: usize) -> Result<(Authority<Range<usize>>, usize), usize>
+where
+  I: InputIter,
+  <I as InputIter>::Item: AsChar,
+{
+  let mut s = scanner(input, start);
+  s.eat_encoded(is_userinfo)?;
+  let (userinfo, host_start) = if s.peek() == Some('@') {
+    (Some(start..s.pos), s.pos + 1)
+  } else {
+    (None, start)
+  };
+
+  let mut s = scanner(input, host_start);
+  s.host()?;
+  let host = host_start..s.pos;
+  let port = if s.eat(':') {
+    let port_start = s.pos;
+    s.eat_while(|c| c.is_ascii_digit());
+    Some(port_start..s.pos)
+  } else {
+    None
+  };
+
+  let authority = Authority {
+    userinfo,
+    host,
+    port,
+  };
+  Ok((authority, s.pos))
+}
+
+fn error<I, O, E: ParseError<I>>(input: I, pos: usize) -> IResult<I, O, E>
+where
+  I: Slice<RangeFrom<usize>>,
+{
+  Err(Err::Error(E::from_error_kind(
+    input.slice(pos..),
+    ErrorKind::Uri,
+  )))
+}
+
+/// Splits the input at the end position returned by a scanning function
+fn split<I, E>(input: I, res: Result<usize, usize>) -> IResult<I, I, E>
+where
+  I: Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  E: ParseError<I>,
+{
+  match res {
+    Ok(end) => Ok((input.slice(end..), input.slice(..end))),
+    Err(pos) => error(input, pos),
+  }
+}
+
+/// Recognizes a scheme: a letter followed by letters, digits, `+`, `-` and `.`
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::uri::scheme;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   scheme(i)
+/// }
+///
+/// assert_eq!(parser("svn+ssh://host"), Ok(("://host", "svn+ssh")));
+/// assert_eq!(parser("1http:"), Err(Err::Error(Error::new("1http:", ErrorKind::Uri))));
+/// ```
+pub fn scheme<I, E>(input: I) -> IResult<I, I, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = {
+    let mut s = scanner(&input, 0);
+    s.scheme().map(|_| s.pos)
+  };
+  split(input, res)
+}
+
+/// Recognizes an authority, the `[userinfo@]host[:port]` part after `//`
+///
+/// The host is a registered name, an IPv4 address, or an IPv6 or future IP
+/// literal in brackets.
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::uri::{authority, Authority};
+///
+/// fn parser(i: &str) -> IResult<&str, Authority<&str>> {
+///   authority(i)
+/// }
+///
+/// assert_eq!(
+///   parser("jane:pw@example.com:8080/path"),
+///   Ok(("/path", Authority { userinfo: Some("jane:pw"), host: "example.com", port: Some("8080") }))
+/// );
+/// assert_eq!(
+///   parser("[2001:db8::1]/"),
+///   Ok(("/", Authority { userinfo: None, host: "[2001:db8::1]", port: None }))
+/// );
+/// assert_eq!(parser("[2001::db8::1]"), Err(Err::Error(Error::new("::1]", ErrorKind::Uri))));
+/// ```
+pub fn authority<I, E>(input: I) -> IResult<I, Authority<I>, E>
+where
+  I: InputIter + Slice<Range<usize>> + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  match scan_authority(&input, 0) {
+    Ok((authority, end)) => Ok((input.slice(end..), authority.slice(&input))),
+    Err(pos) => error(input, pos),
+  }
+}
+
+/// Recognizes a host: a registered name, an IPv4 address, or an IPv6 or
+/// future IP literal in brackets
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::uri::host;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   host(i)
+/// }
+///
+/// assert_eq!(parser("example.com:80"), Ok((":80", "example.com")));
+/// assert_eq!(parser("[::ffff:192.0.2.1]"), Ok(("", "[::ffff:192.0.2.1]")));
+/// assert_eq!(parser("[v1.x]"), Ok(("", "[v1.x]")));
+/// assert_eq!(parser("[::1"), Err(Err::Error(Error::new("", ErrorKind::Uri))));
+/// ```
+pub fn host<I, E>(input: I) -> IResult<I, I, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = {
+    let mut s = scanner(&input, 0);
+    s.host().map(|_| s.pos)
+  };
+  split(input, res)
+}
+
+/// Recognizes a path, made of segments separated by `/`. It can be empty
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::uri::path;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   path(i)
+/// }
+///
+/// assert_eq!(parser("/a/b%20c?q"), Ok(("?q", "/a/b%20c")));
+/// assert_eq!(parser("/a%2"), Err(Err::Error(Error::new("%2", ErrorKind::Uri))));
+/// ```
+pub fn path<I, E>(input: I) -> IResult<I, I, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = {
+    let mut s = scanner(&input, 0);
+    s.eat_encoded(is_path).map(|_| s.pos)
+  };
+  split(input, res)
+}
+
+/// Recognizes a query, the part after the `?`. It can be empty
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::uri::query;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   query(i)
+/// }
+///
+/// assert_eq!(parser("a=1&b=/?#top"), Ok(("#top", "a=1&b=/?")));
+/// ```
+pub fn query<I, E>(input: I) -> IResult<I, I, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = {
+    let mut s = scanner(&input, 0);
+    s.eat_encoded(is_query).map(|_| s.pos)
+  };
+  split(input, res)
+}
+
+/// Recognizes a fragment, the part after the `#`. It can be empty
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::uri::fragment;
+///
+/// fn parser(i: &str) -> IResult<&str, &str> {
+///   fragment(i)
+/// }
+///
+/// assert_eq!(parser("section-2 "), Ok((" ", "section-2")));
+/// ```
+pub fn fragment<I, E>(input: I) -> IResult<I, I, E>
+where
+  I: InputIter + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  query(input)
+}
+
+/// Recognizes a URI: a [scheme], `:`, an optional `//` followed by an
+/// [authority], a [path], an optional [query] and an optional [fragment]
+///
+/// ```rust
+/// # use nom::{Err, error::{Error, ErrorKind}, IResult};
+/// use nom::uri::{uri, Authority, Uri};
+///
+/// fn parser(i: &str) -> IResult<&str, Uri<&str>> {
+///   uri(i)
+/// }
+///
+/// assert_eq!(
+///   parser("https://example.com/a?b#c"),
+///   Ok(("", Uri {
+///     scheme: "https",
+///     authority: Some(Authority { userinfo: None, host: "example.com", port: None }),
+///     path: "/a",
+///     query: Some("b"),
+///     fragment: Some("c"),
+///   }))
+/// );
+/// assert_eq!(
+///   parser("mailto:jane@example.com>"),
+///   Ok((">", Uri {
+///     scheme: "mailto",
+///     authority: None,
+///     path: "jane@example.com",
+///     query: None,
+///     fragment: None,
+///   }))
+/// );
+/// assert_eq!(parser("example.com"), Err(Err::Error(Error::new("", ErrorKind::Uri))));
+/// ```
+pub fn uri<I, E>(input: I) -> IResult<I, Uri<I>, E>
+where
+  I: InputIter + Slice<Range<usize>> + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>>,
+  <I as InputIter>::Item: AsChar,
+  E: ParseError<I>,
+{
+  let res = (|| {
+    let mut s = scanner(&input, 0);
+    s.scheme()?;
+    let scheme = 0..s.pos;
+    if !s.eat(':') {
+      return Err(s.pos);
+    }
+
+    let mut authority = None;
+    let mut path_start = s.pos;
+    if s.eat('/') && s.eat('/') {
+      let (a, end) = scan_authority(&input, s.pos)?;
+      authority = Some(a);
+      path_start = end;
+      s = scanner(&input, end);
+      // after an authority, the path is empty or starts with `/`
+      if s.peek() == Some('/') {
+        s.eat_encoded(is_path)?;
+      }
+    } else {
+      s.eat_encoded(is_path)?;
+    }
+    let path = path_start..s.pos;
+
+    let query = if s.eat('?') {
+      let start = s.pos;
+      s.eat_encoded(is_query)?;
+      Some(start..s.pos)
+    } else {
+      None
+    };
+    let fragment = if s.eat('#') {
+      let start = s.pos;
+      s.eat_encoded(is_query)?;
+      Some(start..s.pos)
+    } else {
+      None
+    };
+
+    let uri = Uri {
+      scheme,
+      authority,
+      path,
+      query,
+      fragment,
+    };
+    Ok((uri, s.pos))
+  })();
+
+  match res {
+    Ok((uri, end)) => {
+      let uri = Uri {
+        scheme: input.slice(uri.scheme),
+        authority: uri.authority.map(|a| a.slice(&input)),
+        path: input.slice(uri.path),
+        query: uri.query.map(|r| input.slice(r)),
+        fragment: uri.fragment.map(|r| input.slice(r)),
+      };
+      Ok((input.slice(end..), uri))
+    }
+    Err(pos) => error(input, pos),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+
+  fn host_ok(i: &str) -> bool {
+    host::<_, Error<&str>>(i) == Ok(("", i))
+  }
+
+  #[test]
+  fn ip_literals() {
+    for i in [
+      "[::]",
+      "[::1]",
+      "[1::]",
+      "[1:2:3:4:5:6:7:8]",
+      "[1:2:3:4:5:6:7::]",
+      "[::2:3:4:5:6:7:8]",
+      "[1:2:3:4:5:6:1.2.3.4]",
+      "[::ffff:255.255.255.255]",
+      "[abcd:EF01::]",
+      "[v7.a:b]",
+    ]
+    .iter()
+    {
+      assert!(host_ok(i), "{}", i);
+    }
+    for i in [
+      "[]",
+      "[:1]",
+      "[1:]",
+      "[1:2:3:4:5:6:7]",
+      "[1:2:3:4:5:6:7:8:9]",
+      "[1::2::3]",
+      "[1:2:3:4:5:6:7::8]",
+      "[12345::]",
+      "[1:2:3:4:5:6:7:1.2.3.4]",
+      "[::1.2.3.256]",
+      "[::1.2.3.04]",
+      "[::1.2.3]",
+      "[v.a]",
+      "[v1.]",
+    ]
+    .iter()
+    {
+      assert!(!host_ok(i), "{}", i);
+    }
+  }
+
+  #[test]
+  fn uri_components() {
+    let p = uri::<_, Error<&[u8]>>;
+    assert_eq!(
+      p(&b"file:///etc/hosts"[..]),
+      Ok((
+        &b""[..],
+        Uri {
+          scheme: &b"file"[..],
+          authority: Some(Authority {
+            userinfo: None,
+            host: &b""[..],
+            port: None
+          }),
+          path: &b"/etc/hosts"[..],
+          query: None,
+          fragment: None,
+        }
+      ))
+    );
+    assert_eq!(
+      p(&b"sip://a%40b@[::1]:/x"[..]).map(|(_, u)| u.authority),
+      Ok(Some(Authority {
+        userinfo: Some(&b"a%40b"[..]),
+        host: &b"[::1]"[..],
+        port: Some(&b""[..])
+      }))
+    );
+    assert_eq!(
+      p(&b"http://host:80x/"[..]).map(|(i, u)| (i, u.path)),
+      Ok((&b"x/"[..], &b""[..]))
+    );
+    assert_eq!(
+      p(&b"urn:a/b?c?d#e/f?g"[..]).map(|(_, u)| (u.path, u.query, u.fragment)),
+      Ok((&b"a/b"[..], Some(&b"c?d"[..]), Some(&b"e/f?g"[..])))
+    );
+    assert_eq!(
+      p(&b"http://a/b?%zz"[..]),
+      Err(Err::Error(Error::new(&b"%zz"[..], ErrorKind::Uri)))
+    );
+  }
+}
@@ -216,3 +216,52 @@ macro_rules! dbg_dmp (
       dbg_dmp!($i, call!($f));
   );
 );
+
+/// Generates a parser that calls the `streaming` version of the parser of the same
+/// name if the input is [partial](crate::partial::Partial), and the `complete` version
+/// otherwise.
+///
+/// The arguments of a combinator are passed as is to both versions, unless an
+/// expression is given after `=`, like `tag: T = tag.clone()` or `cond: F = &cond`.
+macro_rules! dispatch_mode (
+  (__arg $arg:ident) => ($arg);
+  (__arg $arg:ident = $fwd:expr) => ($fwd);
+  (
+    $(#[$attr:meta])*
+    pub fn $name:ident<$($gen:ident $(: $genb:path)?),*>(
+      $($arg:ident: $argty:ty $(= $fwd:expr)?),* $(,)?
+    ) -> impl Fn($input:ty) -> $output:ty
+    where $($bounds:tt)*
+  ) => (
+    $(#[$attr])*
+    pub fn $name<$($gen $(: $genb)?),*>($($arg: $argty),*) -> impl Fn($input) -> $output
+    where $($bounds)*
+    {
+      move |i: $input| {
+        if i.is_partial() {
+          streaming::$name($(dispatch_mode!(__arg $arg $(= $fwd)?)),*)(i)
+        } else {
+          complete::$name($(dispatch_mode!(__arg $arg $(= $fwd)?)),*)(i)
+        }
+      }
+    }
+  );
+  (
+    $(#[$attr:meta])*
+    pub fn $name:ident<$($gen:ident $(: $genb:path)?),*>(
+      $input:ident: $inputty:ty
+    ) -> $output:ty
+    where $($bounds:tt)*
+  ) => (
+    $(#[$attr])*
+    pub fn $name<$($gen $(: $genb)?),*>($input: $inputty) -> $output
+    where $($bounds)*
+    {
+      if $input.is_partial() {
+        streaming::$name($input)
+      } else {
+        complete::$name($input)
+      }
+    }
+  );
+);
@@ -26,3 +26,41 @@ fn test_escaped_transform() {
     Err(Err::Error(("abcd", ErrorKind::EscapedTransform)))
   );
 }
+
+#[cfg(feature = "alloc")]
+fn esc_trans_seq(s: &str) -> IResult<&str, String, (&str, ErrorKind)> {
+  use nom::bytes::complete::{escaped_transform_seq, tag};
+  use nom::combinator::value;
+  escaped_transform_seq(digit1, value("%", tag("%%")))(s)
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_escaped_transform_seq() {
+  assert_eq!(esc_trans_seq("12%%34%"), Ok(("%", String::from("12%34"))));
+  assert_eq!(
+    esc_trans_seq("abcd"),
+    Err(Err::Error(("abcd", ErrorKind::EscapedTransform)))
+  );
+}
+
+#[cfg(feature = "alloc")]
+fn esc_trans_seq_alpha0(s: &str) -> IResult<&str, String, (&str, ErrorKind)> {
+  use nom::bytes::complete::{escaped_transform_seq, tag};
+  use nom::character::complete::alpha0;
+  use nom::combinator::value;
+  escaped_transform_seq(alpha0, value("%", tag("%%")))(s)
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_escaped_transform_seq_empty_normal() {
+  assert_eq!(
+    esc_trans_seq_alpha0("%%ab%%cd1"),
+    Ok(("1", String::from("%ab%cd")))
+  );
+  assert_eq!(
+    esc_trans_seq_alpha0("1"),
+    Err(Err::Error(("1", ErrorKind::EscapedTransform)))
+  );
+}
@@ -0,0 +1,334 @@
+//! The complete and streaming versions of a parser must agree when the input
+//! contains enough data for the streaming version to decide.
+
+use nom::bytes::{complete as bc, streaming as bs};
+use nom::character::{complete as cc, streaming as cs};
+use nom::number::{complete as nc, streaming as ns};
+use nom::{error::ErrorKind, IResult};
+
+type Res<'a, I, O> = IResult<&'a I, O, (&'a I, ErrorKind)>;
+
+macro_rules! parity {
+  ($name:ident, $input:ty, $output:ty, ($complete:expr, $streaming:expr), [$($i:expr),* $(,)?]) => {
+    #[test]
+    fn $name() {
+      fn complete(i: &$input) -> Res<'_, $input, $output> {
+        $complete(i)
+      }
+      fn streaming(i: &$input) -> Res<'_, $input, $output> {
+        $streaming(i)
+      }
+      $(
+        assert_eq!(complete($i), streaming($i), "input: {:?}", $i);
+      )*
+    }
+  };
+}
+
+parity!(
+  tag,
+  str,
+  &str,
+  (bc::tag("ab"), bs::tag("ab")),
+  ["abc", "acb", "Abc", "xab"]
+);
+
+parity!(
+  tag_no_case,
+  str,
+  &str,
+  (bc::tag_no_case("ab"), bs::tag_no_case("ab")),
+  ["abc", "ABc", "acb"]
+);
+
+parity!(
+  take_while,
+  str,
+  &str,
+  (
+    bc::take_while(|c: char| c.is_alphabetic()),
+    bs::take_while(|c: char| c.is_alphabetic())
+  ),
+  ["abc1", "1", "éa;"]
+);
+
+parity!(
+  take_while1,
+  str,
+  &str,
+  (
+    bc::take_while1(|c: char| c.is_alphabetic()),
+    bs::take_while1(|c: char| c.is_alphabetic())
+  ),
+  ["abc1", "1", "éa;"]
+);
+
+parity!(
+  take_while_m_n,
+  str,
+  &str,
+  (
+    bc::take_while_m_n(2, 3, |c: char| c.is_alphabetic()),
+    bs::take_while_m_n(2, 3, |c: char| c.is_alphabetic())
+  ),
+  ["abcd", "ab1", "a1", "éé1", "éa1", "ééé1", "ééé", "éééé", "1"]
+);
+
+parity!(
+  take_till1,
+  str,
+  &str,
+  (bc::take_till1(|c| c == ';'), bs::take_till1(|c| c == ';')),
+  ["ab;", ";", "é;"]
+);
+
+parity!(
+  is_not,
+  str,
+  &str,
+  (bc::is_not(";,"), bs::is_not(";,")),
+  ["ab;", ",", "é,"]
+);
+
+parity!(
+  is_a,
+  str,
+  &str,
+  (bc::is_a("ab"), bs::is_a("ab")),
+  ["aab;", ";"]
+);
+
+parity!(
+  take,
+  str,
+  &str,
+  (bc::take(2u8), bs::take(2u8)),
+  ["abc", "éé", "éé;"]
+);
+
+parity!(
+  peek_n,
+  [u8],
+  &[u8],
+  (bc::peek_n(2u8), bs::peek_n(2u8)),
+  [&b"abc"[..], &b"ab"[..]]
+);
+
+parity!(
+  take_until,
+  str,
+  &str,
+  (bc::take_until("ab"), bs::take_until("ab")),
+  ["xxab", "ab", "éab"]
+);
+
+parity!(
+  take_until_partial,
+  str,
+  (&str, bool),
+  (
+    bc::take_until_partial("ab", 3),
+    bs::take_until_partial("ab", 3)
+  ),
+  ["xab", "xxxxab", "éab;"]
+);
+
+parity!(
+  escaped,
+  str,
+  &str,
+  (
+    bc::escaped(cc::alpha1, '\\', cc::one_of("\\n")),
+    bs::escaped(cs::alpha1, '\\', cs::one_of("\\n"))
+  ),
+  ["ab\\ncd;", "\\\\;", ";", "ab\\x;"]
+);
+
+#[cfg(feature = "alloc")]
+parity!(
+  escaped_transform,
+  str,
+  String,
+  (
+    bc::escaped_transform(cc::alpha1, '\\', nom::combinator::value("\n", bc::tag("n"))),
+    bs::escaped_transform(cs::alpha1, '\\', nom::combinator::value("\n", bs::tag("n")))
+  ),
+  ["ab\\ncd;", "\\n;", "ab\\x;"]
+);
+
+parity!(
+  char,
+  str,
+  char,
+  (cc::char('a'), cs::char('a')),
+  ["ab", "ba"]
+);
+
+parity!(
+  one_of,
+  str,
+  char,
+  (cc::one_of("ab"), cs::one_of("ab")),
+  ["ab", "cb"]
+);
+
+parity!(
+  none_of,
+  str,
+  char,
+  (cc::none_of("ab"), cs::none_of("ab")),
+  ["ab", "cb"]
+);
+
+parity!(
+  satisfy,
+  str,
+  char,
+  (cc::satisfy(|c| c == 'é'), cs::satisfy(|c| c == 'é')),
+  ["éa", "a"]
+);
+
+parity!(anychar, str, char, (cc::anychar, cs::anychar), ["éa", "a"]);
+
+parity!(
+  line_ending,
+  str,
+  &str,
+  (cc::line_ending, cs::line_ending),
+  ["\n;", "\r\n;", "\ra", "a"]
+);
+
+parity!(
+  crlf,
+  str,
+  &str,
+  (cc::crlf, cs::crlf),
+  ["\r\n;", "\n;", "a\r\n"]
+);
+
+parity!(newline, str, char, (cc::newline, cs::newline), ["\n;", "a"]);
+
+parity!(tab, str, char, (cc::tab, cs::tab), ["\t;", "a"]);
+
+parity!(
+  not_line_ending,
+  str,
+  &str,
+  (cc::not_line_ending, cs::not_line_ending),
+  ["ab\n", "ab\r\n", "\n", "ab\rc\n"]
+);
+
+parity!(
+  line,
+  str,
+  &str,
+  (cc::line, cs::line),
+  ["ab\n;", "ab\r\n;", "\n;", "ab\r;"]
+);
+
+parity!(
+  folded_line,
+  str,
+  &str,
+  (cc::folded_line, cs::folded_line),
+  ["ab\n cd\n;", "ab\n;", "\n;"]
+);
+
+parity!(
+  digits,
+  str,
+  (&str, &str, &str, &str),
+  (
+    nom::sequence::tuple((
+      cc::digit0,
+      cc::hex_digit0,
+      cc::oct_digit0,
+      cc::alphanumeric0
+    )),
+    nom::sequence::tuple((
+      cs::digit0,
+      cs::hex_digit0,
+      cs::oct_digit0,
+      cs::alphanumeric0
+    ))
+  ),
+  ["12af7z;", ";", "é;"]
+);
+
+parity!(
+  digits1,
+  str,
+  (&str, &str, &str, &str, &str),
+  (
+    nom::sequence::tuple((
+      cc::digit1,
+      cc::alpha1,
+      cc::hex_digit1,
+      cc::oct_digit1,
+      cc::alphanumeric1
+    )),
+    nom::sequence::tuple((
+      cs::digit1,
+      cs::alpha1,
+      cs::hex_digit1,
+      cs::oct_digit1,
+      cs::alphanumeric1
+    ))
+  ),
+  ["12zfa77b;", "z;", "1;", "12zg;"]
+);
+
+parity!(
+  spaces,
+  str,
+  (&str, &str, &str),
+  (
+    nom::sequence::tuple((cc::space0, cc::multispace1, cc::space1)),
+    nom::sequence::tuple((cs::space0, cs::multispace1, cs::space1))
+  ),
+  [" \n\t a", "\n a", " a"]
+);
+
+parity!(
+  integers,
+  [u8],
+  (u8, u16, u32, u64, i16, i32),
+  (
+    nom::sequence::tuple((
+      nc::be_u8,
+      nc::be_u16,
+      nc::le_u24,
+      nc::be_u64,
+      nc::le_i16,
+      nc::be_i24
+    )),
+    nom::sequence::tuple((
+      ns::be_u8,
+      ns::be_u16,
+      ns::le_u24,
+      ns::be_u64,
+      ns::le_i16,
+      ns::be_i24
+    ))
+  ),
+  [&[0xffu8; 21][..], &[1u8; 22][..]]
+);
+
+parity!(
+  floats,
+  [u8],
+  (f32, f64),
+  (
+    nom::sequence::pair(nc::be_f32, nc::le_f64),
+    nom::sequence::pair(ns::be_f32, ns::le_f64)
+  ),
+  [&[0x40u8; 12][..], &[0u8; 13][..]]
+);
+
+parity!(
+  text_floats,
+  str,
+  f64,
+  (nc::double, ns::double),
+  ["1.5;", "-12e3;", "abc", ".5;"]
+);